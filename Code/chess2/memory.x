@@ -1,5 +1,6 @@
 MEMORY
 {
-	FLASH : ORIGIN = 0x08000000, LENGTH = 64K
+	/* The last 3KB of the 64KB flash is kept for the records (see record.rs) and saved game (see save.rs) */
+	FLASH : ORIGIN = 0x08000000, LENGTH = 61K
 	RAM : ORIGIN = 0x20000000, LENGTH = 20K
 }
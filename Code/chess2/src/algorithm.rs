@@ -1,5 +1,4 @@
-use core::cmp::PartialOrd;
-use rtt_target::{rprintln, rtt_init_print};
+use core::sync::atomic::{AtomicBool, Ordering};
 
 use crate::board::board_representation;
 use crate::board::move_generator::EnemyAttacks;
@@ -9,8 +8,10 @@ use crate::TeamBitboards;
 pub struct Move {
     pub initial_piece_coordinates: board_representation::BoardCoordinates,
     pub final_piece_bit: usize,
+    pub promotion: Option<usize>, // Piece a pawn promotes to as a board index for the white team (see move_generator::PROMOTION_PIECES), None if the move isn't a promotion (pawns promote to queens without a promotion piece)
     pub value: i8,
-    pub heatmap_value: i16,
+    pub heatmap_value: i16, // Value of the move from the opening heatmap and the piece square tables, used to order quiet moves
+    pub history_value: i8, // Value of the move from the history heuristic tables
 }
 
 impl Move {
@@ -18,8 +19,10 @@ impl Move {
         Move {
             initial_piece_coordinates: board_representation::BoardCoordinates::new(),
             final_piece_bit: 0,
+            promotion: None,
             value: 0,
             heatmap_value: 0,
+            history_value: 0,
         }
     }
 
@@ -29,65 +32,619 @@ impl Move {
 
         self
     }
+
+    // Typed versions of the move (see square.rs), for code outside the engine
+    // Promotions are given as a piece kind, the team is the team of the piece
+    pub fn from_squares(piece: crate::square::Piece, from: crate::square::Square, to: crate::square::Square, promotion: Option<crate::square::PieceKind>) -> Self {
+        let mut piece_move = Move::new();
+        piece_move.initial_piece_coordinates = board_representation::BoardCoordinates::from_piece_square(piece, from);
+        piece_move.final_piece_bit = to.bit();
+        match promotion {
+            Some(kind) => piece_move.promotion = Some(kind.white_board_index()),
+            None => (),
+        }
+        piece_move
+    }
+
+    // Returns None if the initial bit is off the board
+    pub fn from_square(&self) -> Option<crate::square::Square> {
+        self.initial_piece_coordinates.square()
+    }
+
+    // Returns None if the final bit is off the board
+    pub fn to_square(&self) -> Option<crate::square::Square> {
+        crate::square::Square::new(self.final_piece_bit)
+    }
+
+    // Returns None if the board index isn't a piece bitboard
+    pub fn piece(&self) -> Option<crate::square::Piece> {
+        self.initial_piece_coordinates.piece()
+    }
+
+    // Returns the piece kind a pawn promotes to, None if the move isn't a promotion with a promotion piece
+    pub fn promotion_kind(&self) -> Option<crate::square::PieceKind> {
+        match self.promotion {
+            Some(index) => crate::square::PieceKind::from_board_index(index),
+            None => None,
+        }
+    }
+
+    // Transforms for the move on a transformed board (see Board::flip_vertical, flip_horizontal, swap_colours and mirror)
+    // The same transform applied to a board and a move gives the move on the transformed board
+
+    // Flips the rows of the initial and final bits
+    pub fn flip_vertical(mut self) -> Self {
+        self.initial_piece_coordinates.bit ^= 56;
+        self.final_piece_bit ^= 56;
+
+        self
+    }
+
+    // Flips the columns of the initial and final bits
+    pub fn flip_horizontal(mut self) -> Self {
+        self.initial_piece_coordinates.bit ^= 7;
+        self.final_piece_bit ^= 7;
+
+        self
+    }
+
+    // Moves the piece to the bitboard of the other team
+    // The promotion piece is always a white board index, so it doesn't change
+    pub fn swap_colours(mut self) -> Self {
+        let board_index = self.initial_piece_coordinates.board_index;
+        if crate::board_index_white(board_index) {
+            self.initial_piece_coordinates.board_index = board_index + 6;
+        } else {
+            self.initial_piece_coordinates.board_index = board_index - 6;
+        }
+
+        self
+    }
+
+    // Flips the rows and swaps the colours, the move on a board from Board::mirror
+    pub fn mirror(self) -> Self {
+        self.flip_vertical().swap_colours()
+    }
+}
+
+// Prints the move in long algebraic notation (e.g. e2e4, or e7e8n for an underpromotion to a knight)
+// With the alternate flag ({:#}) the squares are separated by an arrow and promotions are written like SAN (e.g. e2→e4, e7→e8=N)
+impl core::fmt::Display for Move {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        if f.alternate() {
+            write!(f, "{}→{}", crate::bit_to_ccn(self.initial_piece_coordinates.bit), crate::bit_to_ccn(self.final_piece_bit))?;
+            match self.promotion {
+                Some(index) => write!(f, "={}", &"PRNBQK"[index..index + 1]),
+                None => Ok(()),
+            }
+        } else {
+            write!(f, "{}{}", crate::bit_to_ccn(self.initial_piece_coordinates.bit), crate::bit_to_ccn(self.final_piece_bit))?;
+            match self.promotion {
+                Some(index) => f.write_str(&"prnbqk"[index..index + 1]),
+                None => Ok(()),
+            }
+        }
+    }
 }
 
+// Value of a search and the best move found
+// Values are relative to the team to move
 #[derive(Copy, Clone, PartialEq, Debug)]
-pub struct AlphaBeta {
-    pub alpha: i8,
-    pub beta: i8,
+pub struct SearchResult {
+    pub value: i16,
     pub piece_move: Option<Move>,
 }
 
-impl AlphaBeta {
+// Why there is no move to return from gen_best_move
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GameEnd {
+    Checkmate, // The team to move is checkmated
+    Stalemate, // The team to move has no legal moves and isn't in check
+}
+
+// Larger than any value a search can return
+pub const INFINITY: i16 = i16::MAX;
+
+// Value of checkmating the enemy king at the root
+// Mates further from the root are worth less so the engine prefers faster mates
+pub const MATE_VALUE: i16 = 30000;
+
+// Returns the moves until a checkmate if a value is within 100 half moves of one, None if it isn't a checkmate
+// The moves are positive if the team the value is for gives the checkmate, and negative if it's checkmated
+// A move that checkmates straight away is worth MATE_VALUE, so it's a mate in 1, values past MATE_VALUE (e.g. from the skill noise) are taken as a mate in 1 too
+pub fn mate_moves(value: i16) -> Option<i16> {
+    let mate_plies = (MATE_VALUE - value.saturating_abs()).max(0) + 1; // Half moves until the checkmate, including the checkmating move
+    if mate_plies > 100 {
+        return None;
+    }
+
+    let moves = (mate_plies + 1) / 2;
+    if value > 0 {
+        Some(moves)
+    } else {
+        Some(-moves)
+    }
+}
+
+// The opening heatmap only affects move ordering for this many half moves
+// Its influence shrinks every half move until it is gone, after that quiet moves are only ordered by the piece square tables
+pub const HEATMAP_HALF_MOVES: i16 = 20;
+
+// Highest skill level, the engine plays at full strength at this level
+pub const MAX_SKILL_LEVEL: u8 = 10;
+
+// Maximum random noise (centipawns) added to root move values for every skill level below MAX_SKILL_LEVEL
+const SKILL_NOISE_PER_LEVEL: i16 = 30;
+
+// Configuration for a search
+// The firmware settings menu and any other frontend configure the engine through this one struct
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SearchParams {
+    pub max_depth: usize, // Maximum search depth
+    pub max_elapsed_cycles: u64, // Maximum number of clock cycles the search can take (time budget)
+    pub max_nodes: u64, // Maximum number of nodes the search can visit (node budget), unlike the time budget it gives the same result on any hardware
+    pub skill_level: u8, // 0 to MAX_SKILL_LEVEL, lower skill levels add random noise to the values of moves at the root
+    pub contempt: i16, // Value (centipawns) a draw is worse than an equal position for the master team, positive contempt avoids draws
+
+    // Heuristic toggles and margins
+    pub late_move_pruning: bool,
+    pub lmp_max_depth: usize, // Late move pruning is applied when the remaining search depth is less than or equal to this
+    pub lmp_base_moves: usize, // Legal moves that have to be searched before late move pruning starts skipping quiet moves (the remaining depth squared is added to this)
+    pub capture_history: bool, // Order captures with the same value by capture history
+    pub hash_move: bool, // Search the best move from the last time a board was searched first (see HashMoves), at the root it's the best move from the last iterative deepening iteration
+}
+
+impl SearchParams {
     pub fn new() -> Self {
-        AlphaBeta {
-            alpha: i8::MIN, // -Infinity
-            beta: i8::MAX, // +Infinity
-            piece_move: None,
+        SearchParams {
+            max_depth: 6,
+            max_elapsed_cycles: u64::MAX,
+            max_nodes: u64::MAX,
+            skill_level: MAX_SKILL_LEVEL,
+            contempt: 0,
+
+            late_move_pruning: true,
+            lmp_max_depth: 3,
+            lmp_base_moves: 4,
+            capture_history: true,
+            hash_move: true,
         }
     }
+
+    // Number of legal moves that have to be searched before late move pruning starts skipping quiet moves
+    // The threshold grows with depth so pruning is more aggressive closer to the leaves
+    fn lmp_threshold(&self, remaining_depth: usize) -> usize {
+        self.lmp_base_moves + remaining_depth * remaining_depth
+    }
 }
 
-// If master team update alpha from child beta
-// If not master team update beta from child alpha
-pub fn update_alpha_beta(my_alpha_beta: &mut AlphaBeta, child_alpha_beta: &AlphaBeta, master_team: bool) {
-    if master_team {
-        if my_alpha_beta.alpha < child_alpha_beta.beta {
-            my_alpha_beta.alpha = child_alpha_beta.beta;
-            my_alpha_beta.piece_move = child_alpha_beta.piece_move;
-        }
+// Time management
+// In a timed game the search budget comes from the time left on the computer's clock instead of a fixed search time
+// The time left is shared between the moves that are still to be played, and the increment is mostly spent on the move it is given for
+
+// Number of moves the time left is shared between, the number of moves left in a game isn't known so this is a guess
+const MOVES_TO_GO: u64 = 30;
+
+// Time (ms) held back from every move for the work around the search, e.g. lighting the move and the player making it on the board
+const MOVE_OVERHEAD_MS: u64 = 200;
+
+// Below this much time left (ms) the computer is in panic time, and moves fast enough to not lose on time
+const PANIC_TIME_MS: u64 = 10000;
+
+// Returns the time (ms) the computer can search for a move, from the time left on its clock, its increment and the delay of the time control (see clock::TimeControl)
+// The search never takes more than a quarter of the time left, and in panic time it only takes a small part of the time left and the increment
+// Time within the delay isn't taken off the clock, so it is always added on top
+pub fn move_time_ms(remaining_ms: u64, increment_ms: u64, delay_ms: u64) -> u64 {
+    let usable_ms = remaining_ms.saturating_sub(MOVE_OVERHEAD_MS);
+
+    let move_time_ms;
+    if remaining_ms < PANIC_TIME_MS {
+        move_time_ms = usable_ms / (MOVES_TO_GO * 2) + increment_ms / 2;
     } else {
-        if my_alpha_beta.beta > child_alpha_beta.alpha {
-            my_alpha_beta.beta = child_alpha_beta.alpha;
-            my_alpha_beta.piece_move = child_alpha_beta.piece_move;
+        move_time_ms = usable_ms / MOVES_TO_GO + increment_ms * 3 / 4;
+    }
+
+    core::cmp::min(move_time_ms, usable_ms / 4) + delay_ms
+}
+
+// Information about an iterative deepening iteration, passed to the progress callback
+// The callback is called after every completed iteration, and every PROGRESS_INTERVAL_NODES nodes while an iteration is being searched
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SearchProgress {
+    pub depth: usize,
+    pub value: i16, // Relative to the team to move
+    pub best_move: Option<Move>,
+    pub nodes: u64, // Nodes visited since the start of the search
+    pub elapsed_cycles: u64, // Clock cycles since the start of the search
+    pub completed: bool, // False while the iteration is being searched, the value and best move are only set once it has completed
+}
+
+// Nodes between the progress reports made while an iteration is being searched
+// Iterations at higher depths can take seconds, the reports let a display show the search is still running (see thinking.rs)
+const PROGRESS_INTERVAL_NODES: u64 = 2048;
+
+// Data used by every node in a search
+pub struct SearchData<'a, 'b> {
+    pub cycle_counter: &'a mut crate::embedded::cycle_counter::Counter,
+    pub start_cycles: u64, // Cycle count when the search started, set by gen_best_move
+    pub nodes: u64, // Nodes visited by the search, reset by gen_best_move
+    pub stop: &'a AtomicBool, // The search stops as soon as this is set, it can be set from an interrupt (e.g. a button press) to abort a search
+    pub progress: Option<&'a mut dyn FnMut(&SearchProgress)>, // Called after every completed iteration, e.g. to update a display while the engine is thinking
+    pub capture_history: &'a mut CaptureHistory,
+    pub hash_moves: &'a mut HashMoves<'b>, // Best moves of the boards that have been searched, used when SearchParams::hash_move is true
+    pub eval_cache: &'a mut crate::evaluation::EvalCache<'b>,
+    pub pawn_cache: &'a mut crate::evaluation::EvalCache<'b>,
+    pub opening_heatmap: &'a [[i16; 64]; 12],
+    pub pieces_info: &'a [crate::piece::constants::PieceInfo; 12],
+}
+
+impl<'a, 'b> SearchData<'a, 'b> {
+
+    // Returns true if the search has used up it's time or node budget, or has been stopped
+    fn out_of_budget(&mut self, search_params: &SearchParams) -> bool {
+        if self.nodes >= search_params.max_nodes || self.stop.load(Ordering::Relaxed) {
+            return true;
+        }
+
+        self.cycle_counter.update();
+        self.cycle_counter.cycles > self.start_cycles.saturating_add(search_params.max_elapsed_cycles)
+    }
+}
+
+// Returns true if a move is quiet (it isn't a capture, en passant capture, or pawn promotion)
+fn quiet_move(piece_move: &Move, board: &board_representation::Board, team_bitboards: &TeamBitboards) -> bool {
+    use crate::bit_on;
+
+    let board_index = piece_move.initial_piece_coordinates.board_index;
+    let final_bit = piece_move.final_piece_bit;
+
+    if bit_on(team_bitboards.enemy_team, final_bit) {
+        return false;
+    }
+
+    // Pawn moves to the en passant target are captures, and pawn moves to the last row are promotions
+    if board_index == 0 || board_index == 6 {
+        if board.en_passant_target == Some(final_bit) || final_bit < 8 || final_bit > 55 {
+            return false;
+        }
+    }
+
+    true
+}
+
+// Capture history heuristic
+// Stores how often a capture has caused a beta cutoff, indexed by [piece type][captured piece type][final bit]
+// Piece types are board indexes for the white team (the table is shared by both teams)
+// Used to order captures which have the same value
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct CaptureHistory {
+    pub table: [[[i8; 64]; 6]; 6],
+}
+
+impl CaptureHistory {
+    pub fn new() -> Self {
+        CaptureHistory {
+            table: [[[0; 64]; 6]; 6],
+        }
+    }
+
+    // Returns the capture history value of a piece at board_index capturing a piece at captured_index on final_bit
+    pub fn get(&self, board_index: usize, captured_index: usize, final_bit: usize) -> i8 {
+        self.table[board_index % 6][captured_index % 6][final_bit]
+    }
+
+    // Adds bonus to a capture history entry
+    // Entries are pulled towards 0 as they get larger so they stay in the range of an i8
+    fn update(&mut self, board_index: usize, captured_index: usize, final_bit: usize, bonus: i16) {
+        let entry = &mut self.table[board_index % 6][captured_index % 6][final_bit];
+        let value = *entry as i16;
+
+        *entry = (value + bonus - value * bonus.abs() / i8::MAX as i16).clamp(i8::MIN as i16, i8::MAX as i16) as i8;
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct HashMoveEntry {
+    pub key: u64, // Zobrist hash of the board the move is the best move of
+    pub initial_bit: u8,
+    pub final_bit: u8,
+    pub promotion: u8, // Promotion piece as a board index for the white team, 0 if the move isn't a promotion
+    pub used: bool, // False if nothing has been stored in the entry
+}
+
+impl HashMoveEntry {
+    pub fn new() -> Self {
+        HashMoveEntry {
+            key: 0,
+            initial_bit: 0,
+            final_bit: 0,
+            promotion: 0,
+            used: false,
+        }
+    }
+}
+
+// Hash table of the best move found for boards, keyed by zobrist hash
+// The best move of a board is searched first the next time the board is searched, by the next iterative deepening iteration or through a transposition
+// The entries are owned by the caller so the size can be chosen for the target, the same as evaluation::EvalCache
+pub struct HashMoves<'a> {
+    entries: &'a mut [HashMoveEntry],
+}
+
+impl<'a> HashMoves<'a> {
+    pub fn new(entries: &'a mut [HashMoveEntry]) -> Self {
+        HashMoves {
+            entries: entries,
+        }
+    }
+
+    // Returns the stored best move of the board if there is one
+    // Different boards can have the same hash, so the move has to be checked before it is made (see pseudo_legal)
+    pub fn probe(&self, board: &board_representation::Board) -> Option<Move> {
+        if self.entries.is_empty() {
+            return None;
+        }
+
+        let entry = &self.entries[(board.hash % self.entries.len() as u64) as usize];
+        if !entry.used || entry.key != board.hash {
+            return None;
+        }
+
+        // Only the bits are stored, the piece is found with the mailbox
+        match board.piece_on(entry.initial_bit as usize) {
+            Some(board_index) => {
+                let mut piece_move = Move::new();
+                piece_move.initial_piece_coordinates = board_representation::BoardCoordinates {
+                    board_index: board_index,
+                    bit: entry.initial_bit as usize,
+                };
+                piece_move.final_piece_bit = entry.final_bit as usize;
+                if entry.promotion != 0 {
+                    piece_move.promotion = Some(entry.promotion as usize);
+                }
+                Some(piece_move)
+            },
+            None => None,
+        }
+    }
+
+    // Stores the best move of a board, replacing whatever was in the entry
+    pub fn store(&mut self, board: &board_representation::Board, piece_move: &Move) {
+        if self.entries.is_empty() {
+            return;
+        }
+
+        let promotion;
+        match piece_move.promotion {
+            Some(index) => promotion = index as u8,
+            None => promotion = 0,
+        }
+
+        let index = (board.hash % self.entries.len() as u64) as usize;
+        self.entries[index] = HashMoveEntry {
+            key: board.hash,
+            initial_bit: piece_move.initial_piece_coordinates.bit as u8,
+            final_bit: piece_move.final_piece_bit as u8,
+            promotion: promotion,
+            used: true,
+        };
+    }
+
+    // Removes all stored moves
+    pub fn clear(&mut self) {
+        for i in 0..self.entries.len() {
+            self.entries[i] = HashMoveEntry::new();
+        }
+    }
+}
+
+// Returns the board index of the piece captured by piece_move, or None if the move isn't a capture
+fn captured_piece(piece_move: &Move, board: &board_representation::Board) -> Option<usize> {
+    match board.piece_on(piece_move.final_piece_bit) {
+        Some(index) => {
+            if crate::board_index_white(index) != crate::board_index_white(piece_move.initial_piece_coordinates.board_index) {
+                return Some(index);
+            }
+            None
+        },
+        None => None,
+    }
+}
+
+// Bitset of the indexes of the moves searched at a node
+// Moves which were skipped by late move pruning or were invalid aren't searched, so they aren't penalized in the capture history
+#[derive(Copy, Clone)]
+struct SearchedMoves {
+    bits: [u64; (crate::board::move_generator::MAX_LEGAL_MOVES + 63) / 64],
+}
+
+impl SearchedMoves {
+    fn new() -> Self {
+        SearchedMoves {
+            bits: [0; (crate::board::move_generator::MAX_LEGAL_MOVES + 63) / 64],
         }
     }
+
+    fn insert(&mut self, index: usize) {
+        self.bits[index / 64] |= 1 << (index % 64);
+    }
+
+    fn contains(&self, index: usize) -> bool {
+        self.bits[index / 64] & (1 << (index % 64)) != 0
+    }
+}
+
+// Updates the capture history after moves[cutoff] caused a beta cutoff
+// If the cutoff move is a capture it is rewarded, and the captures searched before it are penalized
+fn update_capture_history(capture_history: &mut CaptureHistory, moves: &[OrderedMove], searched: &SearchedMoves, cutoff: usize, bonus: i16, board: &board_representation::Board) {
+    let cutoff_move = moves[cutoff].to_move();
+    match captured_piece(&cutoff_move, board) {
+        Some(captured_index) => {
+            capture_history.update(cutoff_move.initial_piece_coordinates.board_index, captured_index, cutoff_move.final_piece_bit, bonus);
+
+            for j in 0..cutoff {
+                if !searched.contains(j) {
+                    continue;
+                }
+
+                let piece_move = moves[j].to_move();
+                match captured_piece(&piece_move, board) {
+                    Some(captured_index) => capture_history.update(piece_move.initial_piece_coordinates.board_index, captured_index, piece_move.final_piece_bit, -bonus),
+                    None => (),
+                }
+            }
+        },
+        None => (),
+    }
+}
+
+// Generates the best move for the team to move on the board
+// Uses iterative deepening, each iteration searches the best move of the last iteration first to increase alpha beta cuttoffs
+// If the time or node budget runs out (or the search is stopped) during an iteration the result from the last completed iteration is returned
+// Returns a GameEnd error if the team to move has no legal moves
+pub fn gen_best_move(board: board_representation::Board, search_params: &SearchParams, search_data: &mut SearchData) -> Result<Move, GameEnd> {
+    search_best(board, search_params, search_data).map(|(piece_move, _)| piece_move)
 }
 
+// Same as gen_best_move, but also returns the value of the best move (relative to the team to move)
+// Used where the value is needed after the search, e.g. reviewing a game (see review.rs)
+pub fn search_best(board: board_representation::Board, search_params: &SearchParams, search_data: &mut SearchData) -> Result<(Move, i16), GameEnd> {
+    search_data.cycle_counter.update();
+    search_data.start_cycles = search_data.cycle_counter.cycles;
+    search_data.nodes = 0;
+
+    let mut best_result = SearchResult {
+        value: -INFINITY,
+        piece_move: None,
+    };
+
+    for search_depth in 1..(search_params.max_depth + 1) {
+        let mut hash_move = None;
+        if search_params.hash_move {
+            hash_move = best_result.piece_move;
+        }
+
+        let result = search(search_depth, 0, -INFINITY, INFINITY, hash_move, board, search_params, search_data);
+
+        // An unfinished iteration is only used if there are no completed iterations
+        if search_data.out_of_budget(search_params) {
+            if search_depth == 1 {
+                best_result = result;
+            }
+            break;
+        }
+
+        best_result = result;
+
+        match &mut search_data.progress {
+            Some(progress) => progress(&SearchProgress {
+                depth: search_depth,
+                value: best_result.value,
+                best_move: best_result.piece_move,
+                nodes: search_data.nodes,
+                elapsed_cycles: search_data.cycle_counter.cycles - search_data.start_cycles,
+                completed: true,
+            }),
+            None => (),
+        }
+    }
 
-pub fn gen_best_move(
-    master_team: bool,
-    cycle_counter: &mut crate::embedded::cycle_counter::Counter,
-    start_cycles: &u64,
-    max_elapsed_cycles: &u64,
+    match best_result.piece_move {
+        Some(piece_move) => Ok((piece_move, best_result.value)),
+
+        // The root is always searched fully, so no move means there are no legal moves
+        None => {
+            use crate::board::move_generator;
+
+            let friendly_king_index;
+            if board.whites_move {
+                friendly_king_index = 5;
+            } else {
+                friendly_king_index = 11;
+            }
+
+            let friendly_king = board_representation::BoardCoordinates {
+                board_index: friendly_king_index,
+                bit: crate::find_bit_on(board.board[friendly_king_index], 0),
+            };
+
+            let team_bitboards = TeamBitboards::new(friendly_king_index, &board);
+            let enemy_attacks = move_generator::gen_enemy_attacks(&friendly_king, team_bitboards, &board, search_data.pieces_info);
+
+            if enemy_attacks.in_check() {
+                return Err(GameEnd::Checkmate);
+            }
+            Err(GameEnd::Stalemate)
+        },
+    }
+}
+
+// Returns random noise used to weaken the engine at lower skill levels
+// The noise is seeded with the cycle count at the start of the search, so the same move gets different noise every search
+fn skill_noise(skill_level: u8, seed: u64, piece_move: &Move) -> i16 {
+    if skill_level >= MAX_SKILL_LEVEL {
+        return 0;
+    }
+    let max_noise = (MAX_SKILL_LEVEL - skill_level) as i16 * SKILL_NOISE_PER_LEVEL;
+
+    // Xorshift the seed combined with the move
+    let mut x = seed ^ ((piece_move.initial_piece_coordinates.bit as u64) << 6 | piece_move.final_piece_bit as u64).wrapping_mul(0x9E3779B97F4A7C15);
+    for _ in 0..2 {
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+    }
+
+    (x % (2 * max_noise as u64 + 1)) as i16 - max_noise
+}
+
+// Negamax search with fail soft alpha beta pruning
+// Returns the value of the board relative to the team to move, and the best move
+// The value is exact when it is between alpha and beta, otherwise it is a bound (lower bound if >= beta, upper bound if <= alpha)
+fn search(
     search_depth: usize,
     current_depth: usize,
-    init_value: i8,
-    mut alpha_beta: AlphaBeta,
-    opening_heatmap: &[[i16; 64]; 12],
+    mut alpha: i16,
+    beta: i16,
+    hash_move: Option<Move>,
     board: board_representation::Board,
-    pieces_info: &[crate::piece::constants::PieceInfo; 12]
-) -> AlphaBeta {
+    search_params: &SearchParams,
+    search_data: &mut SearchData,
+) -> SearchResult {
     use crate::board::move_generator;
     use crate::board::move_generator::TurnError;
 
+    let pieces_info = search_data.pieces_info;
+    search_data.nodes += 1;
+
+    if search_data.nodes % PROGRESS_INTERVAL_NODES == 0 {
+        let nodes = search_data.nodes;
+        let elapsed_cycles = search_data.cycle_counter.cycles.saturating_sub(search_data.start_cycles);
+        match &mut search_data.progress {
+            Some(progress) => progress(&SearchProgress {
+                depth: search_depth,
+                value: 0,
+                best_move: None,
+                nodes: nodes,
+                elapsed_cycles: elapsed_cycles,
+                completed: false,
+            }),
+            None => (),
+        }
+    }
+
     // If current depth and search depth are equal stop searching down the move tree
-    // Or stop searching if the time elapsed is greater than the maximum allowed time
-    cycle_counter.update();
-    if current_depth == search_depth || cycle_counter.cycles > start_cycles + max_elapsed_cycles {
-        return AlphaBeta {
-            alpha: init_value,
-            beta: init_value,
+    // Or stop searching if the time or node budget has been used up (except at the root, so there is always a move to return)
+    if current_depth == search_depth || (current_depth > 0 && search_data.out_of_budget(search_params)) {
+        let mut value = crate::evaluation::cached_evaluate(&board, search_data.eval_cache, search_data.pawn_cache, pieces_info);
+
+        // The evaluation is relative to the white team, so it has to be negated if black is to move
+        if !board.whites_move {
+            value *= -1;
+        }
+
+        return SearchResult {
+            value: value,
             piece_move: None,
         };
     }
@@ -119,223 +676,386 @@ pub fn gen_best_move(
     // Generate enemy attacks
     let enemy_attacks = move_generator::gen_enemy_attacks(&friendly_king, team_bitboards, &board, pieces_info);
 
-    // Generate moves
-    let moves = &mut order_moves(true, &board, &enemy_attacks, &friendly_king, opening_heatmap, &team_bitboards, pieces_info);
-
-    // Add pv move from lower search depth to the start of moves vec to increase alpha beta cuttoffs
-    // Iterative deepening
-    let pv_alpha_beta: Option<AlphaBeta>;
-    if current_depth == 0 && search_depth > 1 {
-        let alpha_beta = gen_best_move(
-            true,
-            cycle_counter,
-            start_cycles,
-            max_elapsed_cycles,
-            search_depth - 1,
-            0,
-            0,
-            AlphaBeta::new(),
-            opening_heatmap,
-            board,
-            pieces_info
-        );
-
-        moves.rotate_right(1);
-        moves[0] = alpha_beta.piece_move.unwrap();
-        pv_alpha_beta = Some(alpha_beta);
-    } else {
-        pv_alpha_beta = None;
+    // The hash move is the best move from the last time this board was searched, at the root it's the best move from the last iteration
+    let mut hash_move = hash_move;
+    if hash_move.is_none() && search_params.hash_move {
+        hash_move = search_data.hash_moves.probe(&board);
+    }
+
+    // A different board can have the same hash, so the hash move is only used if it can be made on this board
+    match hash_move {
+        Some(piece_move) => {
+            if !pseudo_legal(&piece_move, &board, &friendly_king, &team_bitboards, pieces_info) {
+                hash_move = None;
+            }
+        },
+        None => (),
+    }
+
+    // The hash move is searched before the other moves are generated, if it causes a cutoff they never have to be generated
+    let mut moves = [OrderedMove::new(&Move::new()); move_generator::MAX_LEGAL_MOVES];
+    let mut moves_len = 0;
+    let mut moves_generated = false;
+    match hash_move {
+        Some(piece_move) => {
+            moves[0] = OrderedMove::new(&piece_move);
+            moves_len = 1;
+        },
+        None => (),
     }
 
+    // Late move pruning is only used away from the root, and never when the king is in check (all evasions have to be searched)
+    let remaining_depth = search_depth - current_depth;
+    let prune_late_moves = search_params.late_move_pruning && current_depth > 0 && remaining_depth <= search_params.lmp_max_depth && !enemy_attacks.in_check();
+    let mut legal_moves_searched = 0; // Number of moves which didn't return an invalid move error
+    let mut searched_moves = SearchedMoves::new();
+
+    let mut best_result = SearchResult {
+        value: -INFINITY,
+        piece_move: None,
+    };
+
     for i in 0..moves.len() {
-        let initial_piece_coordinates = moves[i].initial_piece_coordinates;
-        let final_piece_bit = moves[i].final_piece_bit;
 
-        let new_turn_board = move_generator::new_turn(&initial_piece_coordinates, final_piece_bit, friendly_king, &enemy_king, &enemy_attacks, team_bitboards, board, &pieces_info);
-        
+        // Generate the rest of the moves once the hash move has been searched
+        if i == moves_len && !moves_generated {
+
+            // When capture history is turned off an empty table is used, so captures with the same value are ordered by heatmap_value
+            let empty_capture_history = CaptureHistory::new();
+            let capture_history: &CaptureHistory;
+            if search_params.capture_history {
+                capture_history = search_data.capture_history;
+            } else {
+                capture_history = &empty_capture_history;
+            }
+            moves_len = order_moves(true, hash_move, &mut moves, moves_len, &board, &enemy_attacks, &friendly_king, search_data.opening_heatmap, capture_history, &team_bitboards, pieces_info);
+            moves_generated = true;
+        }
+        if i == moves_len {
+            break;
+        }
+
+        let piece_move = moves[i].to_move();
+        let initial_piece_coordinates = piece_move.initial_piece_coordinates;
+        let final_piece_bit = piece_move.final_piece_bit;
+        let promotion = piece_move.promotion;
+
+        // Skip quiet moves once enough moves have been searched at low depths
+        // Moves are ordered so the moves this late in the list are unlikely to be better than the ones allready searched
+        if prune_late_moves && legal_moves_searched >= search_params.lmp_threshold(remaining_depth) && quiet_move(&piece_move, &board, &team_bitboards) {
+            continue;
+        }
+
+        let new_turn_board = move_generator::new_turn(&initial_piece_coordinates, final_piece_bit, promotion, friendly_king, &enemy_king, &enemy_attacks, team_bitboards, board, pieces_info);
+
+        let value;
         match new_turn_board {
 
             // Only continue searching down the move tree if the move didn't result in an invalid move or the end of the game
             Ok(new_board) => {
-                let mut move_value = new_board.points_delta;
-                
-                // If the current branch is not the master team then it's move values are negative (because they negatively impact the master team)
-                if !master_team {
-                    move_value *= -1;
-                }
+                legal_moves_searched += 1;
+                searched_moves.insert(i);
 
-                let branch_value = init_value + move_value;
-
-                let mut child_alpha_beta = gen_best_move(
-                    !master_team,
-                    cycle_counter,
-                    start_cycles,
-                    max_elapsed_cycles,
-                    search_depth,
-                    current_depth + 1,
-                    branch_value,
-                    alpha_beta,
-                    opening_heatmap,
-                    new_board,
-                    pieces_info
-                );
-
-                let piece_move = Move {
-                    initial_piece_coordinates: initial_piece_coordinates,
-                    final_piece_bit: final_piece_bit,
-                    value: 0,
-                    heatmap_value: 0,
-                };
+                let child_result = search(search_depth, current_depth + 1, -beta, -alpha, None, new_board, search_params, search_data);
+                let mut child_value = -child_result.value;
 
-                child_alpha_beta.piece_move = Some(piece_move);
+                // Weaken the engine at lower skill levels by adding noise to the values of root moves
+                if current_depth == 0 {
+                    child_value = child_value.saturating_add(skill_noise(search_params.skill_level, search_data.start_cycles, &piece_move));
+                }
 
-                update_alpha_beta(&mut alpha_beta, &child_alpha_beta, master_team);
+                value = child_value;
             },
             Err(error) => {
+                match error {
+                    // Variant wins are scored as mates, they end the game the same way
+                    TurnError::Checkmate | TurnError::KingOfTheHill | TurnError::ThreeCheck => value = MATE_VALUE - current_depth as i16, // Mates closer to the root are worth more
+                    TurnError::Stalemate | TurnError::FiftyMoveDraw | TurnError::InsufficientMaterial | TurnError::Repetition => {
 
-                // Update alpha/beta with value of game ending if the game ended
-                let mut branch_value;
-                let valid_move;
+                        // Contempt is relative to the master team (the team to move at the root, and every second depth after it)
+                        if current_depth % 2 == 0 {
+                            value = -search_params.contempt;
+                        } else {
+                            value = search_params.contempt;
+                        }
+                    },
 
-                match error {
-                    TurnError::Win => {branch_value = i8::MAX; valid_move = true},
-                    TurnError::Draw => {branch_value = 0; valid_move = true},
-                    TurnError::InvalidMove => {branch_value = 0; valid_move = false},
-                    TurnError::InvalidMoveCheck => {branch_value = 0; valid_move = false},
+                    // Don't search invalid moves
+                    TurnError::InvalidMove => continue,
+                    TurnError::InvalidMoveCheck => continue,
                 }
 
-                if valid_move {
-                    // If the current branch is not the master team then it's move values are negative (because they negatively impact the master team)
-                    if !master_team {
-                        branch_value *= -1;
-                    }
-
-                    let piece_move = Move {
-                        initial_piece_coordinates: initial_piece_coordinates,
-                        final_piece_bit: final_piece_bit,
-                        value: 0,
-                        heatmap_value: 0,
-                    };
+                legal_moves_searched += 1;
+                searched_moves.insert(i);
+            },
+        }
 
-                    let child_alpha_beta = AlphaBeta {
-                        alpha: branch_value,
-                        beta: branch_value,
-                        piece_move: Some(piece_move),
-                    };
+        if value > best_result.value {
+            best_result = SearchResult {
+                value: value,
+                piece_move: Some(piece_move),
+            };
 
-                    update_alpha_beta(&mut alpha_beta, &child_alpha_beta, master_team);
-                }
-            },
+            if value > alpha {
+                alpha = value;
+            }
         }
+
         // Stop searching this branch if alpha >= beta
-        if alpha_beta.alpha >= alpha_beta.beta {
+        if alpha >= beta {
+
+            // If a capture caused the cutoff reward it in the capture history, and penalize the captures searched before it
+            if search_params.capture_history {
+                let bonus = (remaining_depth * remaining_depth) as i16;
+                update_capture_history(search_data.capture_history, &moves[..moves_len], &searched_moves, i, bonus, &board);
+            }
             break;
         }
     }
 
-    // If the time exceeded the maximum allowed time return the pv move from a lower search depth
-    if current_depth == 0 && search_depth > 1 {
-        if cycle_counter.cycles > start_cycles + max_elapsed_cycles {
-            return pv_alpha_beta.unwrap();
+    // Store the best move, so it's searched first the next time this board is searched
+    if search_params.hash_move {
+        match best_result.piece_move {
+            Some(piece_move) => search_data.hash_moves.store(&board, &piece_move),
+            None => (),
+        }
+    }
+
+    best_result
+}
+
+// Returns true if the piece_move can be made by the team to move, ignoring checks
+// Used to validate suggested moves (e.g. a hash move) which may have come from a different position
+pub fn pseudo_legal(piece_move: &Move, board: &board_representation::Board, friendly_king: &board_representation::BoardCoordinates, team_bitboards: &TeamBitboards, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> bool {
+    use crate::bit_on;
+
+    let piece = &piece_move.initial_piece_coordinates;
+    let final_bit = piece_move.final_piece_bit;
+
+    // The piece has to exist on the board, and be on the team to move
+    if piece.board_index > 11 || piece.bit > 63 || final_bit > 63 || !bit_on(board.board[piece.board_index], piece.bit) {
+        return false;
+    }
+    if crate::board_index_white(piece.board_index) != board.whites_move {
+        return false;
+    }
+
+    // A piece can never move onto a friendly piece
+    if bit_on(team_bitboards.friendly_team, final_bit) {
+        return false;
+    }
+
+    // Castling moves aren't a part of gen_piece, attacks on the king's path are checks so they are left to new_turn
+    if piece == friendly_king {
+        let castles = crate::board::move_generator::legal_castles(board.whites_move, 0, board);
+        if bit_on(crate::board::move_generator::castles_bitboard(&castles), final_bit) {
+            return true;
+        }
+    }
+
+    let piece_moves = crate::board::move_generator::gen_piece(piece, None, team_bitboards, false, board, pieces_info);
+    bit_on(piece_moves.moves_bitboard, final_bit)
+}
+
+// A move in the array returned by order_moves
+// The array is on the stack for every ply of the search, and has space for every legal move (move_generator::MAX_LEGAL_MOVES)
+// A Move is 24 bytes on the board, so the moves are packed into 8 bytes to keep the array of every ply under 2KB of ram
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct OrderedMove {
+    board_index: u8,
+    initial_bit: u8,
+    final_bit: u8,
+    promotion: u8, // Promotion piece as a board index for the white team, 0 if the move isn't a promotion (pawns can't promote to pawns)
+    order: i32, // Moves are searched from the highest order to the lowest, made from the value, history_value and heatmap_value of the move in that order of importance
+}
+
+impl OrderedMove {
+    fn new(piece_move: &Move) -> Self {
+        let promotion;
+        match piece_move.promotion {
+            Some(index) => promotion = index as u8,
+            None => promotion = 0,
+        }
+
+        // The value is in the top 8 bits, then the history_value, then the heatmap_value
+        // The lower fields are offset so they are never negative, and can't change the fields above them
+        let order = ((piece_move.value as i32) << 24) + ((piece_move.history_value as i32 + 128) << 16) + (piece_move.heatmap_value as i32 + 32768);
+
+        OrderedMove {
+            board_index: piece_move.initial_piece_coordinates.board_index as u8,
+            initial_bit: piece_move.initial_piece_coordinates.bit as u8,
+            final_bit: piece_move.final_piece_bit as u8,
+            promotion: promotion,
+            order: order,
         }
     }
 
-    alpha_beta    
+    // Returns the move without its ordering values
+    fn to_move(&self) -> Move {
+        let mut piece_move = Move::new();
+        piece_move.initial_piece_coordinates = board_representation::BoardCoordinates {
+            board_index: self.board_index as usize,
+            bit: self.initial_bit as usize,
+        };
+        piece_move.final_piece_bit = self.final_bit as usize;
+        if self.promotion != 0 {
+            piece_move.promotion = Some(self.promotion as usize);
+        }
+        piece_move
+    }
 }
 
-// Returns a vec with potential moves
-// If sort is true the moves will be ordered from best to worst
+// Pushes a move onto the end of the moves array of order_moves, and increments moves_index
+// The array has space for every legal move, but order_moves generates moves that may not be legal (moves of pinned pieces, and king moves into check)
+// In the rare positions with more of these moves than the array has space for, a move is only added to a full array if it is legal, and replaces a move that isn't
+// There can never be more legal moves than the array has space for, so no legal move is left out
+fn push_ordered_move(moves: &mut [OrderedMove], moves_index: &mut usize, piece_move: &Move, board: &board_representation::Board, pieces_info: &[crate::piece::constants::PieceInfo; 12]) {
+    if *moves_index < moves.len() {
+        moves[*moves_index] = OrderedMove::new(piece_move);
+        *moves_index += 1;
+        return;
+    }
+
+    let legal = |piece_move: &Move| crate::board::move_generator::make_move_unadjudicated(&piece_move.initial_piece_coordinates, piece_move.final_piece_bit, piece_move.promotion, *board, pieces_info).is_ok();
+    if !legal(piece_move) {
+        return;
+    }
+
+    for i in 0..moves.len() {
+        if !legal(&moves[i].to_move()) {
+            moves[i] = OrderedMove::new(piece_move);
+            return;
+        }
+    }
+}
+
+// Adds the potential moves of the board to the moves array after the first moves_index moves, returns the number of moves in the array (the rest of the array is empty moves)
+// If sort is true the added moves will be ordered from best to worst
+// The hash move has allready been searched by the time the moves are generated (it is at the start of the array), so it isn't added again
 // All moves are valid apart from king moves
-fn order_moves(sort: bool, board: &board_representation::Board, enemy_attacks: &EnemyAttacks, friendly_king: &board_representation::BoardCoordinates, opening_heatmap: &[[i16; 64]; 12], team_bitboards: &crate::TeamBitboards, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> [Move; 96]  {
+fn order_moves(sort: bool, hash_move: Option<Move>, moves: &mut [OrderedMove; crate::board::move_generator::MAX_LEGAL_MOVES], mut moves_index: usize, board: &board_representation::Board, enemy_attacks: &EnemyAttacks, friendly_king: &board_representation::BoardCoordinates, opening_heatmap: &[[i16; 64]; 12], capture_history: &CaptureHistory, team_bitboards: &crate::TeamBitboards, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> usize {
     use crate::bit_on;
-    
-    let mut moves_index = 0;
-    let mut moves: [Move; 96] = [Move::new(); 96];
 
-    // Get friendly and enemy board indexes
+    let mut hash_move_coordinates = None;
+    match hash_move {
+        Some(hash_move) => hash_move_coordinates = Some((hash_move.initial_piece_coordinates, hash_move.final_piece_bit, hash_move.promotion)),
+        None => (),
+    }
+    let sort_start = moves_index; // Moves which are allready in the array are not sorted with the new moves
+
+    // The heatmap is made from the openings of master games, so it means less the further the game is from the opening
+    let heatmap_weight = (HEATMAP_HALF_MOVES - board.half_moves).max(0) as i32;
+    let phase = crate::evaluation::game_phase(board);
+
+    // Get friendly board indexes
     let friendly_indexes;
-    let enemy_index_bottom; // Inclusive
-    let enemy_index_top; // Not inclusive
     if board.whites_move {
         friendly_indexes = 0..6;
-        enemy_index_bottom = 6;
-        enemy_index_top = 12;
     } else {
         friendly_indexes = 6..12;
-        enemy_index_bottom = 0;
-        enemy_index_top = 6;
     }
 
     for i in friendly_indexes {
         let piece_value = pieces_info[i].value;
 
-        for initial_bit in 0..64 {
+        for initial_bit in crate::bitboard::bits(board.board[i]) {
             let initial_piece_coordinates = board_representation::BoardCoordinates {
                 board_index: i,
                 bit: initial_bit,
             };
 
-            // If there is no piece on the board at this bit go to the next bit
-            if !bit_on(board.board[i], initial_bit) {
-                continue;
-            }
-
             let piece_moves = crate::board::move_generator::gen_piece(&initial_piece_coordinates, None, team_bitboards, false, board, pieces_info);
-            
-            for final_bit in 0..64 {
 
-                // The piece cannot move to final_bit if it is occupied by a friendly piece
-                if bit_on(team_bitboards.friendly_team, final_bit) {
-                    continue;
-                }
+            // Castling moves aren't a part of gen_piece, so they are added for the king
+            let mut castling_bitboard = 0;
+            if &initial_piece_coordinates == friendly_king {
+                let castles = crate::board::move_generator::legal_castles(board.whites_move, enemy_attacks.enemy_attack_bitboard, board);
+                castling_bitboard = crate::board::move_generator::castles_bitboard(&castles);
+            }
+
+            // The piece cannot move to a bit that is occupied by a friendly piece
+            for final_bit in crate::bitboard::bits((piece_moves.moves_bitboard | castling_bitboard) & !team_bitboards.friendly_team) {
 
                 // Get the heatmap value as the difference of the final and initial bit values
                 // This is to prevent pieces from moving to less advantageous positions than ones they are allready in
-                let heatmap_value = opening_heatmap[i][final_bit] - opening_heatmap[i][initial_bit];
+                // The piece square tables are added in the same way, so they take over ordering once the heatmap has decayed
+                let heatmap_value = (opening_heatmap[i][final_bit] - opening_heatmap[i][initial_bit]) as i32 * heatmap_weight / HEATMAP_HALF_MOVES as i32;
+                let initial_piece_squares = crate::piece::constants::piece_square_values(i, initial_bit);
+                let final_piece_squares = crate::piece::constants::piece_square_values(i, final_bit);
+                let piece_square_value = crate::evaluation::blend([final_piece_squares[0] - initial_piece_squares[0], final_piece_squares[1] - initial_piece_squares[1]], phase);
+                let heatmap_value = (heatmap_value + piece_square_value as i32) as i16;
 
                 // Check the piece can move to final_bit
                 if bit_on(piece_moves.moves_bitboard, final_bit) {
                     
                     // Get value of move based on value of captured piece
                     let mut move_value = 0;
+                    let mut history_value = 0;
                     if bit_on(team_bitboards.enemy_team, final_bit) { // If an enemy piece is in the same bit as the friendly pieces final_bit then it has been captured
 
-                        for j in enemy_index_bottom..enemy_index_top {
-                            if bit_on(board.board[j], final_bit) {
+                        // The captured piece is found with the mailbox
+                        match board.piece_on(final_bit) {
+                            Some(j) => {
                                 let capture_value = pieces_info[j].value;
-    
+
                                 // If an enemy can move to the captured square there will likely be a trade
                                 if bit_on(enemy_attacks.enemy_attack_bitboard, final_bit) {
                                     move_value = piece_value - capture_value;
                                 } else { // If an enemy can't move to the captured square then the friendly team gets the entire value of the captured piece
                                     move_value = capture_value;
                                 }
-    
-                                // Once the piece that has been captured is found break the loop
-                                break;
-                            }
+
+                                history_value = capture_history.get(i, j, final_bit);
+                            },
+                            None => (),
                         }
                     }
 
-                    // Push move to moves vec
-                    moves[moves_index] = Move {
-                        initial_piece_coordinates: initial_piece_coordinates,
-                        final_piece_bit: final_bit,
-                        value: move_value,
-                        heatmap_value: heatmap_value,
-                    };
-                    moves_index += 1;
-                } else if &initial_piece_coordinates == friendly_king && (final_bit as i8 - initial_bit as i8).abs() == 2 {
+                    // Pawns moving to the last row make one move for every promotion piece
+                    let promotions: &[Option<usize>];
+                    if crate::board::move_generator::promotes(i, final_bit) {
+                        promotions = &crate::board::move_generator::PROMOTION_PIECES;
+                    } else {
+                        promotions = &[None];
+                    }
+
+                    for &promotion in promotions {
+
+                        // Don't generate the hash move twice
+                        if hash_move_coordinates == Some((initial_piece_coordinates, final_bit, promotion)) {
+                            continue;
+                        }
+
+                        // Underpromotions are rarely better than a queen promotion, so they are ordered after every other move with the same value
+                        let mut promotion_heatmap_value = heatmap_value;
+                        if promotion.is_some() && promotion != Some(4) {
+                            promotion_heatmap_value = i16::MIN;
+                        }
 
-                    // If the piece can't move to the final bit, but is a king then add potential castling moves moves
-                    // Because king castling moves aren't a part of gen_piece, so they cannot be ruled out
-                    moves[moves_index] = Move {
+                        // Push move to moves array
+                        push_ordered_move(moves, &mut moves_index, &Move {
+                            initial_piece_coordinates: initial_piece_coordinates,
+                            final_piece_bit: final_bit,
+                            promotion: promotion,
+                            value: move_value,
+                            heatmap_value: promotion_heatmap_value,
+                            history_value: history_value,
+                        }, board, pieces_info);
+                    }
+                } else {
+
+                    // If the piece can't move to the final bit it is a castling move
+                    if hash_move_coordinates == Some((initial_piece_coordinates, final_bit, None)) {
+                        continue;
+                    }
+                    push_ordered_move(moves, &mut moves_index, &Move {
                         initial_piece_coordinates: initial_piece_coordinates,
                         final_piece_bit: final_bit,
+                        promotion: None,
                         value: 0,
                         heatmap_value: heatmap_value,
-                    };
-                    moves_index += 1;
+                        history_value: 0,
+                    }, board, pieces_info);
                 }
             }
         }
@@ -345,14 +1065,257 @@ fn order_moves(sort: bool, board: &board_representation::Board, enemy_attacks: &
     if sort {
 
         // Sort moves by value first
-        // Sort moves by heatmap_value if they have the same value
-        // https://stackoverflow.com/questions/70193935/how-to-sort-a-vec-of-structs-by-2-or-multiple-fields
+        // Sort moves by history_value if they have the same value (captures with the same value are ordered by capture history)
+        // Sort moves by heatmap_value if they have the same value and history_value
+        // The order of a move is made from these fields (see OrderedMove::new), so sorting by it sorts by all three
+        moves[sort_start..moves_index].sort_unstable_by(| a, b | b.order.cmp(&a.order));
+    }
+    moves_index
+}
 
-        moves.sort_unstable_by(| a, b | if a.value == b.value {
-            b.heatmap_value.partial_cmp(&a.heatmap_value).unwrap()
-        } else {
-            b.value.partial_cmp(&a.value).unwrap()
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Searches a FEN to a fixed depth, returns the best move and its value
+    fn search_fen(fen: &str, depth: usize) -> Result<(Move, i16), GameEnd> {
+        let search_params = SearchParams {
+            max_depth: depth,
+            ..SearchParams::new()
+        };
+        search_fen_params(fen, &search_params).0
+    }
+
+    // Searches a FEN with the search parameters, returns the result and the nodes searched
+    fn search_fen_params(fen: &str, search_params: &SearchParams) -> (Result<(Move, i16), GameEnd>, u64) {
+        let pieces_info = crate::piece::constants::gen();
+        let board = board_representation::Board::from_fen(fen).unwrap();
+
+        let mut hash_move_entries = [HashMoveEntry::new(); 256];
+        let mut eval_entries = [crate::evaluation::EvalCacheEntry::new(); 256];
+        let mut pawn_entries = [crate::evaluation::EvalCacheEntry::new(); 64];
+        let mut hash_moves = HashMoves::new(&mut hash_move_entries);
+        let mut eval_cache = crate::evaluation::EvalCache::new(&mut eval_entries);
+        let mut pawn_cache = crate::evaluation::EvalCache::new(&mut pawn_entries);
+        let stop = AtomicBool::new(false);
+
+        let mut search_data = SearchData {
+            cycle_counter: &mut crate::embedded::cycle_counter::Counter::new(),
+            start_cycles: 0,
+            nodes: 0,
+            stop: &stop,
+            progress: None,
+            capture_history: &mut CaptureHistory::new(),
+            hash_moves: &mut hash_moves,
+            eval_cache: &mut eval_cache,
+            pawn_cache: &mut pawn_cache,
+            opening_heatmap: &[[0; 64]; 12],
+            pieces_info: &pieces_info,
+        };
+        let result = search_best(board, search_params, &mut search_data);
+        (result, search_data.nodes)
+    }
+
+    // Positions with one clearly best move, the initial and final bits of the move, and the depth they are searched to
+    // A change to the search or evaluation that stops finding these is a regression
+    const BEST_MOVES: [(&str, usize, usize, usize); 6] = [
+        ("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", 56, 0, 3), // Ra8#, back rank mate
+        ("r5k1/8/8/8/8/8/5PPP/6K1 b - - 0 1", 0, 56, 3), // Ra1#, back rank mate for the black team
+        ("7k/8/5KQ1/8/8/8/8/8 w - - 0 1", 22, 14, 3), // Qg7#, the king protects the queen
+        ("4k3/8/8/3q4/8/8/3R4/4K3 w - - 0 1", 51, 27, 3), // Rxd5, the queen is hanging
+        ("4k3/8/8/3n4/4P3/8/8/4K3 w - - 0 1", 36, 27, 3), // exd5, the knight is hanging
+        ("2r3k1/6pp/8/3N4/8/8/6PP/6K1 w - - 0 1", 27, 12, 3), // Ne7+, forks the king and rook
+    ];
+
+    #[test]
+    fn best_moves() {
+        for (fen, initial_bit, final_bit, depth) in BEST_MOVES {
+            let (best_move, _) = search_fen(fen, depth).unwrap();
+            assert_eq!((best_move.initial_piece_coordinates.bit, best_move.final_piece_bit), (initial_bit, final_bit), "{}", fen);
+        }
+    }
+
+    // The values of mates are written as the moves until the checkmate, including the checkmating move
+    #[test]
+    fn mate_distance() {
+        let (best_move, value) = search_fen("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", 2).unwrap();
+        assert_eq!(best_move.final_piece_bit, 0);
+        assert_eq!(mate_moves(value), Some(1));
+
+        assert_eq!(mate_moves(MATE_VALUE), Some(1));
+        assert_eq!(mate_moves(MATE_VALUE + 20), Some(1));
+        assert_eq!(mate_moves(-(MATE_VALUE - 1)), Some(-1));
+        assert_eq!(mate_moves(MATE_VALUE - 2), Some(2));
+        assert_eq!(mate_moves(MATE_VALUE - 99), Some(50));
+        assert_eq!(mate_moves(MATE_VALUE - 100), None);
+        assert_eq!(mate_moves(35), None);
+    }
+
+    // A position with 218 legal moves, the most any position can have (UCI and the console can set up any legal position)
+    #[test]
+    fn most_legal_moves() {
+        let fen = "R6R/3Q4/1Q4Q1/4Q3/2Q4Q/Q4Q2/pp1Q4/kBNN1KB1 w - - 0 1";
+        assert!(search_fen(fen, 2).is_ok());
+
+        // Every move is ordered (king moves are only pseudo legal, so there can be more)
+        let pieces_info = crate::piece::constants::gen();
+        let board = board_representation::Board::from_fen(fen).unwrap();
+        let friendly_king = board_representation::BoardCoordinates {
+            board_index: 5,
+            bit: crate::find_bit_on(board.board[5], 0),
+        };
+        let team_bitboards = TeamBitboards::new(5, &board);
+        let enemy_attacks = crate::board::move_generator::gen_enemy_attacks(&friendly_king, team_bitboards, &board, &pieces_info);
+        let mut moves = [OrderedMove::new(&Move::new()); crate::board::move_generator::MAX_LEGAL_MOVES];
+        let moves_len = order_moves(true, None, &mut moves, 0, &board, &enemy_attacks, &friendly_king, &[[0; 64]; 12], &CaptureHistory::new(), &team_bitboards, &pieces_info);
+        assert!(moves_len >= 218);
+    }
+
+    // A full moves array only takes legal moves, which replace moves that aren't legal
+    #[test]
+    fn full_ordered_moves() {
+        let pieces_info = crate::piece::constants::gen();
+        let board = board_representation::Board::from_fen("4r1k1/8/8/8/8/8/4R3/4K3 w - - 0 1").unwrap(); // The rook on e2 is pinned
+
+        let piece_move = |initial_bit: usize, final_bit: usize| {
+            let mut piece_move = Move::new();
+            piece_move.initial_piece_coordinates = board_representation::BoardCoordinates {
+                board_index: board.piece_on(initial_bit).unwrap(),
+                bit: initial_bit,
+            };
+            piece_move.final_piece_bit = final_bit;
+            piece_move
+        };
+        let re4 = piece_move(52, 36);
+        let rd2 = piece_move(52, 51); // Illegal, the rook is pinned to the king
+        let kd1 = piece_move(60, 59);
+        let kf1 = piece_move(60, 61);
+
+        let mut moves = [OrderedMove::new(&Move::new()); 2];
+        let mut moves_index = 0;
+        push_ordered_move(&mut moves, &mut moves_index, &re4, &board, &pieces_info);
+        push_ordered_move(&mut moves, &mut moves_index, &rd2, &board, &pieces_info);
+        assert_eq!(moves_index, 2);
+
+        // The legal king move replaces the pinned rook move, and the array is still full so the count doesn't change
+        push_ordered_move(&mut moves, &mut moves_index, &kd1, &board, &pieces_info);
+        assert_eq!(moves_index, 2);
+        assert_eq!(moves[0].to_move(), re4);
+        assert_eq!(moves[1].to_move(), kd1);
+
+        // There is no illegal move left to replace, so moves are left out (this can't happen in order_moves, which has space for every legal move)
+        push_ordered_move(&mut moves, &mut moves_index, &kf1, &board, &pieces_info);
+        push_ordered_move(&mut moves, &mut moves_index, &rd2, &board, &pieces_info);
+        assert_eq!(moves[0].to_move(), re4);
+        assert_eq!(moves[1].to_move(), kd1);
+    }
+
+    // Packed moves unpack to the same move, and their order is the same as sorting by value, then history_value, then heatmap_value
+    #[test]
+    fn ordered_moves() {
+        let mut piece_move = Move::new();
+        piece_move.initial_piece_coordinates = board_representation::BoardCoordinates {
+            board_index: 6,
+            bit: 52,
+        };
+        piece_move.final_piece_bit = 61;
+        piece_move.promotion = Some(2);
+        assert_eq!(OrderedMove::new(&piece_move).to_move(), piece_move);
+
+        piece_move.promotion = None;
+        assert_eq!(OrderedMove::new(&piece_move).to_move(), piece_move);
+
+        let mut order = |value: i8, history_value: i8, heatmap_value: i16| {
+            piece_move.value = value;
+            piece_move.history_value = history_value;
+            piece_move.heatmap_value = heatmap_value;
+            OrderedMove::new(&piece_move).order
+        };
+        assert!(order(1, i8::MIN, i16::MIN) > order(0, i8::MAX, i16::MAX));
+        assert!(order(-1, i8::MAX, i16::MAX) < order(0, i8::MIN, i16::MIN));
+        assert!(order(i8::MIN, 1, i16::MIN) > order(i8::MIN, 0, i16::MAX));
+        assert!(order(i8::MAX, -1, i16::MAX) < order(i8::MAX, 0, i16::MIN));
+        assert!(order(0, 0, 1) > order(0, 0, 0));
+        assert!(order(0, 0, i16::MIN) < order(0, 0, -1));
+    }
+
+    // Only captures which were searched before the cutoff are penalized
+    // An empty move reads as a white pawn on a8, and there is a black rook on a8, so it would look like a capture if it was penalized
+    #[test]
+    fn capture_history_searched_moves() {
+        let board = board_representation::Board::from_fen("r3k3/8/8/3p1n2/4P3/8/8/4K3 w - - 0 1").unwrap();
+
+        let mut exd5 = Move::new();
+        exd5.final_piece_bit = 27;
+        exd5.initial_piece_coordinates.bit = 36;
+        let mut exf5 = exd5;
+        exf5.final_piece_bit = 29;
+
+        let moves = [OrderedMove::new(&Move::new()), OrderedMove::new(&exf5), OrderedMove::new(&exd5)];
+        let bonus = 9;
+
+        // Only the cutoff move is searched, exf5 is an unsearched (e.g. invalid) move
+        let mut searched = SearchedMoves::new();
+        searched.insert(2);
+
+        let mut capture_history = CaptureHistory::new();
+        update_capture_history(&mut capture_history, &moves, &searched, 2, bonus, &board);
+
+        let mut expected = CaptureHistory::new();
+        expected.update(0, 6, 27, bonus);
+        assert_eq!(capture_history, expected);
+
+        // Once exf5 has been searched it is penalized
+        searched.insert(1);
+        let mut capture_history = CaptureHistory::new();
+        update_capture_history(&mut capture_history, &moves, &searched, 2, bonus, &board);
+
+        expected.update(0, 8, 29, -bonus);
+        assert_eq!(capture_history, expected);
+    }
+
+    // Searching the best move from the last time a board was searched first gives more cutoffs, at every node and not just the root
+    #[test]
+    fn hash_move_nodes() {
+        let fen = "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3";
+        let search_params = SearchParams {
+            max_depth: 5,
+            ..SearchParams::new()
+        };
+        let (result, nodes) = search_fen_params(fen, &search_params);
+        let (result_without, nodes_without) = search_fen_params(fen, &SearchParams {
+            hash_move: false,
+            ..search_params
         });
+
+        assert!(result.is_ok() && result_without.is_ok());
+        assert!(nodes < nodes_without, "{} nodes with the hash move, {} without", nodes, nodes_without);
+    }
+
+    // Stored moves are returned for the same board, and found with the mailbox
+    #[test]
+    fn hash_moves() {
+        let pieces_info = crate::piece::constants::gen();
+        let board = board_representation::Board::from_fen("4k3/P7/8/8/8/8/8/4K3 w - - 0 1").unwrap();
+        let other_board = crate::board::move_generator::make_move(&board_representation::BoardCoordinates { board_index: 5, bit: 60 }, 61, None, board, &pieces_info).unwrap();
+
+        let mut entries = [HashMoveEntry::new(); 16];
+        let mut hash_moves = HashMoves::new(&mut entries);
+        assert_eq!(hash_moves.probe(&board), None);
+
+        let mut piece_move = Move::new();
+        piece_move.initial_piece_coordinates.bit = 8;
+        piece_move.promotion = Some(2);
+        hash_moves.store(&board, &piece_move);
+        assert_eq!(hash_moves.probe(&board), Some(piece_move));
+        assert_eq!(hash_moves.probe(&other_board), None);
+
+        hash_moves.clear();
+        assert_eq!(hash_moves.probe(&board), None);
+
+        // A table without entries never has a move
+        let mut hash_moves = HashMoves::new(&mut []);
+        hash_moves.store(&board, &piece_move);
+        assert_eq!(hash_moves.probe(&board), None);
     }
-    moves
-}
\ No newline at end of file
+}
@@ -0,0 +1,327 @@
+// Attack lookups for every piece
+// Knights, kings and pawns have the same attacks on every board, so they are stored for every bit
+// Sliding pieces (rooks, bishops and queens) return every bit they attack on a board with the pieces in the occupancy bitboard, including the first piece they hit in each direction
+// By default kindergarten bitboards are used, which only need ~4.5kB of tables so they fit in the STM32F103's flash
+// The pext feature uses PEXT indexed tables instead, they are faster but use ~840kB of flash so they are only for hosts
+// https://www.chessprogramming.org/Kindergarten_Bitboards
+// https://www.chessprogramming.org/BMI2#PEXTBitboards
+
+use crate::rays;
+
+// Column and row deltas of each piece's moves
+const KNIGHT_DELTAS: [[i8; 2]; 8] = [[-1, -2], [1, -2], [-2, -1], [2, -1], [-2, 1], [2, 1], [-1, 2], [1, 2]];
+const KING_DELTAS: [[i8; 2]; 8] = [[-1, -1], [0, -1], [1, -1], [-1, 0], [1, 0], [-1, 1], [0, 1], [1, 1]];
+const WHITE_PAWN_CAPTURE_DELTAS: [[i8; 2]; 8] = [[-1, -1], [1, -1], [0, 0], [0, 0], [0, 0], [0, 0], [0, 0], [0, 0]];
+const BLACK_PAWN_CAPTURE_DELTAS: [[i8; 2]; 8] = [[-1, 1], [1, 1], [0, 0], [0, 0], [0, 0], [0, 0], [0, 0], [0, 0]];
+
+// Bits a knight or king on a bit attacks
+// Generated at compile time so there is no runtime initialisation
+pub const KNIGHT_ATTACKS: [u64; 64] = gen_step_attacks(KNIGHT_DELTAS, 8);
+pub const KING_ATTACKS: [u64; 64] = gen_step_attacks(KING_DELTAS, 8);
+
+// Bits a pawn on a bit attacks, indexed by [team][bit] where team 0 is white and 1 is black
+pub const PAWN_ATTACKS: [[u64; 64]; 2] = [gen_step_attacks(WHITE_PAWN_CAPTURE_DELTAS, 2), gen_step_attacks(BLACK_PAWN_CAPTURE_DELTAS, 2)];
+
+// Bit a pawn on a bit moves to without capturing, indexed by [team][bit] where team 0 is white and 1 is black
+// Empty for pawns on the last row, the move from the first row a pawn can move 2 squares from is PAWN_PUSHES of the bit it moves to
+pub const PAWN_PUSHES: [[u64; 64]; 2] = [gen_step_attacks([[0, -1], [0, 0], [0, 0], [0, 0], [0, 0], [0, 0], [0, 0], [0, 0]], 1), gen_step_attacks([[0, 1], [0, 0], [0, 0], [0, 0], [0, 0], [0, 0], [0, 0], [0, 0]], 1)];
+
+// Attacks of a piece that moves once in each direction, only the first deltas_no deltas are used
+const fn gen_step_attacks(deltas: [[i8; 2]; 8], deltas_no: usize) -> [u64; 64] {
+    let mut attacks = [0; 64];
+
+    let mut bit = 0;
+    while bit < 64 {
+        let mut i = 0;
+        while i < deltas_no {
+            let column = (bit % 8) as i8 + deltas[i][0];
+            let row = (bit / 8) as i8 + deltas[i][1];
+
+            // Don't move off the edge of the board
+            if column >= 0 && column < 8 && row >= 0 && row < 8 {
+                attacks[bit] |= 1 << (row * 8 + column);
+            }
+            i += 1;
+        }
+        bit += 1;
+    }
+
+    attacks
+}
+
+// Returns the index of a team in the pawn tables
+pub fn pawn_team(board_index: usize) -> usize {
+    if crate::board_index_white(board_index) {
+        0
+    } else {
+        1
+    }
+}
+
+// Returns the bits a rook on a bit attacks
+pub fn rook_attacks(bit: usize, occupancy: u64) -> u64 {
+    lookup::rook_attacks(bit, occupancy)
+}
+
+// Returns the bits a bishop on a bit attacks
+pub fn bishop_attacks(bit: usize, occupancy: u64) -> u64 {
+    lookup::bishop_attacks(bit, occupancy)
+}
+
+// Returns the bits a queen on a bit attacks
+pub fn queen_attacks(bit: usize, occupancy: u64) -> u64 {
+    rook_attacks(bit, occupancy) | bishop_attacks(bit, occupancy)
+}
+
+// Attacks found by moving one square at a time in every direction until a piece is hit
+// Only used to generate the lookup tables at compile time
+const fn slow_attacks(bit: usize, occupancy: u64, directions: [usize; 4]) -> u64 {
+    let mut attacks = 0;
+
+    let mut i = 0;
+    while i < 4 {
+        let direction = directions[i];
+        let blockers = rays::RAYS[direction][bit] & occupancy;
+
+        // Stop at the closest blocker, positive directions move towards higher bits
+        let mut ray = rays::RAYS[direction][bit];
+        if blockers != 0 {
+            let blocker_bit;
+            if rays::DIRECTIONS[direction] > 0 {
+                blocker_bit = blockers.trailing_zeros() as usize;
+            } else {
+                blocker_bit = 63 - blockers.leading_zeros() as usize;
+            }
+            ray ^= rays::RAYS[direction][blocker_bit];
+        }
+
+        attacks |= ray;
+        i += 1;
+    }
+
+    attacks
+}
+
+#[cfg(not(feature = "pext"))]
+mod lookup {
+    use crate::rays;
+
+    // Attacks along a row from every column, indexed by [column][inner occupancy]
+    // The inner occupancy is the occupancy of the row without the edge columns (the edges are always attacked if the ray reaches them), shifted down 1 bit
+    const ROW_ATTACKS: [[u8; 64]; 8] = gen_row_attacks();
+
+    // Attacks along the a column from every row, indexed by [row][inner occupancy], shifted to other columns when used
+    const COLUMN_ATTACKS: [[u64; 64]; 8] = gen_column_attacks();
+
+    const A_COLUMN: u64 = 0x0101010101010101;
+    const B_COLUMN: u64 = 0x0202020202020202;
+
+    // Multiplying by this moves rows 1 to 6 of the a column into the top 6 bits, in order
+    const COLUMN_MULTIPLIER: u64 = 1 << 50 | 1 << 43 | 1 << 36 | 1 << 29 | 1 << 22 | 1 << 15;
+
+    const fn gen_row_attacks() -> [[u8; 64]; 8] {
+        let mut table = [[0; 64]; 8];
+
+        let mut column = 0;
+        while column < 8 {
+            let mut inner_occupancy = 0;
+            while inner_occupancy < 64 {
+                let attacks = super::slow_attacks(column, (inner_occupancy as u64) << 1, [2, 3, 2, 3]);
+                table[column][inner_occupancy] = attacks as u8;
+                inner_occupancy += 1;
+            }
+            column += 1;
+        }
+
+        table
+    }
+
+    const fn gen_column_attacks() -> [[u64; 64]; 8] {
+        let mut table = [[0; 64]; 8];
+
+        let mut row = 0;
+        while row < 8 {
+            let mut inner_occupancy = 0;
+            while inner_occupancy < 64 {
+
+                // Spread the inner occupancy over rows 1 to 6 of the a column
+                let mut occupancy = 0;
+                let mut i = 0;
+                while i < 6 {
+                    if inner_occupancy & (1 << i) != 0 {
+                        occupancy |= 1 << ((i + 1) * 8);
+                    }
+                    i += 1;
+                }
+
+                table[row][inner_occupancy] = super::slow_attacks(row * 8, occupancy, [0, 1, 0, 1]);
+                inner_occupancy += 1;
+            }
+            row += 1;
+        }
+
+        table
+    }
+
+    pub fn rook_attacks(bit: usize, occupancy: u64) -> u64 {
+        let row = bit / 8;
+        let column = bit % 8;
+
+        // Inner occupancy of the row is the 6 bits after the first bit of the row
+        let row_index = (occupancy >> (row * 8 + 1)) as usize & 63;
+        let row_attacks = (ROW_ATTACKS[column][row_index] as u64) << (row * 8);
+
+        // Move the column to the a column and collapse its inner rows into the top 6 bits
+        let column_index = (((occupancy >> column) & A_COLUMN).wrapping_mul(COLUMN_MULTIPLIER) >> 58) as usize;
+        let column_attacks = COLUMN_ATTACKS[row][column_index] << column;
+
+        row_attacks | column_attacks
+    }
+
+    pub fn bishop_attacks(bit: usize, occupancy: u64) -> u64 {
+        let column = bit % 8;
+
+        let diagonal = rays::RAYS[4][bit] | rays::RAYS[5][bit];
+        let anti_diagonal = rays::RAYS[6][bit] | rays::RAYS[7][bit];
+
+        diagonal_attacks(column, occupancy, diagonal) | diagonal_attacks(column, occupancy, anti_diagonal)
+    }
+
+    // Every column of a diagonal has one bit, so multiplying by the b column collapses the diagonal into the top row without carries
+    // The attacks along the top row are then filled down every row, and the diagonal picks out its bit in every attacked column
+    fn diagonal_attacks(column: usize, occupancy: u64, diagonal: u64) -> u64 {
+        let index = ((occupancy & diagonal).wrapping_mul(B_COLUMN) >> 58) as usize;
+        (ROW_ATTACKS[column][index] as u64).wrapping_mul(A_COLUMN) & diagonal
+    }
+}
+
+#[cfg(feature = "pext")]
+mod lookup {
+    use crate::rays;
+
+    // Directions (see rays::DIRECTIONS) of each piece
+    const ROOK_DIRECTIONS: [usize; 4] = [0, 1, 2, 3];
+    const BISHOP_DIRECTIONS: [usize; 4] = [4, 5, 6, 7];
+
+    // Squares whose occupancy changes a piece's attacks, the edge of each ray isn't included because it's always attacked if the ray reaches it
+    const ROOK_MASKS: [u64; 64] = gen_masks(ROOK_DIRECTIONS);
+    const BISHOP_MASKS: [u64; 64] = gen_masks(BISHOP_DIRECTIONS);
+
+    // Offsets of each bit's attacks in ATTACKS, rooks then bishops
+    const ROOK_OFFSETS: [usize; 64] = gen_offsets(&ROOK_MASKS, 0);
+    const BISHOP_OFFSETS: [usize; 64] = gen_offsets(&BISHOP_MASKS, ROOK_ATTACKS_LEN);
+
+    const ROOK_ATTACKS_LEN: usize = 102400;
+    const ATTACKS_LEN: usize = ROOK_ATTACKS_LEN + 5248;
+
+    // Attacks of every bit and occupancy, indexed by the offset of the bit plus the occupancy extracted with the bit's mask
+    // A static so the table is only stored once
+    #[allow(long_running_const_eval)]
+    static ATTACKS: [u64; ATTACKS_LEN] = gen_attacks();
+
+    const fn gen_masks(directions: [usize; 4]) -> [u64; 64] {
+        let mut masks = [0; 64];
+
+        let mut bit = 0;
+        while bit < 64 {
+            let mut i = 0;
+            while i < 4 {
+                let ray = rays::RAYS[directions[i]][bit];
+
+                // Remove the last bit of the ray (the edge of the board)
+                if ray != 0 {
+                    let edge_bit;
+                    if rays::DIRECTIONS[directions[i]] > 0 {
+                        edge_bit = 63 - ray.leading_zeros() as usize;
+                    } else {
+                        edge_bit = ray.trailing_zeros() as usize;
+                    }
+                    masks[bit] |= ray & !(1 << edge_bit);
+                }
+                i += 1;
+            }
+            bit += 1;
+        }
+
+        masks
+    }
+
+    const fn gen_offsets(masks: &[u64; 64], start: usize) -> [usize; 64] {
+        let mut offsets = [0; 64];
+
+        let mut offset = start;
+        let mut bit = 0;
+        while bit < 64 {
+            offsets[bit] = offset;
+            offset += 1 << masks[bit].count_ones();
+            bit += 1;
+        }
+
+        offsets
+    }
+
+    const fn gen_attacks() -> [u64; ATTACKS_LEN] {
+        let mut attacks = [0; ATTACKS_LEN];
+
+        let mut bit = 0;
+        while bit < 64 {
+
+            // Counting up through the subsets of a mask (carry rippler) gives them in the same order as their extracted index
+            let mut subset: u64 = 0;
+            let mut index = 0;
+            loop {
+                attacks[ROOK_OFFSETS[bit] + index] = super::slow_attacks(bit, subset, ROOK_DIRECTIONS);
+                index += 1;
+                subset = subset.wrapping_sub(ROOK_MASKS[bit]) & ROOK_MASKS[bit];
+                if subset == 0 {
+                    break;
+                }
+            }
+
+            let mut subset: u64 = 0;
+            let mut index = 0;
+            loop {
+                attacks[BISHOP_OFFSETS[bit] + index] = super::slow_attacks(bit, subset, BISHOP_DIRECTIONS);
+                index += 1;
+                subset = subset.wrapping_sub(BISHOP_MASKS[bit]) & BISHOP_MASKS[bit];
+                if subset == 0 {
+                    break;
+                }
+            }
+
+            bit += 1;
+        }
+
+        attacks
+    }
+
+    // Gathers the bits of a value under a mask into the low bits
+    // Uses the BMI2 instruction when the target has it (e.g. -C target-cpu=native), otherwise a loop over the mask
+    #[cfg(all(target_arch = "x86_64", target_feature = "bmi2"))]
+    fn pext(value: u64, mask: u64) -> usize {
+        unsafe { core::arch::x86_64::_pext_u64(value, mask) as usize }
+    }
+
+    #[cfg(not(all(target_arch = "x86_64", target_feature = "bmi2")))]
+    fn pext(value: u64, mut mask: u64) -> usize {
+        let mut result = 0;
+        let mut result_bit = 1;
+        while mask != 0 {
+            let lowest_bit = mask & mask.wrapping_neg();
+            if value & lowest_bit != 0 {
+                result |= result_bit;
+            }
+            result_bit <<= 1;
+            mask ^= lowest_bit;
+        }
+        result
+    }
+
+    pub fn rook_attacks(bit: usize, occupancy: u64) -> u64 {
+        ATTACKS[ROOK_OFFSETS[bit] + pext(occupancy, ROOK_MASKS[bit])]
+    }
+
+    pub fn bishop_attacks(bit: usize, occupancy: u64) -> u64 {
+        ATTACKS[BISHOP_OFFSETS[bit] + pext(occupancy, BISHOP_MASKS[bit])]
+    }
+}
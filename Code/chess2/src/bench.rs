@@ -0,0 +1,158 @@
+use crate::algorithm;
+use crate::board::board_representation;
+use core::sync::atomic::AtomicBool;
+
+// Positions searched by bench, a mix of openings, middlegames and endgames
+pub const BENCH_POSITIONS: [&str; 8] = [
+    "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+    "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3",
+    "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+    "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+    "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+    "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+    "6k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - 0 1",
+    "8/8/8/4k3/8/8/4P3/4K3 w - - 0 1",
+];
+
+// Depth every bench position is searched to
+pub const BENCH_DEPTH: usize = 4;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct BenchResult {
+    pub nodes: u64, // Total nodes searched
+    pub signature: u64, // Hash of the node counts and best moves, changes if the search behaves differently
+}
+
+// Searches every bench position to BENCH_DEPTH
+// A refactor which isn't meant to change the search should give the same result before and after it
+// Every position is searched with empty caches, hash moves, capture history and opening heatmap, so the result doesn't depend on earlier searches
+pub fn bench<'b>(
+    cycle_counter: &mut crate::embedded::cycle_counter::Counter,
+    hash_moves: &mut algorithm::HashMoves<'b>,
+    eval_cache: &mut crate::evaluation::EvalCache<'b>,
+    pawn_cache: &mut crate::evaluation::EvalCache<'b>,
+    pieces_info: &[crate::piece::constants::PieceInfo; 12],
+) -> BenchResult {
+    let search_params = algorithm::SearchParams {
+        max_depth: BENCH_DEPTH,
+        ..algorithm::SearchParams::new()
+    };
+
+    let opening_heatmap = [[0; 64]; 12];
+    let stop = AtomicBool::new(false);
+
+    let mut result = BenchResult {
+        nodes: 0,
+        signature: 0xCBF29CE484222325, // FNV offset basis
+    };
+
+    for fen in BENCH_POSITIONS {
+        let board = board_representation::Board::from_fen(fen).unwrap();
+
+        hash_moves.clear();
+        eval_cache.clear();
+        pawn_cache.clear();
+        let mut capture_history = algorithm::CaptureHistory::new();
+
+        let mut search_data = algorithm::SearchData {
+            cycle_counter: &mut *cycle_counter,
+            start_cycles: 0,
+            nodes: 0,
+            stop: &stop,
+            progress: None,
+            capture_history: &mut capture_history,
+            hash_moves: &mut *hash_moves,
+            eval_cache: &mut *eval_cache,
+            pawn_cache: &mut *pawn_cache,
+            opening_heatmap: &opening_heatmap,
+            pieces_info: pieces_info,
+        };
+
+        let best_move = algorithm::gen_best_move(board, &search_params, &mut search_data);
+
+        result.nodes += search_data.nodes;
+        result.signature = fnv_fold(result.signature, search_data.nodes);
+        match best_move {
+            Ok(piece_move) => {
+                result.signature = fnv_fold(result.signature, piece_move.initial_piece_coordinates.bit as u64);
+                result.signature = fnv_fold(result.signature, piece_move.final_piece_bit as u64);
+            },
+            Err(_) => (),
+        }
+    }
+
+    result
+}
+
+// Depth every bench position is counted to by perft_bench
+pub const PERFT_BENCH_DEPTH: usize = 3;
+
+// Counts the leaf nodes of every bench position to PERFT_BENCH_DEPTH
+// Only the move generator is used, so timing this measures move generation without the search or evaluation
+// The node count never changes unless the move generator has a bug (208824 nodes)
+pub fn perft_bench(pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> u64 {
+    let mut nodes = 0;
+    for fen in BENCH_POSITIONS {
+        let board = board_representation::Board::from_fen(fen).unwrap();
+        nodes += crate::board::perft::perft(board, PERFT_BENCH_DEPTH, pieces_info);
+    }
+    nodes
+}
+
+// Combines a value into an FNV-1a hash
+fn fnv_fold(hash: u64, value: u64) -> u64 {
+    (hash ^ value).wrapping_mul(0x100000001B3)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    // Pins the result of bench, with the cache sizes the firmware uses (see main.rs)
+    // A change that isn't meant to change the search has to keep both values, a change to the search updates them
+    #[test]
+    fn bench_result() {
+        let pieces_info = crate::piece::constants::gen();
+        let mut cycle_counter = crate::embedded::cycle_counter::Counter::new();
+        let mut hash_move_entries = [algorithm::HashMoveEntry::new(); 64];
+        let mut hash_moves = algorithm::HashMoves::new(&mut hash_move_entries);
+        let mut eval_cache_entries = [crate::evaluation::EvalCacheEntry::new(); 128];
+        let mut eval_cache = crate::evaluation::EvalCache::new(&mut eval_cache_entries);
+        let mut pawn_cache_entries = [crate::evaluation::EvalCacheEntry::new(); 64];
+        let mut pawn_cache = crate::evaluation::EvalCache::new(&mut pawn_cache_entries);
+
+        let expected = BenchResult {
+            nodes: 11551,
+            signature: 2799132668045036293,
+        };
+        assert_eq!(bench(&mut cycle_counter, &mut hash_moves, &mut eval_cache, &mut pawn_cache, &pieces_info), expected);
+
+        // Every position starts with empty caches, so a second bench gives the same result
+        assert_eq!(bench(&mut cycle_counter, &mut hash_moves, &mut eval_cache, &mut pawn_cache, &pieces_info), expected);
+    }
+
+    #[test]
+    fn perft_nodes() {
+        let pieces_info = crate::piece::constants::gen();
+        assert_eq!(perft_bench(&pieces_info), 208824);
+    }
+
+    // Times the move generator on its own, run with --release -- --ignored --nocapture to see the speed
+    // The best of several runs is reported so other work on the host doesn't skew it
+    // It's ignored so the other tests aren't slowed down by it, perft_nodes checks the node count
+    #[test]
+    #[ignore]
+    fn perft_speed() {
+        let pieces_info = crate::piece::constants::gen();
+
+        let mut best = std::time::Duration::MAX;
+        for _ in 0..5 {
+            let start = std::time::Instant::now();
+            perft_bench(&pieces_info);
+            best = best.min(start.elapsed());
+        }
+
+        let knps = 208824 * 1000 / best.as_micros().max(1);
+        std::println!("perft bench: 208824 nodes in {} ms ({} knps)", best.as_millis(), knps);
+    }
+}
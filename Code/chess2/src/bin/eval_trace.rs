@@ -0,0 +1,35 @@
+// Prints the contribution of every evaluation term for a position
+// Run on a host with the std and eval-trace features, e.g.
+// cargo run --no-default-features --features std,eval-trace --target x86_64-unknown-linux-gnu --bin eval_trace -- "<fen>"
+
+use chess2::board::board_representation::Board;
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 2 {
+        eprintln!("Usage: eval_trace <fen>");
+        std::process::exit(1);
+    }
+
+    let board;
+    match Board::from_fen(&args[1]) {
+        Ok(fen_board) => board = fen_board,
+        Err(error) => {
+            eprintln!("Invalid fen {} ({:?})", args[1], error);
+            std::process::exit(1);
+        },
+    }
+
+    let pieces_info = chess2::piece::constants::gen();
+    let trace = chess2::evaluation::evaluate_trace(&board, &pieces_info);
+    println!("{}", trace);
+
+    // The mirrored board must evaluate to the negative of the board, otherwise a term has a sign bug
+    let mirrored_trace = chess2::evaluation::evaluate_trace(&board.mirror(), &pieces_info);
+    if mirrored_trace.total != -trace.total {
+        println!();
+        println!("Evaluation isn't symmetric, the mirrored board evaluates to {}", mirrored_trace.total);
+        println!("{}", mirrored_trace);
+        std::process::exit(2);
+    }
+}
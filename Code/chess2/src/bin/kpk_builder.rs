@@ -0,0 +1,7 @@
+// Generates the KPK bitbase and prints the compressed table as Rust source for src/kpk_table.rs
+// Run on a host with the std feature, e.g.
+// cargo run --no-default-features --features std --target x86_64-unknown-linux-gnu --bin kpk_builder > src/kpk_table.rs
+
+fn main() {
+    print!("{}", chess2::kpk_builder::table_source());
+}
@@ -0,0 +1,71 @@
+// Trains the neural network evaluation and prints the new src/nnue_weights.rs
+// Run on a host with the std and nnue features, e.g.
+// cargo run --release --no-default-features --features std,nnue --target x86_64-unknown-linux-gnu --bin nnue_trainer -- positions.epd 20 > src/nnue_weights.rs
+// Use "random <number of positions>" instead of a positions file to train on the classical evaluation of random positions
+
+use chess2::nnue_trainer::{self, Network};
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 2 {
+        eprintln!("Usage: nnue_trainer <training positions file> [epochs]");
+        eprintln!("       nnue_trainer random <number of positions> [epochs]");
+        std::process::exit(1);
+    }
+
+    let pieces_info = chess2::piece::constants::gen();
+
+    let mut samples;
+    let epochs_arg;
+    if args[1] == "random" {
+        let mut count = 100000;
+        if args.len() > 2 {
+            match args[2].parse() {
+                Ok(positions) => count = positions,
+                Err(_) => {
+                    eprintln!("Invalid number of positions {}", args[2]);
+                    std::process::exit(1);
+                },
+            }
+        }
+
+        samples = nnue_trainer::samples_from_classical(&nnue_trainer::random_boards(count, 0x9E3779B97F4A7C15, &pieces_info), &pieces_info);
+        epochs_arg = 3;
+    } else {
+        let text;
+        match std::fs::read_to_string(&args[1]) {
+            Ok(contents) => text = contents,
+            Err(error) => {
+                eprintln!("Couldn't read {}: {}", args[1], error);
+                std::process::exit(1);
+            },
+        }
+
+        samples = nnue_trainer::samples_from_results(&chess2::tuner::parse_positions(&text));
+        epochs_arg = 2;
+    }
+
+    if samples.len() == 0 {
+        eprintln!("No training positions");
+        std::process::exit(1);
+    }
+
+    let mut epochs = 10;
+    if args.len() > epochs_arg {
+        match args[epochs_arg].parse() {
+            Ok(epochs_number) => epochs = epochs_number,
+            Err(_) => {
+                eprintln!("Invalid number of epochs {}", args[epochs_arg]);
+                std::process::exit(1);
+            },
+        }
+    }
+
+    let mut network = Network::new(0x2545F4914F6CDD1D);
+    eprintln!("{} positions, error = {:.6}", samples.len(), network.error(&samples));
+    network.train(&mut samples, epochs, 0.005, 0x853C49E6748FEA9B, &mut |epoch, error| {
+        eprintln!("Epoch {}, error = {:.6}", epoch, error);
+    });
+
+    print!("{}", network.weights_source());
+}
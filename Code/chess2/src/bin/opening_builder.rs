@@ -0,0 +1,42 @@
+// Builds the opening heatmap and the opening book from a PGN database
+// Run on a host with the std feature, e.g.
+// cargo run --no-default-features --features std --target x86_64-unknown-linux-gnu --bin opening_builder -- games.pgn 5
+
+use chess2::opening_builder::OpeningStats;
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 2 {
+        eprintln!("Usage: opening_builder <pgn file> [minimum times a move is played to be in the book]");
+        std::process::exit(1);
+    }
+
+    let pgn;
+    match std::fs::read_to_string(&args[1]) {
+        Ok(contents) => pgn = contents,
+        Err(error) => {
+            eprintln!("Couldn't read {}: {}", args[1], error);
+            std::process::exit(1);
+        },
+    }
+
+    let mut min_count = 2;
+    if args.len() > 2 {
+        match args[2].parse() {
+            Ok(count) => min_count = count,
+            Err(_) => {
+                eprintln!("Invalid minimum count {}", args[2]);
+                std::process::exit(1);
+            },
+        }
+    }
+
+    let pieces_info = chess2::piece::constants::gen();
+    let mut stats = OpeningStats::new();
+    stats.add_pgn(&pgn, &pieces_info);
+
+    println!("// Built from {} games", stats.games);
+    println!("opening_heatmap = {};", stats.heatmap_source());
+    println!();
+    print!("{}", stats.book_source(min_count));
+}
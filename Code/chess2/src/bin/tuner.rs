@@ -0,0 +1,58 @@
+// Tunes the evaluation weights from training positions and prints the new src/eval_weights.rs
+// Run on a host with the std feature, e.g.
+// cargo run --release --no-default-features --features std --target x86_64-unknown-linux-gnu --bin tuner -- positions.epd 100 > src/eval_weights.rs
+
+use chess2::tuner;
+
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() < 2 {
+        eprintln!("Usage: tuner <training positions file or .pgn file> [maximum iterations]");
+        std::process::exit(1);
+    }
+
+    let text;
+    match std::fs::read_to_string(&args[1]) {
+        Ok(contents) => text = contents,
+        Err(error) => {
+            eprintln!("Couldn't read {}: {}", args[1], error);
+            std::process::exit(1);
+        },
+    }
+
+    let mut max_iterations = 100;
+    if args.len() > 2 {
+        match args[2].parse() {
+            Ok(iterations) => max_iterations = iterations,
+            Err(_) => {
+                eprintln!("Invalid maximum iterations {}", args[2]);
+                std::process::exit(1);
+            },
+        }
+    }
+
+    let pieces_info = chess2::piece::constants::gen();
+
+    // Positions are taken from every game in a PGN database
+    let positions;
+    if args[1].ends_with(".pgn") {
+        positions = tuner::positions_from_pgn(&text, &pieces_info);
+    } else {
+        positions = tuner::parse_positions(&text);
+    }
+    if positions.len() == 0 {
+        eprintln!("No training positions in {}", args[1]);
+        std::process::exit(1);
+    }
+
+    let weights = chess2::evaluation::WEIGHTS;
+
+    let k = tuner::find_k(&positions, &weights, &pieces_info);
+    eprintln!("{} positions, K = {:.4}, error = {:.6}", positions.len(), k, tuner::error(&positions, &weights, k, &pieces_info));
+
+    let tuned_weights = tuner::tune(&positions, &weights, k, max_iterations, &pieces_info, &mut |iteration, error| {
+        eprintln!("Iteration {}, error = {:.6}", iteration, error);
+    });
+
+    print!("{}", tuner::weights_source(&tuned_weights));
+}
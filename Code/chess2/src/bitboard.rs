@@ -0,0 +1,77 @@
+// Helpers for working with the bits of a bitboard
+// Looping over the bits that are on with trailing_zeros is much faster than checking all 64 bits with bit_on, especially for bitboards with only a few bits on
+
+// Bitboards of the first and last columns, used to stop shifted bits wrapping onto the next row
+pub const A_COLUMN: u64 = 0x0101010101010101;
+pub const H_COLUMN: u64 = 0x8080808080808080;
+
+// Iterator over the bits that are on in a bitboard, from the lowest bit to the highest bit
+// E.g. for bit in bitboard::bits(board.board[0]) loops through every white pawn
+#[derive(Copy, Clone, Debug)]
+pub struct Bits {
+    bitboard: u64,
+}
+
+impl Iterator for Bits {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.bitboard == 0 {
+            return None;
+        }
+        Some(pop_lsb(&mut self.bitboard))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let bits = count(self.bitboard);
+        (bits, Some(bits))
+    }
+}
+
+impl ExactSizeIterator for Bits {}
+
+// Returns an iterator over the bits that are on in a bitboard
+pub const fn bits(bitboard: u64) -> Bits {
+    Bits {
+        bitboard: bitboard,
+    }
+}
+
+// Returns the lowest bit that is on in a bitboard
+// The bitboard shouldn't be empty, 64 is returned if it is
+pub const fn lsb(bitboard: u64) -> usize {
+    bitboard.trailing_zeros() as usize
+}
+
+// Turns off the lowest bit that is on in a bitboard and returns it
+// The bitboard shouldn't be empty, 64 is returned if it is
+pub fn pop_lsb(bitboard: &mut u64) -> usize {
+    let bit = lsb(*bitboard);
+    *bitboard &= bitboard.wrapping_sub(1); // Turn off the lowest bit
+    bit
+}
+
+// Returns the number of bits that are on in a bitboard
+pub const fn count(bitboard: u64) -> usize {
+    bitboard.count_ones() as usize
+}
+
+// Shifts every bit one row towards the black team (row 8), bits on row 8 are shifted off the board
+pub const fn north_one(bitboard: u64) -> u64 {
+    bitboard >> 8
+}
+
+// Shifts every bit one row towards the white team (row 1), bits on row 1 are shifted off the board
+pub const fn south_one(bitboard: u64) -> u64 {
+    bitboard << 8
+}
+
+// Shifts every bit one column towards the h column, bits on the h column are shifted off the board
+pub const fn east_one(bitboard: u64) -> u64 {
+    (bitboard & !H_COLUMN) << 1
+}
+
+// Shifts every bit one column towards the a column, bits on the a column are shifted off the board
+pub const fn west_one(bitboard: u64) -> u64 {
+    (bitboard & !A_COLUMN) >> 1
+}
@@ -1,9 +1,7 @@
-use core::convert::TryFrom;
-use core::convert::TryInto;
 
 pub mod board_representation {
 
-    #[derive(Copy, Clone, PartialEq, Debug)]
+    #[derive(Copy, Clone, PartialEq, Eq, Debug)]
     pub struct Points { 
         pub white_points: i8,
         pub black_points: i8,
@@ -36,8 +34,15 @@ pub mod board_representation {
     //   y |--------
     //     x 0, 1, 2
     // Bitboards do not need to be flipped to perspective of moving team
+
+    // Entry of Board::mailbox for a bit without a piece
+    pub const NO_PIECE: u8 = 0xFF;
+
+    // Bitboard of the light squares (a8 is a light square)
+    pub const LIGHT_SQUARES: u64 = 0xAA55AA55AA55AA55;
     
-    #[derive(Copy, Clone, PartialEq, Debug)]
+    // Boards are equal if they are the same game state (see the PartialEq impl), the clocks and evaluations aren't compared
+    #[derive(Copy, Clone, Debug)]
     pub struct Board {
         pub board: [u64; 13], // Bitboards for every type of piece
         pub whites_move: bool, 
@@ -46,6 +51,13 @@ pub mod board_representation {
         pub half_moves: i16, // The total number of half moves
         pub half_move_clock: i16, // The number of half moves since the last capture or pawn move
         pub en_passant_target: Option<usize>, // En passant target bit
+        pub tapered_evaluation: [i16; 2], // Middlegame and endgame material and piece square evaluation relative to white, updated by new_turn
+        pub hash: u64, // Zobrist hash of the board (see zobrist::hash), updated by new_turn
+        pub variant: crate::variant::Variant, // Rules the game is played by, see variant.rs
+        pub checks: [u8; 2], // Number of times the white and black teams have checked the enemy king, only counted in Three-Check
+        pub mailbox: [u8; 64], // Board index of the piece on every bit (NO_PIECE if the bit is empty), so the piece on a bit is found without looping over the bitboards, updated by new_turn
+        #[cfg(feature = "nnue")]
+        pub accumulator: [i16; crate::nnue::NNUE_HIDDEN], // Hidden layer of the neural network evaluation, updated by new_turn
     }
 
     // Coordinates used to reference a single piece on the board
@@ -62,6 +74,288 @@ pub mod board_representation {
                 bit: 0,
             }
         }
+
+        // Typed versions of the coordinates (see square.rs), for code outside the engine
+        pub fn from_piece_square(piece: crate::square::Piece, square: crate::square::Square) -> Self {
+            BoardCoordinates {
+                board_index: piece.board_index(),
+                bit: square.bit(),
+            }
+        }
+
+        // Returns None if the board index isn't a piece bitboard
+        pub fn piece(&self) -> Option<crate::square::Piece> {
+            crate::square::Piece::from_board_index(self.board_index)
+        }
+
+        // Returns None if the bit is off the board
+        pub fn square(&self) -> Option<crate::square::Square> {
+            crate::square::Square::new(self.bit)
+        }
+
+        // Returns the coordinates of the piece on a square of a board, from the square name (e.g. "e4")
+        // The square can start with the FEN letter of the piece (e.g. "Ne4", "pe5", the same as the {:#} format), which has to match the piece on the board
+        // Returns an error if the square isn't valid or there is no piece on it
+        // Raw bits are converted with notation::square_to_bit and bit_to_ccn
+        pub fn from_algebraic(text: &str, board: &Board) -> Result<Self, ()> {
+            let square;
+            let mut piece_letter = None;
+            match text.len() {
+                2 => square = text,
+                3 => {
+                    piece_letter = Some(text.get(0..1).ok_or(())?);
+                    square = text.get(1..).ok_or(())?;
+                },
+                _ => return Err(()),
+            }
+
+            let bit = crate::notation::square_to_bit(square)?;
+            let board_index = crate::find_board_index(board, bit)?;
+            match piece_letter {
+                Some(letter) => {
+                    if letter != &"PRNBQKprnbqk"[board_index..board_index + 1] {
+                        return Err(());
+                    }
+                },
+                None => (),
+            }
+
+            Ok(BoardCoordinates {
+                board_index: board_index,
+                bit: bit,
+            })
+        }
+
+        // Returns the square name of the coordinates (e.g. "e4")
+        pub fn to_algebraic(&self) -> &'static str {
+            crate::bit_to_ccn(self.bit)
+        }
+    }
+
+    // Prints the square of the coordinates (e.g. e4)
+    // With the alternate flag ({:#}) the FEN letter of the piece is printed before the square (e.g. Ne4)
+    impl core::fmt::Display for BoardCoordinates {
+        fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            if f.alternate() {
+                f.write_str(&"PRNBQKprnbqk"[self.board_index..self.board_index + 1])?;
+            }
+            f.write_str(crate::bit_to_ccn(self.bit))
+        }
+    }
+
+    // Number of pieces of every type on a board (see Board::material)
+    // Counted from the bitboards when it is needed, because every new_turn would have to update it otherwise
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    pub struct Material {
+        pub counts: [u8; 12], // Number of pieces on every bitboard, indexed by board index
+    }
+
+    impl Material {
+        // Returns a key made from the number of every piece except the kings (the material signature)
+        // Boards with the same pieces have the same key wherever the pieces are, so endgames can be recognised by comparing the key with signature_key
+        // Every count gets 4 bits, a team can have at most 10 of a piece after promotions
+        pub const fn key(&self) -> u64 {
+            let mut key = 0;
+            let mut i = 0;
+            while i < 12 {
+                if i % 6 != 5 {
+                    key |= (self.counts[i] as u64) << (SIGNATURE_BITS[i] * 4);
+                }
+                i += 1;
+            }
+            key
+        }
+
+        // Returns the number of pieces of every board index that are missing from the starting position
+        // Promoted pieces make up for pawns that are missing, so a promotion isn't counted as a captured pawn
+        pub fn captured(&self) -> [u8; 12] {
+            let starting_pieces = [8, 2, 2, 2, 1, 1];
+            let mut captured = [0; 12];
+            for offset in [0, 6] {
+                // Pieces that are above the starting count must have been promoted from pawns
+                let mut promotions = 0;
+                for i in 1..5 {
+                    promotions += self.counts[offset + i].saturating_sub(starting_pieces[i]);
+                    captured[offset + i] = starting_pieces[i].saturating_sub(self.counts[offset + i]);
+                }
+                captured[offset] = starting_pieces[0].saturating_sub(self.counts[offset] + promotions);
+            }
+            captured
+        }
+    }
+
+    // Position of the count of every board index in a material key (in 4 bit steps), 0 for the kings because they aren't in the key
+    const SIGNATURE_BITS: [u64; 12] = [0, 1, 2, 3, 4, 0, 5, 6, 7, 8, 9, 0];
+
+    // Returns the material key of a signature, the white pieces then the black pieces, both starting with the king and separated by a v (e.g. "KRPvKR")
+    // Const so the keys of the endgames the engine recognises are worked out at compile time
+    // Panics (a compile error when it is used for a constant) if the signature isn't valid
+    pub const fn signature_key(signature: &str) -> u64 {
+        let signature = signature.as_bytes();
+        let mut counts = [0; 12];
+        let mut offset = 0;
+        let mut i = 0;
+        while i < signature.len() {
+            match signature[i] {
+                b'P' => counts[offset] += 1,
+                b'R' => counts[offset + 1] += 1,
+                b'N' => counts[offset + 2] += 1,
+                b'B' => counts[offset + 3] += 1,
+                b'Q' => counts[offset + 4] += 1,
+                b'K' => counts[offset + 5] += 1,
+                b'v' if offset == 0 => offset = 6,
+                _ => panic!("Invalid material signature"),
+            }
+            i += 1;
+        }
+        if counts[5] != 1 || counts[11] != 1 {
+            panic!("Invalid material signature");
+        }
+
+        Material { counts: counts }.key()
+    }
+
+    // Bitboards of the king and rook of every castle, in the same order as zobrist::castling_rights
+    // [White king side, white queen side, black king side, black queen side]
+    const CASTLING_KING_ROOK_BITS: [u64; 4] = [1 << 60 | 1 << 63, 1 << 60 | 1 << 56, 1 << 4 | 1 << 7, 1 << 4 | 1 << 0];
+
+    // Notations for the castling rights of a FEN string
+    // Both notations name the rook a team can castle with, so Chess960 starting positions (where the king and rooks can start on any file of the back row) can be written
+    // The move generator only castles from the standard squares (see move_generator::CASTLES), other castling rights are only kept so the position is written back the same way
+    // https://www.chessprogramming.org/Forsyth-Edwards_Notation#Shredder-FEN
+    // https://www.chessprogramming.org/X-FEN
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    pub enum CastlingNotation {
+        XFen, // KQkq for the outermost rook on each side of the king, the file of the rook when there is another rook further out (classic FEN for standard chess)
+        Shredder, // The file of the rook (e.g. HAha)
+    }
+
+    // Returns the bitboard of the king and rook of a castling right in a FEN string, read with either CastlingNotation
+    // Upper case letters are the white team, and the rook has to be on the king's back row
+    // K or Q is the outermost rook on the king side (towards the h file) or queen side of the king
+    // If the king or rook is missing the standard squares are used instead, the same as classic FEN, because those rights were always accepted and are ignored by the move generator
+    fn fen_castling_bits(c: char, board: &Board) -> Result<u64, FenError> {
+        let king_index;
+        let rook_index;
+        let back_row: u64;
+        let standard_right;
+        if c.is_ascii_uppercase() {
+            king_index = 5;
+            rook_index = 1;
+            back_row = 0xFF00000000000000;
+            standard_right = 0;
+        } else {
+            king_index = 11;
+            rook_index = 7;
+            back_row = 0x00000000000000FF;
+            standard_right = 2;
+        }
+        let king = board.board[king_index] & back_row;
+        let rooks = board.board[rook_index] & back_row;
+
+        match c.to_ascii_lowercase() {
+            'k' | 'q' => {
+                let side_rooks;
+                let rook;
+                if king == 0 {
+                    side_rooks = 0;
+                } else if c.eq_ignore_ascii_case(&'k') {
+                    side_rooks = rooks & (!0 << crate::bitboard::lsb(king) << 1);
+                } else {
+                    side_rooks = rooks & (king - 1);
+                }
+
+                if side_rooks == 0 {
+                    if c.eq_ignore_ascii_case(&'k') {
+                        return Ok(CASTLING_KING_ROOK_BITS[standard_right]);
+                    }
+                    return Ok(CASTLING_KING_ROOK_BITS[standard_right + 1]);
+                }
+
+                // The outermost rook is the highest bit on the king side and the lowest bit on the queen side
+                if c.eq_ignore_ascii_case(&'k') {
+                    rook = 1 << (63 - side_rooks.leading_zeros());
+                } else {
+                    rook = 1 << crate::bitboard::lsb(side_rooks);
+                }
+                Ok(king | rook)
+            },
+            'a'..='h' => {
+                let rook = rooks & (crate::bitboard::A_COLUMN << (c.to_ascii_lowercase() as usize - 'a' as usize));
+                if king == 0 || rook == 0 {
+                    return Err(FenError::InvalidCastling);
+                }
+                Ok(king | rook)
+            },
+            _ => Err(FenError::InvalidCastling),
+        }
+    }
+
+    // Starting position of a standard game
+    pub const START_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+
+    // Handicap starting positions given by the white team (the usual way odds are given), see Odds
+    pub const KNIGHT_ODDS_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/R1BQKBNR w KQkq - 0 1";
+    pub const ROOK_ODDS_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/1NBQKBNR w Kkq - 0 1";
+    pub const PAWN_AND_MOVE_FEN: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPP1PP/RNBQKBNR b KQkq - 0 1";
+
+    // Handicaps a stronger player can give a weaker player, by starting without a piece
+    // https://en.wikipedia.org/wiki/Handicap_(chess)
+    #[derive(Copy, Clone, PartialEq, Eq, Debug)]
+    pub enum Odds {
+        Knight, // The team giving odds starts without its queen side knight
+        Rook, // The team giving odds starts without its queen side rook, so it can only castle king side
+        PawnAndMove, // The team giving odds starts without its f pawn, and the other team moves first
+    }
+
+    // Every handicap, in the order the firmware checks for them
+    pub const ODDS: [Odds; 3] = [Odds::Knight, Odds::Rook, Odds::PawnAndMove];
+
+    // Number of starting positions a game can start from, the standard starting position and every handicap given by either team (see Board::indexed_start)
+    pub const STARTING_BOARDS: usize = 1 + 2 * ODDS.len();
+
+    impl Odds {
+        // Returns the FEN of the starting position with the white or black team giving the odds
+        pub fn fen(&self, giver_white: bool) -> &'static str {
+            match (self, giver_white) {
+                (Odds::Knight, true) => KNIGHT_ODDS_FEN,
+                (Odds::Rook, true) => ROOK_ODDS_FEN,
+                (Odds::PawnAndMove, true) => PAWN_AND_MOVE_FEN,
+                (Odds::Knight, false) => "r1bqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+                (Odds::Rook, false) => "1nbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQk - 0 1",
+                (Odds::PawnAndMove, false) => "rnbqkbnr/ppppp1pp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+            }
+        }
+    }
+
+    // Number of bytes a board takes up when it is written with Board::to_bytes
+    pub const BOARD_BYTES: usize = 32;
+
+    // Errors that can be found when parsing a FEN string
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    pub enum FenError {
+        MissingField, // One of the piece placement, team, castling or en passant fields is missing
+        InvalidPiece(char), // A character in the piece placement isn't a piece, a number of empty squares or a row separator
+        WrongRowLength(usize), // A row (0 is the 8th rank) doesn't have 8 squares
+        WrongRowCount, // The piece placement doesn't have 8 rows
+        InvalidTeam, // The team to move isn't w or b
+        InvalidCastling, // The castling field has a character other than KQkq, the file of a rook on the king's back row (Shredder-FEN and X-FEN) or -
+        InvalidEnPassant, // The en passant target isn't a square behind a pawn of the team that just moved
+        InvalidHalfMoveClock, // The halfmove clock isn't a number, or is negative
+        InvalidFullMoveNumber, // The fullmove number isn't a number, or is too large for the number of half moves to fit in an i16
+        InvalidKings, // A team doesn't have exactly one king
+        InvalidPosition(PositionError), // The pieces are placed correctly but the position can't happen in a game (see Board::validate)
+    }
+
+    // Problems with a position that can't happen in a game, found by Board::validate
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    pub enum PositionError {
+        OverlappingPieces(usize), // More than one piece is on a bit
+        WrongKingCount(bool), // A team (true for white) doesn't have exactly one king
+        TooManyPieces(bool), // A team has more than 16 pieces, or more than 8 pawns
+        PawnOnLastRow(usize), // A pawn is on the first or eighth rank (bit)
+        InvalidEnPassant, // The en passant target isn't an empty square behind a pawn that just moved two squares
+        EnemyInCheck, // The team that isn't moving is in check, so the last move left its own king in check
     }
 
     impl Board {
@@ -79,6 +373,507 @@ pub mod board_representation {
                 half_moves: 0,
                 half_move_clock: 0,
                 en_passant_target: None,
+                tapered_evaluation: [0, 0],
+                hash: 0, // The hash of an empty board with white to move
+                variant: crate::variant::Variant::Standard,
+                checks: [0, 0],
+                mailbox: [NO_PIECE; 64],
+                #[cfg(feature = "nnue")]
+                accumulator: crate::nnue::NNUE_INPUT_BIASES,
+            }
+        }
+
+        // Creates the starting position of a standard game
+        pub fn start() -> Self {
+            Board::from_fen(START_FEN).unwrap()
+        }
+
+        // Creates a handicap starting position, with the white or black team giving the odds
+        // The team receiving odds gets the points of the missing piece, the same as if it had been captured
+        pub fn odds_start(odds: Odds, giver_white: bool) -> Self {
+            Board::from_fen(odds.fen(giver_white)).unwrap()
+        }
+
+        // Creates a starting position from its index, the standard starting position is 0 then every handicap (in the order of ODDS) with the white team then the black team giving the odds
+        // Lets a starting position be stored as one number, e.g. in a saved game (see save.rs)
+        // Returns None if the index is STARTING_BOARDS or more
+        pub fn indexed_start(index: usize) -> Option<Self> {
+            if index == 0 {
+                Some(Board::start())
+            } else if index < STARTING_BOARDS {
+                Some(Board::odds_start(ODDS[(index - 1) / 2], index % 2 == 1))
+            } else {
+                None
+            }
+        }
+
+        // Creates a board from a FEN string
+        // The halfmove clock and fullmove number fields are optional
+        // Returns an error describing the first problem found, so a position sent to the board can be rejected with a reason
+        // https://www.chessprogramming.org/Forsyth-Edwards_Notation
+        pub fn from_fen(fen: &str) -> Result<Self, FenError> {
+            let mut board = Board::new();
+            let mut fields = fen.split_whitespace();
+
+            // Piece placement, starting at a8 (bit 0)
+            let mut rows = 0;
+            for row in fields.next().ok_or(FenError::MissingField)?.split('/') {
+                if rows == 8 {
+                    return Err(FenError::WrongRowCount);
+                }
+
+                let mut file = 0;
+                for c in row.chars() {
+                    match c {
+                        '1'..='8' => file += c as usize - '0' as usize,
+                        _ => {
+                            let board_index = "PRNBQKprnbqk".find(c).ok_or(FenError::InvalidPiece(c))?;
+                            if file > 7 {
+                                return Err(FenError::WrongRowLength(rows));
+                            }
+                            board.board[board_index] |= 1 << (rows * 8 + file);
+                            file += 1;
+                        },
+                    }
+                }
+                if file != 8 {
+                    return Err(FenError::WrongRowLength(rows));
+                }
+                rows += 1;
+            }
+            if rows != 8 {
+                return Err(FenError::WrongRowCount);
+            }
+
+            // Team to move
+            match fields.next().ok_or(FenError::MissingField)? {
+                "w" => board.whites_move = true,
+                "b" => board.whites_move = false,
+                _ => return Err(FenError::InvalidTeam),
+            }
+
+            // Every piece is marked as moved apart from pawns on their starting rows, and kings and rooks that can still castle
+            let mut unmoved = (board.board[0] & 0x00FF000000000000) | (board.board[6] & 0x000000000000FF00);
+            for c in fields.next().ok_or(FenError::MissingField)?.chars() {
+                match c {
+                    '-' => (),
+                    _ => unmoved |= fen_castling_bits(c, &board)?,
+                }
+            }
+            board.board[12] = !unmoved;
+
+            // En passant target
+            // The target is behind a pawn that has just moved two squares, so it is on row 6 with white to move and row 3 with black to move
+            let en_passant = fields.next().ok_or(FenError::MissingField)?;
+            if en_passant != "-" {
+                let mut chars = en_passant.chars();
+                let x = chars.next().ok_or(FenError::InvalidEnPassant)? as i16 - 'a' as i16;
+                let y = chars.next().ok_or(FenError::InvalidEnPassant)? as i16 - '0' as i16;
+                let target_row;
+                if board.whites_move {
+                    target_row = 6;
+                } else {
+                    target_row = 3;
+                }
+                if !(0..=7).contains(&x) || y != target_row || chars.next().is_some() {
+                    return Err(FenError::InvalidEnPassant);
+                }
+                board.en_passant_target = Some((x + (8 - y) * 8) as usize);
+            }
+
+            // Optional halfmove clock and fullmove number
+            // Some programs write a fullmove number of 0, which is read as 1
+            match fields.next() {
+                Some(half_move_clock) => {
+                    let half_move_clock: i16 = half_move_clock.parse().map_err(|_| FenError::InvalidHalfMoveClock)?;
+                    if half_move_clock < 0 {
+                        return Err(FenError::InvalidHalfMoveClock);
+                    }
+                    board.half_move_clock = half_move_clock;
+                },
+                None => (),
+            }
+            match fields.next() {
+                Some(full_moves) => {
+                    let full_moves: i16 = full_moves.parse().map_err(|_| FenError::InvalidFullMoveNumber)?;
+
+                    // The number of half moves has to fit in an i16
+                    if !(0..=i16::MAX / 2).contains(&full_moves) {
+                        return Err(FenError::InvalidFullMoveNumber);
+                    }
+                    board.half_moves = (full_moves - 1).max(0) * 2;
+                    if !board.whites_move {
+                        board.half_moves += 1;
+                    }
+                },
+                None => (),
+            }
+
+            // Each team needs exactly one king
+            if board.board[5].count_ones() != 1 || board.board[11].count_ones() != 1 {
+                return Err(FenError::InvalidKings);
+            }
+
+            // Points are the value of the enemy pieces missing from the starting position
+            let pieces_info = crate::piece::constants::gen();
+            let captured = board.material().captured();
+            for i in 0..12 {
+                let captured = captured[i] as i8;
+                if crate::board_index_white(i) {
+                    board.points.black_points += captured * pieces_info[i].value;
+                } else {
+                    board.points.white_points += captured * pieces_info[i].value;
+                }
+            }
+
+            board.validate(&pieces_info).map_err(FenError::InvalidPosition)?;
+
+            board.refresh_mailbox();
+            crate::evaluation::refresh_tapered_evaluation(&mut board, &pieces_info);
+            board.hash = crate::zobrist::hash(&board);
+            #[cfg(feature = "nnue")]
+            crate::nnue::refresh_accumulator(&mut board);
+
+            Ok(board)
+        }
+
+        // Checks the position could happen in a game, so positions from a FEN string, a setup on the physical board or the hall sensors can be rejected before they reach the move generator
+        // Returns the first problem found
+        pub fn validate(&self, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Result<(), PositionError> {
+
+            // Each bit can only have one piece
+            let mut pieces: u64 = 0;
+            for i in 0..12 {
+                let overlap = pieces & self.board[i];
+                if overlap != 0 {
+                    return Err(PositionError::OverlappingPieces(crate::find_bit_on(overlap, 0)));
+                }
+                pieces |= self.board[i];
+            }
+
+            // Each team needs exactly one king, at most 8 pawns, and at most 16 pieces
+            for (team_white, offset) in [(true, 0), (false, 6)] {
+                if self.board[offset + 5].count_ones() != 1 {
+                    return Err(PositionError::WrongKingCount(team_white));
+                }
+                if self.board[offset].count_ones() > 8 || crate::or_bitboards(offset, offset + 5, &self.board).count_ones() > 16 {
+                    return Err(PositionError::TooManyPieces(team_white));
+                }
+            }
+
+            // Pawns promote on the last row, and can never move back to the first row
+            let last_rows: u64 = 0xFF000000000000FF;
+            let pawns_on_last_rows = (self.board[0] | self.board[6]) & last_rows;
+            if pawns_on_last_rows != 0 {
+                return Err(PositionError::PawnOnLastRow(crate::find_bit_on(pawns_on_last_rows, 0)));
+            }
+
+            // The en passant target is on row 6 (white to move) or row 3 (black to move), with an enemy pawn in front of it and nothing on it or the square the pawn moved from
+            match self.en_passant_target {
+                Some(bit) => {
+                    let pawn_bit;
+                    let from_bit;
+                    let enemy_pawns;
+                    if self.whites_move {
+                        if bit / 8 != 2 {
+                            return Err(PositionError::InvalidEnPassant);
+                        }
+                        pawn_bit = bit + 8;
+                        from_bit = bit - 8;
+                        enemy_pawns = self.board[6];
+                    } else {
+                        if bit / 8 != 5 {
+                            return Err(PositionError::InvalidEnPassant);
+                        }
+                        pawn_bit = bit - 8;
+                        from_bit = bit + 8;
+                        enemy_pawns = self.board[0];
+                    }
+
+                    if !crate::bit_on(enemy_pawns, pawn_bit) || crate::bit_on(pieces, bit) || crate::bit_on(pieces, from_bit) {
+                        return Err(PositionError::InvalidEnPassant);
+                    }
+                },
+                None => (),
+            }
+
+            // The team that just moved can't have left its king in check
+            let enemy_king_index;
+            if self.whites_move {
+                enemy_king_index = 11;
+            } else {
+                enemy_king_index = 5;
+            }
+            let enemy_king_bit = crate::find_bit_on(self.board[enemy_king_index], 0);
+            if super::move_generator::attackers_to(enemy_king_bit, self.whites_move, self, pieces_info) != 0 {
+                return Err(PositionError::EnemyInCheck);
+            }
+
+            Ok(())
+        }
+
+        // Writes the board as a FEN string, the reverse of from_fen
+        // Castling rights are written in X-FEN, which is the same as classic FEN (KQkq) for standard chess
+        pub fn write_fen<W: core::fmt::Write>(&self, out: &mut W) -> core::fmt::Result {
+            self.write_fen_with(out, CastlingNotation::XFen)
+        }
+
+        // Writes the board as a FEN string with the castling rights in a chosen notation (see CastlingNotation)
+        pub fn write_fen_with<W: core::fmt::Write>(&self, out: &mut W, notation: CastlingNotation) -> core::fmt::Result {
+            // Piece placement, starting at a8 (bit 0)
+            for row in 0..8 {
+                let mut empty_squares = 0;
+                for file in 0..8 {
+                    let bit = row * 8 + file;
+                    match crate::find_board_index(self, bit) {
+                        Ok(board_index) => {
+                            if empty_squares > 0 {
+                                write!(out, "{}", empty_squares)?;
+                                empty_squares = 0;
+                            }
+                            out.write_str(&"PRNBQKprnbqk"[board_index..board_index + 1])?;
+                        },
+                        Err(()) => empty_squares += 1,
+                    }
+                }
+                if empty_squares > 0 {
+                    write!(out, "{}", empty_squares)?;
+                }
+                if row < 7 {
+                    out.write_str("/")?;
+                }
+            }
+
+            if self.whites_move {
+                out.write_str(" w ")?;
+            } else {
+                out.write_str(" b ")?;
+            }
+
+            // A team can castle with every rook on its back row that hasn't moved, if its king is on the back row and hasn't moved either
+            // King side rooks are written first, then queen side rooks, each starting from the outermost rook (e.g. KQkq, HAha)
+            let mut castling = false;
+            for (white, king_index, rook_index, back_row, first_bit) in [(true, 5, 1, 0xFF00000000000000, 56), (false, 11, 7, 0x00000000000000FF, 0)] {
+                let king = self.board[king_index] & back_row & !self.board[12];
+                if king == 0 {
+                    continue;
+                }
+                let king_file = crate::bitboard::lsb(king) % 8;
+                let rooks = self.board[rook_index] & back_row;
+
+                for file in (king_file + 1..8).rev().chain(0..king_file) {
+                    let rook_bit = first_bit + file;
+                    if !crate::bit_on(rooks & !self.board[12], rook_bit) {
+                        continue;
+                    }
+
+                    // X-FEN only uses the file when there is another rook further out on the same side, which K or Q would mean instead
+                    let outer_rooks;
+                    if file > king_file {
+                        outer_rooks = rooks & (back_row & !0 << rook_bit << 1);
+                    } else {
+                        outer_rooks = rooks & (back_row & !(!0 << rook_bit));
+                    }
+                    let mut c;
+                    if notation == CastlingNotation::XFen && outer_rooks == 0 {
+                        if file > king_file {
+                            c = 'K';
+                        } else {
+                            c = 'Q';
+                        }
+                    } else {
+                        c = (b'A' + file as u8) as char;
+                    }
+                    if !white {
+                        c = c.to_ascii_lowercase();
+                    }
+
+                    write!(out, "{}", c)?;
+                    castling = true;
+                }
+            }
+            if !castling {
+                out.write_str("-")?;
+            }
+
+            match self.en_passant_target {
+                Some(bit) => write!(out, " {} ", crate::bit_to_ccn(bit))?,
+                None => out.write_str(" - ")?,
+            }
+
+            write!(out, "{} {}", self.half_move_clock, self.full_move_number())
+        }
+
+        // Returns the full move number, which starts at 1 and goes up after every black move
+        pub fn full_move_number(&self) -> i16 {
+            self.half_moves / 2 + 1
+        }
+
+        // Writes the board as BOARD_BYTES bytes, for storing games in flash and sending positions over UART where a FEN string (up to 90 bytes) is too long
+        // Only the game state of standard chess and the clocks are written, everything else is calculated again by from_bytes
+        // The variant and checks aren't written, so boards are always read as standard chess
+        // Layout:
+        // 0 - 7: Bitboard of every piece (little endian)
+        // 8 - 23: Board index of every piece in the order of its bits, 4 bits each starting with the low bits of byte 8, so a board can have at most 32 pieces
+        // 24: Flags, bit 0 is on with white to move, bits 1 - 4 are the castling rights (in the order of zobrist::castling_rights) and bit 5 is on if there is an en passant target
+        // 25: En passant target bit
+        // 26 - 27: Halfmove clock (little endian)
+        // 28 - 29: Number of half moves (little endian)
+        // 30 - 31: White and black points
+        // Boards with more than 32 pieces can't happen in a game (see validate), only the first 32 pieces are written so from_bytes rejects them
+        pub fn to_bytes(&self) -> [u8; BOARD_BYTES] {
+            let mut bytes = [0; BOARD_BYTES];
+
+            let pieces = self.to_bitboard();
+            bytes[0..8].copy_from_slice(&pieces.to_le_bytes());
+            for (i, bit) in crate::bitboard::bits(pieces).take(32).enumerate() {
+                match crate::find_board_index(self, bit) {
+                    Ok(board_index) => bytes[8 + i / 2] |= (board_index as u8) << (i % 2 * 4),
+                    Err(()) => (),
+                }
+            }
+
+            let mut flags = self.whites_move as u8;
+            let rights = crate::zobrist::castling_rights(self);
+            for i in 0..rights.len() {
+                if rights[i] {
+                    flags |= 1 << (i + 1);
+                }
+            }
+            match self.en_passant_target {
+                Some(bit) => {
+                    flags |= 1 << 5;
+                    bytes[25] = bit as u8;
+                },
+                None => (),
+            }
+            bytes[24] = flags;
+
+            bytes[26..28].copy_from_slice(&self.half_move_clock.to_le_bytes());
+            bytes[28..30].copy_from_slice(&self.half_moves.to_le_bytes());
+            bytes[30] = self.points.white_points as u8;
+            bytes[31] = self.points.black_points as u8;
+
+            bytes
+        }
+
+        // Reads a board written by to_bytes
+        // The bytes can come from a corrupted flash page or a noisy UART, so anything that to_bytes wouldn't write or any position that fails validate is an error
+        // Pieces are marked as moved the same way as from_fen
+        pub fn from_bytes(bytes: &[u8; BOARD_BYTES]) -> Result<Self, ()> {
+            let mut board = Board::new();
+
+            let mut piece_bytes = [0; 8];
+            piece_bytes.copy_from_slice(&bytes[0..8]);
+            let pieces = u64::from_le_bytes(piece_bytes);
+            if crate::bitboard::count(pieces) > 32 {
+                return Err(());
+            }
+            for (i, bit) in crate::bitboard::bits(pieces).enumerate() {
+                let board_index = ((bytes[8 + i / 2] >> (i % 2 * 4)) & 0xF) as usize;
+                if board_index > 11 {
+                    return Err(());
+                }
+                board.board[board_index] |= 1 << bit;
+            }
+
+            let flags = bytes[24];
+            if flags >> 6 != 0 {
+                return Err(());
+            }
+            board.whites_move = flags & 1 != 0;
+
+            let mut unmoved = (board.board[0] & 0x00FF000000000000) | (board.board[6] & 0x000000000000FF00);
+            for i in 0..CASTLING_KING_ROOK_BITS.len() {
+                if flags & (1 << (i + 1)) != 0 {
+                    unmoved |= CASTLING_KING_ROOK_BITS[i];
+                }
+            }
+            board.board[12] = !unmoved;
+
+            if flags & (1 << 5) != 0 {
+                if bytes[25] > 63 {
+                    return Err(());
+                }
+                board.en_passant_target = Some(bytes[25] as usize);
+            }
+
+            board.half_move_clock = i16::from_le_bytes([bytes[26], bytes[27]]);
+            board.half_moves = i16::from_le_bytes([bytes[28], bytes[29]]);
+            if board.half_move_clock < 0 || board.half_moves < 0 {
+                return Err(());
+            }
+            board.points = Points {
+                white_points: bytes[30] as i8,
+                black_points: bytes[31] as i8,
+            };
+
+            let pieces_info = crate::piece::constants::gen();
+            board.validate(&pieces_info).map_err(|_| ())?;
+
+            board.refresh_mailbox();
+            crate::evaluation::refresh_tapered_evaluation(&mut board, &pieces_info);
+            board.hash = crate::zobrist::hash(&board);
+            #[cfg(feature = "nnue")]
+            crate::nnue::refresh_accumulator(&mut board);
+
+            Ok(board)
+        }
+
+        // Returns the board index of the piece on a bit, None if the bit is empty
+        pub fn piece_on(&self, bit: usize) -> Option<usize> {
+            match self.mailbox[bit] {
+                NO_PIECE => None,
+                board_index => Some(board_index as usize),
+            }
+        }
+
+        // Fills the mailbox from the bitboards
+        // Needed after the bitboards are changed outside of new_turn (e.g. a board made from a fen, or a transformed board), the same as the tapered evaluation and hash
+        pub fn refresh_mailbox(&mut self) {
+            self.mailbox = [NO_PIECE; 64];
+            for i in 0..12 {
+                for bit in crate::bitboard::bits(self.board[i]) {
+                    self.mailbox[bit] = i as u8;
+                }
+            }
+        }
+
+        // Updates the mailbox after a move, from the bitboards of the board before the move
+        // Only the bits that changed are updated, so captures, castling, en passant and promotions are all handled
+        pub fn update_mailbox(&mut self, initial_bitboards: &[u64; 13]) {
+            // Empty the bits pieces moved off before filling the bits pieces moved to, a captured piece and the capturing piece change the same bit
+            for i in 0..12 {
+                for bit in crate::bitboard::bits(initial_bitboards[i] & !self.board[i]) {
+                    self.mailbox[bit] = NO_PIECE;
+                }
+            }
+            for i in 0..12 {
+                for bit in crate::bitboard::bits(self.board[i] & !initial_bitboards[i]) {
+                    self.mailbox[bit] = i as u8;
+                }
+            }
+        }
+
+        // Returns true if the mailbox matches the bitboards, used by debug assertions to catch a board that was changed without updating its mailbox
+        pub fn mailbox_consistent(&self) -> bool {
+            for bit in 0..64 {
+                let board_index = crate::find_board_index(self, bit);
+                if board_index.ok() != self.piece_on(bit) {
+                    return false;
+                }
+            }
+            true
+        }
+
+        // Returns the number of pieces of every type on the board
+        pub fn material(&self) -> Material {
+            let mut counts = [0; 12];
+            for i in 0..12 {
+                counts[i] = crate::bitboard::count(self.board[i]) as u8;
+            }
+            Material {
+                counts: counts,
             }
         }
 
@@ -93,6 +888,380 @@ pub mod board_representation {
 
             bitboard
         }
+
+        // Returns the board with the rows flipped and the teams swapped, so the white pieces become black pieces and the other team is to move
+        // The evaluation of a mirrored board should always be the negative of the evaluation of the board, which catches sign bugs in the evaluation
+        pub fn mirror(&self) -> Self {
+            let mut board = self.flipped(crate::flip_bitboard_vertical, 56);
+            board.swap_bitboard_colours();
+
+            // The piece square tables are mirrored for the black team, so the tapered evaluation is just negated
+            board.tapered_evaluation = [-self.tapered_evaluation[0], -self.tapered_evaluation[1]];
+            board.refresh_hash_and_accumulator();
+
+            board
+        }
+
+        // Returns the board with the rows flipped (a8 becomes a1), the teams and the team to move stay the same
+        // Pawns end up moving backwards, so this is only useful with swap_colours (see mirror) or for pawnless positions
+        pub fn flip_vertical(&self, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Self {
+            let mut board = self.flipped(crate::flip_bitboard_vertical, 56);
+            crate::evaluation::refresh_tapered_evaluation(&mut board, pieces_info);
+            board.refresh_hash_and_accumulator();
+            board
+        }
+
+        // Returns the board with the columns flipped (a8 becomes h8), the teams and the team to move stay the same
+        // The kings and rooks don't start on mirrored columns, so a flipped board can't castle
+        pub fn flip_horizontal(&self, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Self {
+            let mut board = self.flipped(crate::flip_bitboard_horizontal, 7);
+            crate::evaluation::refresh_tapered_evaluation(&mut board, pieces_info);
+            board.refresh_hash_and_accumulator();
+            board
+        }
+
+        // Returns the board with the white pieces swapped for black pieces (and the other way around) without moving them, the other team is to move
+        // Like flip_vertical the pawns end up moving backwards, so this is mostly used with flip_vertical (see mirror)
+        pub fn swap_colours(&self, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Self {
+            let mut board = *self;
+            board.swap_bitboard_colours();
+            crate::evaluation::refresh_tapered_evaluation(&mut board, pieces_info);
+            board.refresh_hash_and_accumulator();
+            board
+        }
+
+        // Returns the board with a flip applied to every bitboard (including the moved bitboard) and the en passant target
+        // The en passant target is flipped by xoring its bit, 56 flips the row and 7 flips the column
+        // Castling rights depend on the moved bitboard and the starting squares, so they are lost if the kings or rooks don't land on starting squares
+        fn flipped(&self, flip_bitboard: fn(u64) -> u64, en_passant_xor: usize) -> Self {
+            let mut board = *self;
+            for i in 0..13 {
+                board.board[i] = flip_bitboard(self.board[i]);
+            }
+
+            match self.en_passant_target {
+                Some(bit) => board.en_passant_target = Some(bit ^ en_passant_xor),
+                None => (),
+            }
+            board.refresh_mailbox();
+
+            board
+        }
+
+        // Swaps the bitboards, points and checks of the teams, and the team to move
+        fn swap_bitboard_colours(&mut self) {
+            for i in 0..6 {
+                self.board.swap(i, i + 6);
+            }
+            self.checks.swap(0, 1);
+            self.refresh_mailbox();
+
+            self.whites_move = !self.whites_move;
+            self.points = Points {
+                white_points: self.points.black_points,
+                black_points: self.points.white_points,
+            };
+        }
+
+        // Calculates the hash and the nnue accumulator from scratch after the board has been transformed
+        fn refresh_hash_and_accumulator(&mut self) {
+            self.hash = crate::zobrist::hash(self);
+            #[cfg(feature = "nnue")]
+            crate::nnue::refresh_accumulator(self);
+        }
+
+        // Returns the board after the team to move passes (a null move), used by null move pruning and to see what the other team would do if it could move again
+        // Nothing is moved, so the evaluations don't change, but the en passant target is lost and the clocks count the pass as a half move
+        // The team to move shouldn't be in check, because passing would leave the king in check
+        pub fn make_null_move(&self) -> Self {
+            let mut board = *self;
+
+            board.whites_move = !self.whites_move;
+            board.en_passant_target = None;
+            board.points_delta = 0;
+            board.half_moves += 1;
+            board.half_move_clock += 1;
+
+            crate::zobrist::update_hash(&self.board, self.en_passant_target, &mut board);
+
+            board
+        }
+
+        // Returns true if the king of the team to move is in check
+        pub fn is_check(&self, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> bool {
+            super::move_generator::in_check(self, pieces_info)
+        }
+
+        // Returns true if the team to move is checkmated
+        pub fn is_checkmate(&self, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> bool {
+            let (check, mate) = super::move_generator::check_and_mate(self, pieces_info);
+            check && mate
+        }
+
+        // Returns true if the team to move has no legal moves, and isn't in check
+        pub fn is_stalemate(&self, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> bool {
+            let (check, mate) = super::move_generator::check_and_mate(self, pieces_info);
+            !check && mate
+        }
+
+        // Returns true if neither team has enough pieces left to checkmate
+        // K vs K, K + N vs K, K + B vs K, and any number of bishops that are all on the same colour squares
+        // In King of the Hill a bare king can still win by reaching the centre, and in Three-Check any piece can still check, so only bare kings are a draw there
+        pub fn insufficient_material(&self) -> bool {
+            use crate::variant::Variant;
+
+            match self.variant {
+                Variant::Standard => (),
+                Variant::KingOfTheHill => return false,
+                Variant::ThreeCheck => return self.to_bitboard() == self.board[5] | self.board[11],
+            }
+
+            let material = self.material();
+
+            // Pawns, rooks or queens can always checkmate
+            for i in [0, 1, 4, 6, 7, 10] {
+                if material.counts[i] != 0 {
+                    return false;
+                }
+            }
+
+            let knights = material.counts[2] + material.counts[8];
+
+            // A single minor piece
+            if knights + material.counts[3] + material.counts[9] <= 1 {
+                return true;
+            }
+
+            // Bishops on the same colour squares can never attack the squares next to the king on the other colour
+            let bishops = self.board[3] | self.board[9];
+            knights == 0 && (bishops & LIGHT_SQUARES == 0 || bishops & !LIGHT_SQUARES == 0)
+        }
+    }
+
+    // Boards are the same game state if they have the same pieces, team to move, castling rights, en passant target and variant (including the checks given in Three-Check)
+    // The clocks aren't compared, so a board is equal to the same position reached earlier in a game (a repetition)
+    // The moved bitboard is only compared through the castling rights, because pieces that have moved and come back don't change what moves can be made
+    impl PartialEq for Board {
+        fn eq(&self, other: &Self) -> bool {
+            self.board[0..12] == other.board[0..12]
+                && self.whites_move == other.whites_move
+                && crate::zobrist::castling_rights(self) == crate::zobrist::castling_rights(other)
+                && self.en_passant_target == other.en_passant_target
+                && self.variant == other.variant
+                && self.checks == other.checks
+        }
+    }
+
+    impl Eq for Board {}
+
+    // The zobrist hash is made from the same parts of the board that are compared by PartialEq (apart from the variant), so equal boards always have equal hashes
+    // Boards keep their zobrist hash up to date, so nothing needs to be calculated
+    impl core::hash::Hash for Board {
+        fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+            state.write_u64(self.hash);
+        }
+    }
+
+    // Unicode glyphs of every piece (board index), used instead of letters when the board is printed with {:#}
+    const PIECE_GLYPHS: [char; 12] = ['♙', '♖', '♘', '♗', '♕', '♔', '♟', '♜', '♞', '♝', '♛', '♚'];
+
+    // Prints the board as a diagram with the ranks and files labelled, white at the bottom, followed by the FEN of the board
+    // Pieces are FEN letters (white uppercase), or Unicode glyphs with the alternate flag ({:#}), and empty squares are dots
+    impl core::fmt::Display for Board {
+        fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            use core::fmt::Write;
+
+            for row in 0..8 {
+                write!(f, "{} ", 8 - row)?;
+                for file in 0..8 {
+                    let bit = row * 8 + file;
+                    match crate::find_board_index(self, bit) {
+                        Ok(board_index) => {
+                            if f.alternate() {
+                                f.write_char(PIECE_GLYPHS[board_index])?;
+                            } else {
+                                f.write_str(&"PRNBQKprnbqk"[board_index..board_index + 1])?;
+                            }
+                        },
+                        Err(()) => f.write_char('.')?,
+                    }
+                    if file < 7 {
+                        f.write_char(' ')?;
+                    }
+                }
+                f.write_char('\n')?;
+            }
+            writeln!(f, "  a b c d e f g h")?;
+
+            self.write_fen(f)
+        }
+    }
+
+    // Writes a board as its FEN string with Display, so boards can be serialized without allocating
+    #[cfg(feature = "serde")]
+    struct Fen<'a>(&'a Board);
+
+    #[cfg(feature = "serde")]
+    impl core::fmt::Display for Fen<'_> {
+        fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            self.0.write_fen(f)
+        }
+    }
+
+    // Boards are serialized as a FEN string for human readable formats (e.g. JSON), and as the bytes of Board::to_bytes for binary formats
+    #[cfg(feature = "serde")]
+    impl serde::Serialize for Board {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if serializer.is_human_readable() {
+                serializer.collect_str(&Fen(self))
+            } else {
+                serializer.serialize_bytes(&self.to_bytes())
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    struct BoardVisitor;
+
+    #[cfg(feature = "serde")]
+    impl<'de> serde::de::Visitor<'de> for BoardVisitor {
+        type Value = Board;
+
+        fn expecting(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+            f.write_str("a FEN string or the bytes of Board::to_bytes")
+        }
+
+        fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Board, E> {
+            Board::from_fen(v).map_err(|_| E::invalid_value(serde::de::Unexpected::Str(v), &self))
+        }
+
+        fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Board, E> {
+            let bytes: &[u8; BOARD_BYTES] = v.try_into().map_err(|_| E::invalid_length(v.len(), &self))?;
+            Board::from_bytes(bytes).map_err(|_| E::invalid_value(serde::de::Unexpected::Bytes(v), &self))
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    impl<'de> serde::Deserialize<'de> for Board {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Board, D::Error> {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_str(BoardVisitor)
+            } else {
+                deserializer.deserialize_bytes(BoardVisitor)
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        // Every square converts to its name and back, with and without the letter of the piece on it
+        #[test]
+        fn algebraic_round_trip() {
+            // A FEN can't have a piece on every square, so the bitboards are filled in directly, every piece takes a turn
+            let mut board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+            board.board = [0; 13];
+            for bit in 0..64 {
+                board.board[bit % 12] |= 1 << bit;
+            }
+
+            for bit in 0..64 {
+                let coordinates = BoardCoordinates {
+                    board_index: crate::find_board_index(&board, bit).unwrap(),
+                    bit: bit,
+                };
+
+                // Bit 0 is a8, the files go across and the ranks go down
+                let square = coordinates.to_algebraic();
+                assert_eq!(square.as_bytes(), &[b'a' + (bit % 8) as u8, b'8' - (bit / 8) as u8]);
+                assert_eq!(BoardCoordinates::from_algebraic(square, &board), Ok(coordinates));
+
+                let letter = "PRNBQKprnbqk".as_bytes()[coordinates.board_index];
+                let lettered = [letter, square.as_bytes()[0], square.as_bytes()[1]];
+                assert_eq!(BoardCoordinates::from_algebraic(core::str::from_utf8(&lettered).unwrap(), &board), Ok(coordinates));
+            }
+        }
+
+        // Every field of a FEN string is checked, and the first problem is returned
+        #[test]
+        fn fen_errors() {
+            let fen = |fen: &str| Board::from_fen(fen).unwrap_err();
+            assert!(Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").is_ok());
+
+            // Piece placement
+            assert_eq!(fen(""), FenError::MissingField);
+            assert_eq!(fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNX w KQkq - 0 1"), FenError::InvalidPiece('X'));
+            assert_eq!(fen("rnbqkbnr/pppp0ppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"), FenError::InvalidPiece('0'));
+            assert_eq!(fen("rnbqkbnr/ppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"), FenError::WrongRowLength(1));
+            assert_eq!(fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNRR w KQkq - 0 1"), FenError::WrongRowLength(7));
+            assert_eq!(fen("rnbqkbnr/pppppppp/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"), FenError::WrongRowCount);
+            assert_eq!(fen("rnbqkbnr/pppppppp/8/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"), FenError::WrongRowCount);
+
+            // Team to move
+            assert_eq!(fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR"), FenError::MissingField);
+            assert_eq!(fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR W KQkq - 0 1"), FenError::InvalidTeam);
+
+            // Castling
+            assert_eq!(fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w"), FenError::MissingField);
+            assert_eq!(fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkx - 0 1"), FenError::InvalidCastling);
+            assert_eq!(fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQk1 - 0 1"), FenError::InvalidCastling);
+
+            // En passant target, it has to be behind a pawn of the team that just moved
+            assert_eq!(fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq"), FenError::MissingField);
+            assert_eq!(fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e6 0 1"), FenError::InvalidEnPassant);
+            assert_eq!(fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq i3 0 1"), FenError::InvalidEnPassant);
+            assert_eq!(fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e33 0 1"), FenError::InvalidEnPassant);
+            assert_eq!(fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e 0 1"), FenError::InvalidEnPassant);
+            assert!(Board::from_fen("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1").is_ok());
+
+            // A target without the pawn that moved is a problem with the position
+            assert_eq!(fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR b KQkq e3 0 1"), FenError::InvalidPosition(PositionError::InvalidEnPassant));
+
+            // Halfmove clock and fullmove number
+            assert_eq!(fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - x 1"), FenError::InvalidHalfMoveClock);
+            assert_eq!(fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - -1 1"), FenError::InvalidHalfMoveClock);
+            assert_eq!(fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 x"), FenError::InvalidFullMoveNumber);
+            assert_eq!(fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 20000"), FenError::InvalidFullMoveNumber);
+
+            // Position
+            assert_eq!(fen("rnbq1bnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"), FenError::InvalidKings);
+            assert_eq!(fen("4k3/8/8/8/8/8/8/4K2P w - - 0 1"), FenError::InvalidPosition(PositionError::PawnOnLastRow(63)));
+            assert_eq!(fen("4k3/8/8/8/8/8/8/r3K3 b - - 0 1"), FenError::InvalidPosition(PositionError::EnemyInCheck));
+        }
+
+        // Every starting position can be played, and the team giving odds has fewer pieces than the team receiving them
+        #[test]
+        fn indexed_starts() {
+            let pieces_info = crate::piece::constants::gen();
+            for index in 0..STARTING_BOARDS {
+                let board = Board::indexed_start(index).unwrap();
+                assert_eq!(board.validate(&pieces_info), Ok(()), "starting board {}", index);
+
+                let white_pieces: u32 = board.board[0..6].iter().map(|bitboard| bitboard.count_ones()).sum();
+                let black_pieces: u32 = board.board[6..12].iter().map(|bitboard| bitboard.count_ones()).sum();
+                if index == 0 {
+                    assert_eq!(board, Board::start());
+                } else if index % 2 == 1 {
+                    assert!(white_pieces < black_pieces, "starting board {}", index);
+                } else {
+                    assert!(black_pieces < white_pieces, "starting board {}", index);
+                }
+            }
+            assert_eq!(Board::indexed_start(STARTING_BOARDS), None);
+        }
+
+        // Squares without a piece, pieces that don't match the letter and names that aren't squares are errors
+        #[test]
+        fn algebraic_errors() {
+            let board = Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+            assert_eq!(BoardCoordinates::from_algebraic("e4", &board), Err(()));
+            assert_eq!(BoardCoordinates::from_algebraic("Ne2", &board), Err(()));
+            assert_eq!(BoardCoordinates::from_algebraic("i2", &board), Err(()));
+            assert_eq!(BoardCoordinates::from_algebraic("e9", &board), Err(()));
+            assert_eq!(BoardCoordinates::from_algebraic("Pe2x", &board), Err(()));
+            assert_eq!(BoardCoordinates::from_algebraic("", &board), Err(()));
+        }
     }
 }
 
@@ -116,6 +1285,15 @@ pub mod move_generator {
         }
     }
 
+    // Pieces a pawn can promote to, as board indexes for the white team (queen, knight, rook, bishop)
+    // Ordered from the most to least useful, so the search tries a queen promotion before the underpromotions
+    pub const PROMOTION_PIECES: [Option<usize>; 4] = [Some(4), Some(2), Some(1), Some(3)];
+
+    // Returns true if a pawn (board index) moving to a bit promotes
+    pub fn promotes(board_index: usize, piece_move_bit: usize) -> bool {
+        (board_index == 0 && piece_move_bit < 8) || (board_index == 6 && piece_move_bit > 55)
+    }
+
     // Returns a move struct of semi legal moves that the piece can make
     // Set only_gen_attacks to true to turn off pawn moves
     // If an enemy king is provided it will be ignored as a piece, so sliding pieces moves will go through the enemy king
@@ -133,106 +1311,84 @@ pub mod move_generator {
 
         let mut moves = Moves::new();
 
-        let piece_pawn;
-        if piece.board_index == 0 || piece.board_index == 6 {
-            piece_pawn = true;
-        } else {
-            piece_pawn = false
-        }
+        // Sliding pieces use attack lookups (see attacks.rs) instead of moving one square at a time
+        if piece_info.sliding {
+            let mut occupancy = team_bitboards.friendly_team | team_bitboards.enemy_team;
 
-        // Initialize moves with pawn capture moves if the piece is a pawn
-        if piece_pawn {
-            moves = gen_pawn_captures(&piece, only_gen_attacks, *team_bitboards, &board);
-        }
+            // Remove the enemy king so the squares behind it are attacked
+            match enemy_king {
+                Some(enemy_king) => {
+                    if only_gen_attacks {
+                        occupancy &= !(1 << enemy_king.bit);
+                    }
+                },
+                None => (),
+            }
 
-        if only_gen_attacks && piece_pawn // Do not generate regular moves for pawns if only_gen_attacks is true
-        {        
-        } else {
-            for i in 0..piece_info.moves_no {
-                let move_delta_bit = piece_info.moves[i];
-    
-                let mut piece_bit = piece.bit;
+            let attacks;
+            match piece.board_index % 6 {
+                1 => attacks = crate::attacks::rook_attacks(piece.bit, occupancy),
+                3 => attacks = crate::attacks::bishop_attacks(piece.bit, occupancy),
+                _ => attacks = crate::attacks::queen_attacks(piece.bit, occupancy),
+            }
+
+            // Friendly pieces are considered attacked if only_gen_attacks is true, otherwise they can't be captured
+            if only_gen_attacks {
+                moves.moves_bitboard = attacks;
+            } else {
+                moves.moves_bitboard = attacks & !team_bitboards.friendly_team;
+            }
+            return moves;
+        }
 
-                let mut move_repeated = 0;
-                loop {
-                    match move_piece(piece_bit, move_delta_bit) {
-                        Ok(bitboard) => {
-                            let piece_bit_i8: i8 = piece_bit as i8;
-                            let piece_move_bit = usize::try_from(piece_bit_i8 + move_delta_bit).unwrap();
+        // Knights and kings attack the same bits on every board
+        if piece.board_index % 6 != 0 {
+            let attacks;
+            if piece.board_index % 6 == 2 {
+                attacks = crate::attacks::KNIGHT_ATTACKS[piece.bit];
+            } else {
+                attacks = crate::attacks::KING_ATTACKS[piece.bit];
+            }
 
-                            let mut break_after_move = false;
-    
-                            // If a friendly piece is in the way of the move break the loop so the piece cannot move there
-                            if bit_on(team_bitboards.friendly_team, piece_move_bit) {
-                                if only_gen_attacks {
-                                    break_after_move = true; // Considered friendly sqaures attacked if only_gen_attacks is true
-                                } else {
-                                    break;
-                                }
-                            }
-    
-                            // If an enemy piece is in the way of the move break after the move, so the piece can capture it and then stop moving in that direction
-                            if bit_on(team_bitboards.enemy_team, piece_move_bit) {
+            // Friendly pieces are considered attacked if only_gen_attacks is true, otherwise they can't be captured
+            if only_gen_attacks {
+                moves.moves_bitboard = attacks;
+            } else {
+                moves.moves_bitboard = attacks & !team_bitboards.friendly_team;
+            }
+            return moves;
+        }
 
-                                // If an enemy king is supplied, and only_gen_attacks is true, and the piece_move_bit == enemy_king_bit then continue moving through the enemy king.
-                                // Because the squares behind the king are technically attacked
+        // Initialize moves with pawn capture moves
+        moves = gen_pawn_captures(&piece, only_gen_attacks, *team_bitboards, &board);
 
-                                let mut continue_in_direction = false;
-                                match enemy_king {
-                                    Some(enemy_king) => {
-                                        if enemy_king.bit == piece_move_bit && only_gen_attacks {
-                                            continue_in_direction = true;
-                                        }
-                                    },
-                                    None => {
-                                        
-                                    },
-                                }
-    
-                                // If the piece can only move don't allow it to capture
-                                if piece_info.move_only {
-                                    break;
-                                }
+        // Do not generate regular moves for pawns if only_gen_attacks is true
+        if only_gen_attacks {
+            return moves;
+        }
 
-                                if !continue_in_direction {
-                                    break_after_move = true;
-                                }
-                            }
-    
-                            // Update bitboards
-                            moves.moves_bitboard |= bitboard;
-                            move_repeated += 1;
-    
-                            if break_after_move {
-                                break;
-                            }
-                            
-    
-                            // Update piece_bit to make it current with the pieces location after the move
-                            piece_bit = piece_move_bit;
-                        },
-                        Err(()) => break,
-                    }
+        // Pawns can't move onto any piece
+        let all_pieces_bitboard = team_bitboards.friendly_team | team_bitboards.enemy_team;
+        let team = crate::attacks::pawn_team(piece.board_index);
 
-                    // Do not let a pawn move more then 2 times in a turn
-                    if piece_pawn && move_repeated == 2 {
-                        break;
-                    }
+        let push = crate::attacks::PAWN_PUSHES[team][piece.bit];
+        if push == 0 || push & all_pieces_bitboard != 0 {
+            return moves;
+        }
+        moves.moves_bitboard |= push;
 
-                    // Allow a pawn to move 2 times in a turn if it has moved 0 times
-                    if piece_pawn && !bit_on(board.board[12], piece.bit) {
-                        moves.en_passant_target_bit = Some(piece_bit);
-                        continue;
-                    }
+        // Allow a pawn to move 2 squares if it has moved 0 times
+        // The square it moves over is the en passant target
+        if !bit_on(board.board[12], piece.bit) {
+            let push_bit = crate::find_bit_on(push, 0);
+            moves.en_passant_target_bit = Some(push_bit);
 
-                    // If the piece doesn't slide break the while loop so it can only move once in each direction
-                    if !piece_info.sliding {
-                        break;
-                    }
-                }
+            let double_push = crate::attacks::PAWN_PUSHES[team][push_bit];
+            if double_push & all_pieces_bitboard == 0 {
+                moves.moves_bitboard |= double_push;
             }
         }
-        
+
         moves
     }
 
@@ -240,8 +1396,6 @@ pub mod move_generator {
     fn gen_pawn_captures(piece: &board_representation::BoardCoordinates, force_attacks: bool, mut team_bitboards: crate::TeamBitboards, board: &board_representation::Board) -> Moves {
         use crate::bit_on;
 
-        let mut moves_bitboard = 0; // Stores the capture moves for the pawns
-
         // Add an imaginary piece at the en passant target so a friendly pawn can capture it
         let en_passant_target_bit = board.en_passant_target.unwrap_or(0);
         let mut en_passant_target_bitboard: u64 = 0;
@@ -250,50 +1404,24 @@ pub mod move_generator {
         }
         team_bitboards.enemy_team |= en_passant_target_bitboard;
 
-        // Get capture moves for pawn
-        let capture_moves: [i8; 2];
+        // Only capture if an enemy occupies the square that will be moved to, or force attacks is true
         let team_white = crate::board_index_white(piece.board_index);
-        if team_white {
-            capture_moves = [-9, -7];
+        let attacks = crate::attacks::PAWN_ATTACKS[crate::attacks::pawn_team(piece.board_index)][piece.bit];
+        let moves_bitboard;
+        if force_attacks {
+            moves_bitboard = attacks;
         } else {
-            capture_moves = [9, 7];
+            moves_bitboard = attacks & team_bitboards.enemy_team;
         }
 
+        // Set en passant capture bit if the piece can capture the en passant target, the captured pawn is behind the target
         let mut en_passant_capture_bit = None;
-        let piece_bit_i8: i8 = piece.bit.try_into().unwrap();
-        for i in 0..capture_moves.len() {
-            
-            let move_delta_bit = capture_moves[i];
-
-            // Check moving the piece here is a valid move
-            let moved_piece = move_piece(piece.bit, move_delta_bit);
-            match moved_piece {
-                Ok(_) => (),
-                Err(_) => continue,
-            }
-
-            let piece_move_bit_i8 = piece_bit_i8 + move_delta_bit;
-            let piece_move_bit = usize::try_from(piece_move_bit_i8).unwrap();
-
-            if bit_on(team_bitboards.enemy_team, piece_move_bit) || force_attacks { // Only try to move if an enemy occupies the square that will be moved to or force attacks is true
-                match move_piece(piece.bit, move_delta_bit) {
-                    Ok(bitboard) => {
-                        moves_bitboard |= bitboard;
-
-                        // Set en passant capture bit if the piece captured an en passant target bit
-                        if piece_move_bit == en_passant_target_bit {
-                            if team_white {
-                                en_passant_capture_bit = Some(usize::try_from(piece_move_bit_i8 + 8).unwrap());
-                            } else {
-                                en_passant_capture_bit = Some(usize::try_from(piece_move_bit_i8 - 8).unwrap());
-                            }
-                        }
-                    },
-    
-                    Err(()) => continue,
-                }
+        if en_passant_target_bitboard != 0 && bit_on(moves_bitboard, en_passant_target_bit) {
+            if team_white {
+                en_passant_capture_bit = Some(en_passant_target_bit + 8);
+            } else {
+                en_passant_capture_bit = Some(en_passant_target_bit - 8);
             }
-            
         }
 
         Moves {
@@ -304,69 +1432,101 @@ pub mod move_generator {
 
     }
 
-    // Returns a Moves struct
-    // Uses en passant target to show where a rook should be added on the board
-    // Uses en passant capture bit to show where a rook should be removed from the board
-    fn castle(king: &board_representation::BoardCoordinates, king_move_bit: usize, team_bitboards: &crate::TeamBitboards, enemy_attack_bitboard: u64, board: &board_representation::Board) -> Moves {
-        use crate::bit_on;
+    // A castling move, the bits of the king and the rook before and after the castle
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    pub struct Castle {
+        pub king_bit: usize,
+        pub king_final_bit: usize,
+        pub rook_bit: usize,
+        pub rook_final_bit: usize,
+    }
 
-        // If the king is in check, or has moved don't castle
-        if bit_on(enemy_attack_bitboard, king.bit) || bit_on(board.board[12], king.bit) {
-            return Moves::new();
+    // King side then queen side castles of the white team, then of the black team
+    const CASTLES: [[Castle; 2]; 2] = [
+        [
+            Castle { king_bit: 60, king_final_bit: 62, rook_bit: 63, rook_final_bit: 61 },
+            Castle { king_bit: 60, king_final_bit: 58, rook_bit: 56, rook_final_bit: 59 },
+        ],
+        [
+            Castle { king_bit: 4, king_final_bit: 6, rook_bit: 7, rook_final_bit: 5 },
+            Castle { king_bit: 4, king_final_bit: 2, rook_bit: 0, rook_final_bit: 3 },
+        ],
+    ];
+
+    // Returns the castles a team can make, king side then queen side, None for a castle that can't be made
+    // A team can castle if its king and rook haven't moved, every bit between them is empty,
+    // and the king isn't in check and doesn't move through or onto a bit attacked by the enemy team
+    pub fn castling_moves(board: &board_representation::Board, white: bool, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> [Option<Castle>; 2] {
+        let king_index;
+        if white {
+            king_index = 5;
+        } else {
+            king_index = 11;
         }
 
-        let all_pieces_bitboard = team_bitboards.friendly_team | team_bitboards.enemy_team;
+        let king = board_representation::BoardCoordinates {
+            board_index: king_index,
+            bit: crate::find_bit_on(board.board[king_index], 0),
+        };
+        let enemy_attacks = gen_enemy_attacks(&king, crate::TeamBitboards::new(king_index, board), board, pieces_info);
+        legal_castles(white, enemy_attacks.enemy_attack_bitboard, board)
+    }
 
-        let king_castle_moves: [i8; 2] =         [1, -1];
-        let rook_relative_coordinates: [i8; 2] = [3, -4];
-        let rook_castle_moves: [i8; 2] =         [-2, 3];
-        
-        for i in 0..king_castle_moves.len() {
-            let mut piece_bit = king.bit;
-
-            // Get rook bit
-            let rook_bit = (piece_bit as i8 + rook_relative_coordinates[i]).try_into();
-            let rook_bit = match rook_bit {
-                Ok(bit) => bit,
-                Err(_) => continue,
-            };
+    // Castling moves (see castling_moves) from an enemy attack bitboard that has already been generated
+    // Pass an empty attack bitboard to only check the castling rights and the bits between the king and rook
+    pub(crate) fn legal_castles(white: bool, enemy_attack_bitboard: u64, board: &board_representation::Board) -> [Option<Castle>; 2] {
+        use crate::bit_on;
+
+        let team;
+        let king_index;
+        let rook_index;
+        if white {
+            team = 0;
+            king_index = 5;
+            rook_index = 1;
+        } else {
+            team = 1;
+            king_index = 11;
+            rook_index = 7;
+        }
+
+        let mut castles = [None; 2];
+        let all_pieces_bitboard = board.to_bitboard();
+        for i in 0..2 {
+            let castle = CASTLES[team][i];
 
-            // A castle cannot be performed in this direction if the rook is:
-            // Outside the range of the board
-            // Doesn't exist on the board
-            // Not at moves = 0
-            if rook_bit > 63 || !bit_on(board.board[king.board_index - 4], rook_bit) || bit_on(board.board[12], rook_bit) {
+            // The king and rook have to be on their starting bits, and can't have moved
+            if !bit_on(board.board[king_index], castle.king_bit) || !bit_on(board.board[rook_index], castle.rook_bit)
+                || bit_on(board.board[12], castle.king_bit) || bit_on(board.board[12], castle.rook_bit) {
                 continue;
             }
 
-            for j in 0..2 {
-                let piece_move_bit = usize::try_from(piece_bit as i8 + king_castle_moves[i]).unwrap();
-                
-                // Do not allow the king to castle through squares that are attacked or to castle through pieces
-                if bit_on(enemy_attack_bitboard, piece_move_bit) || bit_on(all_pieces_bitboard, piece_move_bit)  {
-                    break;
-                }
+            // Every bit between the king and rook has to be empty (on the queen side this includes the bit next to the rook, which the king doesn't cross)
+            if crate::rays::between(castle.king_bit, castle.rook_bit) & all_pieces_bitboard != 0 {
+                continue;
+            }
 
-                piece_bit = piece_move_bit;
+            // The king can't castle out of, through, or into check
+            let king_path = crate::rays::between(castle.king_bit, castle.king_final_bit) | 1 << castle.king_bit | 1 << castle.king_final_bit;
+            if king_path & enemy_attack_bitboard != 0 {
+                continue;
+            }
 
-                if j == 1 && piece_move_bit == king_move_bit {
+            castles[i] = Some(castle);
+        }
+        castles
+    }
 
-                    // Check there is no piece next to the rook on the queen side
-                    if i == 1 && bit_on(all_pieces_bitboard, usize::try_from(piece_bit as i8 + king_castle_moves[i]).unwrap()) {
-                        return Moves::new();
-                    } else {
-                        return Moves {
-                            moves_bitboard: 1 << king_move_bit,
-                            en_passant_target_bit: Some((rook_bit as i8 + rook_castle_moves[i]).try_into().unwrap()), // Use en passant target to show where a rook should be added on the board
-                            en_passant_capture_bit: Some(rook_bit), // Use en passant capture bit to show where a rook should be removed from the board
-                        };
-                    }
-                    
-                }
+    // Returns the bitboard of the bits the king can castle to, from the castles returned by legal_castles
+    pub(crate) fn castles_bitboard(castles: &[Option<Castle>; 2]) -> u64 {
+        let mut bitboard = 0;
+        for castle in castles {
+            match castle {
+                Some(castle) => bitboard |= 1 << castle.king_final_bit,
+                None => (),
             }
         }
-
-        Moves::new()
+        bitboard
     }
 
     #[derive(PartialEq, Debug)]
@@ -376,6 +1536,14 @@ pub mod move_generator {
         checking_pieces_no: usize,
     }
 
+    impl EnemyAttacks {
+
+        // Returns true if the king the attacks were generated for is in check
+        pub fn in_check(&self) -> bool {
+            self.checking_pieces_no != 0
+        }
+    }
+
     // Generates atacks of enemys to the kings team, stores enemy pieces that put the king in check
     pub fn gen_enemy_attacks(king: &board_representation::BoardCoordinates, team_bitboards: crate::TeamBitboards, board: &board_representation::Board, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> EnemyAttacks {
         use board_representation::BoardCoordinates;
@@ -402,13 +1570,7 @@ pub mod move_generator {
 
         // Loop through all enemy pieces
         for i in enemy_indexes {
-            for j in 0..64 {
-
-                // Skip if there is no piece on the square
-                if !bit_on(board.board[i], j) {
-                    continue;
-                }
-
+            for j in crate::bitboard::bits(board.board[i]) {
                 let board_coordinates = BoardCoordinates {
                     board_index: i,
                     bit: j,
@@ -433,26 +1595,44 @@ pub mod move_generator {
         }
     }
 
-    // Returns true if the given king a move safely
-    fn king_can_move(king: &board_representation::BoardCoordinates, enemy_attacks: &EnemyAttacks, team_bitboards: crate::TeamBitboards, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> bool {
-        use crate::bit_on;
-        
-        let king_no_move_bitboard = enemy_attacks.enemy_attack_bitboard | team_bitboards.friendly_team; // Bitboard containing squares the king can't move to
-        let king_piece_info = &pieces_info[5];
-
-        let mut king_can_move = false;
-        for i in 0..king_piece_info.moves_no { // Loop through all kings moves to see if there is a safe square to move to
-            if crate::bit_move_valid(king.bit, king_piece_info.moves[i]) { // Check move is valid on the board space
-                let king_move_bit = usize::try_from(king.bit as i8 + king_piece_info.moves[i]).unwrap();
+    // Returns true if moving a piece that isn't the king would leave its king in check, when the king isn't in check before the move
+    // Only a sliding piece can be uncovered, either by moving a pinned piece off the line through the king,
+    // or by an en passant capture that removes both pawns from between the king and a rook or queen on the same row (which pinned_pieces can't see)
+    fn exposes_king(piece: &board_representation::BoardCoordinates, final_bit: usize, king_bit: usize, board: &board_representation::Board) -> bool {
+        let piece_white = crate::board_index_white(piece.board_index);
+        let enemy_offset;
+        if piece_white {
+            enemy_offset = 6;
+        } else {
+            enemy_offset = 0;
+        }
 
-                if !bit_on(king_no_move_bitboard, king_move_bit) { // Check if the king can safely move to the new coordinates
-                    king_can_move = true;
-                    break;
-                }
+        // Pieces on the board after the move, and the bits of the enemy pieces that were captured
+        let mut occupancy = board.to_bitboard() & !(1 << piece.bit) | 1 << final_bit;
+        let mut captured: u64 = 1 << final_bit;
+        if piece.board_index % 6 == 0 && board.en_passant_target == Some(final_bit) {
+            // The captured pawn is behind the en passant target
+            let capture_bit;
+            if piece_white {
+                capture_bit = final_bit + 8;
+            } else {
+                capture_bit = final_bit - 8;
             }
+            occupancy &= !(1 << capture_bit);
+            captured |= 1 << capture_bit;
         }
 
-        king_can_move
+        let rooks = (board.board[enemy_offset + 1] | board.board[enemy_offset + 4]) & !captured;
+        let bishops = (board.board[enemy_offset + 3] | board.board[enemy_offset + 4]) & !captured;
+        crate::attacks::rook_attacks(king_bit, occupancy) & rooks != 0 || crate::attacks::bishop_attacks(king_bit, occupancy) & bishops != 0
+    }
+
+    // Returns true if the given king a move safely
+    fn king_can_move(king: &board_representation::BoardCoordinates, enemy_attacks: &EnemyAttacks, team_bitboards: crate::TeamBitboards) -> bool {
+        let king_no_move_bitboard = enemy_attacks.enemy_attack_bitboard | team_bitboards.friendly_team; // Bitboard containing squares the king can't move to
+
+        // Check if the king can safely move to any of the squares around it
+        crate::attacks::KING_ATTACKS[king.bit] & !king_no_move_bitboard != 0
     }
 
     // Is mate expects the king team, and the current teams turn on the board to be the same
@@ -462,7 +1642,7 @@ pub mod move_generator {
         use board_representation::BoardCoordinates;
 
         // True if the king can safely move
-        let king_can_move = king_can_move(king, enemy_attacks, team_bitboards, pieces_info);
+        let king_can_move = king_can_move(king, enemy_attacks, team_bitboards);
 
         if !king_can_move {
 
@@ -495,13 +1675,7 @@ pub mod move_generator {
             }
             
             for board_index in friendly_indexes {
-                for initial_bit in 0..64 {
-
-                    // If a piece doesn't exist at these coordinates continue to the next iteration
-                    if !bit_on(board.board[board_index], initial_bit) {
-                        continue;
-                    }
-
+                for initial_bit in crate::bitboard::bits(board.board[board_index]) {
                     let piece_coordinates = BoardCoordinates {
                         board_index: board_index,
                         bit: initial_bit,
@@ -529,111 +1703,113 @@ pub mod move_generator {
                     let checking_piece_attacks = gen_piece(&checking_piece, None, &enemy_team_bitboards, true, board, pieces_info).moves_bitboard;
                     
                     
-                    for final_bit in 0..64 {
-                        if bit_on(piece_attacks.moves_bitboard | pawn_moves_bitboard, final_bit) { // true if final_bit can be moved to
-                            if use_checking_piece {
-                                if bit_on(piece_attacks.moves_bitboard, checking_piece.bit) && final_bit == checking_piece.bit { // true if the checking piece can be captured
-                                    // Update team bitboards and main board with piece move and piece captures
-                                    let mut team_bitboards = team_bitboards;
-                                    team_bitboards.friendly_team ^= 1 << initial_bit | 1 << final_bit; // Move piece on friendly team bitboard
-                                    team_bitboards.enemy_team ^= 1 << checking_piece.bit; // Remove captured piece from enemy teams bitboard
-
-                                    let mut board = *board;
-                                    board.board[checking_piece.board_index] ^= 1 << checking_piece.bit; // Remove captured piece from board
-                                    board.board[board_index] ^= 1 << initial_bit | 1 << final_bit; // Move friendly piece on board
+                    for final_bit in crate::bitboard::bits(piece_attacks.moves_bitboard | pawn_moves_bitboard) { // Bits that can be moved to
+                        if use_checking_piece {
+                            // A pawn that just moved two squares and gives check can also be captured en passant, the capturing pawn moves behind it
+                            let mut en_passant_capture = false;
+                            if (board_index == 0 || board_index == 6) && checking_piece.board_index % 6 == 0 && board.en_passant_target == Some(final_bit) {
+                                en_passant_capture = checking_piece.bit == final_bit + 8 || checking_piece.bit + 8 == final_bit;
+                            }
+
+                            if (bit_on(piece_attacks.moves_bitboard, checking_piece.bit) && final_bit == checking_piece.bit) || en_passant_capture { // true if the checking piece can be captured
+                                // Update team bitboards and main board with piece move and piece captures
+                                let mut team_bitboards = team_bitboards;
+                                team_bitboards.friendly_team ^= 1 << initial_bit | 1 << final_bit; // Move piece on friendly team bitboard
+                                team_bitboards.enemy_team ^= 1 << checking_piece.bit; // Remove captured piece from enemy teams bitboard
+
+                                let mut board = *board;
+                                board.board[checking_piece.board_index] ^= 1 << checking_piece.bit; // Remove captured piece from board
+                                board.board[board_index] ^= 1 << initial_bit | 1 << final_bit; // Move friendly piece on board
+                                
+                                // Regenerate enemy attacks after capturing the checking piece
+                                let enemy_attacks = gen_enemy_attacks(&king, team_bitboards, &board, pieces_info);
+                    
+                                // If there is a new checking piece after capturing the original one then the king is in mate, otherwise the king is not in mate
+                                match enemy_attacks.checking_pieces[0] {
+                                    Some(_) => {
+                                        continue;
+                                    },
+                                    None => return false,
+                                };
+                            } else { // If the checking piece cannot be captured, check if its attack can be blocked
+                                if bit_on(checking_piece_attacks, final_bit) { // True if the friendly piece moved into the checking pieces path
                                     
-                                    // Regenerate enemy attacks after capturing the checking piece
-                                    let enemy_attacks = gen_enemy_attacks(&king, team_bitboards, &board, pieces_info);
-                        
-                                    // If there is a new checking piece after capturing the original one then the king is in mate, otherwise the king is not in mate
-                                    match enemy_attacks.checking_pieces[0] {
-                                        Some(_) => {
+                                    // If the current piece is a pawn and the the current move isn't also on it's move bitboard then the move isn't valid
+                                    if board_index == 0 || board_index == 6 {
+                                        if !bit_on(pawn_moves_bitboard, final_bit) {
                                             continue;
-                                        },
-                                        None => return false,
-                                    };
-                                } else { // If the checking piece cannot be captured, check if its attack can be blocked
-                                    if bit_on(checking_piece_attacks, final_bit) { // True if the friendly piece moved into the checking pieces path
-                                        
-                                        // If the current piece is a pawn and the the current move isn't also on it's move bitboard then the move isn't valid
-                                        if board_index == 0 || board_index == 6 {
-                                            if !bit_on(pawn_moves_bitboard, final_bit) {
-                                                continue;
-                                            }
                                         }
+                                    }
 
-                                        // Update enemy teams bitboard
-                                        let mut enemy_team_bitboards = enemy_team_bitboards;
+                                    // Update enemy teams bitboard
+                                    let mut enemy_team_bitboards = enemy_team_bitboards;
 
-                                        // Remove captured piece
-                                        if bit_on(enemy_team_bitboards.friendly_team, final_bit) {
-                                            enemy_team_bitboards.friendly_team ^= 1 << checking_piece.bit; 
-                                        }
-                                        
-                                        enemy_team_bitboards.enemy_team ^= 1 << initial_bit | 1 << final_bit; // Move piece
+                                    // Remove captured piece
+                                    if bit_on(enemy_team_bitboards.friendly_team, final_bit) {
+                                        enemy_team_bitboards.friendly_team ^= 1 << checking_piece.bit; 
+                                    }
+                                    
+                                    enemy_team_bitboards.enemy_team ^= 1 << initial_bit | 1 << final_bit; // Move piece
 
-                                        // Get checking piece attacks after the move
-                                        let checking_piece_attacks = gen_piece(&checking_piece, None, &enemy_team_bitboards, true, board, pieces_info).moves_bitboard;
+                                    // Get checking piece attacks after the move
+                                    let checking_piece_attacks = gen_piece(&checking_piece, None, &enemy_team_bitboards, true, board, pieces_info).moves_bitboard;
+                                    
+                                    // True if the original checking piece is no longer putting the king in check
+                                    if !bit_on(checking_piece_attacks, king.bit) {
+                                        let team_bitboards = crate::TeamBitboards {
+                                            friendly_team: enemy_team_bitboards.enemy_team,
+                                            enemy_team: enemy_team_bitboards.friendly_team,
+                                        };
+
+                                        let sliding_king = BoardCoordinates {
+                                            board_index: 4,
+                                            bit: king.bit,
+                                        };
+
+                                        // Get squares that could potentially be putting the king in check afer the move
+                                        let king_check_squares = gen_piece(&sliding_king, None, &team_bitboards, false, board, pieces_info);
                                         
-                                        // True if the original checking piece is no longer putting the king in check
-                                        if !bit_on(checking_piece_attacks, king.bit) {
-                                            let team_bitboards = crate::TeamBitboards {
-                                                friendly_team: enemy_team_bitboards.enemy_team,
-                                                enemy_team: enemy_team_bitboards.friendly_team,
-                                            };
-
-                                            let sliding_king = BoardCoordinates {
-                                                board_index: 4,
-                                                bit: king.bit,
-                                            };
-
-                                            // Get squares that could potentially be putting the king in check afer the move
-                                            let king_check_squares = gen_piece(&sliding_king, None, &team_bitboards, false, board, pieces_info);
-                                            
-                                            // Loop through every king_check_square to check for enemy pieces
-                                            // Generate moves from enemy pieces to see if any are putting the king in check
-
-                                            let mut mate = false;
-                                            for i in 0..64 {
-                                                if bit_on(king_check_squares.moves_bitboard, i) && bit_on(team_bitboards.enemy_team, i) {
-                                                    for j in 0..12 {
-                                                        if bit_on(board.board[j], i) {
-
-                                                            // Potential checking piece
-                                                            let checking_piece = BoardCoordinates {
-                                                                board_index: j,
-                                                                bit: i,
-                                                            };
-
-                                                            let enemy_team_bitboards = crate::TeamBitboards {
-                                                                friendly_team: team_bitboards.enemy_team,
-                                                                enemy_team: team_bitboards.friendly_team,
-                                                            };
-
-                                                            // Get checking piece attacks
-                                                            let checking_piece_attacks = gen_piece(&checking_piece, None, &enemy_team_bitboards, true, board, pieces_info);
-
-                                                            // If the king is in check after the move then it is mate
-                                                            if bit_on(checking_piece_attacks.moves_bitboard, king.bit) {
-                                                                mate = true;
-                                                            }
-                                                        }
+                                        // Loop through every king_check_square to check for enemy pieces
+                                        // Generate moves from enemy pieces to see if any are putting the king in check
+
+                                        let mut mate = false;
+                                        for i in crate::bitboard::bits(king_check_squares.moves_bitboard & team_bitboards.enemy_team) {
+                                            for j in 0..12 {
+                                                if bit_on(board.board[j], i) {
+
+                                                    // Potential checking piece
+                                                    let checking_piece = BoardCoordinates {
+                                                        board_index: j,
+                                                        bit: i,
+                                                    };
+
+                                                    let enemy_team_bitboards = crate::TeamBitboards {
+                                                        friendly_team: team_bitboards.enemy_team,
+                                                        enemy_team: team_bitboards.friendly_team,
+                                                    };
+
+                                                    // Get checking piece attacks
+                                                    let checking_piece_attacks = gen_piece(&checking_piece, None, &enemy_team_bitboards, true, board, pieces_info);
+
+                                                    // If the king is in check after the move then it is mate
+                                                    if bit_on(checking_piece_attacks.moves_bitboard, king.bit) {
+                                                        mate = true;
                                                     }
                                                 }
                                             }
+                                        }
 
-                                            if !mate {
-                                                return false;
-                                            }
+                                        if !mate {
+                                            return false;
                                         }
                                     }
                                 }
-                            } else { // Check for stalemate
-    
-                                // If the any piece can move then it is not mate
-                                if piece_attacks.moves_bitboard > 0 {
-                                    return false;
-                                }
+                            }
+                        } else { // Check for stalemate
+
+                            // If the any piece can move without leaving the king in check then it is not mate
+                            if !exposes_king(&piece_coordinates, final_bit, king.bit, board) {
+                                return false;
                             }
                         }
                     }
@@ -647,50 +1823,132 @@ pub mod move_generator {
     }
 
     // Errors that can be encountered when a team makes a turn
-    #[derive(PartialEq, Debug)]
+    #[derive(Copy, Clone, PartialEq, Debug)]
     pub enum TurnError {
-        Win, // The team wins
-        Draw, // The team draws
+        Checkmate, // The team wins by checkmating the enemy king
+        KingOfTheHill, // The team wins by moving its king to the centre (King of the Hill, see variant.rs)
+        ThreeCheck, // The team wins by checking the enemy king for the third time (Three-Check, see variant.rs)
+        Stalemate, // The enemy team has no legal moves, and isn't in check
+        FiftyMoveDraw, // 50 moves have been made by each team without a capture or pawn move
+        InsufficientMaterial, // Neither team has enough pieces left to checkmate
+        Repetition, // The same position has occured three times (new_turn has no move history so this is detected by whatever keeps the history)
         InvalidMove, // The piece cannot move to the new coordinates
         InvalidMoveCheck, // The piece cannot move to the new coordinates because the king is in check
     }
 
+    impl TurnError {
+
+        // Returns true if the error is a draw
+        pub fn is_draw(&self) -> bool {
+            match self {
+                TurnError::Stalemate | TurnError::FiftyMoveDraw | TurnError::InsufficientMaterial | TurnError::Repetition => true,
+                _ => false,
+            }
+        }
+
+        // Returns true if the error is a win for the team that moved
+        pub fn is_win(&self) -> bool {
+            match self {
+                TurnError::Checkmate | TurnError::KingOfTheHill | TurnError::ThreeCheck => true,
+                _ => false,
+            }
+        }
+
+        // Returns true if the error ends the game
+        pub fn is_game_over(&self) -> bool {
+            self.is_win() || self.is_draw()
+        }
+    }
+
     // Move piece to piece_move_bit if the move is valid
+    // A pawn moving to the last row promotes to the promotion piece (a board index for the white team, see PROMOTION_PIECES), or a queen if there is no promotion piece
     // If move is valid update the board, else return an error
     pub fn new_turn(
         piece: &board_representation::BoardCoordinates,
         piece_move_bit: usize,
+        promotion: Option<usize>,
+        friendly_king: board_representation::BoardCoordinates,
+        enemy_king: &board_representation::BoardCoordinates,
+        enemy_attacks: &EnemyAttacks,
+        team_bitboards: crate::TeamBitboards,
+        board: board_representation::Board,
+        pieces_info: &[crate::piece::constants::PieceInfo; 12]
+    ) -> Result<board_representation::Board, TurnError> {
+        play_turn(piece, piece_move_bit, promotion, friendly_king, enemy_king, enemy_attacks, team_bitboards, board, true, pieces_info)
+    }
+
+    // Same as new_turn, but the game never ends, the board after every valid move is returned even if the move wins, draws or mates
+    // Used by perft, which counts the moves of every position until a team has no legal moves whatever the rules of the game say
+    pub(crate) fn new_turn_unadjudicated(
+        piece: &board_representation::BoardCoordinates,
+        piece_move_bit: usize,
+        promotion: Option<usize>,
+        friendly_king: board_representation::BoardCoordinates,
+        enemy_king: &board_representation::BoardCoordinates,
+        enemy_attacks: &EnemyAttacks,
+        team_bitboards: crate::TeamBitboards,
+        board: board_representation::Board,
+        pieces_info: &[crate::piece::constants::PieceInfo; 12]
+    ) -> Result<board_representation::Board, TurnError> {
+        play_turn(piece, piece_move_bit, promotion, friendly_king, enemy_king, enemy_attacks, team_bitboards, board, false, pieces_info)
+    }
+
+    // Makes a move for new_turn and new_turn_unadjudicated, the end of the game is only checked for if adjudicate is true
+    fn play_turn(
+        piece: &board_representation::BoardCoordinates,
+        piece_move_bit: usize,
+        promotion: Option<usize>,
         mut friendly_king: board_representation::BoardCoordinates,
         enemy_king: &board_representation::BoardCoordinates,
         enemy_attacks: &EnemyAttacks,
         mut team_bitboards: crate::TeamBitboards,
         mut board: board_representation::Board,
+        adjudicate: bool,
         pieces_info: &[crate::piece::constants::PieceInfo; 12]
     ) -> Result<board_representation::Board, TurnError> {
         use crate::TeamBitboards;
         use crate::board_index_white;
 
-        // If the piece is a king generate castle moves
-        let mut piece_moves = Moves::new();
-        if piece == &friendly_king {
-            piece_moves = castle(piece, piece_move_bit, &team_bitboards, enemy_attacks.enemy_attack_bitboard, &board);
-        }
+        // Bitboards and en passant target before the move, used to update the incremental evaluations and the hash with the pieces that moved
+        let initial_bitboards = board.board;
+        let initial_en_passant_target = board.en_passant_target;
 
         // Get piece team
         let piece_white = board_index_white(piece.board_index);
 
+        // If the piece is a king moving to a castling bit get the castle
+        let mut castle = None;
+        if piece == &friendly_king {
+            for legal_castle in legal_castles(piece_white, enemy_attacks.enemy_attack_bitboard, &board) {
+                match legal_castle {
+                    Some(legal_castle) if legal_castle.king_final_bit == piece_move_bit => castle = Some(legal_castle),
+                    _ => (),
+                }
+            }
+        }
+
         // If the piece is on the wrong team return an error
         if piece_white != board_index_white(friendly_king.board_index) {
             return Err(TurnError::InvalidMove);
         }
 
-        // If the castle was valid move the rook
-        // If the castle was not valid generate regular piece moves
-        let mut castled = false;
-        match piece_moves.en_passant_target_bit {
-            Some(rook_add_bit) => {
-                let rook_remove_bit = piece_moves.en_passant_capture_bit.unwrap();
+        // Only pawns moving to the last row can have a promotion piece, and pawns can't promote to pawns or kings
+        let promotion_index;
+        match promotion {
+            Some(index) => {
+                if !promotes(piece.board_index, piece_move_bit) || !PROMOTION_PIECES.contains(&promotion) {
+                    return Err(TurnError::InvalidMove);
+                }
+                promotion_index = index;
+            },
+            None => promotion_index = 4,
+        }
 
+        // If the move is a castle move the rook, otherwise generate regular piece moves
+        let mut piece_moves;
+        let castled;
+        match castle {
+            Some(castle) => {
                 // Get friendly rook board index
                 let friendly_rook_board_index;
                 if piece_white {
@@ -699,11 +1957,16 @@ pub mod move_generator {
                     friendly_rook_board_index = 7;
                 }
 
-                // Remove rook rook_remove_bit and add rook at rook_add_bit
-                board.board[friendly_rook_board_index] ^= 1 << rook_remove_bit | 1 << rook_add_bit;
+                // Remove the rook from its starting bit and add it next to the king
+                board.board[friendly_rook_board_index] ^= 1 << castle.rook_bit | 1 << castle.rook_final_bit;
+                piece_moves = Moves::new();
+                piece_moves.moves_bitboard = 1 << castle.king_final_bit;
                 castled = true;
             },
-            None => piece_moves = gen_piece(piece, None, &team_bitboards, false, &board, pieces_info), // Gen non castle moves
+            None => {
+                piece_moves = gen_piece(piece, None, &team_bitboards, false, &board, pieces_info);
+                castled = false;
+            },
         }
         
         // Return an error if the piece_move bit is not on in the piece_moves bitboard
@@ -711,8 +1974,14 @@ pub mod move_generator {
             return Err(TurnError::InvalidMove);
         }
 
+        let mut captured = false; // True if an enemy piece was captured, including en passant captures
         if !castled {
 
+            // A pawn that could capture en passant only does if it moved to the en passant target, otherwise a pawn push would also remove the enemy pawn
+            if board.en_passant_target != Some(piece_move_bit) {
+                piece_moves.en_passant_capture_bit = None;
+            }
+
             // Remove en passant capture from the board
             match piece_moves.en_passant_capture_bit {
                 Some(capture_bit) => {
@@ -727,6 +1996,8 @@ pub mod move_generator {
                         }
                         
                         board.board[enemy_pawn_board_index] ^= en_passant_capture_xor_bitboard; // Remove en passant capture piece from the board
+                        team_bitboards.enemy_team ^= en_passant_capture_xor_bitboard; // Also remove it from the enemy team, so a king on the same row as both pawns is found in check after the capture
+                        captured = true;
                 },
                 None => (),
             }
@@ -741,17 +2012,14 @@ pub mod move_generator {
         team_bitboards.friendly_team ^= piece_move_xor_bitboard;
         board.board[12] |= piece_move_bitboard;
 
-        // Promote pawns to queens if they are in the top row (for their respective team)
-        // Give value for promoting pawns to queens
+        // Promote pawns if they are in the top row (for their respective team)
+        // Give value for the piece the pawn promoted to, minus the pawn
         let mut value = 0;
-        if piece_white && piece.board_index == 0 && piece_move_bit < 8 {
-            board.board[piece.board_index] ^= 1 << piece.bit;
-            board.board[4] |= piece_move_bitboard;
-            value += 8;
-        } else if !piece_white && piece.board_index == 6 && piece_move_bit > 55 {
+        if promotes(piece.board_index, piece_move_bit) {
+            let promotion_board_index = promotion_index + piece.board_index;
             board.board[piece.board_index] ^= 1 << piece.bit;
-            board.board[10] |= piece_move_bitboard;
-            value += 8;
+            board.board[promotion_board_index] |= piece_move_bitboard;
+            value += pieces_info[promotion_board_index].value - pieces_info[piece.board_index].value;
         } else {
             board.board[piece.board_index] ^= piece_move_xor_bitboard; // Else move piece on its bitboard to the new coordinates
         }
@@ -764,23 +2032,16 @@ pub mod move_generator {
         // If an enemy piece is captured get the value of the piece
         if crate::bit_on(team_bitboards.enemy_team, piece_move_bit) {
             team_bitboards.enemy_team ^= piece_move_bitboard; // Removed captured piece on enemy team bitboard
-
-            // Get enemy board indexes
-            let enemy_indexes;
-            if piece_white {
-                enemy_indexes = 6..12;
-            } else {
-                enemy_indexes = 0..6;
-            }
-
-            // If a piece was captured remove it on the appropriate enemy bitboard and store the captured pieces value
-            for i in enemy_indexes {
-                let new_piece_bitboard = board.board[i] ^ piece_move_bitboard ;
-                if new_piece_bitboard < board.board[i] {
-                    board.board[i] = new_piece_bitboard;
-                    value = pieces_info[i].value;
-                    break;
-                }
+            captured = true;
+
+            // The mailbox is still the board before the move, so it has the captured piece
+            // Remove it on its bitboard and store the captured pieces value
+            match board.piece_on(piece_move_bit) {
+                Some(captured_index) => {
+                    board.board[captured_index] ^= piece_move_bitboard;
+                    value = pieces_info[captured_index].value;
+                },
+                None => (),
             }
         }
 
@@ -798,17 +2059,20 @@ pub mod move_generator {
             board.en_passant_target = None;
         }
 
-        // Increment half move clock if no capture was made and a pawn did not move, otherwise reset the half move clock
-        if value == 0 && !(piece.board_index == 0 || piece.board_index == 6) {
-            board.half_move_clock += 1;
-        } else {
+        // Reset the half move clock if a piece was captured or a pawn moved, otherwise increment it
+        if captured || piece.board_index == 0 || piece.board_index == 6 {
             board.half_move_clock = 0;
-            team_bitboards.enemy_team ^= 1 << piece_move_bit; // Remove captured piece from enemy team bitboard
+        } else {
+            board.half_move_clock += 1;
         }
 
         // Increment total half moves
         board.half_moves += 1;
 
+        // Every bitboard has been updated, so the mailbox can be updated
+        board.update_mailbox(&initial_bitboards);
+        debug_assert!(board.mailbox_consistent(), "Mailbox out of sync with the bitboards");
+
         // If the king is in check after the move return an error
         let enemy_attacks = gen_enemy_attacks(&friendly_king, team_bitboards, &board, pieces_info);
         if enemy_attacks.checking_pieces_no != 0 {
@@ -816,28 +2080,58 @@ pub mod move_generator {
         }
 
         // Generate necassary values to check if the enemy king has been mated
-        let mut enemy_team_bitboards = TeamBitboards {
+        let enemy_team_bitboards = TeamBitboards {
             friendly_team: team_bitboards.enemy_team,
             enemy_team: team_bitboards.friendly_team,
         };
 
-        // If a piece was captured during the move remove it from the team bitboard
-        if crate::bit_on(enemy_team_bitboards.friendly_team, piece_move_bit) {
-            enemy_team_bitboards.friendly_team ^= piece_move_bitboard;
-        }
-
         // The team to move is now opposite
         board.whites_move = !board.whites_move;
 
         let friendly_attacks = gen_enemy_attacks(enemy_king, enemy_team_bitboards, &board, pieces_info); // Get friendly attacks to use as enemy attacks for enemy king
-        let enemy_mate =  is_mate(enemy_king, &friendly_attacks, enemy_team_bitboards, &board, pieces_info);
 
-        // Return errors for the end of the game if there is a mate
-        if enemy_mate {
-            if friendly_attacks.checking_pieces_no == 0 {
-                return Err(TurnError::Draw); // Stalemate
+        // Count the check for Three-Check
+        // The hash is updated here because update_hash only knows about the changes to the bitboards
+        // Checks past the win are only made without adjudication, they aren't counted so the count stays within the zobrist keys
+        let team;
+        if piece_white {
+            team = 0;
+        } else {
+            team = 1;
+        }
+        if board.variant == crate::variant::Variant::ThreeCheck && friendly_attacks.checking_pieces_no != 0 && board.checks[team] < crate::variant::CHECKS_TO_WIN {
+            board.hash ^= crate::zobrist::checks_key(team, board.checks[team]);
+            board.checks[team] += 1;
+            board.hash ^= crate::zobrist::checks_key(team, board.checks[team]);
+        }
+
+        // Without adjudication the board is returned whatever the position, the rest of the board is updated the same either way
+        if adjudicate {
+            // Wins by the rules of the variant come first, a king that reaches the centre wins even if the enemy would also be stalemated
+            match crate::variant::variant_win(&board, piece_white) {
+                Some(error) => return Err(error),
+                None => (),
+            }
+
+            let enemy_mate =  is_mate(enemy_king, &friendly_attacks, enemy_team_bitboards, &board, pieces_info);
+
+            // Return errors for the end of the game if there is a mate
+            if enemy_mate {
+                if friendly_attacks.checking_pieces_no == 0 {
+                    return Err(TurnError::Stalemate);
+                }
+                return Err(TurnError::Checkmate);
+            }
+
+            // Draw by the fifty move rule after checking for mate, so checkmates made this move take priority
+            if board.half_move_clock >= 100 {
+                return Err(TurnError::FiftyMoveDraw);
+            }
+
+            // Neither team can checkmate with the pieces that are left
+            if board.insufficient_material() {
+                return Err(TurnError::InsufficientMaterial);
             }
-            return Err(TurnError::Win); // Checkmate
         }
 
         // Update points
@@ -848,25 +2142,523 @@ pub mod move_generator {
         }
         board.points_delta = value;
 
+        crate::evaluation::update_tapered_evaluation(&initial_bitboards, &mut board, pieces_info);
+        crate::zobrist::update_hash(&initial_bitboards, initial_en_passant_target, &mut board);
+        #[cfg(feature = "nnue")]
+        crate::nnue::update_accumulator(&initial_bitboards, &mut board);
+
         Ok(board)
     }
 
-    // Returns a bitboard where a piece is moved from inital by delta bit
-    // Only moves if the piece will still be on the board
-    fn move_piece(initial_bit: usize, delta_bit: i8) -> Result<u64, ()> {
-        use crate::bit_move_valid;
+    // Makes a move on a board, finding the kings and enemy attacks that new_turn needs
+    // Used where a single move is made (e.g. notation), the search generates these once for every move of a board instead
+    pub fn make_move(
+        piece: &board_representation::BoardCoordinates,
+        piece_move_bit: usize,
+        promotion: Option<usize>,
+        board: board_representation::Board,
+        pieces_info: &[crate::piece::constants::PieceInfo; 12]
+    ) -> Result<board_representation::Board, TurnError> {
+        play_move(piece, piece_move_bit, promotion, board, true, pieces_info)
+    }
+
+    // Same as make_move, but the game never ends (see new_turn_unadjudicated)
+    // Used where the board after a move is needed even if the move ends the game, e.g. to find if a drawing move gives check
+    pub(crate) fn make_move_unadjudicated(
+        piece: &board_representation::BoardCoordinates,
+        piece_move_bit: usize,
+        promotion: Option<usize>,
+        board: board_representation::Board,
+        pieces_info: &[crate::piece::constants::PieceInfo; 12]
+    ) -> Result<board_representation::Board, TurnError> {
+        play_move(piece, piece_move_bit, promotion, board, false, pieces_info)
+    }
+
+    // Finds the kings and enemy attacks for make_move and make_move_unadjudicated
+    fn play_move(
+        piece: &board_representation::BoardCoordinates,
+        piece_move_bit: usize,
+        promotion: Option<usize>,
+        board: board_representation::Board,
+        adjudicate: bool,
+        pieces_info: &[crate::piece::constants::PieceInfo; 12]
+    ) -> Result<board_representation::Board, TurnError> {
+        let friendly_king;
+        let enemy_king;
+        if board.whites_move {
+            friendly_king = 5;
+            enemy_king = 11;
+        } else {
+            friendly_king = 11;
+            enemy_king = 5;
+        }
+
+        let friendly_king = board_representation::BoardCoordinates {
+            board_index: friendly_king,
+            bit: crate::find_bit_on(board.board[friendly_king], 0),
+        };
+        let enemy_king = board_representation::BoardCoordinates {
+            board_index: enemy_king,
+            bit: crate::find_bit_on(board.board[enemy_king], 0),
+        };
+
+        let team_bitboards = crate::TeamBitboards::new(friendly_king.board_index, &board);
+        let enemy_attacks = gen_enemy_attacks(&friendly_king, team_bitboards, &board, pieces_info);
+        play_turn(piece, piece_move_bit, promotion, friendly_king, &enemy_king, &enemy_attacks, team_bitboards, board, adjudicate, pieces_info)
+    }
+
+    // Returns true if the king of the team to move is in check
+    pub fn in_check(board: &board_representation::Board, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> bool {
+        let king_index;
+        if board.whites_move {
+            king_index = 5;
+        } else {
+            king_index = 11;
+        }
+
+        let king = board_representation::BoardCoordinates {
+            board_index: king_index,
+            bit: crate::find_bit_on(board.board[king_index], 0),
+        };
+        let team_bitboards = crate::TeamBitboards::new(king_index, board);
+        gen_enemy_attacks(&king, team_bitboards, board, pieces_info).in_check()
+    }
+
+    // Returns true if the king of the team to move is in check, and true if the team to move has no legal moves
+    // Checkmate is both, stalemate is only the second
+    pub fn check_and_mate(board: &board_representation::Board, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> (bool, bool) {
+        let king_index;
+        if board.whites_move {
+            king_index = 5;
+        } else {
+            king_index = 11;
+        }
+
+        let king = board_representation::BoardCoordinates {
+            board_index: king_index,
+            bit: crate::find_bit_on(board.board[king_index], 0),
+        };
+        let team_bitboards = crate::TeamBitboards::new(king_index, board);
+        let enemy_attacks = gen_enemy_attacks(&king, team_bitboards, board, pieces_info);
+        (enemy_attacks.in_check(), is_mate(&king, &enemy_attacks, team_bitboards, board, pieces_info))
+    }
+
+    // Returns a bitboard of every piece of a team (white if by_white is true) that attacks a bit
+    // Pieces defending a piece of their own team on the bit are included, and pinned pieces still count as attackers
+    pub fn attackers_to(bit: usize, by_white: bool, board: &board_representation::Board, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> u64 {
+        use crate::bit_on;
+
+        let indexes;
+        if by_white {
+            indexes = 0..6;
+        } else {
+            indexes = 6..12;
+        }
+
+        let team_bitboards = crate::TeamBitboards::new(indexes.start, board);
+
+        let mut attackers: u64 = 0;
+        for i in indexes {
+            for j in crate::bitboard::bits(board.board[i]) {
+                let piece = board_representation::BoardCoordinates {
+                    board_index: i,
+                    bit: j,
+                };
+
+                // Generate the piece's attacks only (pawn pushes aren't attacks)
+                if bit_on(gen_piece(&piece, None, &team_bitboards, true, board, pieces_info).moves_bitboard, bit) {
+                    attackers |= 1 << j;
+                }
+            }
+        }
+
+        attackers
+    }
+
+    // Returns a bitboard of the pieces of the blocking team that are the only piece between a bit and a sliding piece of the sliding team
+    // Only sliding pieces that move in the direction of the bit are counted (rooks and queens on rows and columns, bishops and queens on diagonals)
+    fn slider_blockers(bit: usize, blocking_white: bool, sliding_white: bool, board: &board_representation::Board) -> u64 {
+        use crate::bit_on;
+        use crate::rays;
+
+        let blocking_team;
+        if blocking_white {
+            blocking_team = crate::or_bitboards(0, 5, &board.board);
+        } else {
+            blocking_team = crate::or_bitboards(6, 11, &board.board);
+        }
+        let all_pieces = crate::or_bitboards(0, 11, &board.board);
+
+        let sliding_offset;
+        if sliding_white {
+            sliding_offset = 0;
+        } else {
+            sliding_offset = 6;
+        }
 
-        if bit_move_valid(initial_bit, delta_bit) {
-            let mut move_bitboard = 1 << initial_bit; // Set move bitboard to have piece at initial_bit
+        let mut blockers: u64 = 0;
+        for direction in 0..rays::DIRECTIONS.len() {
 
-            // Shift left / right dependign on delta_bit
-            if delta_bit > 0 {
-                move_bitboard = move_bitboard << delta_bit.abs();
+            // Rooks and queens slide in the first 4 directions, bishops and queens in the last 4
+            let sliders;
+            if direction < 4 {
+                sliders = board.board[sliding_offset + 1] | board.board[sliding_offset + 4];
             } else {
-                move_bitboard = move_bitboard >> delta_bit.abs();
+                sliders = board.board[sliding_offset + 3] | board.board[sliding_offset + 4];
+            }
+
+            // The first piece in the direction is a blocker if it's on the blocking team and the piece behind it is a slider
+            let blocker_bit;
+            match rays::first_on_ray(bit, direction, all_pieces) {
+                Some(first_bit) => blocker_bit = first_bit,
+                None => continue,
+            }
+            if !bit_on(blocking_team, blocker_bit) {
+                continue;
+            }
+
+            match rays::first_on_ray(blocker_bit, direction, all_pieces) {
+                Some(slider_bit) => {
+                    if bit_on(sliders, slider_bit) {
+                        blockers |= 1 << blocker_bit;
+                    }
+                },
+                None => (),
+            }
+        }
+
+        blockers
+    }
+
+    // Returns a bitboard of the pieces of a team (white if white_king is true) that are absolutely pinned to their king
+    // A pinned piece can only move along the line between its king and the enemy piece pinning it
+    pub fn pinned_pieces(white_king: bool, board: &board_representation::Board) -> u64 {
+        let king_index;
+        if white_king {
+            king_index = 5;
+        } else {
+            king_index = 11;
+        }
+
+        if board.board[king_index] == 0 {
+            return 0;
+        }
+
+        slider_blockers(crate::find_bit_on(board.board[king_index], 0), white_king, !white_king, board)
+    }
+
+    // Returns a bitboard of the pieces of a team (white if by_white is true) that give a discovered check when they move off the line to the enemy king
+    // These are the pieces between a friendly sliding piece and the enemy king
+    pub fn discovered_checkers(by_white: bool, board: &board_representation::Board) -> u64 {
+        let enemy_king_index;
+        if by_white {
+            enemy_king_index = 11;
+        } else {
+            enemy_king_index = 5;
+        }
+
+        if board.board[enemy_king_index] == 0 {
+            return 0;
+        }
+
+        slider_blockers(crate::find_bit_on(board.board[enemy_king_index], 0), by_white, by_white, board)
+    }
+
+    // Most legal moves any chess position can have
+    pub const MAX_LEGAL_MOVES: usize = 218;
+
+    // Legal moves of a board, made by generate_legal_moves
+    // Only the first len moves are used
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    pub struct MoveList {
+        pub moves: [crate::algorithm::Move; MAX_LEGAL_MOVES],
+        pub len: usize,
+    }
+
+    impl MoveList {
+        pub fn new() -> Self {
+            MoveList {
+                moves: [crate::algorithm::Move::new(); MAX_LEGAL_MOVES],
+                len: 0,
+            }
+        }
+
+        // Returns the moves in the list
+        pub fn as_slice(&self) -> &[crate::algorithm::Move] {
+            &self.moves[..self.len]
+        }
+
+        // Returns the legal move with the same initial bit, final bit and promotion piece, or None if the move isn't legal
+        // A pawn moving to the last row without a promotion piece matches the queen promotion, the same as new_turn
+        pub fn find(&self, initial_bit: usize, final_bit: usize, promotion: Option<usize>) -> Option<crate::algorithm::Move> {
+            for piece_move in self.as_slice() {
+                if piece_move.initial_piece_coordinates.bit == initial_bit
+                    && piece_move.final_piece_bit == final_bit
+                    && (piece_move.promotion == promotion || (promotion.is_none() && piece_move.promotion == Some(4))) {
+                    return Some(*piece_move);
+                }
+            }
+            None
+        }
+
+        // Returns true if a move is in the list
+        pub fn contains(&self, piece_move: &crate::algorithm::Move) -> bool {
+            match self.find(piece_move.initial_piece_coordinates.bit, piece_move.final_piece_bit, piece_move.promotion) {
+                Some(legal_move) => legal_move.initial_piece_coordinates == piece_move.initial_piece_coordinates,
+                None => false,
+            }
+        }
+    }
+
+    // Returns every strictly legal move of the team to move
+    // Unlike gen_piece (used by the search), moves that leave the king in check, castling out of or through check, and moves of pinned pieces are left out
+    // Used to validate moves made by a player before they are played
+    pub fn generate_legal_moves(board: &board_representation::Board, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> MoveList {
+        let mut move_list = MoveList::new();
+        super::perft::legal_moves(*board, pieces_info, |piece, piece_move_bit, promotion, _| {
+            let mut piece_move = crate::algorithm::Move::new();
+            piece_move.initial_piece_coordinates = piece;
+            piece_move.final_piece_bit = piece_move_bit;
+            piece_move.promotion = promotion;
+
+            move_list.moves[move_list.len] = piece_move;
+            move_list.len += 1;
+        });
+        move_list
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        // Returns true if a move from one bit to another is legal in a position
+        fn legal(fen: &str, initial_bit: usize, final_bit: usize) -> bool {
+            let pieces_info = crate::piece::constants::gen();
+            let board = board_representation::Board::from_fen(fen).unwrap();
+            generate_legal_moves(&board, &pieces_info).find(initial_bit, final_bit, None).is_some()
+        }
+
+        // Capturing en passant removes two pawns from the king's row, so a rook or queen behind them gives check
+        #[test]
+        fn en_passant_discovered_check_on_row() {
+            assert!(!legal("8/8/8/KPp4r/8/8/8/7k w - c6 0 2", 25, 18)); // bxc6 exposes the a5 king to the h5 rook
+            assert!(legal("8/8/8/KPp4r/8/8/8/7k w - c6 0 2", 25, 17)); // The pawn can still be pushed
+            assert!(!legal("8/8/8/8/k2Pp2Q/8/8/3K4 b - d3 0 1", 36, 43)); // exd3 exposes the a4 king to the h4 queen
+        }
+
+        // En passant is legal when nothing is behind the captured pawn, or the capturing pawn still blocks the line
+        #[test]
+        fn en_passant_without_discovered_check() {
+            assert!(legal("8/8/8/KPp5/8/8/8/7k w - c6 0 2", 25, 18));
+            assert!(legal("8/8/8/1k6/2pP4/8/8/5B1K b - d3 0 1", 34, 43)); // cxd3 moves onto the f1 bishop's diagonal, blocking it again
+        }
+
+        // Capturing en passant can also open a diagonal to the king, the captured pawn was the only piece blocking it
+        #[test]
+        fn en_passant_discovered_check_on_diagonal() {
+            assert!(!legal("8/8/8/2k5/3Pp3/8/8/K5B1 b - d3 0 1", 36, 43)); // exd3 exposes the c5 king to the g1 bishop
+        }
+    }
+}
+
+// Counts the leaf nodes of the move tree to a fixed depth
+// The counts can be compared against published values to check the move generator is correct
+// https://www.chessprogramming.org/Perft
+pub mod perft {
+    use super::*;
+
+    // Nodes under one root move, from perft_divide
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    pub struct DivideEntry {
+        pub initial_piece_coordinates: board_representation::BoardCoordinates,
+        pub final_piece_bit: usize,
+        pub promotion: Option<usize>,
+        pub nodes: u64,
+    }
+
+    impl DivideEntry {
+        pub fn new() -> Self {
+            DivideEntry {
+                initial_piece_coordinates: board_representation::BoardCoordinates::new(),
+                final_piece_bit: 0,
+                promotion: None,
+                nodes: 0,
+            }
+        }
+    }
+
+    // Result of perft_divide
+    // Only the first moves_no entries are used
+    #[derive(Copy, Clone, PartialEq, Debug)]
+    pub struct Divide {
+        pub entries: [DivideEntry; move_generator::MAX_LEGAL_MOVES],
+        pub moves_no: usize,
+        pub nodes: u64, // Total nodes of every root move
+    }
+
+    // Returns the number of leaf nodes depth moves from the board
+    pub fn perft(board: board_representation::Board, depth: usize, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        let mut nodes = 0;
+        legal_moves(board, pieces_info, |_, _, _, new_board| {
+            nodes += perft(new_board, depth - 1, pieces_info);
+        });
+        nodes
+    }
+
+    // Same as perft, but also returns the nodes under every root move
+    // Comparing the root moves to another engines divide output narrows down which move is generated incorrectly
+    pub fn perft_divide(board: board_representation::Board, depth: usize, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Divide {
+        let mut divide = Divide {
+            entries: [DivideEntry::new(); move_generator::MAX_LEGAL_MOVES],
+            moves_no: 0,
+            nodes: 0,
+        };
+
+        if depth == 0 {
+            divide.nodes = 1;
+            return divide;
+        }
+
+        legal_moves(board, pieces_info, |piece, piece_move_bit, promotion, new_board| {
+            let nodes = perft(new_board, depth - 1, pieces_info);
+
+            divide.entries[divide.moves_no] = DivideEntry {
+                initial_piece_coordinates: piece,
+                final_piece_bit: piece_move_bit,
+                promotion: promotion,
+                nodes: nodes,
+            };
+            divide.moves_no += 1;
+            divide.nodes += nodes;
+        });
+        divide
+    }
+
+    // Calls f with every legal move of the team to move (the piece, final bit and promotion piece), and the board after the move
+    // Pawns moving to the last row are called once for every promotion piece
+    // The game is never adjudicated (see move_generator::new_turn_unadjudicated), so there is a board after every move even if it mates, draws or wins the variant
+    // A position only has no moves when the team to move is checkmated or stalemated
+    pub(crate) fn legal_moves<F: FnMut(board_representation::BoardCoordinates, usize, Option<usize>, board_representation::Board)>(board: board_representation::Board, pieces_info: &[crate::piece::constants::PieceInfo; 12], mut f: F) {
+        let friendly_king_index;
+        let enemy_king_index;
+        let friendly_indexes;
+        if board.whites_move {
+            friendly_king_index = 5;
+            enemy_king_index = 11;
+            friendly_indexes = 0..6;
+        } else {
+            friendly_king_index = 11;
+            enemy_king_index = 5;
+            friendly_indexes = 6..12;
+        }
+
+        let friendly_king = board_representation::BoardCoordinates {
+            board_index: friendly_king_index,
+            bit: crate::find_bit_on(board.board[friendly_king_index], 0),
+        };
+
+        let enemy_king = board_representation::BoardCoordinates {
+            board_index: enemy_king_index,
+            bit: crate::find_bit_on(board.board[enemy_king_index], 0),
+        };
+
+        let team_bitboards = crate::TeamBitboards::new(friendly_king_index, &board);
+        let enemy_attacks = move_generator::gen_enemy_attacks(&friendly_king, team_bitboards, &board, pieces_info);
+
+        for i in friendly_indexes {
+            for initial_bit in crate::bitboard::bits(board.board[i]) {
+                let piece = board_representation::BoardCoordinates {
+                    board_index: i,
+                    bit: initial_bit,
+                };
+
+                let mut moves_bitboard = move_generator::gen_piece(&piece, None, &team_bitboards, false, &board, pieces_info).moves_bitboard;
+
+                // Castling moves aren't generated by gen_piece
+                if i == friendly_king_index {
+                    moves_bitboard |= move_generator::castles_bitboard(&move_generator::legal_castles(board.whites_move, enemy_attacks.enemy_attack_bitboard, &board));
+                }
+
+                for final_bit in crate::bitboard::bits(moves_bitboard & !team_bitboards.friendly_team) {
+                    let promotions: &[Option<usize>];
+                    if move_generator::promotes(i, final_bit) {
+                        promotions = &move_generator::PROMOTION_PIECES;
+                    } else {
+                        promotions = &[None];
+                    }
+
+                    for &promotion in promotions {
+                        match move_generator::new_turn_unadjudicated(&piece, final_bit, promotion, friendly_king, &enemy_king, &enemy_attacks, team_bitboards, board, pieces_info) {
+                            Ok(new_board) => f(piece, final_bit, promotion, new_board),
+                            Err(_) => (), // The move is invalid or leaves the king in check
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        // Checks the nodes of a position at every depth against the published counts (the Chess Programming Wiki's Perft Results page), counts[0] is depth 1
+        fn assert_perft(fen: &str, counts: &[u64]) {
+            let pieces_info = crate::piece::constants::gen();
+            let board = board_representation::Board::from_fen(fen).unwrap();
+            for (depth, &count) in counts.iter().enumerate() {
+                assert_eq!(perft(board, depth + 1, &pieces_info), count, "{} at depth {}", fen, depth + 1);
             }
-            return Ok(move_bitboard);
         }
-        Err(())
+
+        #[test]
+        fn start_position() {
+            assert_perft("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", &[20, 400, 8902]);
+        }
+
+        #[test]
+        fn kiwipete() {
+            assert_perft("r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1", &[48, 2039, 97862]);
+        }
+
+        #[test]
+        fn position_3() {
+            assert_perft("8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1", &[14, 191, 2812, 43238]);
+        }
+
+        #[test]
+        fn position_4() {
+            assert_perft("r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1", &[6, 264, 9467]);
+        }
+
+        #[test]
+        fn position_5() {
+            assert_perft("rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8", &[44, 1486, 62379]);
+        }
+
+        // Every root move of a position with the most legal moves fits in the divide
+        #[test]
+        fn divide_most_moves() {
+            let pieces_info = crate::piece::constants::gen();
+            let board = board_representation::Board::from_fen("R6R/3Q4/1Q4Q1/4Q3/2Q4Q/Q4Q2/pp1Q4/kBNN1KB1 w - - 0 1").unwrap();
+            let divide = perft_divide(board, 1, &pieces_info);
+            assert_eq!(divide.moves_no, 218);
+            assert_eq!(divide.nodes, 218);
+        }
+
+        // Perft keeps counting past draws, the knight captured by Bxc2 leaves insufficient material but the black king still has 7 moves
+        #[test]
+        fn descends_through_draws() {
+            let pieces_info = crate::piece::constants::gen();
+            let board = board_representation::Board::from_fen("8/8/8/3k4/8/8/2n5/1B2K3 w - - 0 1").unwrap();
+            let divide = perft_divide(board, 2, &pieces_info);
+
+            let capture = divide.entries[..divide.moves_no].iter().find(|entry| entry.initial_piece_coordinates.bit == 57 && entry.final_piece_bit == 50).unwrap();
+            assert_eq!(capture.nodes, 7);
+        }
     }
 }
@@ -0,0 +1,65 @@
+// Captured pieces and the material balance of a board, written for one row of the lcd (e.g. "+2 N2P vs B", with the glyphs of the pieces in place of the letters)
+// The balance is relative to the white team, then the pieces white has captured, then the pieces black has captured
+// Pieces missing from the starting position count as captured (see board_representation::Material::captured), so the piece given as odds is shown too
+
+use crate::board::board_representation;
+use crate::glyph::Glyph;
+
+// Order the captured pieces are written in (offsets from the team's first board index), the most valuable first
+const CAPTURE_ORDER: [usize; 5] = [4, 1, 3, 2, 0];
+
+// Writes the material balance and captured pieces of a board
+// Nothing is written if neither team has captured anything, so the caller can leave the row out
+pub fn write_captures<W: core::fmt::Write>(out: &mut W, board: &board_representation::Board, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Result<(), ()> {
+    let material = board.material();
+    let captured = material.captured();
+    if captured.iter().all(|count| *count == 0) {
+        return Ok(());
+    }
+
+    // Values of the pieces on the board, the kings have no value
+    let mut balance: i16 = 0;
+    for i in 0..12 {
+        let value = material.counts[i] as i16 * pieces_info[i].value as i16;
+        if crate::board_index_white(i) {
+            balance += value;
+        } else {
+            balance -= value;
+        }
+    }
+
+    if balance > 0 {
+        write!(out, "+{} ", balance).map_err(|_| ())?;
+    } else if balance < 0 {
+        write!(out, "{} ", balance).map_err(|_| ())?;
+    } else {
+        out.write_str("= ").map_err(|_| ())?;
+    }
+
+    // White captures the black pieces (board indexes 6 - 11) and black captures the white pieces
+    write_team_captures(out, &captured, 6)?;
+    out.write_str(" vs ").map_err(|_| ())?;
+    write_team_captures(out, &captured, 0)
+}
+
+// Writes the captured pieces of a team, each with its glyph and a count in front if more than one has been captured, or - if there are none
+fn write_team_captures<W: core::fmt::Write>(out: &mut W, captured: &[u8; 12], offset: usize) -> Result<(), ()> {
+    let mut any = false;
+    for i in CAPTURE_ORDER {
+        let count = captured[offset + i];
+        if count == 0 {
+            continue;
+        }
+
+        if count > 1 {
+            write!(out, "{}", count).map_err(|_| ())?;
+        }
+        write!(out, "{}", Glyph::piece(i).char()).map_err(|_| ())?;
+        any = true;
+    }
+
+    if !any {
+        out.write_str("-").map_err(|_| ())?;
+    }
+    Ok(())
+}
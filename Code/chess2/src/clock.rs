@@ -0,0 +1,193 @@
+// Chess clock for timed games
+// Keeps the time each team has left, and takes time off the team to move while it's their turn
+// Times are in milliseconds and are passed in by the caller, so the clock works from the firmware's cycle counter or any other timer
+
+use crate::board::board_representation;
+
+// Time each team starts with and the time added to or held back from each move
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct TimeControl {
+    pub base_ms: u64, // Time each team starts with
+    pub increment_ms: u64, // Fischer increment, added to a team's time after each of its moves
+    pub delay_ms: u64, // Delay, a team's time only starts going down once this much of its turn has passed
+}
+
+impl TimeControl {
+    pub const fn new(base_minutes: u64, increment_seconds: u64, delay_seconds: u64) -> Self {
+        TimeControl {
+            base_ms: base_minutes * 60000,
+            increment_ms: increment_seconds * 1000,
+            delay_ms: delay_seconds * 1000,
+        }
+    }
+}
+
+// Prints the time control as minutes + increment seconds (e.g. 10+5), with the delay seconds after a d (e.g. 5 d3)
+impl core::fmt::Display for TimeControl {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "{}", self.base_ms / 60000)?;
+        if self.increment_ms != 0 || self.delay_ms == 0 {
+            write!(f, "+{}", self.increment_ms / 1000)?;
+        }
+        if self.delay_ms != 0 {
+            write!(f, " d{}", self.delay_ms / 1000)?;
+        }
+        Ok(())
+    }
+}
+
+// Time controls that can be chosen from the menu, None is an untimed game
+pub const TIME_CONTROLS: [Option<TimeControl>; 4] = [
+    None,
+    Some(TimeControl::new(5, 0, 0)),
+    Some(TimeControl::new(10, 5, 0)),
+    Some(TimeControl::new(15, 10, 0)),
+];
+
+// Time (ms) left on a team's clock when it's warned that its time is running out (see sound::Sound::LowTime)
+pub const LOW_TIME_MS: u64 = 10000;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Clock {
+    pub time_control: TimeControl,
+    remaining_ms: [u64; 2], // Time the white and black teams have left
+    white_running: bool, // True if it's the white team's turn
+    turn_start_ms: u64, // Time the current turn started at
+    last_update_ms: u64, // Time the clock was last updated at
+}
+
+impl Clock {
+    // Creates a clock with the team to move's time running from now
+    pub fn new(time_control: TimeControl, white_to_move: bool, now_ms: u64) -> Self {
+        Clock {
+            time_control: time_control,
+            remaining_ms: [time_control.base_ms; 2],
+            white_running: white_to_move,
+            turn_start_ms: now_ms,
+            last_update_ms: now_ms,
+        }
+    }
+
+    // Takes the time since the last update off the team to move
+    // Time within the delay at the start of a turn isn't taken off
+    pub fn update(&mut self, now_ms: u64) {
+        let delay_end_ms = self.turn_start_ms + self.time_control.delay_ms;
+
+        let running_from_ms;
+        if self.last_update_ms > delay_end_ms {
+            running_from_ms = self.last_update_ms;
+        } else {
+            running_from_ms = delay_end_ms;
+        }
+
+        if now_ms > running_from_ms {
+            let team = team_index(self.white_running);
+            self.remaining_ms[team] = self.remaining_ms[team].saturating_sub(now_ms - running_from_ms);
+        }
+
+        if now_ms > self.last_update_ms {
+            self.last_update_ms = now_ms;
+        }
+    }
+
+    // Ends the turn of the team to move, adds its increment and starts the other team's time
+    // A team that has run out of time doesn't get an increment
+    pub fn end_turn(&mut self, now_ms: u64) {
+        self.update(now_ms);
+
+        let team = team_index(self.white_running);
+        if self.remaining_ms[team] != 0 {
+            self.remaining_ms[team] += self.time_control.increment_ms;
+        }
+
+        self.white_running = !self.white_running;
+        self.turn_start_ms = self.last_update_ms;
+    }
+
+    // Returns the time a team has left, as of the last update
+    pub fn remaining_ms(&self, white: bool) -> u64 {
+        self.remaining_ms[team_index(white)]
+    }
+
+    // Sets the time a team has left, e.g. when a saved game is resumed (see save.rs)
+    pub fn set_remaining_ms(&mut self, white: bool, remaining_ms: u64) {
+        self.remaining_ms[team_index(white)] = remaining_ms;
+    }
+
+    // Returns true if it's the white team's turn
+    pub fn white_running(&self) -> bool {
+        self.white_running
+    }
+
+    // Returns true if a team has LOW_TIME_MS or less left, but hasn't run out
+    pub fn low_time(&self, white: bool) -> bool {
+        let remaining_ms = self.remaining_ms(white);
+        remaining_ms > 0 && remaining_ms <= LOW_TIME_MS
+    }
+
+    // Returns the team that has run out of time (true for the white team), None if neither team has
+    pub fn flagged(&self) -> Option<bool> {
+        if self.remaining_ms[0] == 0 {
+            Some(true)
+        } else if self.remaining_ms[1] == 0 {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    // Returns the time the computer should spend searching for a move (see algorithm::move_time_ms)
+    pub fn move_time_ms(&self, white: bool) -> u64 {
+        crate::algorithm::move_time_ms(self.remaining_ms(white), self.time_control.increment_ms, self.time_control.delay_ms)
+    }
+
+    // Returns the times of both teams, as they are shown on the lcd
+    pub fn display(&self) -> ClockDisplay {
+        ClockDisplay {
+            white_ms: self.remaining_ms[0],
+            black_ms: self.remaining_ms[1],
+        }
+    }
+}
+
+fn team_index(white: bool) -> usize {
+    if white {
+        0
+    } else {
+        1
+    }
+}
+
+// Times of both teams (see Clock::display), printed as minutes and seconds on one row of the lcd (e.g. "W  4:59  B  5:00")
+// Seconds are rounded up, so a team only shows 0:00 once it has run out of time
+pub struct ClockDisplay {
+    white_ms: u64,
+    black_ms: u64,
+}
+
+impl core::fmt::Display for ClockDisplay {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let white_seconds = (self.white_ms + 999) / 1000;
+        let black_seconds = (self.black_ms + 999) / 1000;
+        write!(f, "W {:2}:{:02}  B {:2}:{:02}", white_seconds / 60, white_seconds % 60, black_seconds / 60, black_seconds % 60)
+    }
+}
+
+// Returns true if a team has enough pieces to checkmate
+// A team that has run out of time only loses if the other team could still checkmate it, otherwise the game is a draw
+// A lone king, or a king and one bishop or knight, can't checkmate
+pub fn has_mating_material(board: &board_representation::Board, white: bool) -> bool {
+    let offset;
+    if white {
+        offset = 0;
+    } else {
+        offset = 6;
+    }
+
+    // Pawns, rooks or queens can always checkmate
+    if board.board[offset] | board.board[offset + 1] | board.board[offset + 4] != 0 {
+        return true;
+    }
+
+    crate::bits_on(board.board[offset + 2] | board.board[offset + 3]) > 1
+}
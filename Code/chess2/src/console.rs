@@ -0,0 +1,106 @@
+// Debug console, for bringing up and debugging the board without recompiling test code
+// Commands are typed over the serial port (see embedded::serial), this parses them and writes the output of the engine commands
+// The firmware runs the commands, the commands that use the hardware (halls and leds) only exist there
+//
+// Commands:
+//   fen <fen>       sets the board
+//   d               displays the board and its FEN
+//   go [depth N]    searches the board, printing every completed depth then the best move
+//   perft N         counts the move paths N half moves deep, N is at most MAX_PERFT_DEPTH
+//   eval            prints the static evaluation, with every term when built with the eval-trace feature
+//   halls           prints the bitboard of the pieces the hall sensors can see
+//   leds <hex>      lights the leds of a bitboard (bit 0 is a8), "leds 0" turns them off
+//   help            lists the commands
+//   quit            returns to the menu
+
+use crate::board::board_representation::Board;
+
+// Deepest perft the console will count, every half move of a perft keeps a move list on the stack and a deeper count takes minutes on the board
+pub const MAX_PERFT_DEPTH: usize = 5;
+
+pub const HELP: &str = "fen <fen>, d, go [depth N], perft N, eval, halls, leds <hex>, help, quit\n";
+
+pub enum Command {
+    Fen(Board),
+    Display,
+    Go(Option<usize>), // Depth of the search, None to use the frontend's depth
+    Perft(usize),
+    Eval,
+    Halls,
+    Leds(u64),
+    Help,
+    Quit,
+}
+
+// Parses a line typed into the console
+// Returns an error if the command doesn't exist or its argument can't be parsed, e.g. an invalid FEN
+pub fn parse_command(line: &str) -> Result<Command, ()> {
+    let line = line.trim();
+    let (name, argument);
+    match line.find(' ') {
+        Some(index) => {
+            name = &line[..index];
+            argument = line[index..].trim();
+        },
+        None => {
+            name = line;
+            argument = "";
+        },
+    }
+
+    match name {
+        "fen" => Ok(Command::Fen(Board::from_fen(argument).map_err(|_| ())?)),
+        "d" => Ok(Command::Display),
+        "go" => match argument.strip_prefix("depth") {
+            Some(depth) => Ok(Command::Go(Some(depth.trim().parse::<usize>().map_err(|_| ())?))),
+            None if argument.is_empty() => Ok(Command::Go(None)),
+            None => Err(()),
+        },
+        "perft" => {
+            let depth = argument.parse::<usize>().map_err(|_| ())?;
+            if depth > MAX_PERFT_DEPTH {
+                return Err(());
+            }
+            Ok(Command::Perft(depth))
+        },
+        "eval" => Ok(Command::Eval),
+        "halls" => Ok(Command::Halls),
+        "leds" => Ok(Command::Leds(u64::from_str_radix(argument.trim_start_matches("0x"), 16).map_err(|_| ())?)),
+        "help" => Ok(Command::Help),
+        "quit" => Ok(Command::Quit),
+        _ => Err(()),
+    }
+}
+
+// Writes a bitboard as a grid with the a8 square in the top left (the same as Board's Display), then as hex
+pub fn write_bitboard<W: core::fmt::Write>(out: &mut W, bitboard: u64) -> Result<(), ()> {
+    for row in 0..8 {
+        write!(out, "{} ", 8 - row).map_err(|_| ())?;
+        for file in 0..8 {
+            if crate::bit_on(bitboard, row * 8 + file) {
+                out.write_str("1").map_err(|_| ())?;
+            } else {
+                out.write_str(".").map_err(|_| ())?;
+            }
+            if file < 7 {
+                out.write_str(" ").map_err(|_| ())?;
+            }
+        }
+        out.write_str("\n").map_err(|_| ())?;
+    }
+    write!(out, "  a b c d e f g h\n{:016x}\n", bitboard).map_err(|_| ())
+}
+
+// Writes the static evaluation of a board relative to the white team (see evaluation::evaluate)
+// With the eval-trace feature every term of the evaluation is written before the total
+pub fn write_eval<W: core::fmt::Write>(out: &mut W, board: &Board, pawn_cache: &mut crate::evaluation::EvalCache, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Result<(), ()> {
+    #[cfg(feature = "eval-trace")]
+    writeln!(out, "{}", crate::evaluation::evaluate_trace(board, pieces_info)).map_err(|_| ())?;
+
+    writeln!(out, "Eval: {}", crate::evaluation::evaluate(board, pawn_cache, pieces_info)).map_err(|_| ())
+}
+
+// Writes the number of move paths from a board (see board::perft)
+pub fn write_perft<W: core::fmt::Write>(out: &mut W, board: &Board, depth: usize, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Result<(), ()> {
+    writeln!(out, "Nodes: {}", crate::board::perft::perft(*board, depth, pieces_info)).map_err(|_| ())
+}
@@ -0,0 +1,73 @@
+// Decoding of a quadrature rotary encoder, for scrolling through the menus and options faster than the button can
+// The encoder's two contacts (A and B) open and close a quarter of a step apart, the order they change in is the direction it's turning
+// This only decodes the levels of the contacts, the firmware reads them when they change (see embedded::encoder)
+//
+// Bouncing contacts are ignored, a change is only counted if the other contact hasn't changed at the same time,
+// and a step is only made once all 4 changes of a step have been made in the same direction and the encoder is back at rest (both contacts open)
+
+// Change in position for each change of the levels, indexed by the last levels then the new levels (A is bit 1, B is bit 0)
+// Changes where both contacts change at once can't be made by turning the encoder, they're bouncing and are ignored
+const TRANSITIONS: [[i8; 4]; 4] = [
+    [0, -1, 1, 0],
+    [1, 0, 0, -1],
+    [-1, 0, 0, 1],
+    [0, 1, -1, 0],
+];
+
+// Levels of the contacts while the encoder is resting between steps, the contacts are open and pulled up
+const REST: u8 = 0b11;
+
+// Quarter steps in a step
+const QUARTERS: i8 = 4;
+
+// Steps made while the encoder is turned quickly count as this many steps
+pub const ACCELERATION: i32 = 4;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Decoder {
+    levels: u8, // Levels of the contacts when they last changed (A is bit 1, B is bit 0)
+    quarters: i8, // Quarter steps made since the encoder was last at rest, positive clockwise
+}
+
+impl Decoder {
+    // Const so the firmware's decoder can be a static, the interrupt that reads the contacts uses it
+    pub const fn new() -> Self {
+        Decoder {
+            levels: REST,
+            quarters: 0,
+        }
+    }
+
+    // Updates the decoder with the levels of the contacts, returns the steps made (1 clockwise, -1 anticlockwise, or 0)
+    pub fn update(&mut self, a: bool, b: bool) -> i8 {
+        let levels = (a as u8) << 1 | b as u8;
+        self.quarters += TRANSITIONS[self.levels as usize][levels as usize];
+        self.levels = levels;
+
+        // A step is only made once the encoder has come back to rest, a part of a step that was turned back doesn't make a step
+        if levels != REST {
+            return 0;
+        }
+
+        let step;
+        if self.quarters >= QUARTERS {
+            step = 1;
+        } else if self.quarters <= -QUARTERS {
+            step = -1;
+        } else {
+            step = 0;
+        }
+        self.quarters = 0;
+        step
+    }
+}
+
+// Returns the steps a step counts as, steps made less than fast_cycles after the last step are multiplied by ACCELERATION
+// so long lists (e.g. the search depths) can be scrolled through quickly while single steps still move by one
+pub fn accelerate(step: i8, elapsed_cycles: u64, fast_cycles: u64) -> i32 {
+    if elapsed_cycles < fast_cycles {
+        step as i32 * ACCELERATION
+    } else {
+        step as i32
+    }
+}
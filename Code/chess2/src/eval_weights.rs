@@ -0,0 +1,14 @@
+pub const WEIGHTS: EvalWeights = EvalWeights {
+    doubled_pawn: 15,
+    isolated_pawn: 15,
+    backward_pawn: 10,
+    passed_pawn: [0, 5, 10, 20, 35, 60, 100, 0],
+    mobility: [0, 2, 4, 3, 1, 0],
+    bishop_pair: 30,
+    rook_open_file: 20,
+    rook_semi_open_file: 10,
+    knight_outpost: 20,
+    threat: 30,
+    hanging_piece: 15,
+    tempo: 10,
+};
@@ -0,0 +1,975 @@
+use crate::board::board_representation;
+
+// Evaluations are in centipawns (a pawn is worth 100)
+pub const PAWN_VALUE: i16 = 100;
+
+// Bitboard of the a file, shift it left by the file number to get the other files
+const FILE_A: u64 = 0x0101010101010101;
+
+// Weights of the positional evaluation terms (centipawns)
+// They are kept together so they can be tuned by the tuner (std feature), which writes a new eval_weights.rs
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct EvalWeights {
+    pub doubled_pawn: i16, // Penalty for every extra pawn on a file
+    pub isolated_pawn: i16, // Penalty for a pawn with no friendly pawns on the adjacent files
+    pub backward_pawn: i16, // Penalty for a pawn that can't be defended by friendly pawns and can't advance safely
+    pub passed_pawn: [i16; 8], // Bonus for a pawn with no enemy pawns in front of it, indexed by how many ranks the pawn has advanced from its teams back rank
+
+    // Bonus for every safe square a piece can move to (board index % 6)
+    // Pawns and kings are not included, their mobility is mostly covered by the pawn structure and piece square tables
+    pub mobility: [i16; 6],
+
+    pub bishop_pair: i16, // Bonus for having two or more bishops
+    pub rook_open_file: i16, // Bonus for a rook on a file with no pawns
+    pub rook_semi_open_file: i16, // Bonus for a rook on a file with no friendly pawns
+    pub knight_outpost: i16, // Bonus for a knight on the enemy half of the board, defended by a friendly pawn and safe from enemy pawns
+    pub threat: i16, // Bonus for every enemy piece attacked by a lower valued piece
+    pub hanging_piece: i16, // Bonus for every enemy piece that is attacked and not defended
+    pub tempo: i16, // Bonus for the team to move, being able to move first is worth something in most positions
+}
+
+// Squares attacked by one team, split by the value of the attacking pieces so threats from lower valued pieces can be found
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TeamAttacks {
+    pub pawns: u64,
+    pub minor_pieces: u64, // Knights and bishops
+    pub rooks: u64,
+    pub all: u64, // Every piece, including queens and the king
+}
+
+impl TeamAttacks {
+    pub fn new() -> Self {
+        TeamAttacks {
+            pawns: 0,
+            minor_pieces: 0,
+            rooks: 0,
+            all: 0,
+        }
+    }
+}
+
+// The weights used by the engine, WEIGHTS
+include!("eval_weights.rs");
+
+// Evaluation of a KPK position that the bitbase says is won, less than a queen so the pawn is still promoted
+const KPK_WIN_VALUE: i16 = 5 * PAWN_VALUE;
+// Bonus for every rank the pawn has advanced in a won KPK position, so the winning team makes progress
+const KPK_PAWN_ADVANCE_WEIGHT: i16 = 10;
+
+// Mate driving weights for basic mating endgames (KQK, KRK, KBNK)
+// Bonus for every step the defending king is from the centre (or for KBNK closer to a mating corner)
+const MATE_EDGE_WEIGHT: i16 = 20;
+// Bonus for every step the attacking king is closer to the defending king
+const MATE_KING_DISTANCE_WEIGHT: i16 = 10;
+
+// Variant weights (see variant_evaluation)
+// Bonus in King of the Hill for every step the king is closer to the centre
+const KING_OF_THE_HILL_CENTRE_WEIGHT: i16 = 30;
+// Bonus in Three-Check for the number of checks a team has given, a third check wins so the second is worth much more than the first
+const THREE_CHECK_VALUES: [i16; 3] = [0, 150, 500];
+
+// Drawishness scale factors, the evaluation is multiplied by the scale factor and divided by SCALE_NORMAL
+// Some endings are very hard to win even with more material, without scaling the engine would trade into them to stay a pawn up
+const SCALE_NORMAL: i16 = 64;
+// Opposite colour bishops with only pawns, the bishops can't challenge each other so a pawn or two up is usually a draw
+const SCALE_OPPOSITE_BISHOPS: i16 = 24;
+// Opposite colour bishops with other pieces, still drawish but the other pieces can make progress
+const SCALE_OPPOSITE_BISHOPS_PIECES: i16 = 48;
+// The stronger team has no pawns and is a minor piece or less up (e.g. KR vs KB or KBN vs KR), which can't usually be won
+const SCALE_NO_PAWNS: i16 = 8;
+
+// Game phase weights of every piece (board index % 6), the phase is the sum of the weights of the pieces on the board
+// The starting position has a phase of MAX_PHASE, and a board with only kings and pawns has a phase of 0
+const PHASE_WEIGHTS: [i16; 6] = [0, 2, 1, 1, 4, 0];
+pub const MAX_PHASE: i16 = 24;
+
+// Returns the game phase of the board, from MAX_PHASE (opening / middlegame) to 0 (endgame)
+pub fn game_phase(board: &board_representation::Board) -> i16 {
+    let mut phase = 0;
+    for i in 0..12 {
+        phase += board.board[i].count_ones() as i16 * PHASE_WEIGHTS[i % 6];
+    }
+
+    // Promotions can make the phase larger than the starting position
+    phase.min(MAX_PHASE)
+}
+
+// Returns the static evaluation of the board relative to the white team
+pub fn evaluate(board: &board_representation::Board, pawn_cache: &mut EvalCache, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> i16 {
+    evaluate_with_weights(board, pawn_cache, &WEIGHTS, pieces_info)
+}
+
+// Evaluates the board with different weights, used by the tuner
+// The pawn cache must be empty or only have evaluations made with the same weights
+pub fn evaluate_with_weights(board: &board_representation::Board, pawn_cache: &mut EvalCache, weights: &EvalWeights, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> i16 {
+    // Dead positions and KPK positions are evaluated without the other terms
+    match exact_evaluation(board) {
+        Some(evaluation) => return evaluation,
+        None => (),
+    }
+
+    // The variant terms aren't scaled, the ways to win a variant don't get harder in drawish endings
+    let variant = variant_evaluation(board);
+
+    // The neural network replaces the classical evaluation with the nnue feature
+    // Basic mating endgames still use the classical evaluation, the mate driving terms are what finish the game at low depths
+    #[cfg(feature = "nnue")]
+    if mate_driving(board, true).is_none() && mate_driving(board, false).is_none() {
+        return scale_evaluation(board, crate::nnue::evaluate(board) + tempo(board, weights), pieces_info) + variant;
+    }
+
+    scale_evaluation(board, classical_evaluation(board, pawn_cache, weights, pieces_info) + tempo(board, weights), pieces_info) + variant
+}
+
+// Returns the evaluation of the ways to win the board's variant relative to the white team, 0 for standard chess
+// The search only sees a variant win when it is in reach, these terms steer the engine towards one before that
+pub fn variant_evaluation(board: &board_representation::Board) -> i16 {
+    use crate::variant::Variant;
+
+    match board.variant {
+        Variant::Standard => 0,
+        Variant::KingOfTheHill => {
+            let white_distance = centre_distance(crate::bitboard::lsb(board.board[5]));
+            let black_distance = centre_distance(crate::bitboard::lsb(board.board[11]));
+            (black_distance - white_distance) * KING_OF_THE_HILL_CENTRE_WEIGHT
+        },
+        Variant::ThreeCheck => {
+            let white_checks = (board.checks[0] as usize).min(2);
+            let black_checks = (board.checks[1] as usize).min(2);
+            THREE_CHECK_VALUES[white_checks] - THREE_CHECK_VALUES[black_checks]
+        },
+    }
+}
+
+// Returns the tempo bonus relative to the white team
+fn tempo(board: &board_representation::Board, weights: &EvalWeights) -> i16 {
+    if board.whites_move {
+        weights.tempo
+    } else {
+        -weights.tempo
+    }
+}
+
+// Pulls the evaluation towards 0 in drawish endings (see scale_factor)
+fn scale_evaluation(board: &board_representation::Board, evaluation: i16, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> i16 {
+    (evaluation as i32 * scale_factor(board, evaluation > 0, pieces_info) as i32 / SCALE_NORMAL as i32) as i16
+}
+
+// Returns the scale factor of the evaluation, SCALE_NORMAL when the ending isn't drawish
+// white_stronger is true when the evaluation is in favour of the white team
+pub fn scale_factor(board: &board_representation::Board, white_stronger: bool, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> i16 {
+    let strong_offset;
+    let weak_offset;
+    if white_stronger {
+        strong_offset = 0;
+        weak_offset = 6;
+    } else {
+        strong_offset = 6;
+        weak_offset = 0;
+    }
+
+    // Material of the rooks, knights, bishops and queens of both teams
+    let mut strong_material = 0;
+    let mut weak_material = 0;
+    for i in 1..5 {
+        strong_material += board.board[strong_offset + i].count_ones() as i16 * pieces_info[strong_offset + i].value as i16;
+        weak_material += board.board[weak_offset + i].count_ones() as i16 * pieces_info[weak_offset + i].value as i16;
+    }
+
+    // Without pawns the stronger team needs more than a minor piece extra to force mate
+    // Mating endgames against a bare king are left alone, the mate driving evaluation handles those
+    if board.board[strong_offset] == 0 && weak_material > 0 && strong_material - weak_material <= pieces_info[3].value as i16 {
+        return SCALE_NO_PAWNS;
+    }
+
+    // Opposite colour bishops, each team has one bishop and they are on different colour squares
+    let white_bishops = board.board[3];
+    let black_bishops = board.board[9];
+    if white_bishops.count_ones() == 1 && black_bishops.count_ones() == 1
+        && (white_bishops & board_representation::LIGHT_SQUARES == 0) != (black_bishops & board_representation::LIGHT_SQUARES == 0) {
+        // Only kings, pawns and the bishops
+        if strong_material == pieces_info[3].value as i16 && weak_material == pieces_info[3].value as i16 {
+            return SCALE_OPPOSITE_BISHOPS;
+        }
+        return SCALE_OPPOSITE_BISHOPS_PIECES;
+    }
+
+    SCALE_NORMAL
+}
+
+// Returns the classical evaluation of the board relative to the white team
+// Also used with the nnue feature, to train the network on positions without game results
+pub fn classical_evaluation(board: &board_representation::Board, pawn_cache: &mut EvalCache, weights: &EvalWeights, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> i16 {
+    // Blend the middlegame and endgame evaluations of the material and piece square tables based on the game phase
+    // They are kept up to date on the board by new_turn, so they don't have to be summed at every leaf
+    let phase = game_phase(board);
+    let mut evaluation = blend(board.tapered_evaluation, phase);
+
+    // Mobility, not cached with the pawn structure as it depends on every piece
+    let (mobility, attacks) = mobility(board, weights, pieces_info);
+    evaluation += mobility;
+
+    // Threats, from the attacks found while counting mobility
+    evaluation += threats(board, true, &attacks, weights) - threats(board, false, &attacks, weights);
+
+    // Bishop pair, rook file and knight outpost bonuses
+    evaluation += piece_bonuses(board, true, weights) - piece_bonuses(board, false, weights);
+
+    // Pawn structure
+    // Only depends on the pawns so it is cached with a key made from the pawns
+    let pawn_key = crate::zobrist::pawn_hash(board);
+    match pawn_cache.probe(pawn_key) {
+        Some(pawn_evaluation) => evaluation += pawn_evaluation,
+        None => {
+            let pawn_evaluation = evaluate_pawns(board, weights);
+            pawn_cache.store(pawn_key, pawn_evaluation);
+            evaluation += pawn_evaluation;
+        },
+    }
+
+    // Basic mating endgames
+    // Without this the engine can't see far enough ahead to make progress, and shuffles pieces until the game is drawn
+    evaluation += mate_driving_evaluation(board);
+
+    evaluation
+}
+
+// Returns the evaluation of positions which don't need the normal evaluation terms
+fn exact_evaluation(board: &board_representation::Board) -> Option<i16> {
+    // Dead positions are draws whatever the material
+    if board.insufficient_material() {
+        return Some(0);
+    }
+
+    // King and pawn vs king positions are evaluated perfectly with the bitbase
+    // The bitbase is only for standard chess, a lone king can still win or check in the variants
+    if board.variant != crate::variant::Variant::Standard {
+        return None;
+    }
+    match crate::kpk::probe(board) {
+        Some(win) => Some(kpk_evaluation(board, win)),
+        None => None,
+    }
+}
+
+// Returns the middlegame and endgame evaluations of the material, and of the piece square tables
+fn material_and_piece_squares(board: &board_representation::Board, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> ([i16; 2], [i16; 2]) {
+    let mut material_evaluation = [0, 0];
+    let mut piece_square_evaluation = [0, 0];
+
+    for i in 0..12 {
+        let material = board.board[i].count_ones() as i16 * pieces_info[i].value as i16 * PAWN_VALUE;
+        let mut piece_squares = [0, 0];
+
+        let mut bitboard = board.board[i];
+        while bitboard != 0 {
+            let bit = bitboard.trailing_zeros() as usize;
+            let piece_square_values = crate::piece::constants::piece_square_values(i, bit);
+            piece_squares[0] += piece_square_values[0];
+            piece_squares[1] += piece_square_values[1];
+            bitboard &= bitboard - 1; // Turn off the lowest bit
+        }
+
+        for j in 0..2 {
+            if crate::board_index_white(i) {
+                material_evaluation[j] += material;
+                piece_square_evaluation[j] += piece_squares[j];
+            } else {
+                material_evaluation[j] -= material;
+                piece_square_evaluation[j] -= piece_squares[j];
+            }
+        }
+    }
+
+    (material_evaluation, piece_square_evaluation)
+}
+
+// Returns the middlegame and endgame value (material and piece square table) of a piece on a bit relative to the white team
+fn piece_value(board_index: usize, bit: usize, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> [i16; 2] {
+    let material = pieces_info[board_index].value as i16 * PAWN_VALUE;
+    let piece_square_values = crate::piece::constants::piece_square_values(board_index, bit);
+
+    if crate::board_index_white(board_index) {
+        [material + piece_square_values[0], material + piece_square_values[1]]
+    } else {
+        [-material - piece_square_values[0], -material - piece_square_values[1]]
+    }
+}
+
+// Calculates the tapered evaluation of a board from scratch
+// Used when a board is made without new_turn (e.g. from a fen)
+pub fn refresh_tapered_evaluation(board: &mut board_representation::Board, pieces_info: &[crate::piece::constants::PieceInfo; 12]) {
+    let (material, piece_squares) = material_and_piece_squares(board, pieces_info);
+    board.tapered_evaluation = [material[0] + piece_squares[0], material[1] + piece_squares[1]];
+}
+
+// Updates the tapered evaluation of a board after a move, from the bitboards of the board before the move
+// Only the pieces that changed are added or removed, so captures, castling, en passant and promotions are all handled
+pub fn update_tapered_evaluation(initial_bitboards: &[u64; 13], board: &mut board_representation::Board, pieces_info: &[crate::piece::constants::PieceInfo; 12]) {
+    for i in 0..12 {
+        let changed = initial_bitboards[i] ^ board.board[i];
+        if changed == 0 {
+            continue;
+        }
+
+        let mut added = changed & board.board[i];
+        while added != 0 {
+            let value = piece_value(i, added.trailing_zeros() as usize, pieces_info);
+            board.tapered_evaluation[0] += value[0];
+            board.tapered_evaluation[1] += value[1];
+            added &= added - 1; // Turn off the lowest bit
+        }
+
+        let mut removed = changed & initial_bitboards[i];
+        while removed != 0 {
+            let value = piece_value(i, removed.trailing_zeros() as usize, pieces_info);
+            board.tapered_evaluation[0] -= value[0];
+            board.tapered_evaluation[1] -= value[1];
+            removed &= removed - 1; // Turn off the lowest bit
+        }
+    }
+}
+
+// Blends a middlegame and endgame evaluation based on the game phase
+pub(crate) fn blend(tapered_evaluation: [i16; 2], phase: i16) -> i16 {
+    let phase = phase as i32;
+    ((tapered_evaluation[0] as i32 * phase + tapered_evaluation[1] as i32 * (MAX_PHASE as i32 - phase)) / MAX_PHASE as i32) as i16
+}
+
+// Returns the mate driving evaluation relative to the white team (see mate_driving)
+fn mate_driving_evaluation(board: &board_representation::Board) -> i16 {
+    let mut evaluation = 0;
+    match mate_driving(board, true) {
+        Some(bonus) => evaluation += bonus,
+        None => (),
+    }
+    match mate_driving(board, false) {
+        Some(bonus) => evaluation -= bonus,
+        None => (),
+    }
+    evaluation
+}
+
+// Contribution of every evaluation term to the evaluation of a board, relative to the white team
+// Used to debug why the engine likes a position, only built with the eval-trace feature so the embedded evaluation stays lean
+#[cfg(feature = "eval-trace")]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct EvalTrace {
+    pub exact: Option<i16>, // Set for dead positions and KPK positions, where this is the whole evaluation and the other terms are 0
+    pub phase: i16,
+    pub material: i16, // Tapered terms are blended separately, so their sum can be off from the total by rounding
+    pub piece_squares: i16,
+    pub mobility: i16,
+    pub threats: i16,
+    pub piece_bonuses: i16,
+    pub pawns: i16,
+    pub mate_driving: i16,
+    pub tempo: i16,
+    pub variant: i16, // Not scaled, see variant_evaluation
+    pub scale_factor: i16, // Out of SCALE_NORMAL, the sum of the terms is scaled by this in drawish endings
+    pub total: i16, // The same as evaluate
+}
+
+#[cfg(feature = "eval-trace")]
+impl core::fmt::Display for EvalTrace {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self.exact {
+            Some(evaluation) => writeln!(f, "Exact: {}", evaluation)?,
+            None => (),
+        }
+        writeln!(f, "Phase: {}/{}", self.phase, MAX_PHASE)?;
+        writeln!(f, "Material: {}", self.material)?;
+        writeln!(f, "Piece squares: {}", self.piece_squares)?;
+        writeln!(f, "Mobility: {}", self.mobility)?;
+        writeln!(f, "Threats: {}", self.threats)?;
+        writeln!(f, "Piece bonuses: {}", self.piece_bonuses)?;
+        writeln!(f, "Pawns: {}", self.pawns)?;
+        writeln!(f, "Mate driving: {}", self.mate_driving)?;
+        writeln!(f, "Tempo: {}", self.tempo)?;
+        writeln!(f, "Variant: {}", self.variant)?;
+        writeln!(f, "Scale factor: {}/{}", self.scale_factor, SCALE_NORMAL)?;
+        write!(f, "Total: {}", self.total)
+    }
+}
+
+// Evaluates the board like evaluate, but returns the contribution of every term
+// The pawn cache isn't used so every term is calculated from scratch
+#[cfg(feature = "eval-trace")]
+pub fn evaluate_trace(board: &board_representation::Board, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> EvalTrace {
+    let phase = game_phase(board);
+
+    match exact_evaluation(board) {
+        Some(evaluation) => {
+            return EvalTrace {
+                exact: Some(evaluation),
+                phase: phase,
+                material: 0,
+                piece_squares: 0,
+                mobility: 0,
+                threats: 0,
+                piece_bonuses: 0,
+                pawns: 0,
+                mate_driving: 0,
+                tempo: 0,
+                variant: 0,
+                scale_factor: SCALE_NORMAL,
+                total: evaluation,
+            };
+        },
+        None => (),
+    }
+
+    let (material, piece_squares) = material_and_piece_squares(board, pieces_info);
+    let tapered_evaluation = [material[0] + piece_squares[0], material[1] + piece_squares[1]];
+
+    let (mobility, attacks) = mobility(board, &WEIGHTS, pieces_info);
+    let threats = threats(board, true, &attacks, &WEIGHTS) - threats(board, false, &attacks, &WEIGHTS);
+    let piece_bonuses = piece_bonuses(board, true, &WEIGHTS) - piece_bonuses(board, false, &WEIGHTS);
+    let pawns = evaluate_pawns(board, &WEIGHTS);
+    let mate_driving = mate_driving_evaluation(board);
+    let tempo = tempo(board, &WEIGHTS);
+    let variant = variant_evaluation(board);
+    let evaluation = blend(tapered_evaluation, phase) + mobility + threats + piece_bonuses + pawns + mate_driving + tempo;
+
+    EvalTrace {
+        exact: None,
+        phase: phase,
+        material: blend(material, phase),
+        piece_squares: blend(piece_squares, phase),
+        mobility: mobility,
+        threats: threats,
+        piece_bonuses: piece_bonuses,
+        pawns: pawns,
+        mate_driving: mate_driving,
+        tempo: tempo,
+        variant: variant,
+        scale_factor: scale_factor(board, evaluation > 0, pieces_info),
+        total: scale_evaluation(board, evaluation, pieces_info) + variant,
+    }
+}
+
+// Returns the evaluation of a KPK position relative to the white team
+fn kpk_evaluation(board: &board_representation::Board, win: bool) -> i16 {
+    if !win {
+        return 0;
+    }
+
+    if board.board[0] != 0 {
+        let row = board.board[0].trailing_zeros() as i16 / 8;
+        KPK_WIN_VALUE + (7 - row) * KPK_PAWN_ADVANCE_WEIGHT
+    } else {
+        let row = board.board[6].trailing_zeros() as i16 / 8;
+        -(KPK_WIN_VALUE + row * KPK_PAWN_ADVANCE_WEIGHT)
+    }
+}
+
+// Returns the number of king moves between two bits
+fn king_distance(bit1: usize, bit2: usize) -> i16 {
+    let row_distance = (bit1 as i16 / 8 - bit2 as i16 / 8).abs();
+    let file_distance = (bit1 as i16 % 8 - bit2 as i16 % 8).abs();
+    row_distance.max(file_distance)
+}
+
+// Returns how far a bit is from the 4 centre squares (0 - 6)
+fn centre_distance(bit: usize) -> i16 {
+    let row = bit as i16 / 8;
+    let file = bit as i16 % 8;
+    (3 - row).max(row - 4) + (3 - file).max(file - 4)
+}
+
+// Material keys of the KQK, KRK and KBNK endgames, with the white team attacking then with the black team attacking
+const MATE_DRIVING_ENDGAMES: [[u64; 3]; 2] = [
+    [board_representation::signature_key("KQvK"), board_representation::signature_key("KRvK"), board_representation::signature_key("KBNvK")],
+    [board_representation::signature_key("KvKQ"), board_representation::signature_key("KvKR"), board_representation::signature_key("KvKBN")],
+];
+
+// Returns a bonus for the attacking team in KQK, KRK and KBNK endgames, or None if the board is not one of these endgames
+// The bonus rewards driving the defending king to the edge of the board and bringing the attacking king closer
+// In KBNK mate is only possible in the corners that are the same colour as the bishop, so the king is driven there instead
+pub fn mate_driving(board: &board_representation::Board, attacker_white: bool) -> Option<i16> {
+    let attacker_offset;
+    let defender_offset;
+    if attacker_white {
+        attacker_offset = 0;
+        defender_offset = 6;
+    } else {
+        attacker_offset = 6;
+        defender_offset = 0;
+    }
+
+    // Only the attacking team's king and mating pieces against a bare king
+    let material_key = board.material().key();
+    let endgames = MATE_DRIVING_ENDGAMES[attacker_offset / 6];
+    if material_key != endgames[0] && material_key != endgames[1] && material_key != endgames[2] {
+        return None;
+    }
+
+    let attacking_king = board.board[attacker_offset + 5].trailing_zeros() as usize;
+    let defending_king = board.board[defender_offset + 5].trailing_zeros() as usize;
+    let approach = (7 - king_distance(attacking_king, defending_king)) * MATE_KING_DISTANCE_WEIGHT;
+
+    if material_key != endgames[2] {
+        // KQK and KRK
+        return Some(centre_distance(defending_king) * MATE_EDGE_WEIGHT + approach);
+    } else {
+        // KBNK
+        // Bit 0 (a8) is a light square, the light corners are a8 and h1, and the dark corners are h8 and a1
+        let bishop = board.board[attacker_offset + 3].trailing_zeros() as usize;
+        let corners;
+        if (bishop / 8 + bishop % 8) % 2 == 0 {
+            corners = [0i16, 63];
+        } else {
+            corners = [7i16, 56];
+        }
+
+        // Manhattan distance to the closest mating corner (0 - 14)
+        let mut corner_distance = 14;
+        for corner in corners {
+            let distance = (defending_king as i16 / 8 - corner / 8).abs() + (defending_king as i16 % 8 - corner % 8).abs();
+            corner_distance = corner_distance.min(distance);
+        }
+        return Some((14 - corner_distance) * MATE_EDGE_WEIGHT + approach);
+    }
+}
+
+// Returns the mobility evaluation relative to the white team, and the squares attacked by the white and black teams
+// Mobility is the number of squares a piece can move to that are not attacked by enemy pawns
+// The moves are pseudo legal (pins and checks are ignored) to keep the evaluation cheap enough for quiescence search
+pub fn mobility(board: &board_representation::Board, weights: &EvalWeights, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> (i16, [TeamAttacks; 2]) {
+    let white_pawn_attacks = pawn_attacks(board.board[0], true);
+    let black_pawn_attacks = pawn_attacks(board.board[6], false);
+    let white_team_bitboards = crate::TeamBitboards::new(0, board);
+    let black_team_bitboards = crate::TeamBitboards::new(6, board);
+
+    // Pawn and king attacks don't need the move generator
+    let mut attacks = [TeamAttacks::new(), TeamAttacks::new()];
+    attacks[0].pawns = white_pawn_attacks;
+    attacks[1].pawns = black_pawn_attacks;
+    attacks[0].all = white_pawn_attacks | crate::kpk::king_attacks(board.board[5].trailing_zeros() as usize);
+    attacks[1].all = black_pawn_attacks | crate::kpk::king_attacks(board.board[11].trailing_zeros() as usize);
+
+    let mut evaluation = 0;
+    for i in 0..12 {
+        // Pawn and king mobility isn't counted (see EvalWeights)
+        if i % 6 == 0 || i % 6 == 5 {
+            continue;
+        }
+
+        let team;
+        let team_bitboards;
+        let enemy_pawn_attacks;
+        if crate::board_index_white(i) {
+            team = 0;
+            team_bitboards = &white_team_bitboards;
+            enemy_pawn_attacks = black_pawn_attacks;
+        } else {
+            team = 1;
+            team_bitboards = &black_team_bitboards;
+            enemy_pawn_attacks = white_pawn_attacks;
+        }
+
+        let mut bitboard = board.board[i];
+        while bitboard != 0 {
+            let piece = board_representation::BoardCoordinates {
+                board_index: i,
+                bit: bitboard.trailing_zeros() as usize,
+            };
+            bitboard &= bitboard - 1; // Turn off the lowest bit
+
+            // Only generating attacks includes the friendly pieces the piece defends, which are left out of the mobility
+            let piece_attacks = crate::board::move_generator::gen_piece(&piece, None, team_bitboards, true, board, pieces_info).moves_bitboard;
+            let safe_squares = (piece_attacks & !team_bitboards.friendly_team & !enemy_pawn_attacks).count_ones() as i16;
+
+            if team == 0 {
+                evaluation += safe_squares * weights.mobility[i % 6];
+            } else {
+                evaluation -= safe_squares * weights.mobility[i % 6];
+            }
+
+            match i % 6 {
+                1 => attacks[team].rooks |= piece_attacks,
+                2 | 3 => attacks[team].minor_pieces |= piece_attacks,
+                _ => (),
+            }
+            attacks[team].all |= piece_attacks;
+        }
+    }
+
+    (evaluation, attacks)
+}
+
+// Returns the threat evaluation of one team
+// Enemy pieces attacked by lower valued pieces will probably be lost or have to move, and enemy pieces that are attacked and not defended can be taken for free
+// Only counts threats and doesn't look at whose move it is, the search resolves the actual captures
+pub fn threats(board: &board_representation::Board, white: bool, attacks: &[TeamAttacks; 2], weights: &EvalWeights) -> i16 {
+    let team;
+    let enemy_offset;
+    if white {
+        team = 0;
+        enemy_offset = 6;
+    } else {
+        team = 1;
+        enemy_offset = 0;
+    }
+    let team_attacks = &attacks[team];
+    let enemy_attacks = &attacks[1 - team];
+
+    // Enemy pieces attacked by lower valued pieces
+    let minors = board.board[enemy_offset + 2] | board.board[enemy_offset + 3];
+    let threatened = (minors & team_attacks.pawns)
+        | (board.board[enemy_offset + 1] & (team_attacks.pawns | team_attacks.minor_pieces))
+        | (board.board[enemy_offset + 4] & (team_attacks.pawns | team_attacks.minor_pieces | team_attacks.rooks));
+
+    // Enemy pieces (apart from the king) that are attacked and not defended, which aren't allready threatened
+    let enemy_pieces = crate::or_bitboards(enemy_offset, enemy_offset + 4, &board.board);
+    let hanging = enemy_pieces & team_attacks.all & !enemy_attacks.all & !threatened;
+
+    threatened.count_ones() as i16 * weights.threat + hanging.count_ones() as i16 * weights.hanging_piece
+}
+
+// Returns the positional bonuses of one teams pieces
+// Bishop pair, rooks on open and semi open files, and knight outposts
+pub fn piece_bonuses(board: &board_representation::Board, white: bool, weights: &EvalWeights) -> i16 {
+    let offset;
+    if white {
+        offset = 0;
+    } else {
+        offset = 6;
+    }
+
+    let pawns = board.board[offset];
+    let enemy_pawns = board.board[6 - offset];
+
+    let mut evaluation = 0;
+
+    // Bishop pair
+    if board.board[offset + 3].count_ones() >= 2 {
+        evaluation += weights.bishop_pair;
+    }
+
+    // Rooks on open and semi open files
+    let mut rooks = board.board[offset + 1];
+    while rooks != 0 {
+        let file_bitboard = FILE_A << (rooks.trailing_zeros() as usize % 8);
+        rooks &= rooks - 1; // Turn off the lowest bit
+
+        if pawns & file_bitboard == 0 {
+            if enemy_pawns & file_bitboard == 0 {
+                evaluation += weights.rook_open_file;
+            } else {
+                evaluation += weights.rook_semi_open_file;
+            }
+        }
+    }
+
+    // Knight outposts
+    let friendly_pawn_attacks = pawn_attacks(pawns, white);
+    let mut knights = board.board[offset + 2];
+    while knights != 0 {
+        let bit = knights.trailing_zeros() as usize;
+        let row = bit / 8;
+        knights &= knights - 1; // Turn off the lowest bit
+
+        // The enemy half is rows 0 - 3 for white and rows 4 - 7 for black
+        let enemy_half;
+        if white {
+            enemy_half = row < 4;
+        } else {
+            enemy_half = row >= 4;
+        }
+
+        // No enemy pawns in front of the knight on the adjacent files, so it can never be attacked by a pawn
+        let pawn_safe = enemy_pawns & adjacent_files(bit % 8) & rows_in_front(row, white) == 0;
+
+        if enemy_half && pawn_safe && crate::bit_on(friendly_pawn_attacks, bit) {
+            evaluation += weights.knight_outpost;
+        }
+    }
+
+    evaluation
+}
+
+// Returns the evaluation of the pawn structure relative to the white team
+pub fn evaluate_pawns(board: &board_representation::Board, weights: &EvalWeights) -> i16 {
+    team_pawns(board.board[0], board.board[6], true, weights) - team_pawns(board.board[6], board.board[0], false, weights)
+}
+
+// Returns a bitboard of the files next to a file
+fn adjacent_files(file: usize) -> u64 {
+    let mut mask = 0;
+    if file > 0 {
+        mask |= FILE_A << (file - 1);
+    }
+    if file < 7 {
+        mask |= FILE_A << (file + 1);
+    }
+    mask
+}
+
+// Returns a bitboard of the rows in front of a row, from the perspective of a team
+// White pawns move towards bit 0 (row 0), black pawns move towards bit 63 (row 7)
+fn rows_in_front(row: usize, white: bool) -> u64 {
+    if white {
+        if row == 0 {
+            return 0;
+        }
+        u64::MAX >> (64 - row * 8)
+    } else {
+        if row == 7 {
+            return 0;
+        }
+        u64::MAX << ((row + 1) * 8)
+    }
+}
+
+// Returns a bitboard of the squares attacked by a teams pawns
+fn pawn_attacks(pawns: u64, white: bool) -> u64 {
+    use crate::bitboard::{east_one, north_one, south_one, west_one};
+
+    // White pawns attack one row towards row 8, black pawns one row towards row 1
+    let pushed;
+    if white {
+        pushed = north_one(pawns);
+    } else {
+        pushed = south_one(pawns);
+    }
+    west_one(pushed) | east_one(pushed)
+}
+
+// Evaluates one teams pawns
+fn team_pawns(pawns: u64, enemy_pawns: u64, white: bool, weights: &EvalWeights) -> i16 {
+    let mut evaluation = 0;
+
+    for file in 0..8 {
+        let file_pawns = (pawns & FILE_A << file).count_ones() as i16;
+
+        // Doubled pawns
+        if file_pawns > 1 {
+            evaluation -= (file_pawns - 1) * weights.doubled_pawn;
+        }
+    }
+
+    let enemy_attacks = pawn_attacks(enemy_pawns, !white);
+
+    let mut bitboard = pawns;
+    while bitboard != 0 {
+        let bit = bitboard.trailing_zeros() as usize;
+        let file = bit % 8;
+        let row = bit / 8;
+        bitboard &= bitboard - 1; // Turn off the lowest bit
+
+        let neighbour_files = adjacent_files(file);
+        let in_front = rows_in_front(row, white);
+
+        // Passed pawns, no enemy pawns in front of the pawn on its file or the adjacent files
+        // The front span of the pawn is the squares the pawn has to pass, and the squares enemy pawns could capture it from
+        if enemy_pawns & in_front & (neighbour_files | FILE_A << file) == 0 {
+            let ranks_advanced;
+            if white {
+                ranks_advanced = 7 - row;
+            } else {
+                ranks_advanced = row;
+            }
+            evaluation += weights.passed_pawn[ranks_advanced];
+        }
+
+        // Isolated pawns, no friendly pawns on the adjacent files
+        if pawns & neighbour_files == 0 {
+            evaluation -= weights.isolated_pawn;
+            continue;
+        }
+
+        // Backward pawns, all the friendly pawns on the adjacent files are in front of the pawn
+        // and the square in front of the pawn is attacked by an enemy pawn, so the pawn can't advance to be defended
+        // Pawns on the last row (only possible from a fen) have no square in front of them
+        if in_front == 0 {
+            continue;
+        }
+
+        let stop_bit;
+        if white {
+            stop_bit = bit - 8;
+        } else {
+            stop_bit = bit + 8;
+        }
+
+        if pawns & neighbour_files & !in_front == 0 && crate::bit_on(enemy_attacks, stop_bit) {
+            evaluation -= weights.backward_pawn;
+        }
+    }
+
+    evaluation
+}
+
+// Evaluates the board, using the evaluation cache to avoid evaluating positions that have allready been evaluated
+pub fn cached_evaluate(board: &board_representation::Board, eval_cache: &mut EvalCache, pawn_cache: &mut EvalCache, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> i16 {
+    let key = board.hash;
+
+    match eval_cache.probe(key) {
+        Some(evaluation) => evaluation,
+        None => {
+            let evaluation = evaluate(board, pawn_cache, pieces_info);
+            eval_cache.store(key, evaluation);
+            evaluation
+        },
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct EvalCacheEntry {
+    pub key: u64, // Zobrist hash of the evaluated board
+    pub evaluation: i16,
+    pub used: bool, // False if nothing has been stored in the entry
+}
+
+impl EvalCacheEntry {
+    pub fn new() -> Self {
+        EvalCacheEntry {
+            key: 0,
+            evaluation: 0,
+            used: false,
+        }
+    }
+}
+
+// Hash table of static evaluations keyed by zobrist hash
+// Also used as the pawn structure cache, keyed by the pawn only zobrist hash
+// The entries are owned by the caller so the size can be chosen for the target
+// E.g. a few hundred entries on the embedded board, or a heap allocated table with millions of entries on a host
+pub struct EvalCache<'a> {
+    entries: &'a mut [EvalCacheEntry],
+}
+
+impl<'a> EvalCache<'a> {
+    pub fn new(entries: &'a mut [EvalCacheEntry]) -> Self {
+        EvalCache {
+            entries: entries,
+        }
+    }
+
+    // Returns the cached evaluation for the key if there is one
+    pub fn probe(&self, key: u64) -> Option<i16> {
+        if self.entries.is_empty() {
+            return None;
+        }
+
+        let entry = &self.entries[(key % self.entries.len() as u64) as usize];
+        if entry.used && entry.key == key {
+            return Some(entry.evaluation);
+        }
+        None
+    }
+
+    // Stores an evaluation, replacing whatever was in the entry
+    pub fn store(&mut self, key: u64, evaluation: i16) {
+        if self.entries.is_empty() {
+            return;
+        }
+
+        let index = (key % self.entries.len() as u64) as usize;
+        self.entries[index] = EvalCacheEntry {
+            key: key,
+            evaluation: evaluation,
+            used: true,
+        };
+    }
+
+    // Removes all cached evaluations
+    pub fn clear(&mut self) {
+        for i in 0..self.entries.len() {
+            self.entries[i] = EvalCacheEntry::new();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Positions from every stage of the game, with both teams to move, passed pawns, open files, outposts, bishop pairs and unbalanced material
+    const SYMMETRY_POSITIONS: [&str; 10] = [
+        "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1",
+        "r1bqkbnr/pppp1ppp/2n5/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R w KQkq - 2 3",
+        "r3k2r/p1ppqpb1/bn2pnp1/3PN3/1p2P3/2N2Q1p/PPPBBPPP/R3K2R w KQkq - 0 1",
+        "r3k2r/Pppp1ppp/1b3nbN/nP6/BBP1P3/q4N2/Pp1P2PP/R2Q1RK1 w kq - 0 1",
+        "rnbq1k1r/pp1Pbppp/2p5/8/2B5/8/PPP1NnPP/RNBQK2R w KQ - 1 8",
+        "8/2p5/3p4/KP5r/1R3p1k/8/4P1P1/8 w - - 0 1",
+        "r4rk1/1pp2ppp/p1n5/3pP3/3P1B2/2N5/PP3PPP/R4RK1 b - - 0 15",
+        "2r3k1/5ppp/p3p3/1p1nP3/3N4/P4P2/1P4PP/2R3K1 w - - 0 25",
+        "8/5k2/3p4/1p1P1p2/1P3P2/6K1/8/8 b - - 0 40",
+        "6k1/5ppp/8/8/8/8/5PPP/3R2K1 w - - 0 1",
+    ];
+
+    // The mirrored board swaps the teams, so it has to evaluate to the negative of the board or a term has a sign bug
+    #[test]
+    fn mirror_symmetry() {
+        let pieces_info = crate::piece::constants::gen();
+        let mut pawn_entries = [EvalCacheEntry::new(); 64];
+        let mut pawn_cache = EvalCache::new(&mut pawn_entries);
+
+        for fen in SYMMETRY_POSITIONS {
+            let board = board_representation::Board::from_fen(fen).unwrap();
+            let value = evaluate(&board, &mut pawn_cache, &pieces_info);
+            let mirrored_value = evaluate(&board.mirror(), &mut pawn_cache, &pieces_info);
+            assert_eq!(value, -mirrored_value, "{}", fen);
+        }
+    }
+
+    // The files next to a file, without wrapping around the edges of the board
+    #[test]
+    fn adjacent_files_masks() {
+        assert_eq!(adjacent_files(0), FILE_A << 1);
+        assert_eq!(adjacent_files(3), FILE_A << 2 | FILE_A << 4);
+        assert_eq!(adjacent_files(7), FILE_A << 6);
+    }
+
+    // The rows a pawn moves towards, white pawns move towards row 0 and black pawns towards row 7
+    #[test]
+    fn rows_in_front_masks() {
+        assert_eq!(rows_in_front(0, true), 0);
+        assert_eq!(rows_in_front(4, true), 0x00000000FFFFFFFF);
+        assert_eq!(rows_in_front(7, true), 0x00FFFFFFFFFFFFFF);
+        assert_eq!(rows_in_front(7, false), 0);
+        assert_eq!(rows_in_front(3, false), 0xFFFFFFFF00000000);
+        assert_eq!(rows_in_front(0, false), 0xFFFFFFFFFFFFFF00);
+    }
+
+    // Pawn captures are diagonal in front of the pawn, and pawns on the edge files only attack one square
+    #[test]
+    fn pawn_attack_masks() {
+        assert_eq!(pawn_attacks(1 << 36, true), 1 << 27 | 1 << 29); // e4 attacks d5 and f5
+        assert_eq!(pawn_attacks(1 << 48, true), 1 << 41); // a2 attacks b3
+        assert_eq!(pawn_attacks(1 << 15, false), 1 << 22); // h7 attacks g6
+        assert_eq!(pawn_attacks(1 << 28, false), 1 << 35 | 1 << 37); // e5 attacks d4 and f4
+    }
+
+    // Returns the piece bonuses of one team in a position
+    fn bonuses(fen: &str, white: bool) -> i16 {
+        let board = board_representation::Board::from_fen(fen).unwrap();
+        piece_bonuses(&board, white, &WEIGHTS)
+    }
+
+    #[test]
+    fn bishop_pair() {
+        assert_eq!(bonuses("4k3/8/8/8/8/8/8/2B1KB2 w - - 0 1", true), WEIGHTS.bishop_pair);
+        assert_eq!(bonuses("4k3/8/8/8/8/8/8/2B1K3 w - - 0 1", true), 0);
+        assert_eq!(bonuses("2b1kb2/8/8/8/8/8/8/4K3 w - - 0 1", false), WEIGHTS.bishop_pair);
+    }
+
+    // The a1 rook's file only has an enemy pawn, the b1 rook's file has a friendly pawn and the c1 rook's file has no pawns
+    #[test]
+    fn rook_files() {
+        assert_eq!(bonuses("4k3/p7/8/8/8/8/1P6/RRR1K3 w - - 0 1", true), WEIGHTS.rook_open_file + WEIGHTS.rook_semi_open_file);
+        assert_eq!(bonuses("rrr1k3/1p6/8/8/8/8/P7/4K3 w - - 0 1", false), WEIGHTS.rook_open_file + WEIGHTS.rook_semi_open_file);
+    }
+
+    // An outpost is on the enemy half, defended by a friendly pawn and can't be attacked by an enemy pawn
+    #[test]
+    fn knight_outposts() {
+        assert_eq!(bonuses("4k3/8/8/3N4/4P3/8/8/4K3 w - - 0 1", true), WEIGHTS.knight_outpost);
+        assert_eq!(bonuses("4k3/2p5/8/3N4/4P3/8/8/4K3 w - - 0 1", true), 0); // The c7 pawn can attack it
+        assert_eq!(bonuses("4k3/8/8/8/3N4/4P3/8/4K3 w - - 0 1", true), 0); // On its own half
+        assert_eq!(bonuses("4k3/8/8/3N4/8/8/8/4K3 w - - 0 1", true), 0); // Not defended by a pawn
+        assert_eq!(bonuses("4k3/8/8/4p3/3n4/8/8/4K3 w - - 0 1", false), WEIGHTS.knight_outpost);
+    }
+}
@@ -0,0 +1,299 @@
+use crate::algorithm::Move;
+use crate::board::board_representation;
+use crate::board::move_generator::TurnError;
+use crate::pgn::GameResult;
+
+// Keeps track of a game, the moves that have been played, the hashes of past positions and the result
+// new_turn only knows about one board, so draws by repetition are detected here, along with every other way a game can end
+// Works without an allocator so the firmware and any other frontend can share the same game bookkeeping
+
+// Most half moves a game can hold, every move is stored in 4 bytes
+// The same as a game record, so every game can be saved as PGN
+pub const MAX_GAME_HALF_MOVES: usize = crate::pgn::MAX_RECORD_HALF_MOVES;
+
+// Number of past position hashes that are kept to detect repetitions
+// A position can only repeat since the last capture or pawn move, and the fifty move rule ends the game 100 half moves after one
+const HASH_HISTORY: usize = 128;
+
+pub struct Game {
+    pub board: board_representation::Board, // Board of the team to move, if a move ended the game this is the board before the move
+    pub result: GameResult,
+    pub end: Option<TurnError>, // How the game ended, None while the game is in progress or if a team ran out of time (see time_forfeit)
+
+    start: board_representation::Board, // Board the game started from
+    moves: [(u8, u8, u8, u8); MAX_GAME_HALF_MOVES], // Board index, initial bit, final bit and promotion piece (0 if there is none) of every move
+    half_moves: usize, // Number of moves that have been played
+    hashes: [u64; HASH_HISTORY], // Hash of the board after every half move (the start board is half move 0), indexed by the half move % HASH_HISTORY
+}
+
+impl Game {
+    // Creates a game starting from a board
+    pub fn new(start: board_representation::Board) -> Self {
+        let mut game = Game {
+            board: start,
+            result: GameResult::Unfinished,
+            end: None,
+            start: start,
+            moves: [(0, 0, 0, 0); MAX_GAME_HALF_MOVES],
+            half_moves: 0,
+            hashes: [0; HASH_HISTORY],
+        };
+        game.hashes[0] = start.hash;
+        game
+    }
+
+    // Plays a move
+    // Returns the error from new_turn if the move is invalid (the game isn't changed), or the way the game ended if the move ended it
+    // Draws by threefold repetition are returned as TurnError::Repetition
+    // Moves can't be played once the game has ended (including on time, see time_forfeit), or if there is no space left for the move
+    pub fn play(&mut self, piece_move: &Move, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Result<(), TurnError> {
+        if self.end.is_some() || self.result != GameResult::Unfinished || self.half_moves == MAX_GAME_HALF_MOVES {
+            return Err(TurnError::InvalidMove);
+        }
+
+        let new_board;
+        match crate::board::move_generator::make_move(&piece_move.initial_piece_coordinates, piece_move.final_piece_bit, piece_move.promotion, self.board, pieces_info) {
+            Ok(board) => new_board = board,
+            Err(error) => {
+                if error == TurnError::InvalidMove || error == TurnError::InvalidMoveCheck {
+                    return Err(error);
+                }
+
+                self.push_move(piece_move);
+                self.end_game(error);
+                return Err(error);
+            },
+        }
+
+        self.push_move(piece_move);
+        self.hashes[self.half_moves % HASH_HISTORY] = new_board.hash;
+
+        // The position has occured 3 times if it has occured twice before
+        if self.repetitions(&new_board) >= 2 {
+            self.end_game(TurnError::Repetition);
+            return Err(TurnError::Repetition);
+        }
+
+        self.board = new_board;
+        Ok(())
+    }
+
+    // Ends the game because a team has run out of time (see clock.rs)
+    // The other team wins if it could still checkmate, otherwise the game is a draw
+    // There is no TurnError for running out of time, so only the result is set and end stays None
+    // A game that has ended is one with an end or a result other than GameResult::Unfinished, play checks both and the save writes a result entry (see save::SaveWriter::finish)
+    pub fn time_forfeit(&mut self, white_flagged: bool) {
+        if !crate::clock::has_mating_material(&self.board, !white_flagged) {
+            self.result = GameResult::Draw;
+        } else if white_flagged {
+            self.result = GameResult::BlackWin;
+        } else {
+            self.result = GameResult::WhiteWin;
+        }
+    }
+
+    // Takes back the last move, including a move that ended the game
+    // The game is replayed from the start, because new_turn can't reverse a move
+    // Returns an error if no moves have been played
+    pub fn undo(&mut self, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Result<(), ()> {
+        if self.half_moves == 0 {
+            return Err(());
+        }
+        let moves_to_replay = self.half_moves - 1;
+
+        self.board = self.start;
+        self.result = GameResult::Unfinished;
+        self.end = None;
+        self.half_moves = 0;
+        self.hashes[0] = self.start.hash;
+
+        // Replaying a move writes it back to the same place in the moves array
+        for i in 0..moves_to_replay {
+            let piece_move = self.stored_move(i);
+            self.play(&piece_move, pieces_info).map_err(|_| ())?;
+        }
+        Ok(())
+    }
+
+    // Returns the number of moves that have been played
+    pub fn len(&self) -> usize {
+        self.half_moves
+    }
+
+    // Returns true if no moves have been played
+    pub fn is_empty(&self) -> bool {
+        self.half_moves == 0
+    }
+
+    // Returns a move that has been played, 0 is the first move
+    pub fn piece_move(&self, index: usize) -> Option<Move> {
+        if index >= self.half_moves {
+            return None;
+        }
+        Some(self.stored_move(index))
+    }
+
+    // Returns the move at an index of the moves array
+    fn stored_move(&self, index: usize) -> Move {
+        let (board_index, initial_bit, final_bit, promotion) = self.moves[index];
+        let mut piece_move = Move::new();
+        piece_move.initial_piece_coordinates = board_representation::BoardCoordinates {
+            board_index: board_index as usize,
+            bit: initial_bit as usize,
+        };
+        piece_move.final_piece_bit = final_bit as usize;
+        if promotion != 0 {
+            piece_move.promotion = Some(promotion as usize);
+        }
+        piece_move
+    }
+
+    // Returns the board before a move was played, by replaying the game from the start (e.g. to write the move in standard algebraic notation)
+    // Index len() is the board after the last move, None if the index is past it or the last move ended the game
+    pub fn board_before(&self, index: usize, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Option<board_representation::Board> {
+        if index > self.half_moves {
+            return None;
+        }
+
+        let mut board = self.start;
+        for i in 0..index {
+            let piece_move = self.stored_move(i);
+            match crate::board::move_generator::make_move(&piece_move.initial_piece_coordinates, piece_move.final_piece_bit, piece_move.promotion, board, pieces_info) {
+                Ok(new_board) => board = new_board,
+                Err(_) => return None,
+            }
+        }
+        Some(board)
+    }
+
+    // Returns the board the game started from
+    pub fn start(&self) -> &board_representation::Board {
+        &self.start
+    }
+
+    // Returns the number of half moves since the last capture or pawn move
+    pub fn half_move_clock(&self) -> i16 {
+        self.board.half_move_clock
+    }
+
+    // Returns the full move number, which starts at 1 and goes up after every black move
+    pub fn full_move_number(&self) -> i16 {
+        self.board.full_move_number()
+    }
+
+    // Returns a record of the game, so it can be written as PGN
+    pub fn record<'a>(&self) -> crate::pgn::GameRecord<'a> {
+        let mut record = crate::pgn::GameRecord::new(self.start);
+
+        // A record holds as many moves as a game, so pushing can't fail
+        for i in 0..self.half_moves {
+            record.push(&self.stored_move(i)).ok();
+        }
+        record.result = self.result;
+        record
+    }
+
+    fn push_move(&mut self, piece_move: &Move) {
+        self.moves[self.half_moves] = (
+            piece_move.initial_piece_coordinates.board_index as u8,
+            piece_move.initial_piece_coordinates.bit as u8,
+            piece_move.final_piece_bit as u8,
+            piece_move.promotion.unwrap_or(0) as u8,
+        );
+        self.half_moves += 1;
+    }
+
+    // Ends the game, the team that moved is the team to move on the board before the move
+    fn end_game(&mut self, error: TurnError) {
+        self.result = GameResult::from_turn_error(error, self.board.whites_move);
+        self.end = Some(error);
+    }
+
+    // Returns the number of times the board has occured before, with the same team to move
+    // Only the positions since the last capture or pawn move are checked
+    fn repetitions(&self, board: &board_representation::Board) -> usize {
+        let mut repetitions = 0;
+        let mut distance = 2;
+        while distance <= board.half_move_clock as usize && distance <= self.half_moves && distance < HASH_HISTORY {
+            if self.hashes[(self.half_moves - distance) % HASH_HISTORY] == board.hash {
+                repetitions += 1;
+            }
+            distance += 2;
+        }
+        repetitions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn play(game: &mut Game, text: &str, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Result<(), TurnError> {
+        let piece_move = crate::notation::parse_move(text, &game.board, pieces_info).unwrap();
+        game.play(&piece_move, pieces_info)
+    }
+
+    #[test]
+    fn knight_shuffle_repetition() {
+        let pieces_info = crate::piece::constants::gen();
+        let mut game = Game::new(board_representation::Board::indexed_start(0).unwrap());
+
+        // The starting position occurs for the second time after the first 4 moves, and for the third time after the last move
+        let moves = ["Nf3", "Nf6", "Ng1", "Ng8", "Nf3", "Nf6", "Ng1"];
+        for text in moves {
+            assert_eq!(play(&mut game, text, &pieces_info), Ok(()));
+        }
+        assert_eq!(play(&mut game, "Ng8", &pieces_info), Err(TurnError::Repetition));
+        assert_eq!(game.end, Some(TurnError::Repetition));
+        assert_eq!(game.result, GameResult::Draw);
+        assert_eq!(game.len(), 8);
+
+        // No more moves can be played, the board is still the one before the last move
+        assert_eq!(play(&mut game, "Ng8", &pieces_info), Err(TurnError::InvalidMove));
+    }
+
+    #[test]
+    fn undo_game_end() {
+        let pieces_info = crate::piece::constants::gen();
+        let mut game = Game::new(board_representation::Board::indexed_start(0).unwrap());
+
+        for text in ["f3", "e5", "g4"] {
+            assert_eq!(play(&mut game, text, &pieces_info), Ok(()));
+        }
+        let board = game.board;
+        assert_eq!(play(&mut game, "Qh4", &pieces_info), Err(TurnError::Checkmate));
+        assert_eq!(game.result, GameResult::BlackWin);
+        assert_eq!(game.len(), 4);
+
+        // Taking back the checkmate continues the game
+        game.undo(&pieces_info).unwrap();
+        assert_eq!(game.end, None);
+        assert_eq!(game.result, GameResult::Unfinished);
+        assert_eq!(game.len(), 3);
+        assert_eq!(game.board.hash, board.hash);
+        assert_eq!(play(&mut game, "Nc6", &pieces_info), Ok(()));
+    }
+
+    #[test]
+    fn time_forfeit() {
+        let pieces_info = crate::piece::constants::gen();
+
+        // Black can still checkmate, so it wins
+        let mut game = Game::new(board_representation::Board::indexed_start(0).unwrap());
+        game.time_forfeit(true);
+        assert_eq!(game.result, GameResult::BlackWin);
+        assert_eq!(game.end, None);
+        assert_eq!(play(&mut game, "e4", &pieces_info), Err(TurnError::InvalidMove));
+
+        // White only has a king, so it can't checkmate and the game is a draw
+        let board = board_representation::Board::from_fen("4k3/q7/8/8/8/8/8/4K3 b - - 0 1").unwrap();
+        let mut game = Game::new(board);
+        game.time_forfeit(false);
+        assert_eq!(game.result, GameResult::Draw);
+
+        // Black has a queen, so white flagging loses
+        let mut game = Game::new(board);
+        game.time_forfeit(true);
+        assert_eq!(game.result, GameResult::BlackWin);
+    }
+}
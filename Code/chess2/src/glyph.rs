@@ -0,0 +1,127 @@
+// Custom characters of the character lcd, drawn as 5x8 bitmaps in the HD44780's CGRAM (see embedded::character_lcd::Lcd::register_glyphs)
+// The HD44780 has room for 8 custom characters, they're printed with the character codes 0 - 7
+// Glyph::char returns the character of a glyph, so glyphs can be formatted into strings and printed with the rest of the text (e.g. "Hint: \u{2}b1c3")
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Glyph {
+    Pawn,
+    Rook,
+    Knight,
+    Bishop,
+    Queen,
+    King,
+    Hourglass, // Shown next to the team whose clock is running
+    Battery,
+}
+
+// Every glyph, in the order of their character codes
+pub const GLYPHS: [Glyph; 8] = [Glyph::Pawn, Glyph::Rook, Glyph::Knight, Glyph::Bishop, Glyph::Queen, Glyph::King, Glyph::Hourglass, Glyph::Battery];
+
+impl Glyph {
+    // Returns the glyph of a piece, board indexes of both teams have the same glyph
+    pub fn piece(board_index: usize) -> Self {
+        match board_index % 6 {
+            0 => Glyph::Pawn,
+            1 => Glyph::Rook,
+            2 => Glyph::Knight,
+            3 => Glyph::Bishop,
+            4 => Glyph::Queen,
+            _ => Glyph::King,
+        }
+    }
+
+    // Character code of the glyph, its index in GLYPHS
+    pub fn code(&self) -> u8 {
+        *self as u8
+    }
+
+    pub fn char(&self) -> char {
+        self.code() as char
+    }
+
+    // Rows of the glyph from the top, the lowest 5 bits of each row are its pixels with the leftmost pixel as bit 4
+    pub fn bitmap(&self) -> [u8; 8] {
+        match self {
+            Glyph::Pawn => [
+                0b00000,
+                0b00100,
+                0b01110,
+                0b01110,
+                0b00100,
+                0b01110,
+                0b11111,
+                0b00000,
+            ],
+            Glyph::Rook => [
+                0b00000,
+                0b10101,
+                0b11111,
+                0b01110,
+                0b01110,
+                0b01110,
+                0b11111,
+                0b00000,
+            ],
+            Glyph::Knight => [
+                0b00000,
+                0b00110,
+                0b01111,
+                0b11111,
+                0b00111,
+                0b01110,
+                0b11111,
+                0b00000,
+            ],
+            Glyph::Bishop => [
+                0b00100,
+                0b01110,
+                0b01010,
+                0b01110,
+                0b00100,
+                0b01110,
+                0b11111,
+                0b00000,
+            ],
+            Glyph::Queen => [
+                0b00000,
+                0b10101,
+                0b10101,
+                0b11111,
+                0b01110,
+                0b01110,
+                0b11111,
+                0b00000,
+            ],
+            Glyph::King => [
+                0b00100,
+                0b01110,
+                0b00100,
+                0b01110,
+                0b01110,
+                0b01110,
+                0b11111,
+                0b00000,
+            ],
+            Glyph::Hourglass => [
+                0b11111,
+                0b10001,
+                0b01010,
+                0b00100,
+                0b01010,
+                0b10001,
+                0b11111,
+                0b00000,
+            ],
+            Glyph::Battery => [
+                0b01110,
+                0b11011,
+                0b10001,
+                0b10001,
+                0b11111,
+                0b11111,
+                0b11111,
+                0b00000,
+            ],
+        }
+    }
+}
@@ -0,0 +1,380 @@
+// Works out the move a player made on the physical board from the hall sensor readings
+// The hall sensors only show which bits have a piece on them, so a move has to be pieced together from the pieces lifted and placed during the turn
+// Scans are turned into lift and place events, and the changes since the start of the turn are matched against the shapes of every kind of move:
+//   Simple move: one of the player's pieces is lifted and placed on an empty bit (2 bits change)
+//   Capture: the enemy piece and the player's piece are lifted (in either order) and the player's piece is placed where the enemy piece was (1 bit changes)
+//   Castling: the king and rook are both lifted and placed (4 bits change)
+//   En passant: the player's pawn moves to the en passant target and the enemy pawn is lifted (3 bits change)
+// Only the shape of the move is checked here, new_turn checks if the move is legal
+
+use crate::algorithm::Move;
+use crate::bitboard;
+use crate::board::board_representation;
+
+// A change on the physical board between two scans of the hall sensors
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum HallEvent {
+    Lift(usize), // A piece was lifted off the bit
+    Place(usize), // A piece was placed on the bit
+}
+
+// Iterator over the events between two scans, lifts first then places
+// The order of changes within one scan isn't known, and a piece has to be lifted before it can be placed somewhere else
+#[derive(Copy, Clone, Debug)]
+pub struct HallEvents {
+    lifts: bitboard::Bits,
+    places: bitboard::Bits,
+}
+
+impl Iterator for HallEvents {
+    type Item = HallEvent;
+
+    fn next(&mut self) -> Option<HallEvent> {
+        match self.lifts.next() {
+            Some(bit) => Some(HallEvent::Lift(bit)),
+            None => match self.places.next() {
+                Some(bit) => Some(HallEvent::Place(bit)),
+                None => None,
+            },
+        }
+    }
+}
+
+// Returns the events between two scans of the hall sensors
+pub fn hall_events(previous_bitboard: u64, bitboard: u64) -> HallEvents {
+    HallEvents {
+        lifts: bitboard::bits(previous_bitboard & !bitboard),
+        places: bitboard::bits(bitboard & !previous_bitboard),
+    }
+}
+
+// Returns bitboards of the bits every piece of the team to move can legally move to, indexed by the bit of the piece
+// Bits are of the physical board, so the destinations of a lifted piece can be lit straight away
+// Castles are included as the king moving 2 bits, the same way they are entered (see HallTracker::infer_move)
+pub fn legal_destinations(board: &board_representation::Board, player_white: bool, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> [u64; 64] {
+    let mut destinations = [0; 64];
+    for piece_move in crate::board::move_generator::generate_legal_moves(board, pieces_info).as_slice() {
+        let mut initial_bit = piece_move.initial_piece_coordinates.bit;
+        let mut final_bit = piece_move.final_piece_bit;
+        if !player_white {
+            initial_bit = crate::flip_bitboard_bit(initial_bit);
+            final_bit = crate::flip_bitboard_bit(final_bit);
+        }
+
+        destinations[initial_bit] |= 1 << final_bit;
+    }
+    destinations
+}
+
+// Reasons a move couldn't be worked out from the physical board
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum InferenceError {
+    NoMove, // The board is the same as it was at the start of the turn
+    PieceLifted, // One of the player's pieces has been lifted but hasn't been placed yet
+    Unrecognised, // The changes to the board don't make up a move
+}
+
+// Keeps track of the pieces lifted and placed during one turn
+// Bits are of the physical board, which is flipped from the internal board when the player is on the black team
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct HallTracker {
+    start_bitboard: u64, // Hall sensor bitboard at the start of the turn
+    bitboard: u64, // Hall sensor bitboard of the last scan
+    lifted: u64, // Every bit a piece has been lifted from since the start of the turn
+}
+
+impl HallTracker {
+    // Starts tracking a turn from the hall sensor bitboard at the start of it
+    pub fn new(start_bitboard: u64) -> Self {
+        HallTracker {
+            start_bitboard: start_bitboard,
+            bitboard: start_bitboard,
+            lifted: 0,
+        }
+    }
+
+    // Updates the tracker with a new scan of the hall sensors, and returns the events since the last scan
+    // The tracker is updated straight away, so the events don't have to be used
+    pub fn update(&mut self, bitboard: u64) -> HallEvents {
+        let events = hall_events(self.bitboard, bitboard);
+        self.lifted |= self.bitboard & !bitboard;
+        self.bitboard = bitboard;
+        events
+    }
+
+    // Updates the tracker with one event, for events that have already been worked out (e.g. the filtered events of the background scan, see hall_scan.rs)
+    pub fn apply(&mut self, event: HallEvent) {
+        match event {
+            HallEvent::Lift(bit) => {
+                self.lifted |= 1 << bit;
+                self.bitboard &= !(1 << bit);
+            },
+            HallEvent::Place(bit) => self.bitboard |= 1 << bit,
+        }
+    }
+
+    // Returns the hall sensor bitboard of the last scan
+    pub fn bitboard(&self) -> u64 {
+        self.bitboard
+    }
+
+    // Works out the move the player has made since the start of the turn
+    // The board is the internal board before the move, the same orientation as the engine
+    // Returns the move on the physical board, with the board index of the piece on the internal board
+    // Moves are returned without a promotion piece, so pawns promote to queens
+    // The hall sensors only show that a piece is on a bit, not which piece it is, so a player can't choose an underpromotion on the physical board
+    pub fn infer_move(&self, board: &board_representation::Board, player_white: bool) -> Result<Move, InferenceError> {
+        // Converts a bit of the physical board to a bit of the internal board
+        let internal_bit = |bit: usize| -> usize {
+            if player_white {
+                bit
+            } else {
+                crate::flip_bitboard_bit(bit)
+            }
+        };
+
+        // Returns the board index of the player's piece on a physical bit, None if the bit doesn't have one of the player's pieces
+        let friendly_piece = |bit: usize| -> Option<usize> {
+            match board.piece_on(internal_bit(bit)) {
+                Some(index) if crate::board_index_white(index) == board.whites_move => Some(index),
+                _ => None,
+            }
+        };
+
+        let removed = self.start_bitboard & !self.bitboard; // Bits that had a piece at the start of the turn and are now empty
+        let added = self.bitboard & !self.start_bitboard; // Bits that were empty at the start of the turn and now have a piece
+
+        let mut piece_move = Move::new();
+        match (bitboard::count(removed), bitboard::count(added)) {
+            (0, 0) => return Err(InferenceError::NoMove),
+
+            // Simple move
+            (1, 1) => {
+                let initial_bit = bitboard::lsb(removed);
+                match friendly_piece(initial_bit) {
+                    Some(index) => piece_move.initial_piece_coordinates.board_index = index,
+                    None => return Err(InferenceError::Unrecognised),
+                }
+                piece_move.initial_piece_coordinates.bit = initial_bit;
+                piece_move.final_piece_bit = bitboard::lsb(added);
+            },
+
+            // Capture, the captured piece is the enemy piece that was lifted from a bit which is occupied again
+            // If no enemy piece has been swapped out, the player is still holding their piece
+            (1, 0) => {
+                let initial_bit = bitboard::lsb(removed);
+                match friendly_piece(initial_bit) {
+                    Some(index) => piece_move.initial_piece_coordinates.board_index = index,
+                    None => return Err(InferenceError::Unrecognised),
+                }
+
+                let mut captured = 0; // Bits of enemy pieces that were lifted and had a piece placed back on them
+                for bit in bitboard::bits(self.lifted & self.start_bitboard & self.bitboard) {
+                    match board.piece_on(internal_bit(bit)) {
+                        Some(index) if crate::board_index_white(index) != board.whites_move => captured |= 1 << bit,
+                        _ => (),
+                    }
+                }
+
+                match bitboard::count(captured) {
+                    0 => return Err(InferenceError::PieceLifted),
+                    1 => (),
+                    _ => return Err(InferenceError::Unrecognised),
+                }
+                piece_move.initial_piece_coordinates.bit = initial_bit;
+                piece_move.final_piece_bit = bitboard::lsb(captured);
+            },
+
+            // En passant, the player's pawn moves to the en passant target and the enemy pawn is removed
+            (2, 1) => {
+                let final_bit = bitboard::lsb(added);
+                if board.en_passant_target != Some(internal_bit(final_bit)) {
+                    return Err(InferenceError::Unrecognised);
+                }
+
+                // One removed bit has the player's pawn (where the pawn moved from), the other has the enemy pawn that was captured
+                let mut initial_bit = None;
+                let mut enemy_pawn_found = false;
+                for bit in bitboard::bits(removed) {
+                    match board.piece_on(internal_bit(bit)) {
+                        Some(index) if index % 6 == 0 && crate::board_index_white(index) == board.whites_move => {
+                            initial_bit = Some(bit);
+                            piece_move.initial_piece_coordinates.board_index = index;
+                        },
+                        Some(index) if index % 6 == 0 => enemy_pawn_found = true,
+                        _ => (),
+                    }
+                }
+
+                match initial_bit {
+                    Some(bit) if enemy_pawn_found => piece_move.initial_piece_coordinates.bit = bit,
+                    _ => return Err(InferenceError::Unrecognised),
+                }
+                piece_move.final_piece_bit = final_bit;
+            },
+
+            // Castling, the king and rook have both moved
+            // Castles are entered as the king moving 2 bits, the king's final bit is the added bit 2 columns away from where it started
+            // The rook has to be placed on the bit the king moved over
+            (2, 2) => {
+                let mut king = None;
+                let mut rook_found = false;
+                for bit in bitboard::bits(removed) {
+                    match friendly_piece(bit) {
+                        Some(index) if index % 6 == 5 => {
+                            king = Some(bit);
+                            piece_move.initial_piece_coordinates.board_index = index;
+                        },
+                        Some(index) if index % 6 == 1 => rook_found = true,
+                        _ => (),
+                    }
+                }
+
+                let king_bit;
+                match king {
+                    Some(bit) if rook_found => king_bit = bit,
+                    _ => return Err(InferenceError::Unrecognised),
+                }
+
+                let mut final_bit = None;
+                for bit in bitboard::bits(added) {
+                    if bit / 8 == king_bit / 8 && (bit as i8 - king_bit as i8).abs() == 2 && crate::bit_on(added, (bit + king_bit) / 2) {
+                        final_bit = Some(bit);
+                    }
+                }
+
+                match final_bit {
+                    Some(bit) => piece_move.final_piece_bit = bit,
+                    None => return Err(InferenceError::Unrecognised),
+                }
+                piece_move.initial_piece_coordinates.bit = king_bit;
+            },
+
+            _ => return Err(InferenceError::Unrecognised),
+        }
+
+        Ok(piece_move)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Returns the bit of a square on the physical board, which is flipped when the player is on the black team
+    fn physical_bit(square: &str, player_white: bool) -> usize {
+        let bit = crate::notation::square_to_bit(square).unwrap();
+        if player_white {
+            bit
+        } else {
+            crate::flip_bitboard_bit(bit)
+        }
+    }
+
+    // Tracks a turn on the physical board of a FEN, where pieces are lifted from ('l') and placed on ('p') squares
+    fn infer(fen: &str, player_white: bool, events: &[(char, &str)]) -> Result<Move, InferenceError> {
+        let board = board_representation::Board::from_fen(fen).unwrap();
+
+        let mut start_bitboard = board.to_bitboard();
+        if !player_white {
+            start_bitboard = crate::flip_bitboard(start_bitboard);
+        }
+
+        let mut tracker = HallTracker::new(start_bitboard);
+        for &(event, square) in events {
+            let bit = physical_bit(square, player_white);
+            if event == 'l' {
+                tracker.apply(HallEvent::Lift(bit));
+            } else {
+                tracker.apply(HallEvent::Place(bit));
+            }
+        }
+        tracker.infer_move(&board, player_white)
+    }
+
+    // A move of the piece at board_index between two squares, on the physical board
+    fn physical_move(board_index: usize, initial_square: &str, final_square: &str, player_white: bool) -> Result<Move, InferenceError> {
+        let mut piece_move = Move::new();
+        piece_move.initial_piece_coordinates.board_index = board_index;
+        piece_move.initial_piece_coordinates.bit = physical_bit(initial_square, player_white);
+        piece_move.final_piece_bit = physical_bit(final_square, player_white);
+        Ok(piece_move)
+    }
+
+    #[test]
+    fn simple_move() {
+        let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert_eq!(infer(start, true, &[]), Err(InferenceError::NoMove));
+        assert_eq!(infer(start, true, &[('l', "e2")]), Err(InferenceError::PieceLifted));
+        assert_eq!(infer(start, true, &[('l', "e2"), ('p', "e4")]), physical_move(0, "e2", "e4", true));
+
+        // A piece put back where it came from isn't a move
+        assert_eq!(infer(start, true, &[('l', "g1"), ('p', "g1")]), Err(InferenceError::NoMove));
+    }
+
+    #[test]
+    fn capture() {
+        let fen = "4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1";
+        let exd5 = physical_move(0, "e4", "d5", true);
+
+        // The captured piece can be lifted before or after the capturing piece
+        assert_eq!(infer(fen, true, &[('l', "d5"), ('l', "e4"), ('p', "d5")]), exd5);
+        assert_eq!(infer(fen, true, &[('l', "e4"), ('l', "d5"), ('p', "d5")]), exd5);
+
+        // The capturing piece is still held
+        assert_eq!(infer(fen, true, &[('l', "e4")]), Err(InferenceError::PieceLifted));
+
+        // The same capture from scans of the hall sensors, the captured piece is swapped for the capturing piece between two scans
+        let board = board_representation::Board::from_fen(fen).unwrap();
+        let start_bitboard = board.to_bitboard();
+        let mut tracker = HallTracker::new(start_bitboard);
+        tracker.update(start_bitboard & !(1 << 36));
+        tracker.update(start_bitboard & !(1 << 36));
+        assert_eq!(tracker.infer_move(&board, true), Err(InferenceError::PieceLifted));
+        tracker.update(start_bitboard & !(1 << 36) & !(1 << 27));
+        tracker.update(start_bitboard & !(1 << 36));
+        assert_eq!(tracker.infer_move(&board, true), exd5);
+    }
+
+    #[test]
+    fn castling() {
+        let fen = "4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1";
+        assert_eq!(infer(fen, true, &[('l', "e1"), ('l', "h1"), ('p', "g1"), ('p', "f1")]), physical_move(5, "e1", "g1", true));
+        assert_eq!(infer(fen, true, &[('l', "a1"), ('l', "e1"), ('p', "d1"), ('p', "c1")]), physical_move(5, "e1", "c1", true));
+
+        // The rook has to be on the bit the king moved over
+        assert_eq!(infer(fen, true, &[('l', "e1"), ('l', "h1"), ('p', "g1"), ('p', "h2")]), Err(InferenceError::Unrecognised));
+    }
+
+    #[test]
+    fn en_passant() {
+        let fen = "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1";
+        assert_eq!(infer(fen, true, &[('l', "e5"), ('p', "d6"), ('l', "d5")]), physical_move(0, "e5", "d6", true));
+        assert_eq!(infer(fen, true, &[('l', "d5"), ('l', "e5"), ('p', "d6")]), physical_move(0, "e5", "d6", true));
+
+        // Without an en passant target a pawn can't capture this way
+        assert_eq!(infer("4k3/8/8/3pP3/8/8/8/4K3 w - - 0 1", true, &[('l', "e5"), ('p', "d6"), ('l', "d5")]), Err(InferenceError::Unrecognised));
+    }
+
+    // The physical board is flipped for a player on the black team, moves are returned on the physical board
+    #[test]
+    fn black_player() {
+        assert_eq!(infer("4k3/4p3/8/8/8/8/8/4K3 b - - 0 1", false, &[('l', "e7"), ('p', "e5")]), physical_move(6, "e7", "e5", false));
+        assert_eq!(infer("4k3/8/8/3p4/4P3/8/8/4K3 b - - 0 1", false, &[('l', "d5"), ('l', "e4"), ('p', "e4")]), physical_move(6, "d5", "e4", false));
+        assert_eq!(infer("r3k3/8/8/8/8/8/8/4K3 b q - 0 1", false, &[('l', "e8"), ('l', "a8"), ('p', "c8"), ('p', "d8")]), physical_move(11, "e8", "c8", false));
+    }
+
+    #[test]
+    fn unrecognised() {
+        let fen = "4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1";
+
+        // Only the player's pieces can be moved
+        assert_eq!(infer(fen, true, &[('l', "d5"), ('p', "d4")]), Err(InferenceError::Unrecognised));
+
+        // Two of the player's pieces moved
+        assert_eq!(infer(fen, true, &[('l', "e4"), ('p', "e5"), ('l', "e1"), ('p', "e2")]), Err(InferenceError::Unrecognised));
+
+        // A piece was taken off the board without being captured
+        assert_eq!(infer(fen, true, &[('l', "e4"), ('l', "d5")]), Err(InferenceError::Unrecognised));
+    }
+}
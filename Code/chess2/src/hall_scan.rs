@@ -0,0 +1,161 @@
+// Background scanning of the hall sensors, one sensor is read at every step of the led grid's timer interrupt (see embedded::led_grid)
+// The firmware used to read all 64 sensors in one blocking sweep every time it needed the board, now the sensors are read continuously
+// and the main loop just takes the last stable bitboard, or the lift and place events since it last looked
+//
+// Each square is filtered on its own, a change is only accepted once the square's sensor has read the same thing for a number of samples in a row (see Debounce)
+// Sensors bounce as a piece is slid across the board, so without the filter the squares it passes over would show up as pieces being lifted and placed
+// The first sweep after scanning is enabled is accepted straight away, so the board can be read as soon as one sweep has been made
+// Scanning can be turned off (e.g. while the computer is searching) so the interrupt doesn't spend its time shifting out hall sensor selections
+
+use crate::hall_moves::{self, HallEvents};
+
+// Samples in a row a square has to read before a change is accepted, every square is sampled once a sweep
+// Placing is filtered more than lifting, a piece being slid onto a square is often picked up by its neighbours on the way
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Debounce {
+    pub lift_samples: u8, // Samples without a magnet before a piece counts as lifted
+    pub place_samples: u8, // Samples with a magnet before a piece counts as placed
+}
+
+// A sweep takes at most 16ms, so a lift is accepted within 48ms and a place within 64ms
+pub const DEFAULT_DEBOUNCE: Debounce = Debounce {
+    lift_samples: 3,
+    place_samples: 4,
+};
+
+pub struct Scanner {
+    enabled: bool,
+    selected: usize, // Bit of the hall sensor being selected or read
+    pending: bool, // True once the selected sensor has been written to the grid, so it can be read
+    sweep: u64, // Bitboard of the first sweep since scanning was enabled
+    stable: u64, // Bitboard of the accepted readings of every square
+    counts: [u8; 64], // Samples in a row each square has read differently to the stable bitboard
+    debounce: Debounce,
+    reported: u64, // Stable bitboard when the events were last taken
+    settled: bool, // True once a sweep has been made since scanning was enabled
+}
+
+impl Scanner {
+    // Const so the firmware's scanner can be a static, the timer interrupt that reads the sensors uses it
+    pub const fn new() -> Self {
+        Scanner {
+            enabled: false,
+            selected: 0,
+            pending: false,
+            sweep: 0,
+            stable: 0,
+            counts: [0; 64],
+            debounce: DEFAULT_DEBOUNCE,
+            reported: 0,
+            settled: false,
+        }
+    }
+
+    // Starts scanning from the first bit, the stable bitboard is kept until the first sweep replaces it
+    pub fn enable(&mut self) {
+        if self.enabled {
+            return;
+        }
+        self.enabled = true;
+        self.selected = 0;
+        self.pending = false;
+        self.sweep = 0;
+        self.settled = false;
+    }
+
+    // Changes the samples a square has to read before a change is accepted, a threshold of 0 is taken as 1
+    pub fn set_debounce(&mut self, debounce: Debounce) {
+        self.debounce = debounce;
+    }
+
+    pub fn disable(&mut self) {
+        self.enabled = false;
+        self.pending = false;
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    // Returns true once the stable bitboard has been read since scanning was enabled
+    pub fn settled(&self) -> bool {
+        self.settled
+    }
+
+    // Returns the bit of the hall sensor to select when the grid is next written, None while scanning is disabled
+    // The sensor is read by the next sample, once the grid has been written
+    pub fn select(&mut self) -> Option<usize> {
+        if !self.enabled {
+            return None;
+        }
+        self.pending = true;
+        Some(self.selected)
+    }
+
+    // Returns true if a sensor has been selected and is waiting to be read
+    pub fn pending(&self) -> bool {
+        self.pending
+    }
+
+    // Records the reading of the selected sensor and moves on to the next bit
+    // Readings made without a selected sensor are ignored
+    pub fn sample(&mut self, magnet_detected: bool) {
+        if !self.pending {
+            return;
+        }
+        self.pending = false;
+
+        if self.settled {
+            self.filter(self.selected, magnet_detected);
+        } else if magnet_detected {
+            self.sweep |= 1 << self.selected;
+        }
+
+        self.selected += 1;
+        if self.selected < 64 {
+            return;
+        }
+        self.selected = 0;
+
+        // The first sweep is accepted as it is, the filter starts from it
+        if !self.settled {
+            self.stable = self.sweep;
+            self.counts = [0; 64];
+            self.settled = true;
+        }
+    }
+
+    // Accepts the change of a square's reading once it has been read enough times in a row
+    fn filter(&mut self, bit: usize, magnet_detected: bool) {
+        if magnet_detected == crate::bit_on(self.stable, bit) {
+            self.counts[bit] = 0;
+            return;
+        }
+
+        let samples;
+        if magnet_detected {
+            samples = self.debounce.place_samples;
+        } else {
+            samples = self.debounce.lift_samples;
+        }
+
+        self.counts[bit] = self.counts[bit].saturating_add(1);
+        if self.counts[bit] >= samples {
+            self.stable ^= 1 << bit;
+            self.counts[bit] = 0;
+        }
+    }
+
+    // Returns the stable bitboard of the pieces on the board
+    pub fn bitboard(&self) -> u64 {
+        self.stable
+    }
+
+    // Returns the lifts and places of the stable bitboard since the events were last taken
+    // They are filtered, so they can be given to the move inference as they are (see hall_moves::HallTracker::apply)
+    pub fn take_events(&mut self) -> HallEvents {
+        let events = hall_moves::hall_events(self.reported, self.stable);
+        self.reported = self.stable;
+        events
+    }
+}
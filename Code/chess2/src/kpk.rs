@@ -0,0 +1,225 @@
+use crate::board::board_representation;
+
+// King and pawn vs king bitbase
+// Stores if every KPK position is a win for the team with the pawn or a draw, so these endings are played perfectly
+// The table is generated on a host by kpk_builder (std feature), and is compressed so it fits in the flash of the board
+
+// Positions are stored with the pawn team as white, the pawn on the a - d files, and white to move
+// Other positions are mirrored before they are looked up, and positions with black to move are found by searching one move ahead
+pub const KPK_POSITIONS: usize = 24 * 64 * 64;
+
+// Number of positions in every compressed block
+// Every block starts at an offset in KPK_RUNS, so only one block has to be decoded to probe a position
+pub const KPK_BLOCK_SIZE: usize = 1024;
+
+// Compressed table, KPK_BLOCK_OFFSETS and KPK_RUNS
+include!("kpk_table.rs");
+
+// Material keys of the positions the bitbase can be probed for
+const KPK_WHITE_PAWN: u64 = board_representation::signature_key("KPvK");
+const KPK_BLACK_PAWN: u64 = board_representation::signature_key("KvKP");
+
+// Returns the bitbase index of a position with white to move
+// The pawn must be on the a - d files and on rows 1 - 6 (bit 0 is a8)
+// The king bits are ordered along alternating directions on every row, so neighbouring squares are next to each other in the table
+// This makes the runs of wins and draws longer, which compresses the table to a few KB
+pub fn index(white_king: usize, black_king: usize, pawn: usize) -> usize {
+    let pawn_index = (pawn / 8 - 1) * 4 + pawn % 8;
+    (pawn_index * 64 + snake_order(black_king)) * 64 + snake_order(white_king)
+}
+
+// Reverses the files of every odd row
+fn snake_order(bit: usize) -> usize {
+    if (bit / 8) % 2 == 1 {
+        return bit ^ 7;
+    }
+    bit
+}
+
+// Returns a bitboard of the squares a king on a bit attacks
+pub fn king_attacks(bit: usize) -> u64 {
+    let mut attacks = 0;
+    let row = bit as i8 / 8;
+    let file = bit as i8 % 8;
+
+    for row_delta in -1..2 {
+        for file_delta in -1..2 {
+            if row_delta == 0 && file_delta == 0 {
+                continue;
+            }
+
+            let attack_row = row + row_delta;
+            let attack_file = file + file_delta;
+            if (0..8).contains(&attack_row) && (0..8).contains(&attack_file) {
+                attacks |= 1 << (attack_row * 8 + attack_file);
+            }
+        }
+    }
+
+    attacks
+}
+
+// Returns a bitboard of the squares a white pawn on a bit attacks
+pub fn pawn_attacks(bit: usize) -> u64 {
+    let mut attacks = 0;
+    if bit < 8 {
+        return attacks;
+    }
+
+    if bit % 8 > 0 {
+        attacks |= 1 << (bit - 9);
+    }
+    if bit % 8 < 7 {
+        attacks |= 1 << (bit - 7);
+    }
+    attacks
+}
+
+// Returns the value of a position in the compressed table, true if it is a win for white
+fn probe_index(index: usize) -> bool {
+    let block = index / KPK_BLOCK_SIZE;
+    let mut position = index % KPK_BLOCK_SIZE;
+    let mut offset = KPK_BLOCK_OFFSETS[block] as usize;
+
+    // Every block is a list of run lengths, with alternating draw and win runs (starting with a draw run)
+    // Run lengths are stored 7 bits per byte, with the high bit set if more bytes follow
+    let mut win = false;
+    loop {
+        let mut run = 0;
+        let mut shift = 0;
+        loop {
+            let byte = KPK_RUNS[offset];
+            offset += 1;
+            run |= ((byte & 0x7F) as usize) << shift;
+            shift += 7;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+
+        if position < run {
+            return win;
+        }
+        position -= run;
+        win = !win;
+    }
+}
+
+// Probes the bitbase if the board is a KPK endgame
+// Returns Some(true) if the team with the pawn wins, Some(false) if the position is a draw, and None if the board isn't KPK
+pub fn probe(board: &board_representation::Board) -> Option<bool> {
+    use crate::bit_on;
+
+    // Only kings and one pawn can be on the board
+    let material_key = board.material().key();
+    let white_pawn;
+    if material_key == KPK_WHITE_PAWN {
+        white_pawn = true;
+    } else if material_key == KPK_BLACK_PAWN {
+        white_pawn = false;
+    } else {
+        return None;
+    }
+
+    let mut strong_king = board.board[5].trailing_zeros() as usize;
+    let mut weak_king = board.board[11].trailing_zeros() as usize;
+    let mut pawn = board.board[0].trailing_zeros() as usize;
+    let mut strong_to_move = board.whites_move;
+
+    // Mirror the board vertically so the pawn team is white
+    if !white_pawn {
+        let white_king = strong_king;
+        strong_king = weak_king ^ 56;
+        weak_king = white_king ^ 56;
+        pawn = board.board[6].trailing_zeros() as usize ^ 56;
+        strong_to_move = !board.whites_move;
+    }
+
+    // Mirror the board horizontally so the pawn is on the a - d files
+    if pawn % 8 > 3 {
+        strong_king ^= 7;
+        weak_king ^= 7;
+        pawn ^= 7;
+    }
+
+    // A pawn on the last row has promoted, and one on the first row can't exist in a game
+    if !(8..56).contains(&pawn) {
+        return None;
+    }
+
+    if strong_to_move {
+        return Some(probe_index(index(strong_king, weak_king, pawn)));
+    }
+
+    // Black (the team without the pawn) to move
+    // The position is a draw if any black move leads to a draw
+    let mut king_moves = king_attacks(weak_king) & !king_attacks(strong_king) & !pawn_attacks(pawn);
+
+    // The pawn can be captured if it isn't defended
+    if bit_on(king_moves, pawn) && !bit_on(king_attacks(strong_king), pawn) {
+        return Some(false);
+    }
+    king_moves &= !(1 << pawn);
+
+    // Stalemate
+    if king_moves == 0 {
+        return Some(false);
+    }
+
+    while king_moves != 0 {
+        let bit = king_moves.trailing_zeros() as usize;
+        king_moves &= king_moves - 1; // Turn off the lowest bit
+
+        if !probe_index(index(strong_king, bit, pawn)) {
+            return Some(false);
+        }
+    }
+    Some(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn probe_fen(fen: &str) -> Option<bool> {
+        probe(&board_representation::Board::from_fen(fen).unwrap())
+    }
+
+    #[test]
+    fn won_positions() {
+        // A king on the 6th row in front of its pawn wins with either team to move
+        assert_eq!(probe_fen("4k3/8/4K3/4P3/8/8/8/8 w - - 0 1"), Some(true));
+        assert_eq!(probe_fen("4k3/8/4K3/4P3/8/8/8/8 b - - 0 1"), Some(true));
+
+        // The pawn promotes before the king can catch it
+        assert_eq!(probe_fen("8/P7/8/8/8/8/8/K6k w - - 0 1"), Some(true));
+
+        // Black pawn on the h file, the board is mirrored both ways before it is looked up
+        assert_eq!(probe_fen("k7/8/8/8/8/8/7p/K7 b - - 0 1"), Some(true));
+        assert_eq!(probe_fen("7K/8/8/8/8/8/7p/k7 b - - 0 1"), Some(true));
+    }
+
+    #[test]
+    fn drawn_positions() {
+        // The king in front of a rook pawn can't be driven out of the corner
+        assert_eq!(probe_fen("k7/8/8/8/8/8/P7/7K w - - 0 1"), Some(false));
+
+        // Stalemate
+        assert_eq!(probe_fen("4k3/4P3/4K3/8/8/8/8/8 b - - 0 1"), Some(false));
+
+        // The undefended pawn is captured
+        assert_eq!(probe_fen("8/8/8/8/8/8/4Pk2/K7 b - - 0 1"), Some(false));
+
+        // Black king in front of the pawn with the opposition
+        assert_eq!(probe_fen("8/8/8/4k3/8/4K3/4P3/8 w - - 0 1"), Some(false));
+
+        // White takes the undefended black pawn
+        assert_eq!(probe_fen("k7/8/8/8/8/8/6Kp/8 w - - 0 1"), Some(false));
+    }
+
+    #[test]
+    fn not_kpk() {
+        assert_eq!(probe_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"), None);
+        assert_eq!(probe_fen("4k3/8/8/8/8/8/4PP2/4K3 w - - 0 1"), None);
+    }
+}
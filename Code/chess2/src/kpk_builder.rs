@@ -0,0 +1,244 @@
+use std::fmt::Write;
+
+use crate::kpk::{index, king_attacks, pawn_attacks, KPK_BLOCK_SIZE, KPK_POSITIONS};
+
+// Generates the KPK bitbase by retrograde analysis, and compresses it into Rust source for kpk_table.rs
+// Only available with the std feature
+// https://www.chessprogramming.org/KPK
+
+// The generator needs both sides to move, positions with black to move come after the white to move positions
+fn full_index(whites_move: bool, white_king: usize, black_king: usize, pawn: usize) -> usize {
+    let mut side_offset = 0;
+    if !whites_move {
+        side_offset = KPK_POSITIONS;
+    }
+    side_offset + index(white_king, black_king, pawn)
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+enum Outcome {
+    Invalid,
+    Unknown,
+    Draw,
+    Win,
+}
+
+// Classifies a position without looking at any moves
+fn initial_result(whites_move: bool, white_king: usize, black_king: usize, pawn: usize) -> Outcome {
+    use crate::bit_on;
+
+    // Kings on the same square or next to each other, or a piece on the pawn
+    if white_king == black_king || white_king == pawn || black_king == pawn || bit_on(king_attacks(white_king), black_king) {
+        return Outcome::Invalid;
+    }
+
+    // Black is in check when it is whites move
+    if whites_move && bit_on(pawn_attacks(pawn), black_king) {
+        return Outcome::Invalid;
+    }
+
+    if whites_move {
+        // The pawn can promote without being captured
+        let promotion_bit = pawn - 8;
+        if pawn / 8 == 1 && white_king != promotion_bit && black_king != promotion_bit
+            && (!bit_on(king_attacks(black_king), promotion_bit) || bit_on(king_attacks(white_king), promotion_bit)) {
+            return Outcome::Win;
+        }
+    } else {
+        let safe_squares = king_attacks(black_king) & !(king_attacks(white_king) | pawn_attacks(pawn));
+
+        // Stalemate, or the pawn can be captured
+        if safe_squares == 0 || (bit_on(king_attacks(black_king), pawn) && !bit_on(king_attacks(white_king), pawn)) {
+            return Outcome::Draw;
+        }
+    }
+
+    Outcome::Unknown
+}
+
+// Classifies a position from the results of the positions its moves lead to
+fn classify(results: &[Outcome], whites_move: bool, white_king: usize, black_king: usize, pawn: usize) -> Outcome {
+    // Results of every move
+    let mut move_results = Vec::new();
+
+    if whites_move {
+        let mut king_moves = king_attacks(white_king);
+        while king_moves != 0 {
+            let bit = king_moves.trailing_zeros() as usize;
+            king_moves &= king_moves - 1; // Turn off the lowest bit
+            move_results.push(results[full_index(false, bit, black_king, pawn)]);
+        }
+
+        // Pawn pushes, promotions are handled by initial_result
+        let push_bit = pawn - 8;
+        if pawn / 8 > 1 && push_bit != white_king && push_bit != black_king {
+            move_results.push(results[full_index(false, white_king, black_king, push_bit)]);
+
+            let double_push_bit = pawn - 16;
+            if pawn / 8 == 6 && double_push_bit != white_king && double_push_bit != black_king {
+                move_results.push(results[full_index(false, white_king, black_king, double_push_bit)]);
+            }
+        }
+    } else {
+        let mut king_moves = king_attacks(black_king);
+        while king_moves != 0 {
+            let bit = king_moves.trailing_zeros() as usize;
+            king_moves &= king_moves - 1; // Turn off the lowest bit
+            move_results.push(results[full_index(true, white_king, bit, pawn)]);
+        }
+    }
+
+    // White wins if any move wins, black draws if any move draws
+    let good;
+    let bad;
+    if whites_move {
+        good = Outcome::Win;
+        bad = Outcome::Draw;
+    } else {
+        good = Outcome::Draw;
+        bad = Outcome::Win;
+    }
+
+    let mut unknown = false;
+    for result in move_results {
+        if result == good {
+            return good;
+        }
+        if result == Outcome::Unknown {
+            unknown = true;
+        }
+    }
+
+    if unknown {
+        return Outcome::Unknown;
+    }
+    bad
+}
+
+// Returns every position of the bitbase (white to move), true if it is a win for white
+// Invalid positions are None
+pub fn generate() -> Vec<Option<bool>> {
+    let mut results = vec![Outcome::Invalid; KPK_POSITIONS * 2];
+    let mut positions = Vec::new();
+
+    for side_to_move in 0..2 {
+        for row in 1..7 {
+            for file in 0..4 {
+                let pawn = row * 8 + file;
+                for white_king in 0..64 {
+                    for black_king in 0..64 {
+                        let whites_move = side_to_move == 0;
+                        let i = full_index(whites_move, white_king, black_king, pawn);
+                        results[i] = initial_result(whites_move, white_king, black_king, pawn);
+                        positions.push((i, whites_move, white_king, black_king, pawn));
+                    }
+                }
+            }
+        }
+    }
+
+    // Keep classifying the unknown positions until nothing changes
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &(i, whites_move, white_king, black_king, pawn) in &positions {
+            if results[i] != Outcome::Unknown {
+                continue;
+            }
+
+            let result = classify(&results, whites_move, white_king, black_king, pawn);
+            if result != Outcome::Unknown {
+                results[i] = result;
+                changed = true;
+            }
+        }
+    }
+
+    // Only the white to move positions are stored
+    let mut bitbase = Vec::with_capacity(KPK_POSITIONS);
+    for &result in &results[..KPK_POSITIONS] {
+        match result {
+            Outcome::Invalid => bitbase.push(None),
+            Outcome::Win => bitbase.push(Some(true)),
+            // Positions that are still unknown can't be won
+            Outcome::Unknown | Outcome::Draw => bitbase.push(Some(false)),
+        }
+    }
+    bitbase
+}
+
+// Compresses the bitbase into block offsets and run lengths (see kpk::probe_index)
+// Invalid positions are given the value of the position before them, so they extend the current run
+pub fn compress(bitbase: &[Option<bool>]) -> (Vec<u16>, Vec<u8>) {
+    let mut offsets = Vec::new();
+    let mut runs = Vec::new();
+
+    for block in bitbase.chunks(KPK_BLOCK_SIZE) {
+        offsets.push(runs.len() as u16);
+
+        let mut value = false;
+        let mut run = 0;
+        for position in block {
+            let position_value;
+            match position {
+                Some(win) => position_value = *win,
+                None => position_value = value,
+            }
+
+            if position_value != value {
+                push_run(&mut runs, run);
+                value = position_value;
+                run = 0;
+            }
+            run += 1;
+        }
+        push_run(&mut runs, run);
+    }
+
+    (offsets, runs)
+}
+
+// Adds a run length, 7 bits per byte with the high bit set if more bytes follow
+fn push_run(runs: &mut Vec<u8>, mut run: usize) {
+    loop {
+        let byte = (run & 0x7F) as u8;
+        run >>= 7;
+        if run == 0 {
+            runs.push(byte);
+            break;
+        }
+        runs.push(byte | 0x80);
+    }
+}
+
+// Returns the source of kpk_table.rs
+pub fn table_source() -> String {
+    let (offsets, runs) = compress(&generate());
+
+    let mut source = String::new();
+    writeln!(source, "// Generated by kpk_builder, do not edit").unwrap();
+    writeln!(source, "pub const KPK_BLOCK_OFFSETS: [u16; {}] = [", offsets.len()).unwrap();
+    for line in offsets.chunks(16) {
+        let values: Vec<String> = line.iter().map(|offset| offset.to_string()).collect();
+        writeln!(source, "    {},", values.join(", ")).unwrap();
+    }
+    writeln!(source, "];").unwrap();
+    writeln!(source, "pub const KPK_RUNS: [u8; {}] = [", runs.len()).unwrap();
+    for line in runs.chunks(24) {
+        let values: Vec<String> = line.iter().map(|run| run.to_string()).collect();
+        writeln!(source, "    {},", values.join(", ")).unwrap();
+    }
+    writeln!(source, "];").unwrap();
+    source
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // kpk_table.rs has to be regenerated (see bin/kpk_builder.rs) whenever the generator or the compression changes
+    #[test]
+    fn table_matches_builder() {
+        assert_eq!(table_source(), include_str!("kpk_table.rs"));
+    }
+}
@@ -0,0 +1,161 @@
+// Generated by kpk_builder, do not edit
+pub const KPK_BLOCK_OFFSETS: [u16; 96] = [
+    0, 7, 10, 13, 16, 34, 37, 40, 43, 64, 67, 70, 73, 103, 106, 109,
+    112, 120, 129, 132, 135, 168, 189, 192, 195, 240, 269, 272, 275, 325, 360, 363,
+    366, 378, 401, 404, 407, 465, 506, 509, 512, 586, 645, 648, 651, 733, 802, 805,
+    808, 832, 862, 887, 890, 984, 1043, 1080, 1083, 1191, 1264, 1307, 1310, 1437, 1523, 1572,
+    1575, 1611, 1647, 1702, 1705, 1803, 1903, 1972, 1975, 2082, 2195, 2275, 2278, 2387, 2506, 2588,
+    2591, 2627, 2663, 2717, 2720, 2818, 2922, 2993, 2996, 3098, 3215, 3297, 3300, 3368, 3497, 3579,
+];
+pub const KPK_RUNS: [u8; 3582] = [
+    132, 1, 188, 5, 4, 63, 125, 0, 128, 8, 0, 128, 8, 0, 128, 8, 76, 4, 1, 1, 1, 1, 8, 4,
+    96, 132, 5, 11, 5, 110, 2, 8, 8, 44, 0, 128, 8, 0, 128, 8, 0, 128, 8, 2, 126, 11, 6, 1,
+    1, 1, 1, 6, 5, 96, 133, 4, 9, 7, 110, 2, 6, 10, 45, 62, 0, 128, 8, 0, 128, 8, 0, 128,
+    8, 2, 190, 1, 10, 5, 2, 1, 1, 1, 1, 1, 4, 5, 97, 128, 3, 1, 5, 7, 2, 2, 5, 110,
+    2, 4, 5, 5, 2, 45, 125, 0, 128, 8, 0, 128, 8, 0, 128, 8, 145, 1, 2, 45, 128, 5, 192, 1,
+    65, 1, 62, 3, 12, 5, 44, 192, 6, 0, 128, 8, 0, 128, 8, 2, 1, 9, 4, 3, 1, 8, 4, 48,
+    3, 45, 4, 11, 3, 1, 1, 8, 4, 32, 5, 9, 7, 6, 10, 27, 133, 4, 9, 7, 6, 10, 219, 1,
+    0, 4, 8, 8, 8, 4, 96, 3, 12, 5, 11, 1, 32, 4, 10, 7, 9, 5, 29, 128, 6, 0, 128, 8,
+    0, 128, 8, 2, 4, 4, 12, 4, 12, 29, 2, 6, 6, 2, 2, 6, 5, 49, 3, 44, 5, 9, 5, 1,
+    1, 6, 5, 32, 6, 7, 9, 4, 12, 26, 134, 3, 7, 9, 4, 12, 220, 1, 4, 4, 12, 4, 12, 26,
+    0, 6, 4, 12, 4, 6, 1, 5, 27, 4, 6, 10, 6, 5, 96, 4, 10, 7, 9, 2, 32, 5, 8, 9,
+    7, 7, 28, 192, 5, 0, 128, 8, 0, 128, 8, 2, 69, 2, 14, 2, 14, 29, 2, 4, 5, 2, 1, 2,
+    2, 4, 5, 51, 3, 44, 5, 7, 2, 2, 3, 1, 1, 4, 5, 33, 7, 5, 11, 2, 14, 25, 135, 2,
+    5, 11, 2, 14, 220, 1, 4, 2, 14, 2, 14, 27, 62, 0, 64, 1, 6, 2, 14, 2, 7, 2, 5, 27,
+    4, 4, 5, 5, 2, 4, 5, 98, 4, 8, 2, 2, 5, 7, 2, 33, 6, 6, 11, 5, 9, 27, 128, 5,
+    0, 128, 8, 0, 128, 8, 192, 1, 5, 9, 5, 45, 133, 4, 9, 7, 235, 1, 129, 1, 1, 62, 3, 11,
+    7, 9, 2, 32, 132, 4, 8, 9, 9, 7, 27, 3, 10, 7, 58, 5, 109, 0, 128, 8, 0, 128, 8, 2,
+    2, 8, 8, 8, 4, 44, 8, 8, 4, 32, 4, 11, 5, 8, 4, 32, 5, 9, 7, 6, 7, 30, 6, 7,
+    9, 4, 11, 8, 3, 16, 134, 3, 7, 9, 5, 10, 8, 3, 16, 5, 9, 7, 7, 6, 30, 4, 11, 5,
+    11, 1, 98, 2, 8, 8, 8, 3, 33, 192, 1, 3, 11, 7, 10, 3, 30, 4, 9, 9, 7, 7, 9, 3,
+    16, 133, 3, 6, 11, 7, 9, 7, 3, 16, 4, 8, 9, 10, 6, 8, 3, 29, 7, 11, 5, 104, 8, 8,
+    8, 28, 0, 128, 8, 0, 128, 8, 2, 4, 4, 12, 4, 6, 2, 4, 29, 2, 6, 10, 6, 5, 43, 10,
+    6, 5, 32, 5, 9, 7, 6, 5, 32, 6, 7, 9, 4, 9, 29, 7, 5, 11, 2, 13, 6, 4, 16, 135,
+    2, 5, 11, 3, 12, 6, 4, 16, 6, 7, 9, 5, 8, 29, 5, 9, 7, 9, 2, 99, 2, 6, 10, 6,
+    3, 36, 4, 4, 12, 4, 6, 2, 4, 26, 1, 5, 4, 12, 4, 6, 2, 4, 218, 1, 4, 9, 9, 8,
+    5, 29, 5, 7, 11, 5, 9, 7, 4, 16, 134, 2, 4, 13, 5, 11, 5, 4, 16, 5, 6, 11, 8, 8,
+    6, 4, 28, 9, 9, 7, 102, 4, 4, 2, 6, 10, 27, 6, 4, 12, 4, 12, 4, 5, 17, 0, 128, 8,
+    0, 128, 8, 2, 44, 21, 4, 2, 14, 2, 7, 3, 4, 29, 2, 4, 5, 2, 5, 4, 5, 43, 5, 2,
+    5, 4, 5, 34, 5, 7, 2, 2, 5, 4, 5, 33, 7, 5, 11, 2, 11, 28, 39, 4, 5, 16, 152, 1,
+    1, 14, 4, 5, 16, 7, 5, 11, 3, 10, 29, 5, 7, 2, 2, 5, 7, 2, 101, 2, 4, 5, 5, 2,
+    4, 3, 38, 4, 2, 14, 2, 6, 4, 4, 27, 44, 18, 1, 31, 1, 13, 20, 5, 2, 14, 2, 7, 3,
+    4, 217, 1, 5, 7, 11, 6, 7, 28, 6, 5, 27, 5, 5, 16, 135, 1, 2, 31, 3, 5, 16, 6, 4,
+    13, 6, 10, 4, 5, 27, 4, 2, 5, 7, 2, 2, 5, 100, 4, 6, 2, 4, 5, 5, 2, 27, 6, 2,
+    6, 4, 4, 2, 14, 2, 5, 18, 46, 18, 0, 128, 8, 0, 128, 8, 192, 1, 5, 9, 5, 45, 6, 7,
+    7, 8, 5, 31, 134, 3, 7, 9, 6, 5, 31, 5, 9, 7, 235, 1, 192, 1, 3, 11, 7, 9, 3, 31,
+    4, 9, 9, 7, 4, 31, 132, 3, 8, 10, 7, 9, 26, 3, 10, 8, 9, 7, 41, 1, 177, 1, 80, 2,
+    59, 6, 12, 5, 28, 3, 9, 8, 10, 7, 9, 2, 16, 4, 7, 10, 8, 9, 7, 3, 16, 192, 5, 0,
+    128, 8, 2, 2, 8, 8, 8, 4, 2, 2, 40, 8, 8, 4, 32, 4, 11, 5, 8, 6, 30, 5, 9, 7,
+    6, 8, 11, 2, 16, 6, 7, 9, 4, 10, 8, 7, 13, 7, 5, 11, 2, 12, 6, 10, 7, 139, 2, 5,
+    11, 2, 1, 1, 12, 4, 10, 7, 10, 7, 9, 4, 1, 1, 10, 6, 7, 13, 5, 9, 7, 6, 1, 1,
+    8, 9, 2, 16, 4, 11, 5, 8, 1, 1, 6, 57, 1, 1, 4, 31, 2, 8, 8, 8, 3, 1, 4, 28,
+    12, 8, 8, 4, 2, 2, 107, 5, 11, 3, 30, 3, 11, 7, 9, 5, 11, 2, 16, 4, 9, 9, 7, 7,
+    8, 7, 13, 5, 7, 11, 5, 9, 6, 10, 7, 137, 2, 6, 12, 5, 11, 4, 10, 7, 8, 8, 10, 7,
+    9, 6, 7, 26, 8, 9, 7, 9, 2, 208, 1, 16, 4, 8, 8, 8, 4, 96, 3, 12, 5, 11, 1, 28,
+    8, 10, 7, 9, 5, 13, 4, 7, 10, 8, 9, 7, 6, 13, 5, 5, 12, 6, 11, 5, 9, 7, 132, 5,
+    0, 128, 8, 2, 4, 4, 12, 4, 6, 1, 5, 4, 4, 21, 2, 6, 10, 6, 5, 3, 2, 38, 10, 6,
+    5, 32, 5, 9, 7, 6, 8, 29, 6, 7, 9, 4, 10, 9, 3, 16, 7, 5, 11, 2, 12, 6, 9, 12,
+    38, 4, 12, 5, 158, 1, 1, 14, 2, 12, 5, 12, 5, 11, 2, 1, 1, 12, 4, 9, 12, 6, 7, 9,
+    4, 1, 1, 10, 7, 3, 16, 5, 9, 7, 6, 1, 1, 8, 55, 1, 1, 1, 2, 3, 31, 2, 6, 10,
+    6, 3, 1, 6, 29, 4, 4, 12, 4, 5, 1, 6, 4, 4, 18, 1, 5, 4, 12, 4, 6, 1, 5, 4,
+    4, 29, 10, 6, 5, 3, 2, 105, 7, 9, 5, 29, 4, 9, 9, 7, 7, 9, 3, 16, 5, 7, 11, 5,
+    9, 6, 9, 12, 6, 5, 27, 4, 12, 5, 139, 1, 4, 30, 2, 12, 5, 10, 6, 12, 5, 11, 4, 9,
+    24, 10, 7, 9, 7, 3, 218, 1, 5, 3, 4, 4, 12, 4, 4, 18, 10, 12, 4, 12, 4, 6, 1, 5,
+    27, 4, 6, 10, 6, 5, 96, 4, 10, 7, 9, 2, 27, 10, 8, 9, 7, 7, 12, 5, 5, 12, 6, 11,
+    5, 8, 12, 6, 3, 14, 4, 27, 5, 197, 4, 0, 128, 8, 2, 30, 1, 14, 3, 6, 11, 4, 2, 14,
+    2, 7, 2, 5, 2, 4, 23, 2, 4, 5, 2, 5, 4, 5, 5, 2, 36, 5, 2, 5, 4, 5, 34, 5,
+    7, 2, 2, 5, 4, 5, 2, 3, 28, 7, 5, 11, 2, 12, 7, 4, 16, 38, 4, 11, 11, 39, 2, 14,
+    3, 30, 1, 15, 1, 14, 3, 31, 1, 14, 2, 11, 11, 7, 5, 11, 2, 1, 1, 12, 5, 4, 17, 5,
+    7, 2, 2, 5, 4, 1, 1, 3, 2, 5, 53, 1, 1, 1, 4, 3, 31, 2, 4, 5, 5, 2, 4, 3,
+    1, 1, 2, 5, 29, 4, 2, 14, 2, 5, 1, 8, 2, 4, 21, 29, 1, 15, 3, 6, 8, 1, 31, 1,
+    14, 3, 6, 10, 5, 2, 14, 2, 7, 2, 5, 2, 4, 29, 5, 5, 2, 4, 5, 5, 2, 103, 2, 2,
+    5, 7, 2, 2, 3, 28, 5, 7, 11, 5, 9, 7, 4, 16, 6, 5, 27, 4, 11, 11, 7, 3, 29, 2,
+    14, 3, 13, 2, 31, 1, 14, 3, 12, 4, 30, 2, 11, 22, 12, 5, 11, 5, 4, 217, 1, 5, 5, 4,
+    2, 14, 2, 4, 20, 14, 3, 29, 3, 6, 8, 1, 47, 2, 6, 17, 6, 2, 6, 2, 14, 2, 7, 2,
+    5, 27, 4, 4, 5, 5, 2, 4, 5, 98, 4, 8, 2, 2, 5, 7, 2, 27, 12, 6, 11, 5, 9, 11,
+    6, 3, 14, 4, 26, 11, 7, 1, 47, 3, 134, 4, 0, 128, 8, 192, 1, 5, 9, 5, 45, 6, 7, 7,
+    8, 4, 32, 7, 5, 9, 6, 10, 27, 135, 2, 5, 11, 4, 10, 27, 6, 7, 9, 6, 4, 32, 5, 9,
+    7, 235, 1, 206, 1, 7, 9, 2, 32, 4, 9, 9, 7, 3, 32, 5, 7, 11, 5, 9, 27, 133, 2, 6,
+    12, 5, 11, 25, 4, 8, 10, 7, 9, 39, 8, 9, 7, 219, 1, 145, 1, 1, 59, 6, 11, 7, 9, 2,
+    16, 4, 8, 8, 9, 9, 7, 3, 16, 5, 6, 10, 7, 11, 5, 4, 16, 133, 2, 6, 11, 4, 13, 5,
+    11, 9, 4, 8, 9, 6, 11, 7, 9, 23, 7, 8, 9, 9, 7, 27, 3, 10, 7, 58, 5, 93, 0, 128,
+    8, 2, 2, 7, 10, 6, 10, 6, 5, 27, 10, 6, 10, 6, 5, 16, 4, 7, 10, 6, 10, 6, 5, 16,
+    5, 9, 7, 6, 10, 6, 7, 14, 6, 7, 9, 4, 12, 4, 10, 9, 10, 5, 11, 2, 14, 2, 12, 6,
+    59, 4, 174, 1, 2, 12, 4, 13, 5, 11, 2, 14, 4, 10, 6, 11, 7, 9, 4, 12, 6, 8, 9, 8,
+    9, 7, 6, 10, 8, 5, 14, 4, 11, 5, 8, 8, 11, 1, 35, 1, 8, 8, 30, 2, 8, 8, 8, 8,
+    8, 3, 17, 12, 8, 8, 4, 1, 3, 8, 4, 48, 1, 1, 1, 10, 3, 31, 5, 11, 3, 1, 1, 8,
+    4, 30, 7, 9, 5, 1, 1, 6, 7, 14, 4, 9, 9, 7, 7, 1, 1, 4, 10, 9, 8, 7, 11, 5,
+    9, 1, 1, 2, 12, 6, 11, 5, 27, 1, 15, 4, 140, 1, 4, 30, 2, 12, 4, 11, 6, 12, 5, 11,
+    4, 10, 6, 5, 12, 10, 7, 9, 6, 8, 9, 3, 13, 8, 9, 7, 8, 5, 30, 4, 11, 5, 11, 1,
+    98, 2, 8, 8, 8, 3, 17, 208, 1, 3, 11, 7, 10, 3, 26, 8, 9, 9, 7, 7, 9, 8, 6, 10,
+    7, 11, 5, 9, 6, 11, 4, 12, 5, 27, 4, 140, 1, 4, 13, 2, 31, 2, 11, 6, 11, 4, 13, 5,
+    11, 4, 5, 12, 9, 6, 11, 7, 9, 7, 3, 16, 4, 8, 9, 10, 6, 8, 3, 29, 7, 11, 5, 104,
+    8, 8, 8, 12, 0, 128, 8, 2, 4, 4, 12, 4, 12, 4, 6, 2, 4, 13, 2, 5, 12, 4, 12, 4,
+    6, 26, 12, 4, 12, 4, 6, 16, 5, 5, 12, 4, 12, 4, 6, 16, 6, 7, 9, 4, 12, 4, 9, 13,
+    7, 5, 11, 2, 14, 2, 12, 7, 58, 4, 61, 2, 47, 1, 14, 2, 47, 2, 12, 4, 13, 5, 11, 2,
+    14, 4, 10, 7, 10, 7, 9, 4, 12, 6, 7, 13, 5, 9, 7, 6, 10, 9, 2, 32, 5, 6, 10, 30,
+    2, 6, 10, 6, 10, 6, 3, 20, 4, 4, 12, 4, 12, 4, 5, 3, 4, 10, 10, 12, 4, 6, 1, 5,
+    4, 6, 2, 4, 21, 10, 6, 6, 1, 3, 6, 5, 49, 1, 1, 1, 8, 3, 31, 7, 9, 5, 1, 1,
+    6, 5, 29, 9, 7, 7, 1, 1, 4, 9, 13, 5, 7, 11, 5, 9, 1, 1, 2, 12, 7, 10, 5, 27,
+    1, 15, 4, 13, 3, 29, 1, 15, 2, 14, 2, 31, 1, 14, 2, 13, 4, 30, 2, 12, 4, 6, 11, 12,
+    5, 11, 4, 10, 7, 4, 12, 10, 7, 9, 6, 7, 29, 5, 9, 7, 9, 2, 99, 2, 6, 10, 6, 3,
+    28, 5, 3, 4, 4, 12, 4, 5, 3, 4, 10, 17, 5, 4, 12, 4, 6, 2, 4, 218, 1, 4, 9, 9,
+    8, 5, 24, 10, 7, 11, 5, 9, 7, 10, 4, 12, 5, 27, 4, 13, 2, 14, 3, 29, 2, 14, 2, 47,
+    1, 13, 4, 13, 2, 31, 2, 6, 11, 11, 4, 13, 5, 11, 5, 4, 16, 5, 6, 11, 8, 8, 6, 4,
+    28, 9, 9, 7, 102, 4, 4, 2, 6, 10, 27, 6, 4, 12, 4, 12, 4, 5, 1, 0, 128, 8, 2, 46,
+    1, 13, 5, 4, 2, 14, 2, 14, 2, 7, 3, 4, 13, 2, 3, 14, 2, 14, 2, 7, 25, 14, 2, 14,
+    2, 7, 17, 5, 3, 14, 2, 14, 2, 7, 16, 7, 5, 11, 2, 14, 2, 11, 12, 54, 5, 60, 2, 47,
+    1, 14, 2, 47, 2, 12, 5, 12, 5, 11, 2, 14, 4, 9, 13, 5, 7, 2, 2, 5, 4, 5, 2, 5,
+    7, 2, 34, 5, 4, 5, 2, 5, 30, 2, 4, 5, 5, 2, 4, 5, 2, 5, 4, 3, 22, 4, 2, 14,
+    2, 14, 2, 5, 5, 4, 11, 45, 2, 13, 2, 1, 31, 1, 15, 1, 13, 11, 14, 2, 8, 1, 5, 2,
+    7, 3, 4, 19, 5, 5, 2, 4, 5, 2, 1, 1, 3, 4, 5, 51, 1, 1, 1, 6, 3, 31, 2, 2,
+    5, 7, 2, 2, 3, 1, 1, 4, 5, 29, 11, 5, 9, 1, 1, 2, 11, 12, 6, 5, 27, 1, 15, 5,
+    12, 3, 29, 1, 15, 2, 14, 2, 31, 1, 14, 2, 7, 10, 30, 2, 12, 5, 5, 11, 12, 5, 11, 4,
+    9, 29, 5, 7, 2, 2, 5, 7, 2, 101, 2, 4, 5, 5, 2, 4, 3, 28, 5, 5, 4, 2, 14, 2,
+    5, 5, 4, 17, 7, 3, 29, 2, 13, 2, 8, 7, 2, 31, 1, 13, 20, 5, 2, 14, 2, 7, 3, 4,
+    217, 1, 5, 7, 11, 6, 7, 22, 12, 5, 27, 5, 12, 2, 14, 3, 29, 2, 14, 2, 47, 1, 7, 10,
+    13, 2, 31, 3, 5, 16, 6, 4, 13, 6, 10, 4, 5, 27, 4, 2, 5, 7, 2, 2, 5, 100, 4, 6,
+    2, 4, 5, 5, 2, 27, 6, 2, 6, 4, 4, 2, 14, 2, 5, 10, 7, 1, 46, 2, 0, 128, 8, 192,
+    1, 5, 9, 5, 45, 6, 7, 7, 8, 4, 32, 7, 5, 9, 6, 10, 27, 135, 2, 5, 11, 4, 10, 27,
+    6, 7, 9, 6, 4, 32, 5, 9, 7, 235, 1, 206, 1, 7, 9, 2, 32, 4, 9, 9, 7, 3, 32, 5,
+    7, 11, 5, 9, 27, 133, 2, 6, 12, 5, 11, 25, 4, 8, 10, 7, 9, 39, 8, 9, 7, 219, 1, 145,
+    1, 1, 59, 6, 11, 7, 9, 3, 15, 4, 8, 8, 9, 9, 7, 4, 15, 5, 6, 10, 7, 11, 5, 5,
+    15, 133, 2, 6, 12, 2, 14, 5, 11, 9, 4, 8, 10, 4, 12, 7, 9, 23, 8, 6, 10, 9, 7, 27,
+    4, 8, 8, 156, 1, 64, 192, 7, 2, 2, 6, 12, 4, 12, 4, 12, 4, 9, 7, 12, 4, 12, 4, 12,
+    4, 10, 6, 12, 4, 12, 4, 12, 4, 11, 5, 12, 4, 12, 4, 12, 4, 12, 7, 9, 4, 12, 4, 12,
+    4, 13, 5, 11, 2, 14, 2, 14, 2, 255, 1, 2, 13, 5, 11, 2, 14, 2, 14, 4, 11, 7, 9, 4,
+    12, 4, 12, 6, 9, 9, 7, 6, 10, 6, 10, 9, 6, 11, 6, 6, 10, 6, 10, 30, 2, 6, 10, 6,
+    10, 13, 2, 8, 9, 6, 10, 6, 10, 11, 12, 8, 8, 8, 8, 4, 2, 2, 40, 8, 8, 4, 31, 5,
+    11, 5, 8, 6, 28, 7, 9, 7, 6, 8, 11, 2, 13, 9, 7, 9, 4, 10, 8, 9, 7, 11, 5, 11,
+    2, 12, 6, 11, 5, 43, 4, 140, 1, 4, 31, 1, 14, 2, 11, 6, 12, 5, 11, 2, 1, 1, 12, 4,
+    5, 12, 10, 7, 9, 4, 1, 1, 10, 6, 4, 16, 5, 9, 7, 6, 1, 1, 8, 9, 2, 16, 4, 11,
+    5, 8, 1, 1, 6, 57, 1, 1, 4, 31, 2, 8, 8, 8, 3, 1, 4, 12, 28, 8, 8, 4, 2, 2,
+    107, 5, 11, 3, 30, 3, 11, 7, 9, 5, 11, 2, 12, 8, 9, 9, 7, 7, 8, 9, 6, 10, 7, 11,
+    5, 9, 6, 11, 4, 12, 5, 27, 4, 140, 1, 4, 46, 2, 11, 6, 12, 2, 14, 5, 11, 4, 5, 12,
+    10, 4, 12, 7, 9, 6, 4, 16, 5, 6, 10, 9, 7, 9, 66, 128, 1, 0, 128, 8, 2, 4, 3, 14,
+    2, 14, 2, 14, 2, 12, 4, 14, 2, 14, 2, 14, 2, 7, 9, 14, 2, 14, 2, 14, 2, 12, 4, 14,
+    2, 14, 2, 14, 2, 13, 3, 14, 2, 14, 2, 14, 2, 14, 5, 11, 2, 14, 2, 14, 2, 191, 1, 1,
+    63, 2, 13, 5, 11, 2, 14, 2, 14, 4, 11, 7, 9, 4, 12, 4, 12, 7, 8, 9, 8, 4, 12, 4,
+    12, 26, 6, 4, 12, 4, 12, 13, 2, 6, 11, 4, 12, 4, 12, 12, 4, 4, 12, 4, 12, 4, 12, 4,
+    4, 2, 10, 12, 4, 12, 4, 6, 1, 5, 4, 4, 13, 10, 6, 10, 6, 5, 3, 2, 38, 10, 6, 5,
+    30, 7, 9, 7, 6, 8, 26, 9, 7, 9, 4, 10, 9, 3, 12, 11, 5, 11, 2, 12, 6, 11, 5, 43,
+    4, 13, 3, 45, 2, 14, 2, 31, 1, 15, 1, 13, 4, 31, 1, 14, 2, 6, 11, 12, 5, 11, 2, 1,
+    1, 12, 4, 5, 16, 6, 7, 9, 4, 1, 1, 10, 7, 3, 16, 5, 9, 7, 6, 1, 1, 8, 55, 1,
+    1, 1, 2, 3, 31, 2, 6, 10, 6, 3, 1, 6, 29, 4, 4, 12, 4, 5, 1, 6, 4, 4, 2, 17,
+    5, 4, 12, 4, 6, 1, 5, 4, 4, 29, 10, 6, 5, 3, 2, 105, 7, 9, 5, 29, 4, 9, 9, 7,
+    7, 9, 3, 11, 10, 7, 11, 5, 9, 6, 11, 4, 12, 5, 27, 4, 13, 2, 14, 3, 29, 2, 14, 2,
+    47, 1, 13, 4, 46, 2, 6, 11, 12, 2, 14, 5, 11, 4, 5, 16, 6, 4, 12, 7, 9, 7, 67, 144,
+    1, 6, 4, 12, 4, 12, 4, 4, 2, 0, 128, 8, 2, 69, 1, 56, 4, 2, 2, 56, 8, 56, 1, 5,
+    2, 63, 1, 240, 1, 1, 63, 2, 13, 5, 11, 2, 14, 2, 14, 5, 4, 1, 5, 7, 2, 1, 7, 2,
+    14, 2, 14, 25, 7, 2, 14, 2, 14, 13, 2, 4, 5, 1, 7, 2, 14, 2, 14, 12, 4, 2, 14, 2,
+    14, 2, 14, 2, 4, 5, 61, 1, 8, 40, 1, 14, 10, 14, 2, 14, 2, 7, 2, 5, 2, 4, 13, 5,
+    5, 2, 4, 5, 2, 5, 4, 5, 5, 2, 36, 5, 2, 5, 4, 5, 30, 2, 2, 5, 7, 2, 2, 5,
+    4, 5, 2, 3, 24, 11, 5, 11, 2, 12, 7, 4, 11, 43, 4, 13, 3, 45, 2, 14, 2, 31, 1, 15,
+    1, 7, 10, 31, 1, 14, 2, 6, 16, 7, 5, 11, 2, 1, 1, 12, 5, 4, 17, 5, 7, 2, 2, 5,
+    4, 1, 1, 3, 2, 5, 53, 1, 1, 1, 4, 3, 31, 2, 4, 5, 5, 2, 4, 3, 1, 1, 2, 5,
+    29, 4, 2, 14, 2, 5, 1, 8, 2, 4, 11, 7, 3, 29, 1, 15, 1, 8, 7, 2, 31, 1, 14, 19,
+    5, 2, 14, 2, 7, 2, 5, 2, 4, 29, 5, 5, 2, 4, 5, 5, 2, 103, 2, 2, 5, 7, 2, 2,
+    3, 28, 5, 7, 11, 5, 9, 7, 4, 10, 12, 5, 27, 4, 13, 2, 14, 3, 29, 2, 14, 2, 47, 1,
+    7, 10, 46, 2, 6, 16, 7, 2, 14, 5, 11, 5, 68, 144, 1, 7, 2, 14, 2, 14, 2, 4, 11, 7,
+    1, 47, 1, 0, 128, 8,
+];
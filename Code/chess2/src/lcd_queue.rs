@@ -0,0 +1,61 @@
+// Queue of the writes to the character lcd, so printing doesn't have to wait for the lcd
+// The HD44780 takes time to carry out each write (up to 1.52ms for a clear), the firmware used to wait with the delay after every write,
+// which stalled the hall sensor scanning, the clocks and the search while the lcd was being printed to
+// Now the writes are queued and a timer interrupt writes them one at a time, starting the next once the lcd has had time to carry out the last (see embedded::character_lcd)
+
+// A write to the lcd
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Command {
+    pub data_input: bool, // Sets the register select pin, true for characters and cgram rows, false for instructions
+    pub byte: u8,
+    pub wait_us: u16, // Time the lcd takes to carry out the write, the next write can't be made before it's finished
+}
+
+// Most writes the queue can hold, enough to print both rows of the lcd after clearing it twice over
+pub const QUEUE_LEN: usize = 128;
+
+pub struct Queue {
+    commands: [Command; QUEUE_LEN],
+    head: usize, // Index of the oldest write
+    len: usize,
+}
+
+impl Queue {
+    // Const so the firmware's queue can be a static, the timer interrupt that writes to the lcd uses it
+    pub const fn new() -> Self {
+        Queue {
+            commands: [Command { data_input: false, byte: 0, wait_us: 0 }; QUEUE_LEN],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    // Adds a write to the end of the queue, returns an error if the queue is full
+    pub fn push(&mut self, command: Command) -> Result<(), ()> {
+        if self.len == QUEUE_LEN {
+            return Err(());
+        }
+        self.commands[(self.head + self.len) % QUEUE_LEN] = command;
+        self.len += 1;
+        Ok(())
+    }
+
+    // Removes the oldest write from the queue
+    pub fn pop(&mut self) -> Option<Command> {
+        if self.len == 0 {
+            return None;
+        }
+        let command = self.commands[self.head];
+        self.head = (self.head + 1) % QUEUE_LEN;
+        self.len -= 1;
+        Some(command)
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
@@ -0,0 +1,235 @@
+// Text for the 16x2 character lcd that doesn't fit on it at once
+// Rows longer than the lcd are scrolled across it by a Marquee, and screens with more than 2 rows are split into pages that a Pager moves through
+// Neither waits, the firmware ticks the marquees and passes the gestures to the pager in the same loop that reads the button (see show_pages in main.rs)
+
+use crate::menu::Gesture;
+
+// Characters in a row of the lcd
+pub const LCD_COLUMNS: usize = 16;
+
+// Longest row a Row can hold, longer text is cut off
+pub const MAX_ROW: usize = 64;
+
+// Time (ms) between each step of a marquee, and the time it pauses at each end of the row
+const MARQUEE_STEP_MS: u64 = 300;
+const MARQUEE_PAUSE_MS: u64 = 1200;
+
+// A row of text, written with write! so rows can be made without an allocator
+// Only ascii (and the glyph characters, see glyph.rs) can be printed on the lcd, so each character is a byte
+pub struct Row {
+    bytes: [u8; MAX_ROW],
+    len: usize,
+}
+
+impl Row {
+    pub fn new() -> Self {
+        Row {
+            bytes: [0; MAX_ROW],
+            len: 0,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        core::str::from_utf8(&self.bytes[..self.len]).unwrap_or("")
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl core::fmt::Write for Row {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for c in s.chars() {
+            if !c.is_ascii() || self.len == MAX_ROW {
+                continue;
+            }
+            self.bytes[self.len] = c as u8;
+            self.len += 1;
+        }
+        Ok(())
+    }
+}
+
+// Scrolls a row that is longer than the lcd, pausing at its start and end
+// Rows that fit on the lcd never move
+pub struct Marquee {
+    offset: usize, // Index of the first character that is shown
+    max_offset: usize, // Offset that shows the end of the row, 0 if the row fits
+    next_step_ms: u64, // Time the marquee moves next
+}
+
+impl Marquee {
+    // Starts a marquee for a row of len characters, showing the start of the row from now_ms
+    pub fn new(len: usize, now_ms: u64) -> Self {
+        Marquee {
+            offset: 0,
+            max_offset: len.saturating_sub(LCD_COLUMNS),
+            next_step_ms: now_ms + MARQUEE_PAUSE_MS,
+        }
+    }
+
+    // Moves the marquee on, returns true if the part of the row that is shown changed and has to be printed again
+    pub fn tick(&mut self, now_ms: u64) -> bool {
+        if self.max_offset == 0 || now_ms < self.next_step_ms {
+            return false;
+        }
+
+        if self.offset == self.max_offset {
+            // Jump back to the start once the end has been shown
+            self.offset = 0;
+            self.next_step_ms = now_ms + MARQUEE_PAUSE_MS;
+        } else {
+            self.offset += 1;
+            if self.offset == self.max_offset {
+                self.next_step_ms = now_ms + MARQUEE_PAUSE_MS;
+            } else {
+                self.next_step_ms = now_ms + MARQUEE_STEP_MS;
+            }
+        }
+        true
+    }
+
+    // Returns the part of a row that is shown, at most LCD_COLUMNS characters
+    pub fn window<'a>(&self, row: &'a str) -> &'a str {
+        let start = core::cmp::min(self.offset, row.len());
+        let end = core::cmp::min(start + LCD_COLUMNS, row.len());
+        row.get(start..end).unwrap_or("")
+    }
+}
+
+// What a gesture did to a pager
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PagerAction {
+    None,
+    Changed, // The page changed, it has to be printed
+    Exit,
+}
+
+// Moves through pages, a short press moves to the next page, a double press to the previous page and a long press exits
+// The pages wrap around, so the last page is a double press away from the first page
+pub struct Pager {
+    pub page: usize,
+    pub pages: usize,
+}
+
+impl Pager {
+    pub fn new(pages: usize) -> Self {
+        Pager {
+            page: 0,
+            pages: core::cmp::max(pages, 1),
+        }
+    }
+
+    pub fn input(&mut self, gesture: Gesture) -> PagerAction {
+        match gesture {
+            Gesture::Short => self.scroll(1),
+            Gesture::Double => self.scroll(-1),
+            Gesture::Long | Gesture::Hold => PagerAction::Exit,
+            Gesture::Triple => PagerAction::None,
+        }
+    }
+
+    // Moves a number of pages, forwards if steps is positive (e.g. from a rotary encoder, see encoder.rs)
+    pub fn scroll(&mut self, steps: i32) -> PagerAction {
+        if steps == 0 {
+            return PagerAction::None;
+        }
+        self.page = (self.page as i32 + steps).rem_euclid(self.pages as i32) as usize;
+        PagerAction::Changed
+    }
+}
+
+// Returns the number of rows of a move list of a game, one row for each full move (see write_move_row)
+pub fn move_rows(game: &crate::game::Game) -> usize {
+    (game.len() + black_first(game) + 1) / 2
+}
+
+// Writes a row of a move list, the full move number then the white and black moves in standard algebraic notation (e.g. "12. Nbd7 exd5")
+// A game started by the black team starts with "1... " and the black move
+pub fn write_move_row<W: core::fmt::Write>(out: &mut W, game: &crate::game::Game, row: usize, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Result<(), ()> {
+    let full_move_number = game.start().full_move_number() as usize + row;
+    let first_half_move;
+    let last_half_move;
+    if row == 0 && black_first(game) == 1 {
+        write!(out, "{}...", full_move_number).map_err(|_| ())?;
+        first_half_move = 0;
+        last_half_move = 0;
+    } else {
+        write!(out, "{}.", full_move_number).map_err(|_| ())?;
+        first_half_move = row * 2 - black_first(game);
+        last_half_move = first_half_move + 1;
+    }
+
+    // The game is only replayed once for the row, the board before the black move is made from the board before the white move
+    let mut board = game.board_before(first_half_move, pieces_info).ok_or(())?;
+    for half_move in first_half_move..=core::cmp::min(last_half_move, game.len().saturating_sub(1)) {
+        let piece_move = game.piece_move(half_move).ok_or(())?;
+        out.write_str(" ").map_err(|_| ())?;
+        crate::notation::write_san(out, &piece_move, &board, pieces_info)?;
+
+        if half_move < last_half_move {
+            match crate::board::move_generator::make_move(&piece_move.initial_piece_coordinates, piece_move.final_piece_bit, piece_move.promotion, board, pieces_info) {
+                Ok(new_board) => board = new_board,
+                Err(_) => break, // The move ended the game, it was the last move
+            }
+        }
+    }
+    Ok(())
+}
+
+// Returns the number of pages of a move list of a game, 2 rows to a page
+pub fn move_pages(game: &crate::game::Game) -> usize {
+    (move_rows(game) + 1) / 2
+}
+
+// Writes the 2 rows of a page of a move list, the bottom row is left empty on the last page if there is an odd number of rows
+pub fn write_move_page<W: core::fmt::Write>(top: &mut W, bottom: &mut W, game: &crate::game::Game, page: usize, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Result<(), ()> {
+    let row = page * 2;
+    write_move_row(top, game, row, pieces_info)?;
+    if row + 1 < move_rows(game) {
+        write_move_row(bottom, game, row + 1, pieces_info)?;
+    }
+    Ok(())
+}
+
+// Returns 1 if the game started with a black move, the first row of its move list only has one move
+fn black_first(game: &crate::game::Game) -> usize {
+    if game.start().whites_move {
+        0
+    } else {
+        1
+    }
+}
+
+// Writes the last 2 moves of a game in standard algebraic notation with their move numbers, so they fit on one row (e.g. "12.Nf3 Nc6" or "12...Nc6 13.e4")
+// Nothing is written if no moves have been played
+pub fn write_last_moves<W: core::fmt::Write>(out: &mut W, game: &crate::game::Game, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Result<(), ()> {
+    let first_half_move = game.len().saturating_sub(2);
+    let mut board = game.board_before(first_half_move, pieces_info).ok_or(())?;
+
+    for half_move in first_half_move..game.len() {
+        if board.whites_move {
+            write!(out, "{}.", board.full_move_number()).map_err(|_| ())?;
+        } else if half_move == first_half_move {
+            write!(out, "{}...", board.full_move_number()).map_err(|_| ())?;
+        }
+
+        let piece_move = game.piece_move(half_move).ok_or(())?;
+        crate::notation::write_san(out, &piece_move, &board, pieces_info)?;
+
+        if half_move + 1 < game.len() {
+            out.write_str(" ").map_err(|_| ())?;
+            board = crate::board::move_generator::make_move(&piece_move.initial_piece_coordinates, piece_move.final_piece_bit, piece_move.promotion, board, pieces_info).map_err(|_| ())?;
+        }
+    }
+    Ok(())
+}
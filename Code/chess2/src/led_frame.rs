@@ -0,0 +1,68 @@
+// Multiplexing of the led grid, only one led can be on at a time so the lit leds of a frame are shown one after another fast enough that they all look lit
+// The firmware steps a Multiplexer from a timer interrupt (see embedded::led_grid), so the leds stay lit while the main loop is busy (e.g. searching)
+//
+// Frames are double buffered, a new frame is only shown once every led of the frame being shown has had its turn
+// so changing the frame part way through a refresh doesn't leave some leds lit for longer than others
+
+// What the grid has to do next, and the time (us) until the step after
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Step {
+    Light(usize, u32), // Light the led of a bit
+    Dark(bool, u32), // Leave every led off, true if the lit led has to be turned off
+}
+
+pub struct Multiplexer {
+    front: u64, // Frame being shown
+    back: u64, // Frame shown from the start of the next refresh
+    remaining: u64, // Bits of the front frame that haven't been lit yet this refresh
+    lit: bool, // True while a led is on
+}
+
+impl Multiplexer {
+    // Const so the firmware's multiplexer can be a static, the timer interrupt that lights the leds uses it
+    pub const fn new() -> Self {
+        Multiplexer {
+            front: 0,
+            back: 0,
+            remaining: 0,
+            lit: false,
+        }
+    }
+
+    // Sets the bitboard of the leds to light, shown from the start of the next refresh
+    pub fn set_frame(&mut self, frame: u64) {
+        self.back = frame;
+    }
+
+    // Returns the last frame that was set
+    pub fn frame(&self) -> u64 {
+        self.back
+    }
+
+    // Moves to the next step, each lit led gets a slot of slot_us and is on for lit_us of it (the brightness)
+    // The leds are dark for the rest of the slot, and for a whole slot while there are no leds to light
+    pub fn next(&mut self, slot_us: u32, lit_us: u32) -> Step {
+        if self.lit && lit_us < slot_us {
+            self.lit = false;
+            return Step::Dark(true, slot_us - lit_us);
+        }
+
+        // Start the next refresh once every led of the frame has been lit
+        if self.remaining == 0 {
+            self.front = self.back;
+            self.remaining = self.front;
+        }
+
+        if self.remaining == 0 || lit_us == 0 {
+            let turn_off = self.lit;
+            self.lit = false;
+            self.remaining = 0;
+            return Step::Dark(turn_off, slot_us);
+        }
+
+        let bit = self.remaining.trailing_zeros() as usize;
+        self.remaining &= self.remaining - 1;
+        self.lit = true;
+        Step::Light(bit, lit_us)
+    }
+}
@@ -1,16 +1,61 @@
-#![no_std]
-#![no_main]
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "embedded", no_main)]
 
 use core::convert::TryFrom;
 use core::convert::TryInto;
 
 use core::result::Result::Ok;
 use core::result::Result::Err;
-use rtt_target::{rprintln, rtt_init_print};
 
+pub mod log;
 pub mod board;
+pub mod bitboard;
+pub mod square;
+pub mod variant;
+pub mod rays;
+pub mod attacks;
 pub mod piece;
 pub mod algorithm;
+pub mod evaluation;
+pub mod zobrist;
+pub mod bench;
+pub mod opening_book;
+pub mod notation;
+pub mod pgn;
+pub mod game;
+pub mod hall_moves;
+pub mod hall_scan;
+pub mod menu;
+pub mod encoder;
+pub mod sound;
+pub mod glyph;
+pub mod lcd_text;
+pub mod lcd_queue;
+pub mod led_frame;
+pub mod thinking;
+pub mod captures;
+pub mod clock;
+pub mod review;
+pub mod storage;
+pub mod record;
+pub mod settings;
+pub mod statistics;
+pub mod save;
+pub mod uci;
+pub mod console;
+pub mod kpk;
+#[cfg(feature = "nnue")]
+pub mod nnue;
+#[cfg(feature = "std")]
+pub mod polyglot;
+#[cfg(feature = "std")]
+pub mod opening_builder;
+#[cfg(feature = "std")]
+pub mod kpk_builder;
+#[cfg(feature = "std")]
+pub mod tuner;
+#[cfg(all(feature = "std", feature = "nnue"))]
+pub mod nnue_trainer;
 
 // Convert a char of a number to an integer
 // E.g. '1' -> 1
@@ -99,25 +144,17 @@ pub fn bit_on(num: u64, bit: usize) -> bool {
 
 // Returns the number of bits on in the given number
 pub fn bits_on(num: u64) -> usize {
-    let mut bits_on = 0;
-    for i in 0..64 {
-        if bit_on(num, i) {
-            bits_on += 1;
-        }
-    }
-    bits_on
+    bitboard::count(num)
 }
 
 // Function returns when it finds what bit is on in a u64 number
 // E.g. 8 would return 3
 // If no bits are on in the number then default will be returned
 pub fn find_bit_on(num: u64, default: usize) -> usize {
-    for i in 0..64 {
-        if bit_on(num, i) {
-            return i;
-        }
+    if num == 0 {
+        return default;
     }
-    default
+    bitboard::lsb(num)
 }
 
 // Finds the bitboard index for a piece at a given bit
@@ -148,18 +185,22 @@ pub fn flip_bitboard_bit(bit: usize) -> usize {
 
 
 // Flip entire bitboard to enemy team persepctive
+// Every bit moves to 63 - bit (see flip_bitboard_bit), which reverses the order of the bits
 pub fn flip_bitboard(bitboard: u64) -> u64 {
+    bitboard.reverse_bits()
+}
 
-    let mut flipped_bitboard = 0;
-    for bit in 0..64 {
-        let flipped_bit = flip_bitboard_bit(bit);
-
-        if bit_on(bitboard, bit) {
-            flipped_bitboard |= 1 << flipped_bit;
-        }
-    }
+// Flips the rows of a bitboard (a8 becomes a1), every row is one byte so the bytes are swapped
+pub const fn flip_bitboard_vertical(bitboard: u64) -> u64 {
+    bitboard.swap_bytes()
+}
 
-    flipped_bitboard
+// Flips the columns of a bitboard (a8 becomes h8), by reversing the bits of every byte
+// Neighbouring bits are swapped, then pairs of bits, then halves of each byte
+pub const fn flip_bitboard_horizontal(bitboard: u64) -> u64 {
+    let bitboard = ((bitboard >> 1) & 0x5555555555555555) | ((bitboard & 0x5555555555555555) << 1);
+    let bitboard = ((bitboard >> 2) & 0x3333333333333333) | ((bitboard & 0x3333333333333333) << 2);
+    ((bitboard >> 4) & 0x0F0F0F0F0F0F0F0F) | ((bitboard & 0x0F0F0F0F0F0F0F0F) << 4)
 }
 
 // Returns the number of pieces which have been added/removed from a bitboard
@@ -181,15 +222,13 @@ pub fn find_bitboard_move(init_bitboard: u64, final_bitboard: u64, init_board: &
 
     let mut piece_move = algorithm::Move::new();
 
-    for i in 0..64 {
-        if bit_on(change_bitboard, i) {
-            changed_bits += 1;
+    for i in bitboard::bits(change_bitboard) {
+        changed_bits += 1;
 
-            if bit_on(init_bitboard, i) {
-                piece_move.initial_piece_coordinates.bit = i; // Get initial piece bit
-            } else {
-                piece_move.final_piece_bit = i; // Get final piece bit
-            }
+        if bit_on(init_bitboard, i) {
+            piece_move.initial_piece_coordinates.bit = i; // Get initial piece bit
+        } else {
+            piece_move.final_piece_bit = i; // Get final piece bit
         }
     }
     
@@ -250,48 +289,161 @@ impl TeamBitboards {
     }
 }
 
+#[cfg(feature = "embedded")]
 pub mod embedded {
     use super::*;
 
     use embedded_hal::digital::v2::{InputPin, OutputPin};
     use stm32f1xx_hal as hal;
-    use hal::gpio::{Pxx, PushPull, Output, Input, PullDown};
+    use hal::gpio::{Pxx, PushPull, Output, Input, PullDown, PullUp, Floating};
     use hal::{pac::DWT, delay::Delay, prelude::*};
 
+    use core::sync::atomic::{AtomicU8, Ordering};
+
+    // Brightness of the leds as the percent of the time a lit led is on (see settings::LED_BRIGHTNESS_LEVELS)
+    // Kept here so the brightness doesn't have to be passed to everything that lights leds
+    pub static LED_BRIGHTNESS: AtomicU8 = AtomicU8::new(100);
+
+    // How a shift register is driven
+    // Bit banging works with any pins, SPI1 sends the bits in hardware which is much faster (the grid is written for every square of every hall sensor scan)
+    pub enum ShiftBackend {
+        BitBang {
+            clock: Pxx<Output<PushPull>>, // Shift register serial clock pin
+            data: Pxx<Output<PushPull>>, // Shift register serial data pin
+        },
+        Spi(Spi), // SCK (PA5) is the serial clock and MOSI (PA7) the serial data, shared by every shift register driven by SPI
+    }
+
     // Struct for shift register pins
     pub struct ShiftRegister {
-        pub clock: Pxx<Output<PushPull>>, // Shift register serial clock pin
-        pub data: Pxx<Output<PushPull>>, // Shift register serial data pin
-        pub latch: Pxx<Output<PushPull>>, // Shift register data latch pin
-        pub bits: usize, // Shift register bits
+        pub backend: ShiftBackend,
+        pub latch: Pxx<Output<PushPull>>, // Shift register data latch pin, each shift register has its own latch so they can share SPI1
+        pub bits: usize, // Shift register bits, a multiple of 8 for SPI
+    }
+
+    // Shows SPI1 has been set up for the shift registers (see init_spi), only init_spi can make one
+    #[derive(Copy, Clone)]
+    pub struct Spi {
+        _private: (),
+    }
+
+    // Fastest clock (Hz) of SPI1, the 74HC595s shift reliably at this speed at 3.3V
+    const MAX_SPI_HZ: u32 = 10_000_000;
+
+    // Sets up SPI1 to send the bits of the shift registers, pclk2_hz is the clock of APB2
+    // The peripheral and pins are taken so nothing else can use them
+    pub fn init_spi(_spi: hal::pac::SPI1, _sck: hal::gpio::gpioa::PA5<hal::gpio::Alternate<PushPull>>, _mosi: hal::gpio::gpioa::PA7<hal::gpio::Alternate<PushPull>>, pclk2_hz: u32) -> Spi {
+        // The clock of SPI1 is the APB2 clock divided by 2 ^ (divider + 1), the fastest clock up to MAX_SPI_HZ is used (6MHz with a 12MHz APB2 clock)
+        let mut divider = 0;
+        while divider < 7 && pclk2_hz >> (divider + 1) > MAX_SPI_HZ {
+            divider += 1;
+        }
+
+        unsafe {
+            let rcc = &*hal::pac::RCC::ptr();
+            rcc.apb2enr.modify(|r, w| w.bits(r.bits() | 1 << 12)); // Enable the clock of SPI1
+
+            // BIDIMODE and BIDIOE = only send, MSTR = master, SSM and SSI = no slave select pin, SPE = enabled
+            // CPOL and CPHA are 0, the data is set up while the clock is low and shifted in on the rising edge, the same as bit banging
+            let spi = &*hal::pac::SPI1::ptr();
+            spi.cr1.write(|w| w.bits(1 << 15 | 1 << 14 | 1 << 9 | 1 << 8 | 1 << 6 | divider << 3 | 1 << 2));
+        }
+
+        Spi {
+            _private: (),
+        }
     }
 
     impl ShiftRegister {
-        pub fn init(&mut self, delay: &mut Delay) {
-            self.clock.set_low().ok();
-            self.data.set_low().ok();
-            self.latch.set_low().ok();
+        // A shift register driven by bit banging its clock and data pins
+        pub fn bit_bang(clock: Pxx<Output<PushPull>>, data: Pxx<Output<PushPull>>, latch: Pxx<Output<PushPull>>, bits: usize) -> Self {
+            ShiftRegister {
+                backend: ShiftBackend::BitBang {
+                    clock: clock,
+                    data: data,
+                },
+                latch: latch,
+                bits: bits,
+            }
+        }
 
+        // A shift register driven by SPI1, bits has to be a multiple of 8
+        pub fn spi(spi: Spi, latch: Pxx<Output<PushPull>>, bits: usize) -> Self {
+            ShiftRegister {
+                backend: ShiftBackend::Spi(spi),
+                latch: latch,
+                bits: bits,
+            }
+        }
+
+        pub fn init(&mut self, delay: &mut Delay) {
+            self.set_idle();
             self.shift_out(delay, 0, true);
         }
 
+        // Sets the pins low, as they are between writes
+        pub fn set_idle(&mut self) {
+            match &mut self.backend {
+                ShiftBackend::BitBang { clock, data } => {
+                    clock.set_low().ok();
+                    data.set_low().ok();
+                },
+                ShiftBackend::Spi(_) => (),
+            }
+            self.latch.set_low().ok();
+        }
+
         // Shifts given number into a shift register
         fn shift_out(&mut self, delay: &mut Delay, num: u64, msbfirst: bool) {
-            for i in 0..self.bits {
-                
-                // Write bit
-                if !msbfirst {
-                    digital_write(&mut self.data, bit_on(num, i)); 
-                } else {
-                    digital_write(&mut self.data, bit_on(num, (i as i16 - (self.bits as i16 - 1)).abs().try_into().unwrap())); 
-                }
-                
-                delay.delay_us(1u32); // Data hold time
+            self.shift_out_with(num, msbfirst, &mut |micro_seconds| delay.delay_us(micro_seconds));
+        }
+
+        // Shifts given number into a shift register, waiting with wait_us
+        // The delay can't be used from an interrupt, interrupts wait with a busy loop instead (see character_lcd::tick)
+        fn shift_out_with(&mut self, num: u64, msbfirst: bool, wait_us: &mut dyn FnMut(u32)) {
+            let bits = self.bits;
+            match &mut self.backend {
+                ShiftBackend::BitBang { clock, data } => {
+                    for i in 0..bits {
+                        
+                        // Write bit
+                        if !msbfirst {
+                            digital_write(data, bit_on(num, i)); 
+                        } else {
+                            digital_write(data, bit_on(num, (i as i16 - (bits as i16 - 1)).abs().try_into().unwrap())); 
+                        }
+                        
+                        wait_us(1); // Data hold time
+
+                        pulse_pin_with(clock, wait_us, 1); // Bit is shifted into the shift register with a clock pulse
+                    }
+                    wait_us(1); // Data hold time
+                    pulse_pin_with(&mut self.latch, wait_us, 1); // Latch data into internal output register
+                },
+                ShiftBackend::Spi(_) => {
+                    // SPI1 sends the most significant bit of each byte first, so the bits are reversed to send the least significant bit first
+                    let mut num = num;
+                    if !msbfirst {
+                        num = num.reverse_bits() >> (64 - bits);
+                    }
+
+                    // The shift registers on SPI1 all see every bit that is sent, so the lcd's interrupt can't send between these bytes and the latch
+                    let latch = &mut self.latch;
+                    cortex_m::interrupt::free(|_| {
+                        let spi = unsafe { &*hal::pac::SPI1::ptr() };
+                        for i in (0..bits / 8).rev() {
+                            while spi.sr.read().bits() & 1 << 1 == 0 {} // Wait for TXE, room for the next byte
+                            unsafe {
+                                spi.dr.write(|w| w.bits((num >> (i * 8)) as u32 & 0xFF));
+                            }
+                        }
+                        while spi.sr.read().bits() & 1 << 1 == 0 {}
+                        while spi.sr.read().bits() & 1 << 7 != 0 {} // Wait for BSY to clear, the last bit has been shifted in
 
-                pulse_pin(&mut self.clock, delay, 1); // Bit is shifted into the shift register with a clock pulse
+                        pulse_pin_with(latch, wait_us, 1); // Latch data into internal output register
+                    });
+                },
             }
-            delay.delay_us(1u32); // Data hold time
-            pulse_pin(&mut self.latch, delay, 1); // Latch data into internal output register
         }
     }
 
@@ -300,6 +452,11 @@ pub mod embedded {
         millis * clock_mhz * 1000
     }
 
+    // Converts clock cycles to milliseconds
+    pub fn cycles_to_ms(cycles: u64, clock_mhz: u64) -> u64 {
+        cycles / (clock_mhz * 1000)
+    }
+
     // Writes low or high state to given pin
     pub fn digital_write(pin: &mut Pxx<Output<PushPull>>, high: bool) {
         if high {
@@ -320,67 +477,211 @@ pub mod embedded {
     }
 
     // Sets a pin high, waits micro_seconds, sets the pin low, waits micro_seconds
-    fn pulse_pin(pin: &mut Pxx<Output<PushPull>>, delay: &mut Delay, micro_seconds: u32) {
+    fn pulse_pin_with(pin: &mut Pxx<Output<PushPull>>, wait_us: &mut dyn FnMut(u32), micro_seconds: u32) {
         pin.set_high().ok();
-        delay.delay_us(micro_seconds);
+        wait_us(micro_seconds);
         pin.set_low().ok();
-        delay.delay_us(micro_seconds);
+        wait_us(micro_seconds);
     }
     
     // Writes to the led/hall sensor grid shift registers
     // Led/hall can be selected using a bitboard bit
     pub fn write_grid(shift_register: &mut ShiftRegister, delay: &mut Delay, bit: usize, leds_on: bool) {
-        let grid_coordinates = bit_to_cartesian(bit as i8);
+        write_grid_with(shift_register, bit, bit, leds_on, &mut |micro_seconds| delay.delay_us(micro_seconds));
+    }
+
+    // Writes to the led/hall sensor grid shift registers, waiting with wait_us
+    // The led and the hall sensor are selected separately, so a led can be lit while another square's hall sensor is read
+    fn write_grid_with(shift_register: &mut ShiftRegister, led_bit: usize, hall_bit: usize, leds_on: bool, wait_us: &mut dyn FnMut(u32)) {
+        let led_coordinates = bit_to_cartesian(led_bit as i8);
+        let hall_coordinates = bit_to_cartesian(hall_bit as i8);
 
         let mut shift_num: u64 = 0;
-        shift_num += grid_coordinates[1] as u64; // The led y coordinate occupies bits 0,1,2 of the shift register
-        shift_num += (grid_coordinates[0] as u64) << 3; // The led x coordinate occupies bits 3,4,5 of the shift register
+        shift_num += led_coordinates[1] as u64; // The led y coordinate occupies bits 0,1,2 of the shift register
+        shift_num += (led_coordinates[0] as u64) << 3; // The led x coordinate occupies bits 3,4,5 of the shift register
 
         // The hall sensor x and y coordinates occupy the same bits but on the most significant shift register
-        shift_num += shift_num << 8;
+        shift_num += (hall_coordinates[1] as u64) << 8;
+        shift_num += (hall_coordinates[0] as u64) << 11;
 
         // Set bit 7 high to disable leds
         if !leds_on {
             shift_num += 1 << 7;
         }
 
-        shift_register.shift_out(delay, shift_num, true);
+        shift_register.shift_out_with(shift_num, true, wait_us);
     }
 
-    // Turns on leds on the board according to the given bitboard
-    // Simulates turning multiple leds on simultaneously by turning them off and on in quick succesion
-    // When proportional on time is true the led_on_time_us is changed based on how many leds should be turned on
-    pub fn leds_from_bitboard(shift_register: &mut ShiftRegister, delay: &mut Delay, bitboard: u64, mut led_on_time_us: u32, proportional_on_time: bool) {
-        let bits_on = bits_on(bitboard);
+    // The grid is written directly (blocking with the delay) while it's initialised, then led_grid::start hands its shift register and the hall sensor pin to the TIM1 interrupt
+    // Only one led can be on at a time, the interrupt lights the leds of the frame one after another (see led_frame.rs), so they stay lit while the main loop is busy
+    // The interrupt also reads one hall sensor at every step while scanning is enabled (see hall_scan.rs), the sensor for the next step is selected in the same write as the led
+    // The rest of the firmware sets the leds to light with set_frame, and reads the hall sensors through the Grid that start returns
+    pub mod led_grid {
+        use super::*;
+        use core::cell::RefCell;
+        use core::sync::atomic::AtomicU32;
+        use cortex_m::interrupt::Mutex;
+        use crate::led_frame::{Multiplexer, Step};
+        use crate::hall_scan::Scanner;
+        use crate::hall_moves::HallEvents;
+
+        // Time (us) each lit led gets, a full frame of 64 leds is refreshed every 16ms
+        // At least one hall sensor is read in every slot, so a sweep of the board takes at most 16ms too
+        const SLOT_US: u32 = 250;
+
+        // Pins the TIM1 interrupt drives
+        struct Pins {
+            shift_register: ShiftRegister, // Shift register of the led and hall sensor grid
+            hall_sensor: Pxx<Input<Floating>>, // Pin to read value of the selected hall sensor
+        }
+
+        static MULTIPLEXER: Mutex<RefCell<Multiplexer>> = Mutex::new(RefCell::new(Multiplexer::new()));
+        static SCANNER: Mutex<RefCell<Scanner>> = Mutex::new(RefCell::new(Scanner::new()));
+        static PINS: Mutex<RefCell<Option<Pins>>> = Mutex::new(RefCell::new(None));
 
-        if proportional_on_time && bits_on > 0 {
-            led_on_time_us /= bits_on as u32;
+        // Clock cycles in a microsecond, for the busy loop the TIM1 interrupt waits with
+        static CLOCK_MHZ: AtomicU32 = AtomicU32::new(72);
+
+        // Shows the grid's pins have been handed to the TIM1 interrupt, only start can make one
+        pub struct Grid {
+            _private: (),
         }
 
-        for i in 0..64 {
-            if bit_on(bitboard, i) {
-                write_grid(shift_register, delay, i, true); // Turn led on
-                delay.delay_us(led_on_time_us);
-                write_grid(shift_register, delay, i, false); // Turn led off
+        // Hands the grid's shift register and the hall sensor pin to the TIM1 interrupt, and starts refreshing the leds and scanning the hall sensors
+        // timer_hz is the clock of the APB2 timers, the TIM1_UP interrupt has to be unmasked after
+        pub fn start(shift_register: ShiftRegister, hall_sensor: Pxx<Input<Floating>>, _timer: hal::pac::TIM1, timer_hz: u32, clock_mhz: u32) -> Grid {
+            CLOCK_MHZ.store(clock_mhz, Ordering::Relaxed);
+            cortex_m::interrupt::free(|cs| {
+                *PINS.borrow(cs).borrow_mut() = Some(Pins {
+                    shift_register: shift_register,
+                    hall_sensor: hall_sensor,
+                });
+                SCANNER.borrow(cs).borrow_mut().enable();
+            });
+
+            unsafe {
+                let rcc = &*hal::pac::RCC::ptr();
+                rcc.apb2enr.modify(|r, w| w.bits(r.bits() | 1 << 11)); // Enable the clock of TIM1
+
+                // Counts at 1MHz so the period is the time to wait in microseconds
+                // OPM = stop counting at the update, URS = only an overflow interrupts (not setting UG)
+                let timer = &*hal::pac::TIM1::ptr();
+                timer.psc.write(|w| w.bits(timer_hz / 1_000_000 - 1));
+                timer.arr.write(|w| w.bits(SLOT_US - 1));
+                timer.cr1.write(|w| w.bits(1 << 3 | 1 << 2));
+                timer.dier.write(|w| w.bits(1)); // UIE = update interrupt
+                timer.egr.write(|w| w.bits(1)); // UG = load the period
+                timer.cr1.modify(|r, w| w.bits(r.bits() | 1)); // CEN = count
+            }
+
+            Grid {
+                _private: (),
             }
         }
-    }
 
-    // Reads all hall effect sensors on the board, and returns a bitboard
-    pub fn read_board_halls<T: InputPin>(shift_register: &mut ShiftRegister, hall_sensor: &T, delay: &mut Delay) -> u64 {
-        let mut bitboard = 0;
-        
-        for i in 0..64 {
-            write_grid(shift_register, delay, i, false); // Select hall effect sensor to read
-            let magnet_detected = !digital_read(hall_sensor); // Read hall effect sensor
+        // Sets the leds to light, shown from the start of the next refresh of the grid
+        // Doesn't wait for anything, the leds stay lit until the frame is set again (set_frame(0) turns every led off)
+        pub fn set_frame(frame: u64) {
+            cortex_m::interrupt::free(|cs| MULTIPLEXER.borrow(cs).borrow_mut().set_frame(frame));
+        }
+
+        // Turns the background scanning of the hall sensors on or off
+        // Scanning is turned off while the computer is searching, so the interrupt only has to light the leds
+        // read_board_halls turns it back on
+        pub fn set_scanning(scanning: bool) {
+            cortex_m::interrupt::free(|cs| {
+                let mut scanner = SCANNER.borrow(cs).borrow_mut();
+                if scanning {
+                    scanner.enable();
+                } else {
+                    scanner.disable();
+                }
+            });
+        }
+
+        // Changes how many samples in a row a square's hall sensor has to read before a lift or place is accepted
+        pub fn set_debounce(debounce: crate::hall_scan::Debounce) {
+            cortex_m::interrupt::free(|cs| SCANNER.borrow(cs).borrow_mut().set_debounce(debounce));
+        }
+
+        // Reads the hall sensor selected at the last step, then lights the next led of the frame or turns the lit led off, has to be called from the TIM1_UP interrupt
+        pub fn tick() {
+            let timer = unsafe { &*hal::pac::TIM1::ptr() };
+            unsafe {
+                timer.sr.write(|w| w.bits(0)); // Clear the update interrupt
+            }
+
+            cortex_m::interrupt::free(|cs| {
+                let mut pins_ref = PINS.borrow(cs).borrow_mut();
+                let pins;
+                match pins_ref.as_mut() {
+                    Some(grid_pins) => pins = grid_pins,
+                    None => return,
+                }
+
+                // The selected sensor has had the whole step to settle
+                let mut scanner = SCANNER.borrow(cs).borrow_mut();
+                if scanner.pending() {
+                    scanner.sample(!digital_read(&pins.hall_sensor));
+                }
+                let hall_bit = scanner.select();
+
+                let lit_us = SLOT_US * LED_BRIGHTNESS.load(Ordering::Relaxed) as u32 / 100;
+                let step = MULTIPLEXER.borrow(cs).borrow_mut().next(SLOT_US, lit_us);
+
+                // The delay belongs to the main loop, the interrupt waits by counting clock cycles
+                let clock_mhz = CLOCK_MHZ.load(Ordering::Relaxed);
+                let mut wait_us = |micro_seconds| cortex_m::asm::delay(micro_seconds * clock_mhz);
+
+                let next_us;
+                match step {
+                    Step::Light(bit, us) => {
+                        write_grid_with(&mut pins.shift_register, bit, hall_bit.unwrap_or(bit), true, &mut wait_us);
+                        next_us = us;
+                    },
+                    Step::Dark(turn_off, us) => {
+                        // Only write the grid if a led is on or a sensor has to be selected, so an idle grid doesn't keep the interrupt shifting
+                        if turn_off || hall_bit.is_some() {
+                            write_grid_with(&mut pins.shift_register, 0, hall_bit.unwrap_or(0), false, &mut wait_us);
+                        }
+                        next_us = us;
+                    },
+                }
+
+                // Wait until the next step, the update interrupt makes it
+                unsafe {
+                    timer.arr.write(|w| w.bits(next_us - 1));
+                    timer.egr.write(|w| w.bits(1)); // UG = load the period and reset the count
+                    timer.cr1.modify(|r, w| w.bits(r.bits() | 1)); // CEN = count
+                }
+            });
+        }
+
+        // Returns the bitboard of the pieces on the board from the background scan
+        // If scanning was off it's turned on, and this waits for the first sweep of the board (at most 16ms)
+        pub fn read_board_halls(_grid: &mut Grid) -> u64 {
+            set_scanning(true);
+            loop {
+                let bitboard = cortex_m::interrupt::free(|cs| {
+                    let scanner = SCANNER.borrow(cs).borrow();
+                    if scanner.settled() {
+                        Some(scanner.bitboard())
+                    } else {
+                        None
+                    }
+                });
 
-            // If the hall effect sensor is detecting a magenetic field then turn it's bit on
-            if magnet_detected {
-                bitboard |= 1 << i;
+                match bitboard {
+                    Some(bitboard) => return bitboard,
+                    None => (),
+                }
             }
         }
 
-        bitboard
+        // Returns the pieces lifted and placed since the events were last taken
+        pub fn take_events(_grid: &mut Grid) -> HallEvents {
+            cortex_m::interrupt::free(|cs| SCANNER.borrow(cs).borrow_mut().take_events())
+        }
     }
 
     pub mod cycle_counter {
@@ -416,15 +717,92 @@ pub mod embedded {
         }
     }
 
+    // The buttons interrupt on both edges of their pins, the EXTI15_10 interrupt queues every edge with the time it happened (see on_edge)
+    // Button::press reads the queued edges, so presses made while the computer is searching aren't missed and are timed from when they happened
     pub mod button {
         use super::*;
+        use core::cell::RefCell;
+        use cortex_m::interrupt::Mutex;
+
+        // Buttons are on GPIOB pins 10 - 15, the pins that share the EXTI15_10 interrupt
+        pub const FIRST_LINE: u8 = 10;
+        const LINES: usize = 6;
+
+        // Edges that can be queued for each pin before its button is read, bouncing makes a few edges for every press
+        const EDGE_QUEUE_SIZE: usize = 16;
+
+        // Edges of a button pin, the level of the pin after the edge and the DWT cycle count when it happened
+        // The DWT cycle count wraps every 59 seconds at 72MHz, so an edge is only timed correctly if the button is read within 59 seconds of it
+        #[derive(Copy, Clone)]
+        struct EdgeQueue {
+            edges: [(bool, u32); EDGE_QUEUE_SIZE],
+            start: usize,
+            len: usize,
+        }
+
+        static EDGES: Mutex<RefCell<[EdgeQueue; LINES]>> = Mutex::new(RefCell::new([EdgeQueue {
+            edges: [(false, 0); EDGE_QUEUE_SIZE],
+            start: 0,
+            len: 0,
+        }; LINES]));
+
+        // Queues the edges of the button pins, has to be called from the EXTI15_10 interrupt
+        // When a pin's queue is full its last edge is replaced, so the level of the last queued edge is always the level of the pin
+        pub fn on_edge() {
+            let cycles = DWT::cycle_count();
+            let (pending, levels);
+            unsafe {
+                pending = (*hal::pac::EXTI::ptr()).pr.read().bits();
+                levels = (*hal::pac::GPIOB::ptr()).idr.read().bits();
+            }
+
+            for line in 0..LINES {
+                let bit = 1 << (FIRST_LINE as usize + line);
+                if pending & bit == 0 {
+                    continue;
+                }
+                unsafe {
+                    (*hal::pac::EXTI::ptr()).pr.write(|w| w.bits(bit)); // Clear the pending interrupt
+                }
+
+                cortex_m::interrupt::free(|cs| {
+                    let queue = &mut EDGES.borrow(cs).borrow_mut()[line];
+                    let index;
+                    if queue.len < EDGE_QUEUE_SIZE {
+                        index = (queue.start + queue.len) % EDGE_QUEUE_SIZE;
+                        queue.len += 1;
+                    } else {
+                        index = (queue.start + EDGE_QUEUE_SIZE - 1) % EDGE_QUEUE_SIZE;
+                    }
+                    queue.edges[index] = (levels & bit != 0, cycles);
+                });
+            }
+        }
+
+        // Takes the oldest queued edge of a pin
+        fn next_edge(line: u8) -> Option<(bool, u32)> {
+            cortex_m::interrupt::free(|cs| {
+                let queue = &mut EDGES.borrow(cs).borrow_mut()[(line - FIRST_LINE) as usize];
+                if queue.len == 0 {
+                    return None;
+                }
+                let edge = queue.edges[queue.start];
+                queue.start = (queue.start + 1) % EDGE_QUEUE_SIZE;
+                queue.len -= 1;
+                Some(edge)
+            })
+        }
 
         pub struct Button {
-            pub pin: Pxx<Input<PullDown>>, // Button pin
-            pub press_raw: bool,
+            pub pin: Pxx<Input<PullDown>>, // Button pin, set up to interrupt on both edges, it's read by on_edge
+            pub line: u8, // Number of the pin on GPIOB, FIRST_LINE or more
+            pub press_raw: bool, // Level of the button pin after the last edge that was read
             pub press_start_cycle: Option<u64>, // The clock cycle that the button has been held down since
             pub long_press_cycles: u64, // Cycles that must elapse between currenct clock cycle and press_start_cycle for a press to be considered a long press
             pub long_press: bool,
+            pub hold_cycles: u64, // Cycles the button has to be held down for a hold, should be more than long_press_cycles
+            pub hold_sent: bool, // True once gesture has returned a hold for the press the button is held down for
+            pub hold_released: bool, // True when the button was released after being held for hold_cycles, but gesture hasn't returned the hold yet
             pub last_press_cycle: u64, // Processor cycles elapsed when the button was last pressed
             pub debounce_cycles: u64, // Minimum number of processor cycles between button pressed
             pub consecutive_cycles: u64, // After this many cycles have elapsed between the last button press and current button press the press is no longer sequential
@@ -433,85 +811,516 @@ pub mod embedded {
         }
 
         impl Button {
+            // Button on a GPIOB pin (line), with a 50ms debounce, 650ms long presses, 3s holds and presses less than 150ms apart counted as consecutive presses
+            // The times can be changed through the fields
+            pub fn new(pin: Pxx<Input<PullDown>>, line: u8, clock_mhz: u64) -> Self {
+                Button {
+                    pin: pin,
+                    line: line,
+                    press_raw: false,
+                    press_start_cycle: None,
+                    long_press_cycles: ms_to_cycles(650, clock_mhz),
+                    long_press: false,
+                    hold_cycles: ms_to_cycles(3000, clock_mhz),
+                    hold_sent: false,
+                    hold_released: false,
+                    last_press_cycle: 0,
+                    debounce_cycles: ms_to_cycles(50, clock_mhz),
+                    consecutive_cycles: ms_to_cycles(150, clock_mhz),
+                    c_presses: 0,
+                    consecutive_presses: 0,
+                }
+            }
 
             // Returns true only when the button is pressed, so true will not be returned when the button is held down or bouncing
             // This functionality is dependant on the buttons debounce_cycles
+            // Every edge queued since the last call is classified at the cycle it happened, as if the button had been read then
             pub fn press(&mut self, counter: &mut cycle_counter::Counter) -> bool {
                 self.long_press = false;
                 counter.update();
 
                 let mut pressed = false;
-                let pin_high = digital_read(&self.pin);
-                self.press_raw = pin_high;
-                if pin_high {
-
-                    // Update press start cycle
-                    match self.press_start_cycle {
-                        Some(_) => (),
-                        None => self.press_start_cycle = Some(counter.cycles),
+                loop {
+                    let (pin_high, edge_dwt_cycles) = match next_edge(self.line) {
+                        Some(edge) => edge,
+                        None => break,
+                    };
+
+                    // Cycle count of the edge, from how many DWT cycles before the counter was updated it happened
+                    // Edges queued after the counter was updated have wrapped to a huge age, they're taken as happening now
+                    let mut edge_age = counter.last_cycle_count.wrapping_sub(edge_dwt_cycles);
+                    if edge_age > u32::MAX / 2 {
+                        edge_age = 0;
                     }
-                    
-                    // Return true if the button has been pressed and isn't bouncing
-                    if counter.cycles > (self.last_press_cycle + self.debounce_cycles) {
-                        pressed = true;
+                    let edge_cycles = counter.cycles.saturating_sub(edge_age as u64);
+
+                    self.press_raw = pin_high;
+                    if pin_high {
+
+                        // Update press start cycle
+                        match self.press_start_cycle {
+                            Some(_) => (),
+                            None => self.press_start_cycle = Some(edge_cycles),
+                        }
+
+                        // The button has been pressed if it isn't bouncing
+                        if edge_cycles > (self.last_press_cycle + self.debounce_cycles) {
+                            pressed = true;
+                            self.c_presses += 1;
+                        }
+                    } else {
+
+                        // If the button is released check for a long press, or a hold that gesture hasn't seen (e.g. the button was held while the computer was searching)
+                        match self.press_start_cycle {
+                            Some(cycle) => {
+                                let held_cycles = edge_cycles.saturating_sub(cycle);
+                                if held_cycles >= self.hold_cycles {
+                                    if !self.hold_sent {
+                                        self.hold_released = true;
+                                    }
+                                } else if held_cycles >= self.long_press_cycles {
+                                    self.long_press = true;
+                                }
+                            },
+                            None => (),
+                        }
+                        self.press_start_cycle = None; // When the button is not pressed there is no press start cycle
+                        self.hold_sent = false;
                     }
+
+                    // The button was high until this edge, bouncing is timed from the last time it was high
+                    self.last_press_cycle = edge_cycles;
+                }
+
+                // While the button is held it's still pressed now
+                if self.press_raw {
                     self.last_press_cycle = counter.cycles;
-                } else {
+                }
 
-                    // If the button is released check for a long press
-                    match self.press_start_cycle {
-                        Some(cycle) => {
-                            if (counter.cycles - cycle) >= self.long_press_cycles {
-                                self.long_press = true;
-                            }
+                // The consecutive presses have finished once there hasn't been a press for consecutive_cycles
+                if (counter.cycles - self.last_press_cycle) >= self.consecutive_cycles {
+                    self.consecutive_presses = self.c_presses;
+                    self.c_presses = 0;
+                }
+
+                pressed
+            }
+
+            // Returns the gesture the button made (see menu::Gesture), None until a gesture has finished
+            // Holds are returned once the button has been held down for hold_cycles, so the player knows when to let go, and the release isn't another gesture
+            // Long presses are returned when the button is released, short, double and triple presses once the time for another consecutive press has passed
+            // Replaces press, so only one of them should be called in a loop
+            pub fn gesture(&mut self, counter: &mut cycle_counter::Counter) -> Option<crate::menu::Gesture> {
+                self.press(counter);
+
+                // The presses of a hold or long press aren't also short presses
+                let mut hold = self.hold_released;
+                match self.press_start_cycle {
+                    Some(cycle) => {
+                        if self.press_raw && !self.hold_sent && (counter.cycles - cycle) >= self.hold_cycles {
+                            self.hold_sent = true;
+                            hold = true;
+                        }
+                    },
+                    None => (),
+                }
+                if hold {
+                    self.hold_released = false;
+                    self.c_presses = 0;
+                    self.consecutive_presses = 0;
+                    return Some(crate::menu::Gesture::Hold);
+                }
+
+                if self.long_press {
+                    self.c_presses = 0;
+                    self.consecutive_presses = 0;
+                    return Some(crate::menu::Gesture::Long);
+                }
+
+                // The consecutive presses have finished once they have been moved to consecutive_presses
+                // They're cleared once they have been read, so each group of presses is only returned once
+                if self.c_presses == 0 && self.consecutive_presses > 0 {
+                    let presses = self.consecutive_presses;
+                    self.consecutive_presses = 0;
+                    match presses {
+                        1 => return Some(crate::menu::Gesture::Short),
+                        2 => return Some(crate::menu::Gesture::Double),
+                        3 => return Some(crate::menu::Gesture::Triple),
+                        _ => (),
+                    }
+                }
+
+                None
+            }
+        }
+    }
+
+    // Optional rotary encoder (see encoder.rs), its contacts are on GPIOB pins 8 (A) and 9 (B) and interrupt on both edges
+    // The EXTI9_5 interrupt decodes the steps as the contacts change (see on_edge), so steps made while the computer is searching aren't missed
+    pub mod encoder {
+        use super::*;
+        use core::cell::RefCell;
+        use cortex_m::interrupt::Mutex;
+
+        pub const A_LINE: u8 = 8;
+        pub const B_LINE: u8 = 9;
+
+        struct EncoderState {
+            decoder: crate::encoder::Decoder,
+            steps: i32, // Steps made since they were last taken, after acceleration
+            last_step_cycles: u32, // DWT cycle count of the last step
+            fast_cycles: u32, // Steps made less than this many cycles apart are accelerated (see encoder::accelerate)
+        }
+
+        static STATE: Mutex<RefCell<EncoderState>> = Mutex::new(RefCell::new(EncoderState {
+            decoder: crate::encoder::Decoder::new(),
+            steps: 0,
+            last_step_cycles: 0,
+            fast_cycles: 0,
+        }));
+
+        // Decodes a change of the contacts, has to be called from the EXTI9_5 interrupt
+        pub fn on_edge() {
+            let cycles = DWT::cycle_count();
+            let levels;
+            unsafe {
+                (*hal::pac::EXTI::ptr()).pr.write(|w| w.bits(1 << A_LINE | 1 << B_LINE)); // Clear the pending interrupts
+                levels = (*hal::pac::GPIOB::ptr()).idr.read().bits();
+            }
+
+            cortex_m::interrupt::free(|cs| {
+                let mut state = STATE.borrow(cs).borrow_mut();
+                let step = state.decoder.update(levels & 1 << A_LINE != 0, levels & 1 << B_LINE != 0);
+                if step != 0 {
+                    // The DWT cycle count wraps, but the gap between steps that are accelerated is much shorter than the time it takes to wrap
+                    let elapsed_cycles = cycles.wrapping_sub(state.last_step_cycles);
+                    state.steps += crate::encoder::accelerate(step, elapsed_cycles as u64, state.fast_cycles as u64);
+                    state.last_step_cycles = cycles;
+                }
+            });
+        }
+
+        pub struct Encoder {
+            pub a: Pxx<Input<PullUp>>, // Contact pins, set up to interrupt on both edges, they're read by on_edge
+            pub b: Pxx<Input<PullUp>>,
+        }
+
+        impl Encoder {
+            // Steps made less than 60ms apart are accelerated
+            pub fn new(a: Pxx<Input<PullUp>>, b: Pxx<Input<PullUp>>, clock_mhz: u64) -> Self {
+                cortex_m::interrupt::free(|cs| {
+                    STATE.borrow(cs).borrow_mut().fast_cycles = ms_to_cycles(60, clock_mhz) as u32;
+                });
+
+                Encoder {
+                    a: a,
+                    b: b,
+                }
+            }
+
+            // Returns the steps made since the last call, positive clockwise
+            pub fn steps(&mut self) -> i32 {
+                cortex_m::interrupt::free(|cs| {
+                    let mut state = STATE.borrow(cs).borrow_mut();
+                    let steps = state.steps;
+                    state.steps = 0;
+                    steps
+                })
+            }
+        }
+    }
+
+    // Piezo buzzer on PA0, driven by TIM2 with PWM, it plays the sounds of sound.rs
+    // Sounds are played by TIM3's interrupt, which ticks the player and changes the tone (see tick), so play returns straight away
+    pub mod buzzer {
+        use super::*;
+        use core::cell::RefCell;
+        use core::sync::atomic::AtomicBool;
+        use cortex_m::interrupt::Mutex;
+        use hal::gpio::{gpioa::PA0, Alternate};
+        use crate::sound::{Player, Sound};
+
+        // Sounds are only played while this is true (see settings::Settings::sound)
+        // Kept here so the setting doesn't have to be passed to everything that plays a sound
+        pub static SOUND_ON: AtomicBool = AtomicBool::new(true);
+
+        // Time (ms) between ticks of the player, shorter than the shortest note
+        const TICK_MS: u16 = 10;
+
+        // TIM2 counts at 1MHz, so the period of a tone is its number of counts
+        const TONE_COUNT_HZ: u32 = 1_000_000;
+
+        static PLAYER: Mutex<RefCell<Player>> = Mutex::new(RefCell::new(Player::new()));
+
+        // Sets up TIM2 to drive the buzzer and TIM3 to tick the player, timer_hz is the clock of the APB1 timers
+        // The timers and pin are taken so nothing else can use them, the TIM3 interrupt has to be unmasked after
+        pub fn init(_tone_timer: hal::pac::TIM2, _tick_timer: hal::pac::TIM3, _pin: PA0<Alternate<PushPull>>, timer_hz: u32) {
+            unsafe {
+                let rcc = &*hal::pac::RCC::ptr();
+                rcc.apb1enr.modify(|r, w| w.bits(r.bits() | 0b11)); // Enable the clocks of TIM2 and TIM3
+
+                // PWM mode 1 on channel 1, the output is high until the count reaches the compare value
+                // The compare value is 0 while nothing is playing, so the buzzer is off
+                let tone_timer = &*hal::pac::TIM2::ptr();
+                tone_timer.psc.write(|w| w.bits(timer_hz / TONE_COUNT_HZ - 1));
+                tone_timer.ccmr1_output().write(|w| w.bits(0b110 << 4 | 1 << 3)); // OC1M = PWM mode 1, OC1PE = preload the compare value
+                tone_timer.ccr1.write(|w| w.bits(0));
+                tone_timer.ccer.write(|w| w.bits(1)); // CC1E = channel 1 output enabled
+                tone_timer.cr1.write(|w| w.bits(1 << 7 | 1)); // ARPE = preload the period, CEN = count
+
+                // Update interrupt every TICK_MS, counting at 10kHz
+                let tick_timer = &*hal::pac::TIM3::ptr();
+                tick_timer.psc.write(|w| w.bits(timer_hz / 10_000 - 1));
+                tick_timer.arr.write(|w| w.bits(TICK_MS as u32 * 10 - 1));
+                tick_timer.dier.write(|w| w.bits(1)); // UIE = update interrupt
+                tick_timer.cr1.write(|w| w.bits(1)); // CEN = count
+            }
+        }
+
+        // Starts playing a sound, replacing the sound that is playing, does nothing while the sound is off
+        pub fn play(sound: Sound) {
+            if !SOUND_ON.load(Ordering::Relaxed) {
+                return;
+            }
+            cortex_m::interrupt::free(|cs| PLAYER.borrow(cs).borrow_mut().play(sound));
+        }
+
+        // Moves the playing sound on, has to be called from the TIM3 interrupt
+        pub fn tick() {
+            unsafe {
+                (*hal::pac::TIM3::ptr()).sr.write(|w| w.bits(0)); // Clear the update interrupt
+            }
+
+            let frequency_hz = cortex_m::interrupt::free(|cs| PLAYER.borrow(cs).borrow_mut().tick(TICK_MS));
+            match frequency_hz {
+                Some(frequency_hz) => set_tone(frequency_hz),
+                None => (),
+            }
+        }
+
+        // Plays a square wave with a 50% duty cycle, or turns the buzzer off for a frequency of 0
+        fn set_tone(frequency_hz: u16) {
+            let period;
+            if frequency_hz == 0 {
+                period = 0;
+            } else {
+                period = TONE_COUNT_HZ / frequency_hz as u32;
+            }
+
+            unsafe {
+                let tone_timer = &*hal::pac::TIM2::ptr();
+                if period > 0 {
+                    tone_timer.arr.write(|w| w.bits(period - 1));
+                }
+                tone_timer.ccr1.write(|w| w.bits(period / 2));
+            }
+        }
+    }
+
+    // Buttons of the board, registered by what they do (see menu::ButtonRole), and the rotary encoder if the board has one
+    // Only the main button has to be fitted, the gestures of the other buttons are mapped to what the main button would do (see menu::menu_gesture and menu::game_action)
+    // Turning the encoder scrolls (see scroll), everything it does can also be done with gestures
+    pub mod input {
+        use super::*;
+        use crate::menu::{self, ButtonRole, Gesture, GameAction};
+
+        pub struct Input {
+            buttons: [Option<button::Button>; menu::BUTTON_ROLES.len()], // Indexed by ButtonRole
+            encoder: Option<encoder::Encoder>,
+        }
+
+        const NO_BUTTON: Option<button::Button> = None;
+
+        impl Input {
+            pub fn new(main: button::Button) -> Self {
+                let mut input = Input {
+                    buttons: [NO_BUTTON; menu::BUTTON_ROLES.len()],
+                    encoder: None,
+                };
+                input.register(ButtonRole::Main, main);
+                input
+            }
+
+            // Adds a button, replacing the button that had the role
+            pub fn register(&mut self, role: ButtonRole, button: button::Button) {
+                self.buttons[role as usize] = Some(button);
+            }
+
+            pub fn register_encoder(&mut self, encoder: encoder::Encoder) {
+                self.encoder = Some(encoder);
+            }
+
+            // Returns the steps the encoder has been turned since the last call, positive clockwise, 0 without an encoder
+            pub fn scroll(&mut self) -> i32 {
+                match self.encoder.as_mut() {
+                    Some(encoder) => encoder.steps(),
+                    None => 0,
+                }
+            }
+
+            // Returns the next gesture of any button, and the button that made it
+            // Only one gesture is returned at a time, the edges of the other buttons stay queued until the next call
+            pub fn event(&mut self, counter: &mut cycle_counter::Counter) -> Option<(ButtonRole, Gesture)> {
+                for (index, role) in menu::BUTTON_ROLES.iter().enumerate() {
+                    match self.buttons[index].as_mut() {
+                        Some(button) => match button.gesture(counter) {
+                            Some(gesture) => return Some((*role, gesture)),
+                            None => (),
                         },
                         None => (),
                     }
-                    self.press_start_cycle = None; // When the button is not pressed there is no press start cycle
                 }
+                None
+            }
 
-                // Detect consecutive presses
-                if (counter.cycles - self.last_press_cycle) < self.consecutive_cycles {
-                    if pressed {
-                        self.c_presses += 1;
+            // Returns the next gesture as the gesture the main button would make in the menus (see menu::menu_gesture)
+            // Replaces press, so only one of them should be called in a loop
+            pub fn gesture(&mut self, counter: &mut cycle_counter::Counter) -> Option<Gesture> {
+                loop {
+                    match self.event(counter) {
+                        Some((role, gesture)) => match menu::menu_gesture(role, gesture) {
+                            Some(gesture) => return Some(gesture),
+                            None => (), // Buttons without a use in the menus are skipped
+                        },
+                        None => return None,
                     }
-                } else {
-                    self.consecutive_presses = self.c_presses;
-                    self.c_presses = 0;
                 }
+            }
+
+            // Returns the action of the next gesture during a game (see menu::game_action)
+            pub fn game_action(&mut self, counter: &mut cycle_counter::Counter, whites_move: bool) -> Option<GameAction> {
+                loop {
+                    match self.event(counter) {
+                        Some((role, gesture)) => match menu::game_action(role, gesture, whites_move) {
+                            Some(action) => return Some(action),
+                            None => (),
+                        },
+                        None => return None,
+                    }
+                }
+            }
 
+            // Returns true if any button was pressed (see Button::press)
+            pub fn press(&mut self, counter: &mut cycle_counter::Counter) -> bool {
+                let mut pressed = false;
+                for button in self.buttons.iter_mut() {
+                    match button {
+                        Some(button) => pressed |= button.press(counter),
+                        None => (),
+                    }
+                }
                 pressed
             }
+
+            // Returns true if any button is held down, as of the last press or gesture
+            pub fn held(&self) -> bool {
+                self.buttons.iter().any(|button| match button {
+                    Some(button) => button.press_raw,
+                    None => false,
+                })
+            }
         }
     }
 
+    // The lcd is written to directly (blocking with the delay) while it's initialised, then start_queue hands its pins to the TIM4 interrupt
+    // After that every write is queued (see lcd_queue.rs), TIM4 is a one shot timer that fires once the lcd has carried out the last write, and its interrupt makes the next write
+    // So printing only waits if the queue is full, the methods still take the delay so the blocking writes and the queued writes have the same API
     pub mod character_lcd {
         use super::*;
+        use core::cell::RefCell;
+        use core::sync::atomic::AtomicU32;
+        use cortex_m::interrupt::Mutex;
+        use crate::lcd_queue::{Command, Queue};
 
-        pub struct Lcd {
+        // Pins of the lcd, the lcd's 8 bit bus is driven by a shift register
+        pub struct Pins {
             pub shift_register: ShiftRegister, // Shift register connecting to character lcd
             pub register_select: Pxx<Output<PushPull>>, // Register select pin
         }
 
+        impl Pins {
+            // Sets the register select pin and shifts a byte onto the lcd's bus, wait_us waits between the edges of the shift register's pins
+            fn write(&mut self, data_input: bool, data: u8, wait_us: &mut dyn FnMut(u32)) {
+                digital_write(&mut self.register_select, data_input); // Set data_input / instruction input
+                self.shift_register.shift_out_with(data as u64, true, wait_us);
+            }
+        }
+
+        // Shortest time (us) between writes, the lcd carries out most instructions and character writes in 37us
+        const MIN_WAIT_US: u32 = 40;
+
+        static QUEUE: Mutex<RefCell<Queue>> = Mutex::new(RefCell::new(Queue::new()));
+        static PINS: Mutex<RefCell<Option<Pins>>> = Mutex::new(RefCell::new(None));
+
+        // Clock cycles in a microsecond, for the busy loop the TIM4 interrupt waits with
+        static CLOCK_MHZ: AtomicU32 = AtomicU32::new(72);
+
+        pub struct Lcd {
+            pins: Option<Pins>, // Pins while the lcd is written to directly, None once they have been handed to the TIM4 interrupt
+        }
+
         // Instructions derived from various datasheets
         // https://www.sparkfun.com/datasheets/LCD/ADM1602K-NSW-FBS-3.3v.pdf
         // https://www.openhacks.com/uploadsproductos/eone-1602a1.pdf
         // Non 1602 lcds might have different instructions, and definitely different ddram addresses
         impl Lcd {
+            pub fn new(pins: Pins) -> Self {
+                Lcd {
+                    pins: Some(pins),
+                }
+            }
 
             // Writes a byte to a character lcd, data_input sets register select pin
-            pub fn write(&mut self, delay: &mut Delay, write_sleep_ms: u32, data_input: bool, data: u8) {
-                digital_write(&mut self.register_select, data_input); // Set data_input / instruction input
-                
-                self.shift_register.shift_out(delay, data as u64, true);
+            // wait_us is the time the lcd takes to carry out the write, the next write isn't made before then
+            pub fn write(&mut self, delay: &mut Delay, wait_us: u32, data_input: bool, data: u8) {
+                match &mut self.pins {
+                    Some(pins) => {
+                        pins.write(data_input, data, &mut |micro_seconds| delay.delay_us(micro_seconds));
+                        delay.delay_us(wait_us); // Ensure there is time inbetween character lcd writes
+                    },
+                    None => {
+                        let command = Command {
+                            data_input: data_input,
+                            byte: data,
+                            wait_us: core::cmp::max(wait_us, MIN_WAIT_US) as u16,
+                        };
+
+                        // Wait for the interrupt to make room if the queue is full
+                        while cortex_m::interrupt::free(|cs| QUEUE.borrow(cs).borrow_mut().push(command)).is_err() {}
+
+                        // Start the writes if TIM4 isn't already waiting for the lcd (see tick)
+                        cortex_m::peripheral::NVIC::pend(hal::pac::Interrupt::TIM4);
+                    },
+                }
+            }
 
-                delay.delay_us(write_sleep_ms); // Ensure there is time inbetween character lcd writes
+            // Hands the pins to the TIM4 interrupt, every write after this is queued
+            // Called once the lcd has been initialised, timer_hz is the clock of the APB1 timers, the TIM4 interrupt has to be unmasked after
+            pub fn start_queue(&mut self, _timer: hal::pac::TIM4, timer_hz: u32, clock_mhz: u32) {
+                CLOCK_MHZ.store(clock_mhz, Ordering::Relaxed);
+
+                let pins = self.pins.take();
+                cortex_m::interrupt::free(|cs| *PINS.borrow(cs).borrow_mut() = pins);
+
+                unsafe {
+                    let rcc = &*hal::pac::RCC::ptr();
+                    rcc.apb1enr.modify(|r, w| w.bits(r.bits() | 1 << 2)); // Enable the clock of TIM4
+
+                    // Counts at 1MHz so the period is the time to wait in microseconds
+                    // OPM = stop counting at the update, URS = only an overflow interrupts (not setting UG)
+                    let timer = &*hal::pac::TIM4::ptr();
+                    timer.psc.write(|w| w.bits(timer_hz / 1_000_000 - 1));
+                    timer.cr1.write(|w| w.bits(1 << 3 | 1 << 2));
+                    timer.dier.write(|w| w.bits(1)); // UIE = update interrupt
+                }
             }
 
             // Initialze character lcd
             pub fn init(&mut self, delay: &mut Delay) {
-                self.shift_register.clock.set_low().ok();
-                self.shift_register.latch.set_low().ok();
+                match &mut self.pins {
+                    Some(pins) => pins.shift_register.set_idle(),
+                    None => (),
+                }
 
                 self.write(delay, 1, false, 0b00111000); // Initialize lcd with 8-bit bus, 2 lines, and 5x8 dot format
 
@@ -541,8 +1350,7 @@ pub mod embedded {
 
             // Clear display
             pub fn clear(&mut self, delay: &mut Delay) {
-                self.write(delay, 700, false, 0b00000001);
-                delay.delay_ms(1u16);
+                self.write(delay, 1700, false, 0b00000001);
             }
 
             // Home cursor
@@ -586,11 +1394,311 @@ pub mod embedded {
             }
 
             // Prints a string to the lcd
+            // Glyphs can be printed in strings with their characters (see glyph::Glyph::char), once they have been registered
             pub fn print(&mut self, delay: &mut Delay, string: &str) {
                 for c in string.chars() {
                     self.write(delay, 1, true, c as u8);
                 }
             }
+
+            // Sets cgram address, the address of the custom character data that is written next
+            pub fn set_cgram(&mut self, delay: &mut Delay, cgram_address: u8) {
+                let mut write_byte: u8 = 0b01000000;
+                write_byte ^= cgram_address & 0b00111111;
+
+                self.write(delay, 1, false, write_byte);
+            }
+
+            // Draws a glyph into cgram, so its character code prints it
+            // Writing cgram moves the address away from ddram, the cursor has to be set before printing again
+            pub fn define_glyph(&mut self, delay: &mut Delay, glyph: crate::glyph::Glyph) {
+                self.set_cgram(delay, glyph.code() << 3); // Each glyph takes 8 bytes of cgram
+                for row in glyph.bitmap().iter() {
+                    self.write(delay, 1, true, *row);
+                }
+            }
+
+            // Draws every glyph into cgram, cgram is lost when the lcd loses power so this has to be done after init
+            pub fn register_glyphs(&mut self, delay: &mut Delay) {
+                for glyph in crate::glyph::GLYPHS.iter() {
+                    self.define_glyph(delay, *glyph);
+                }
+                self.home(delay);
+            }
+
+            // Prints a registered glyph at the cursor
+            pub fn print_glyph(&mut self, delay: &mut Delay, glyph: crate::glyph::Glyph) {
+                self.write(delay, 1, true, glyph.code());
+            }
+        }
+
+        // Makes the next queued write once the lcd has carried out the last, has to be called from the TIM4 interrupt
+        // The interrupt is also pended by Lcd::write, so it can be called while TIM4 is still waiting, then it does nothing
+        pub fn tick() {
+            let timer = unsafe { &*hal::pac::TIM4::ptr() };
+            unsafe {
+                timer.sr.write(|w| w.bits(0)); // Clear the update interrupt
+            }
+
+            // CEN is cleared by the update at the end of the wait (one pulse mode)
+            if timer.cr1.read().bits() & 1 != 0 {
+                return;
+            }
+
+            cortex_m::interrupt::free(|cs| {
+                let command;
+                match QUEUE.borrow(cs).borrow_mut().pop() {
+                    Some(next) => command = next,
+                    None => return,
+                }
+
+                // The delay belongs to the main loop, the interrupt waits by counting clock cycles
+                let clock_mhz = CLOCK_MHZ.load(Ordering::Relaxed);
+                match PINS.borrow(cs).borrow_mut().as_mut() {
+                    Some(pins) => pins.write(command.data_input, command.byte, &mut |micro_seconds| cortex_m::asm::delay(micro_seconds * clock_mhz)),
+                    None => return,
+                }
+
+                // Wait for the lcd to carry out the write, the update interrupt makes the next write
+                unsafe {
+                    timer.arr.write(|w| w.bits(command.wait_us as u32 - 1));
+                    timer.egr.write(|w| w.bits(1)); // UG = load the period and reset the count
+                    timer.cr1.modify(|r, w| w.bits(r.bits() | 1)); // CEN = count
+                }
+            });
         }
     }
-}
\ No newline at end of file
+
+    // Storage in flash (see storage.rs), the last 3 1KB pages of the 64KB flash
+    // The linker is kept out of these pages by memory.x
+    pub mod flash_storage {
+        use stm32f1xx_hal::flash::FlashWriter;
+
+        // Offsets of the regions from the start of flash
+        pub const RECORDS_OFFSET: u32 = 61 * 1024; // Records of the settings and statistics (see record.rs)
+        pub const SAVE_OFFSET: u32 = 62 * 1024; // Saved game (see save.rs)
+
+        pub struct Flash<'a> {
+            pub writer: FlashWriter<'a>, // Writer made with 1KB sectors and 64KB of flash
+        }
+
+        impl<'a> Flash<'a> {
+            // Returns the region of the saved game
+            pub fn save<'b>(&'b mut self) -> FlashRegion<'a, 'b> {
+                FlashRegion {
+                    writer: &mut self.writer,
+                    offset: SAVE_OFFSET,
+                    size: crate::save::SAVE_SIZE,
+                }
+            }
+
+            // Returns the region of the records
+            pub fn records<'b>(&'b mut self) -> FlashRegion<'a, 'b> {
+                FlashRegion {
+                    writer: &mut self.writer,
+                    offset: RECORDS_OFFSET,
+                    size: crate::record::RECORDS_SIZE,
+                }
+            }
+        }
+
+        // A region of flash, offsets are from the start of the region
+        pub struct FlashRegion<'a, 'b> {
+            writer: &'b mut FlashWriter<'a>,
+            offset: u32,
+            size: usize,
+        }
+
+        impl<'a, 'b> crate::storage::Storage for FlashRegion<'a, 'b> {
+            fn read(&self, offset: usize, buffer: &mut [u8]) -> Result<(), ()> {
+                if offset + buffer.len() > self.size {
+                    return Err(());
+                }
+                let bytes = self.writer.read(self.offset + offset as u32, buffer.len()).map_err(|_| ())?;
+                buffer.copy_from_slice(bytes);
+                Ok(())
+            }
+
+            fn write(&mut self, offset: usize, data: &[u8]) -> Result<(), ()> {
+                if offset + data.len() > self.size {
+                    return Err(());
+                }
+                self.writer.write(self.offset + offset as u32, data).map_err(|_| ())
+            }
+
+            fn erase(&mut self) -> Result<(), ()> {
+                self.writer.erase(self.offset, self.size).map_err(|_| ())
+            }
+        }
+    }
+
+    // Line based serial port on USART1 (PA9 is tx, PA10 is rx), used by the UCI mode (see uci.rs)
+    // Received bytes are moved into a queue by the USART1 interrupt (see receive), so bytes sent while the engine is searching aren't lost
+    // Writing blocks until every byte has been sent
+    pub mod serial {
+        use core::cell::RefCell;
+        use core::sync::atomic::{AtomicBool, Ordering};
+        use cortex_m::interrupt::Mutex;
+        use stm32f1xx_hal::pac::{self, USART1};
+        use stm32f1xx_hal::serial::{Rx, Tx};
+        use stm32f1xx_hal::prelude::*;
+
+        // Bytes that can be received before the firmware reads them, the USART1 interrupt drops bytes once the queue is full
+        const QUEUE_SIZE: usize = 256;
+
+        struct Queue {
+            bytes: [u8; QUEUE_SIZE],
+            start: usize,
+            len: usize,
+            line_start: [u8; 4], // First bytes of the line being received, to find stop commands
+            line_len: usize,
+        }
+
+        // The receiver is moved here when the serial port is created, so the interrupt can read it
+        static RX: Mutex<RefCell<Option<Rx<USART1>>>> = Mutex::new(RefCell::new(None));
+        static QUEUE: Mutex<RefCell<Queue>> = Mutex::new(RefCell::new(Queue {
+            bytes: [0; QUEUE_SIZE],
+            start: 0,
+            len: 0,
+            line_start: [0; 4],
+            line_len: 0,
+        }));
+
+        pub struct Serial {
+            tx: Tx<USART1>,
+            line: crate::uci::LineBuffer,
+        }
+
+        impl Serial {
+            // Creates the serial port from the split USART1, and starts the USART1 interrupt
+            pub fn new(tx: Tx<USART1>, mut rx: Rx<USART1>) -> Self {
+                rx.listen();
+                cortex_m::interrupt::free(|cs| RX.borrow(cs).replace(Some(rx)));
+                unsafe {
+                    cortex_m::peripheral::NVIC::unmask(pac::Interrupt::USART1);
+                }
+
+                Serial {
+                    tx: tx,
+                    line: crate::uci::LineBuffer::new(),
+                }
+            }
+
+            // Returns the next line that has been received, None if a whole line hasn't been received yet
+            pub fn read_line(&mut self) -> Option<&str> {
+                loop {
+                    let byte = cortex_m::interrupt::free(|cs| {
+                        let mut queue = QUEUE.borrow(cs).borrow_mut();
+                        if queue.len == 0 {
+                            return None;
+                        }
+                        let byte = queue.bytes[queue.start];
+                        queue.start = (queue.start + 1) % QUEUE_SIZE;
+                        queue.len -= 1;
+                        Some(byte)
+                    });
+
+                    match byte {
+                        Some(byte) => {
+                            // The line is read again after the loop, a borrow returned from inside the loop would outlive it
+                            if self.line.push(byte).is_some() {
+                                break;
+                            }
+                        },
+                        None => return None,
+                    }
+                }
+                self.line.line()
+            }
+        }
+
+        impl core::fmt::Write for Serial {
+            fn write_str(&mut self, s: &str) -> core::fmt::Result {
+                for byte in s.bytes() {
+                    while self.tx.write(byte).is_err() {}
+                }
+                Ok(())
+            }
+        }
+
+        // Moves a received byte into the queue, has to be called from the USART1 interrupt
+        // A line of "stop" also sets stop, so a search can be stopped while the firmware is busy searching and not reading lines
+        pub fn receive(stop: &AtomicBool) {
+            cortex_m::interrupt::free(|cs| {
+                let byte;
+                match RX.borrow(cs).borrow_mut().as_mut() {
+                    Some(rx) => match rx.read() {
+                        Ok(received) => byte = received,
+                        Err(_) => return,
+                    },
+                    None => return,
+                }
+
+                let mut queue = QUEUE.borrow(cs).borrow_mut();
+                if queue.len < QUEUE_SIZE {
+                    let end = (queue.start + queue.len) % QUEUE_SIZE;
+                    queue.bytes[end] = byte;
+                    queue.len += 1;
+                }
+
+                match byte {
+                    b'\n' | b'\r' => {
+                        if queue.line_len == 4 && &queue.line_start == b"stop" {
+                            stop.store(true, Ordering::Relaxed);
+                        }
+                        queue.line_len = 0;
+                    },
+                    _ => {
+                        if queue.line_len < 4 {
+                            let line_len = queue.line_len;
+                            queue.line_start[line_len] = byte;
+                        }
+                        queue.line_len += 1;
+                    },
+                }
+            });
+        }
+    }
+}
+
+// Host stand-ins for the parts of the embedded module used by the engine, so the engine can be built without the embedded feature
+#[cfg(not(feature = "embedded"))]
+pub mod embedded {
+
+    // Converts milliseconds to clock cycles
+    pub fn ms_to_cycles(millis: u64, clock_mhz: u64) -> u64 {
+        millis * clock_mhz * 1000
+    }
+
+    pub mod cycle_counter {
+
+        // With the std feature cycles are the nanoseconds since the counter was created (a 1000MHz clock)
+        // Without it there is no clock to read, so cycles is the number of times the counter has been updated
+        #[derive(Debug)]
+        pub struct Counter {
+            pub cycles: u64, // Total cycle count
+            #[cfg(feature = "std")]
+            start: std::time::Instant,
+        }
+
+        impl Counter {
+            pub fn new() -> Self {
+                Counter {
+                    cycles: 0,
+                    #[cfg(feature = "std")]
+                    start: std::time::Instant::now(),
+                }
+            }
+
+            #[cfg(feature = "std")]
+            pub fn update(&mut self) {
+                self.cycles = self.start.elapsed().as_nanos() as u64;
+            }
+
+            #[cfg(not(feature = "std"))]
+            pub fn update(&mut self) {
+                self.cycles += 1;
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+// Logging over the debugger, so the firmware can print messages without depending on how they are sent
+// The rtt feature formats the messages on the board and prints them with rtt_target
+// The defmt feature sends defmt frames instead, the messages are formatted on the PC from the format strings kept in the elf, which is much smaller and faster than formatting them on the board
+// With neither feature the messages are dropped
+// Messages are written with log!, its format strings have to work with both core::fmt and defmt (e.g. "{}" and "{:x}")
+
+#[cfg(all(feature = "rtt", feature = "defmt"))]
+compile_error!("Only one of the rtt and defmt features can be enabled");
+
+// Links the defmt logger, which sends the frames over RTT
+#[cfg(feature = "defmt")]
+use defmt_rtt as _;
+
+#[cfg(feature = "rtt")]
+pub use rtt_target;
+
+// Starts logging, has to be called once before anything is logged
+pub fn init() {
+    #[cfg(feature = "rtt")]
+    rtt_target::rtt_init_print!();
+}
+
+// Logs a message on its own line
+#[cfg(feature = "rtt")]
+#[macro_export]
+macro_rules! log {
+    ($($argument:tt)*) => {
+        $crate::log::rtt_target::rprintln!($($argument)*)
+    };
+}
+
+#[cfg(feature = "defmt")]
+#[macro_export]
+macro_rules! log {
+    ($($argument:tt)*) => {
+        defmt::println!($($argument)*)
+    };
+}
+
+// The arguments are still borrowed when messages are dropped, so values that are only logged aren't unused
+#[cfg(not(any(feature = "rtt", feature = "defmt")))]
+#[macro_export]
+macro_rules! log {
+    ($format:literal $(, $argument:expr)* $(,)?) => {{
+        $(let _ = &$argument;)*
+    }};
+}
+
+// Longest line a LogWriter logs at once, longer lines are split
+const MAX_LOG_LINE: usize = 96;
+
+// Writes text to the log, so anything that writes to a core::fmt::Write (e.g. GameRecord::write_pgn) can be logged
+// The text is logged a line at a time, defmt frames can't be joined into one line on the PC
+// Text after the last line break is only logged by flush
+pub struct LogWriter {
+    line: [u8; MAX_LOG_LINE],
+    len: usize,
+}
+
+impl LogWriter {
+    pub fn new() -> Self {
+        LogWriter {
+            line: [0; MAX_LOG_LINE],
+            len: 0,
+        }
+    }
+
+    // Logs the text that hasn't been logged yet
+    pub fn flush(&mut self) {
+        if self.len > 0 {
+            self.log_line();
+        }
+    }
+
+    fn log_line(&mut self) {
+        // Lines are only split between characters, so the bytes are always valid UTF-8
+        let line = core::str::from_utf8(&self.line[..self.len]).unwrap_or("");
+        crate::log!("{}", line);
+        self.len = 0;
+    }
+}
+
+impl core::fmt::Write for LogWriter {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        for c in s.chars() {
+            if c == '\n' {
+                self.log_line();
+                continue;
+            }
+
+            if self.len + c.len_utf8() > MAX_LOG_LINE {
+                self.log_line();
+            }
+            c.encode_utf8(&mut self.line[self.len..]);
+            self.len += c.len_utf8();
+        }
+        Ok(())
+    }
+}
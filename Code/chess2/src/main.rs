@@ -4,23 +4,94 @@
 use panic_halt as _;
 
 use cortex_m_rt::entry;
-use embedded_hal::digital::v2::InputPin;
 use stm32f1xx_hal as hal;
-use hal::{pac, delay::Delay, prelude::*};
+use hal::{pac, pac::interrupt, delay::Delay, gpio::{Edge, ExtiPin}, prelude::*};
 
 use arrform::{arrform, ArrForm};
 
-use rtt_target::{rprintln, rtt_init_print};
+use chess2::log;
 
 
 use chess2::board::board_representation;
 use chess2::algorithm;
 use chess2::embedded;
+use chess2::menu::{ButtonRole, GameAction};
+use chess2::sound::Sound;
+use chess2::glyph::Glyph;
+use chess2::lcd_text::{Marquee, Pager, PagerAction, Row};
+
+use core::fmt::Write;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+// Set to stop the computer searching for a move, can be set from interrupt handlers
+static STOP_SEARCH: AtomicBool = AtomicBool::new(false);
+
+// Time (ms) a move is lit for in demo mode before the computer makes the next move, if nobody makes it on the board
+const DEMO_MOVE_TIMEOUT_MS: u64 = 10000;
+
+// Most time (ms) each search of the post game review can take, the review searches every move twice so this is kept short
+const REVIEW_SEARCH_MS: u64 = 500;
+
+// True if the grid and lcd shift registers are wired to SPI1 (see embedded::ShiftBackend), false for the original bit banged wiring
+const SPI_SHIFT_REGISTERS: bool = false;
+
+// Samples in a row a square's hall sensor has to read before a piece counts as lifted or placed (see hall_scan::Debounce)
+// Boards with sensors that bounce more (e.g. reed switches) need more samples, each sample of a square is up to 16ms apart
+const HALL_DEBOUNCE: chess2::hall_scan::Debounce = chess2::hall_scan::Debounce {
+    lift_samples: 3,
+    place_samples: 4,
+};
+
+// Time (ms) the second row of the lcd shows the team to move or the captured pieces for, before switching to the other during the player's turn
+const STATUS_ROTATE_MS: u64 = 3000;
+
+// Moves the bytes received by the serial port into its queue, and stops a search when the UCI stop command is received (see embedded::serial)
+#[interrupt]
+fn USART1() {
+    embedded::serial::receive(&STOP_SEARCH);
+}
+
+// Queues the edges of the button pins with the time they happened, so presses made while the computer is searching aren't missed (see embedded::button)
+#[interrupt]
+fn EXTI15_10() {
+    embedded::button::on_edge();
+}
+
+// Plays the sounds of the buzzer (see embedded::buzzer)
+#[interrupt]
+fn TIM3() {
+    embedded::buzzer::tick();
+}
+
+// Makes the next queued write to the lcd (see embedded::character_lcd)
+#[interrupt]
+fn TIM4() {
+    embedded::character_lcd::tick();
+}
+
+// Lights the next led of the frame (see embedded::led_grid)
+#[interrupt]
+fn TIM1_UP() {
+    embedded::led_grid::tick();
+}
+
+// Decodes the steps of the rotary encoder as its contacts change (see embedded::encoder)
+#[interrupt]
+fn EXTI9_5() {
+    embedded::encoder::on_edge();
+}
+
+// Sets up a button or encoder pin to interrupt on both of its edges
+fn enable_edge_interrupts<P: ExtiPin>(pin: &mut P, afio: &mut hal::afio::Parts, exti: &pac::EXTI) {
+    pin.make_interrupt_source(afio);
+    pin.trigger_on_edge(exti, Edge::RISING_FALLING);
+    pin.enable_interrupt(exti);
+}
 
 #[entry]
 fn main() -> ! {
     // Init buffers for debug printing
-    rtt_init_print!();
+    chess2::log::init();
 
     // Get access to device and core peripherals
     let dp = pac::Peripherals::take().unwrap();
@@ -29,7 +100,7 @@ fn main() -> ! {
     // Get access to RCC, FLASH, AFIO, and GPIO
     let mut rcc = dp.RCC.constrain();
     let mut flash = dp.FLASH.constrain();
-    //let mut afio = dp.AFIO.constrain(&mut rcc.apb2);
+    let mut afio = dp.AFIO.constrain(&mut rcc.apb2);
     let mut gpioa = dp.GPIOA.split(&mut rcc.apb2);
     let mut gpiob = dp.GPIOB.split(&mut rcc.apb2);
 
@@ -51,318 +122,860 @@ fn main() -> ! {
     // Set up systick delay
     let mut delay = Delay::new(cp.SYST, clocks);
 
+    // Storage in the last pages of flash, for the settings and the saved game
+    let mut flash_storage = embedded::flash_storage::Flash {
+        writer: flash.writer(hal::flash::SectorSize::Sz1K, hal::flash::FlashSize::Sz64K),
+    };
+
     // Enable cycle counter
     cp.DCB.enable_trace();
     cp.DWT.enable_cycle_counter();
 
     let mut cycle_counter = embedded::cycle_counter::Counter::new();
 
+    // Shift registers of the hall and led grid and the character lcd, driven by SPI1 or bit banged (see SPI_SHIFT_REGISTERS)
+    // Both share SCK (PA5) and MOSI (PA7) when they use SPI1, the grid latch is PA4 and the lcd latch is PB0 on either wiring
+    let mut grid_sr;
+    let lcd_sr;
+    if SPI_SHIFT_REGISTERS {
+        let spi = embedded::init_spi(dp.SPI1, gpioa.pa5.into_alternate_push_pull(&mut gpioa.crl), gpioa.pa7.into_alternate_push_pull(&mut gpioa.crl), clocks.pclk2().0);
+        grid_sr = embedded::ShiftRegister::spi(spi, gpioa.pa4.into_push_pull_output(&mut gpioa.crl).downgrade(), 16);
+        lcd_sr = embedded::ShiftRegister::spi(spi, gpiob.pb0.into_push_pull_output(&mut gpiob.crl).downgrade(), 8);
+    } else {
+        grid_sr = embedded::ShiftRegister::bit_bang(
+            gpioa.pa3.into_push_pull_output(&mut gpioa.crl).downgrade(),
+            gpioa.pa5.into_push_pull_output(&mut gpioa.crl).downgrade(),
+            gpioa.pa4.into_push_pull_output(&mut gpioa.crl).downgrade(),
+            16,
+        );
+        lcd_sr = embedded::ShiftRegister::bit_bang(
+            gpiob.pb1.into_push_pull_output(&mut gpiob.crl).downgrade(),
+            gpioa.pa7.into_push_pull_output(&mut gpioa.crl).downgrade(),
+            gpiob.pb0.into_push_pull_output(&mut gpiob.crl).downgrade(),
+            8,
+        );
+    }
+
     // Initialise hall and led grid shift register
-    let mut grid_sr = embedded::ShiftRegister {
-        clock: gpioa.pa3.into_push_pull_output(&mut gpioa.crl).downgrade(),
-        data: gpioa.pa5.into_push_pull_output(&mut gpioa.crl).downgrade(),
-        latch: gpioa.pa4.into_push_pull_output(&mut gpioa.crl).downgrade(),
-        bits: 16,
-    };
     grid_sr.init(&mut delay);
     embedded::write_grid(&mut grid_sr, &mut delay, 0, false); // Initialise grid with leds off
 
     // Initialise character lcd
-    let mut lcd = embedded::character_lcd::Lcd {
-        shift_register: embedded::ShiftRegister {
-            clock: gpiob.pb1.into_push_pull_output(&mut gpiob.crl).downgrade(),
-            data: gpioa.pa7.into_push_pull_output(&mut gpioa.crl).downgrade(),
-            latch: gpiob.pb0.into_push_pull_output(&mut gpiob.crl).downgrade(),
-            bits: 8,
-        },
+    let mut lcd = embedded::character_lcd::Lcd::new(embedded::character_lcd::Pins {
+        shift_register: lcd_sr,
         register_select: gpiob.pb2.into_push_pull_output(&mut gpiob.crl).downgrade(),
-    };
+    });
     lcd.init(&mut delay);
+    lcd.register_glyphs(&mut delay); // Piece symbols and icons (see glyph.rs)
+
+    // From here on the lcd is written to by the TIM4 interrupt, so printing doesn't stall the game (see embedded::character_lcd)
+    lcd.start_queue(dp.TIM4, clocks.pclk1_tim().0, clock_mhz as u32);
 
     let hall_sensor = gpiob.pb12.into_floating_input(&mut gpiob.crh).downgrade(); // Pin to read value of the selected hall sensor
 
-    let mut button = embedded::button::Button {
-        pin: gpiob.pb13.into_pull_down_input(&mut gpiob.crh).downgrade(),
-        press_raw: false,
-        press_start_cycle: None,
-        long_press_cycles: embedded::ms_to_cycles(650, clock_mhz as u64), // Button needs to be held for atleast 650ms for a long press
-        long_press: false,
-        last_press_cycle: 0,
-        debounce_cycles: embedded::ms_to_cycles(50, clock_mhz as u64), // 50ms debounce
-        consecutive_cycles: embedded::ms_to_cycles(150, clock_mhz as u64), // When button presses are registered less than 160ms apart then the presses are sequential
-        c_presses: 0,
-        consecutive_presses: 0, 
-    };
+    // From here on the leds are lit and the hall sensors are scanned by the TIM1 interrupt, so the leds stay lit while the computer is searching (see embedded::led_grid)
+    let mut grid = embedded::led_grid::start(grid_sr, hall_sensor, dp.TIM1, clocks.pclk2_tim().0, clock_mhz as u32);
+    embedded::led_grid::set_debounce(HALL_DEBOUNCE);
+
+    // The main button is on PB13, a board can also have confirm (PB14), back (PB15), white clock (PB10) and black clock (PB11) buttons (see menu::ButtonRole)
+    // The pins interrupt on both edges, the edges are read by Button::press
+    // Pins without a button are pulled down, so they never make a gesture
+    let mut main_pin = gpiob.pb13.into_pull_down_input(&mut gpiob.crh);
+    let mut confirm_pin = gpiob.pb14.into_pull_down_input(&mut gpiob.crh);
+    let mut back_pin = gpiob.pb15.into_pull_down_input(&mut gpiob.crh);
+    let mut white_clock_pin = gpiob.pb10.into_pull_down_input(&mut gpiob.crh);
+    let mut black_clock_pin = gpiob.pb11.into_pull_down_input(&mut gpiob.crh);
+    enable_edge_interrupts(&mut main_pin, &mut afio, &dp.EXTI);
+    enable_edge_interrupts(&mut confirm_pin, &mut afio, &dp.EXTI);
+    enable_edge_interrupts(&mut back_pin, &mut afio, &dp.EXTI);
+    enable_edge_interrupts(&mut white_clock_pin, &mut afio, &dp.EXTI);
+    enable_edge_interrupts(&mut black_clock_pin, &mut afio, &dp.EXTI);
+
+    // A board can also have a rotary encoder on PB8 (A) and PB9 (B), its contacts close to ground so the pins are pulled up
+    let mut encoder_a_pin = gpiob.pb8.into_pull_up_input(&mut gpiob.crh);
+    let mut encoder_b_pin = gpiob.pb9.into_pull_up_input(&mut gpiob.crh);
+    enable_edge_interrupts(&mut encoder_a_pin, &mut afio, &dp.EXTI);
+    enable_edge_interrupts(&mut encoder_b_pin, &mut afio, &dp.EXTI);
+
+    // Buzzer on PA0, driven by TIM2 and played by TIM3 (see embedded::buzzer)
+    embedded::buzzer::init(dp.TIM2, dp.TIM3, gpioa.pa0.into_alternate_push_pull(&mut gpioa.crl), clocks.pclk1_tim().0);
+
+    unsafe {
+        cortex_m::peripheral::NVIC::unmask(pac::Interrupt::EXTI15_10);
+        cortex_m::peripheral::NVIC::unmask(pac::Interrupt::EXTI9_5);
+        cortex_m::peripheral::NVIC::unmask(pac::Interrupt::TIM3);
+        cortex_m::peripheral::NVIC::unmask(pac::Interrupt::TIM4);
+        cortex_m::peripheral::NVIC::unmask(pac::Interrupt::TIM1_UP);
+    }
 
-    // Initiliaze board to starting board
-    let starting_board = board_representation::Board {
-        board: [71776119061217280, 9295429630892703744, 4755801206503243776, 2594073385365405696, 576460752303423488, 1152921504606846976, 65280, 129, 66, 36, 8, 16, 7926616819148718190],
-        whites_move: true,
-        points: board_representation::Points { white_points: 0, black_points: 0 },
-        points_delta: 0,
-        half_moves: 0,
-        half_move_clock: 0,
-        en_passant_target: None
-    };
+    let mut input = embedded::input::Input::new(embedded::button::Button::new(main_pin.downgrade(), 13, clock_mhz as u64));
+    input.register(ButtonRole::Confirm, embedded::button::Button::new(confirm_pin.downgrade(), 14, clock_mhz as u64));
+    input.register(ButtonRole::Back, embedded::button::Button::new(back_pin.downgrade(), 15, clock_mhz as u64));
+    input.register(ButtonRole::WhiteClock, embedded::button::Button::new(white_clock_pin.downgrade(), 10, clock_mhz as u64));
+    input.register(ButtonRole::BlackClock, embedded::button::Button::new(black_clock_pin.downgrade(), 11, clock_mhz as u64));
+    input.register_encoder(embedded::encoder::Encoder::new(encoder_a_pin.downgrade(), encoder_b_pin.downgrade(), clock_mhz as u64));
+
+    // Serial port for the UCI engine (see uci.rs), 115200 baud on USART1
+    let serial_pins = (gpioa.pa9.into_alternate_push_pull(&mut gpioa.crh), gpioa.pa10);
+    let serial_config = hal::serial::Config::default().baudrate(115200.bps());
+    let (serial_tx, serial_rx) = hal::serial::Serial::usart1(dp.USART1, serial_pins, &mut afio.mapr, serial_config, clocks, &mut rcc.apb2).split();
+    let mut serial = embedded::serial::Serial::new(serial_tx, serial_rx);
 
     let pieces_info = chess2::piece::constants::gen(); // Generate piece info
 
-    let led_strobe_us: u32 = 200000; // Led strobe time (on time) in micro seconds
+    let led_strobe_us: u32 = 200000; // Time (us) the leds are on, and then off, for each flash when bits are flashed
+
+    // Settings chosen from the menu, including the maximum search time (ms) for the minimax algorithm
+    // The settings from when the board was last used are loaded, or the default settings if none have been stored
+    let mut settings = chess2::settings::load(&flash_storage.records()).unwrap_or(chess2::settings::Settings::new());
+    let mut stored_settings = settings; // Settings in storage, so they are only stored again once they have changed
+    embedded::LED_BRIGHTNESS.store(settings.led_brightness(), Ordering::Relaxed);
+    embedded::buzzer::SOUND_ON.store(settings.sound, Ordering::Relaxed);
 
-    let max_search_times: [u64; 8] = [1000, 3000, 5000, 10000, 20000, 30000, 50000, 100000]; // Options for maximum search times (ms) for the minimax algorithm
-    let mut search_time_index: usize = 2; // Index for the currently selected minimax search time
+    // Results of the games played on the board, kept with the settings
+    let mut statistics = chess2::statistics::load(&flash_storage.records()).unwrap_or(chess2::statistics::Statistics::new());
     let max_search_depth = 6; // Maximum minimax search depth
 
     let mut opening_heatmap = [[0i16; 64]; 12];
-    // Testing how many clock cycles it takes for the computer the generate a move from a starting board position at a search depth of 4
+    let mut capture_history = algorithm::CaptureHistory::new();
+
+    // Best moves of searched boards, searched first the next time a board is searched, 64 entries uses 1KB of ram
+    let mut hash_move_entries = [algorithm::HashMoveEntry::new(); 64];
+    let mut hash_moves = algorithm::HashMoves::new(&mut hash_move_entries);
+
+    // Cache of static evaluations, 128 entries uses 2KB of ram
+    let mut eval_cache_entries = [chess2::evaluation::EvalCacheEntry::new(); 128];
+    let mut eval_cache = chess2::evaluation::EvalCache::new(&mut eval_cache_entries);
+
+    // Cache of pawn structure evaluations, pawn structures repeat much more often than whole positions so it can be smaller
+    let mut pawn_cache_entries = [chess2::evaluation::EvalCacheEntry::new(); 64];
+    let mut pawn_cache = chess2::evaluation::EvalCache::new(&mut pawn_cache_entries);
+
+    // Benchmark the search over the bench positions, the nodes and signature should only change when the search is meant to behave differently
     /*
     cycle_counter.update();
     let start_cycles = cycle_counter.cycles;
 
-    algorithm::gen_best_move(
-        true,
-        &mut cycle_counter,
-        &start_cycles,
-        &chess2::embedded::ms_to_cycles(100000, clock_mhz as u64),
-        4,
-        0,
-        0,
-        algorithm::AlphaBeta::new(),
-        &opening_heatmap,
-        starting_board,
-        &pieces_info,
-    );
+    let bench_result = chess2::bench::bench(&mut cycle_counter, &mut hash_moves, &mut eval_cache, &mut pawn_cache, &pieces_info);
 
     cycle_counter.update();
     let end_cycles = cycle_counter.cycles;
     let elapsed_cycles = end_cycles - start_cycles;
 
-    rprintln!("Computer move took {} clock cycles", elapsed_cycles);
-    rprintln!("(Rougly {} seconds)", elapsed_cycles / (clock_mhz as u64 * 1000000));
+    log!("Bench nodes: {}, signature: {:x}", bench_result.nodes, bench_result.signature);
+    log!("Bench took {} clock cycles", elapsed_cycles);
+    log!("(Rougly {} seconds)", elapsed_cycles / (clock_mhz as u64 * 1000000));
+
+    // Benchmark the move generator alone
+    cycle_counter.update();
+    let start_cycles = cycle_counter.cycles;
+
+    let perft_nodes = chess2::bench::perft_bench(&pieces_info);
+
+    cycle_counter.update();
+    let elapsed_cycles = cycle_counter.cycles - start_cycles;
+
+    log!("Perft bench nodes: {}", perft_nodes);
+    log!("Perft bench took {} clock cycles", elapsed_cycles);
     */
 
+    // If a game was saved before the power was lost, and the pieces are still where they were, ask the player if they want to resume it
+    let mut saved_game = None;
+    match chess2::save::load(&flash_storage.save(), &pieces_info) {
+        Some(saved) => {
+            let mut saved_physical_bitboard = saved.game.board.to_bitboard();
+            if !saved.settings.player_white {
+                saved_physical_bitboard = chess2::flip_bitboard(saved_physical_bitboard); // Flip the bitboard to physical board perspective
+            }
+
+            if embedded::led_grid::read_board_halls(&mut grid) == saved_physical_bitboard && ask_resume(&mut lcd, &mut input, &mut cycle_counter, &mut delay) {
+                saved_game = Some(saved);
+            }
+        },
+        None => (),
+    }
+
     loop {
         delay.delay_ms(1u16);
         lcd.clear(&mut delay);
         opening_heatmap = [[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0, 10, 1, 18, 10, 9, 9, 1, 0, 1, 33, 61, 475, 338, 22, 6, 5, 51, 142, 1144, 2288, 2246, 392, 88, 80, 88, 74, 361, 111, 276, 124, 322, 62, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0], [0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 7, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 1, 0, 4, 0, 1, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 5, 35, 32, 94, 499, 3, 0], [1, 0, 0, 0, 0, 0, 0, 2, 0, 0, 2, 0, 0, 19, 0, 2, 0, 0, 15, 1, 2, 7, 0, 0, 1, 31, 0, 19, 145, 2, 79, 0, 9, 0, 11, 268, 58, 0, 1, 7, 16, 17, 1470, 1, 3, 2054, 9, 15, 0, 0, 2, 115, 62, 1, 0, 0, 0, 1, 0, 0, 5, 2, 2, 0], [1, 0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 3, 20, 22, 1, 0, 0, 1, 35, 0, 0, 17, 0, 2, 0, 314, 1, 13, 2, 0, 292, 0, 139, 2, 509, 2, 0, 47, 0, 35, 6, 108, 1, 162, 124, 1, 2, 3, 0, 51, 19, 57, 148, 1, 205, 0, 1, 0, 2, 0, 0, 3, 0, 0], [0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 3, 2, 0, 0, 0, 0, 0, 0, 0, 1, 0, 1, 1, 4, 1, 2, 0, 24, 22, 0, 13, 32, 3, 2, 24, 3, 0, 48, 7, 17, 6, 42, 0, 0, 0, 0, 66, 49, 67, 3, 0, 0, 0, 1, 0, 3, 3, 0, 0, 0], [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 1, 0, 0, 0, 0, 0, 9, 4, 1, 0, 0, 0, 23, 4, 0, 26, 498, 6], [0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 348, 125, 418, 716, 867, 40, 525, 86, 17, 238, 834, 1360, 1326, 216, 134, 18, 0, 13, 174, 512, 190, 170, 68, 4, 1, 0, 34, 3, 4, 37, 4, 0, 0, 6, 0, 0, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0], [0, 8, 3, 3, 17, 458, 5, 0, 1, 0, 0, 0, 0, 1, 0, 1, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0], [0, 13, 0, 2, 1, 1, 8, 0, 0, 4, 3, 219, 58, 2, 1, 0, 21, 32, 1057, 15, 1, 1874, 4, 29, 56, 0, 8, 130, 31, 3, 1, 10, 0, 9, 4, 40, 190, 2, 21, 0, 0, 0, 31, 0, 2, 1, 3, 0, 0, 0, 1, 2, 0, 2, 0, 0, 0, 0, 0, 0, 0, 2, 0, 0], [0, 0, 0, 0, 1, 3, 0, 1, 1, 74, 0, 44, 307, 0, 387, 2, 20, 31, 2, 44, 56, 5, 9, 5, 27, 0, 241, 0, 2, 79, 3, 1, 0, 297, 3, 5, 2, 0, 98, 4, 0, 0, 60, 3, 1, 8, 0, 3, 0, 0, 0, 5, 1, 3, 1, 1, 0, 1, 0, 1, 0, 3, 0, 0], [1, 1, 2, 4, 5, 0, 0, 0, 0, 0, 36, 10, 62, 0, 0, 0, 0, 28, 0, 10, 2, 36, 6, 0, 79, 0, 0, 53, 5, 4, 12, 2, 0, 1, 1, 9, 3, 2, 0, 51, 1, 0, 1, 0, 0, 0, 0, 2, 0, 2, 0, 0, 0, 0, 3, 0, 0, 0, 0, 0, 0, 0, 0, 0], [0, 0, 2, 7, 0, 4, 458, 0, 0, 0, 0, 0, 5, 17, 2, 0, 0, 0, 0, 0, 1, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]];
 
-        // Get player team
-        let mut player_white = true;
-        {
-            let mut game_started = false;
-            
-
-            let mut team_message_start_cycle = 0; // The clock cycle the current team select message started getting displayed at
-            let team_message_cycles = embedded::ms_to_cycles(1000, clock_mhz as u64); // How many clock cycles the game start message should be displayed for before switching to the oposite team
+        // A saved game is only resumed straight after the board is turned on, after it every game starts from the menu
+        let resumed_game = saved_game.take();
+
+        // Show the menu until the player starts a game
+        // Settings are kept between games, and the menu starts on the new game screen so another game can be started straight away
+        // A resumed game is played with the settings it was saved with
+        match &resumed_game {
+            Some(saved) => {
+                settings = saved.settings;
+                embedded::LED_BRIGHTNESS.store(settings.led_brightness(), Ordering::Relaxed);
+                embedded::buzzer::SOUND_ON.store(settings.sound, Ordering::Relaxed);
+            },
+            None => {
+                let mut menu = chess2::menu::Menu::new();
+                let mut redraw = true;
+
+                // Values longer than the lcd (e.g. a long variant name) scroll across the second row
+                let mut value = Row::new();
+                let mut value_marquee = Marquee::new(0, 0);
+                loop {
+                    cycle_counter.update();
+                    let now_ms = embedded::cycles_to_ms(cycle_counter.cycles, clock_mhz as u64);
 
-            while !game_started {
+                    if redraw {
+                        lcd.clear(&mut delay);
+                        lcd.set_cursor(&mut delay, [0, 0]);
+                        let af = arrform!(32, "{}", menu.title());
+                        lcd.print(&mut delay, af.as_str());
+                        lcd.set_cursor(&mut delay, [0, 1]);
+                        value.clear();
+                        write!(value, "{}", menu.value(&settings)).ok();
+                        value_marquee = Marquee::new(value.len(), now_ms);
+                        lcd.print(&mut delay, value_marquee.window(value.as_str()));
+                        redraw = false;
+                    } else if value_marquee.tick(now_ms) {
+                        lcd.set_cursor(&mut delay, [0, 1]);
+                        let af = arrform!(32, "{:<16}", value_marquee.window(value.as_str()));
+                        lcd.print(&mut delay, af.as_str());
+                    }
 
-                // Display start game message for white and black
-                lcd.set_cursor(&mut delay, [0, 0]);
-                if player_white {
-                    lcd.print(&mut delay, "Start as white?");
-                } else {
-                    lcd.print(&mut delay, "Start as black?");                
-                }
-                lcd.set_cursor(&mut delay, [0, 1]);
-                lcd.print(&mut delay, "(Press button)");
+                    match input.gesture(&mut cycle_counter) {
+                        Some(gesture) => {
+                            match menu.input(gesture, &mut settings) {
+                                chess2::menu::MenuAction::StartGame => break,
+                                chess2::menu::MenuAction::UciEngine => run_uci(&mut serial, &mut lcd, &mut input, &mut cycle_counter, &mut capture_history, &mut hash_moves, &mut eval_cache, &mut pawn_cache, &opening_heatmap, &pieces_info, max_search_depth, clock_mhz as u64, &mut delay),
+                                chess2::menu::MenuAction::ExportGame => export_game(&mut flash_storage, &pieces_info, &mut lcd, &mut delay),
+                                chess2::menu::MenuAction::MoveHistory => show_move_history(&mut flash_storage, &pieces_info, &mut lcd, &mut input, &mut cycle_counter, clock_mhz as u64, &mut delay),
+                                chess2::menu::MenuAction::Statistics => show_statistics(&statistics, &mut lcd, &mut input, &mut cycle_counter, clock_mhz as u64, &mut delay),
+                                chess2::menu::MenuAction::ResetStatistics => {
+                                    statistics = chess2::statistics::Statistics::new();
+                                    chess2::statistics::store(&mut flash_storage.records(), &statistics).ok();
+                                    lcd.clear(&mut delay);
+                                    lcd.set_cursor(&mut delay, [0, 0]);
+                                    lcd.print(&mut delay, "Stats reset");
+                                    delay.delay_ms(1000u16);
+                                },
+                                chess2::menu::MenuAction::Diagnostics => run_diagnostics(&mut lcd, &mut grid, &mut input, &mut cycle_counter, &mut delay),
+                                chess2::menu::MenuAction::Console => run_console(&mut serial, &mut lcd, &mut grid, &mut input, &mut cycle_counter, &mut capture_history, &mut hash_moves, &mut eval_cache, &mut pawn_cache, &opening_heatmap, &pieces_info, max_search_depth, clock_mhz as u64, &mut delay),
+                                chess2::menu::MenuAction::None => (),
+                            }
 
+                            embedded::LED_BRIGHTNESS.store(settings.led_brightness(), Ordering::Relaxed);
+                            embedded::buzzer::SOUND_ON.store(settings.sound, Ordering::Relaxed);
+                            redraw = true;
 
-                if button.press(&mut cycle_counter) {
-                    game_started = true;
-                }
+                            // Store the settings once an option has been changed, so they are kept when the board is turned off
+                            if !menu.editing && settings != stored_settings {
+                                chess2::settings::store(&mut flash_storage.records(), &settings).ok();
+                                stored_settings = settings;
+                            }
+                        },
+                        None => (),
+                    }
 
-                if cycle_counter.cycles > team_message_start_cycle + team_message_cycles {
-                    team_message_start_cycle = cycle_counter.cycles;
-                    player_white = !player_white;
+                    // Turning the encoder scrolls through the screens or options, the settings are stored once the option stops being changed
+                    let steps = input.scroll();
+                    if steps != 0 {
+                        menu.scroll(steps, &mut settings);
+                        embedded::LED_BRIGHTNESS.store(settings.led_brightness(), Ordering::Relaxed);
+                        embedded::buzzer::SOUND_ON.store(settings.sound, Ordering::Relaxed);
+                        redraw = true;
+                    }
                 }
-            }
+            },
         }
+        let player_white = settings.player_white;
 
         lcd.clear(&mut delay);
 
         // Ensure the physical board is set up properly
-        let mut physical_bitboard = embedded::read_board_halls(&mut grid_sr, &hall_sensor, &mut delay); // Get bitboard of pieces on the physical board
-        {
-            let expected_board: u64 = 0b1111111111111111000000000000000000000000000000001111111111111111;
-            
-            while physical_bitboard != expected_board {
-                lcd.set_cursor(&mut delay, [0, 0]);
-                lcd.print(&mut delay, "Please setup");
-                lcd.set_cursor(&mut delay, [0, 1]);
-                lcd.print(&mut delay, "the board");
+        // The standard starting position or any handicap starting position is accepted, the missing piece shows which team is giving odds
+        // A resumed game was only offered if the physical board matched it, so it doesn't have to be set up
+        let mut physical_bitboard = embedded::led_grid::read_board_halls(&mut grid); // Get bitboard of pieces on the physical board
+        let start_index; // Index of the starting board (see Board::indexed_start), kept so the game can be saved
+        let mut game;
+        let saved_remaining_ms; // Time each team had left in a resumed game
+        match resumed_game {
+            Some(saved) => {
+                start_index = saved.start_index;
+                game = saved.game;
+                saved_remaining_ms = saved.remaining_ms;
+            },
+            None => {
+                saved_remaining_ms = [None; 2];
+
+                let expected_board: u64 = 0b1111111111111111000000000000000000000000000000001111111111111111;
+
+                // Starting boards that can be set up, the standard starting position first
+                let mut starting_boards = [board_representation::Board::start(); board_representation::STARTING_BOARDS];
+                for i in 1..board_representation::STARTING_BOARDS {
+                    match board_representation::Board::indexed_start(i) {
+                        Some(board) => starting_boards[i] = board,
+                        None => (),
+                    }
+                }
+
+                // Returns the index of the starting board that is set up on the physical board, if there is one
+                let find_starting_board = |physical_bitboard: u64| {
+                    for i in 0..starting_boards.len() {
+                        let mut board_bitboard = starting_boards[i].to_bitboard();
+                        if !player_white {
+                            board_bitboard = chess2::flip_bitboard(board_bitboard); // Flip the bitboard to physical board perspective
+                        }
+                        if board_bitboard == physical_bitboard {
+                            return Some(i);
+                        }
+                    }
+                    None
+                };
 
-                physical_bitboard = embedded::read_board_halls(&mut grid_sr, &hall_sensor, &mut delay); // Update physical bitboard
+                loop {
+                    match find_starting_board(physical_bitboard) {
+                        Some(index) => {
+                            start_index = index;
+                            break;
+                        },
+                        None => (),
+                    }
+
+                    lcd.set_cursor(&mut delay, [0, 0]);
+                    lcd.print(&mut delay, "Please setup");
+                    lcd.set_cursor(&mut delay, [0, 1]);
+                    lcd.print(&mut delay, "the board");
+
+                    physical_bitboard = embedded::led_grid::read_board_halls(&mut grid); // Update physical bitboard
 
-                // If the button is pressed highlight the positions where pieces have to placed
-                button.press(&mut cycle_counter);
-                if button.press_raw {
-                    embedded::leds_from_bitboard(&mut grid_sr, &mut delay, expected_board ^ physical_bitboard, led_strobe_us, true);
+                    // If the button is pressed highlight the positions where pieces have to placed
+                    input.press(&mut cycle_counter);
+                    if input.held() {
+                        embedded::led_grid::set_frame(expected_board ^ physical_bitboard);
+                    } else {
+                        embedded::led_grid::set_frame(0);
+                    }
+
+                    delay.delay_ms(1u16);
                 }
+                embedded::led_grid::set_frame(0);
 
-                delay.delay_ms(1u16);
-            }
+                // Initialise game
+                let mut game_board = starting_boards[start_index];
+                game_board.variant = settings.variant();
+                game = chess2::game::Game::new(game_board);
+            },
         }
+        capture_history = algorithm::CaptureHistory::new(); // Capture history from the last game isn't relevant to this game
 
-        // Initialise board
-        let mut board = starting_board;
+        // Start the clock of a timed game, the time of the team to move starts going down straight away
+        // A resumed game carries on with the time each team had left after its last move
+        let mut clock = None;
+        match settings.time_control() {
+            Some(time_control) => {
+                cycle_counter.update();
+                let mut new_clock = chess2::clock::Clock::new(time_control, game.board.whites_move, embedded::cycles_to_ms(cycle_counter.cycles, clock_mhz as u64));
+                for (white, remaining_ms) in [(true, saved_remaining_ms[0]), (false, saved_remaining_ms[1])] {
+                    match remaining_ms {
+                        Some(remaining_ms) => new_clock.set_remaining_ms(white, remaining_ms),
+                        None => (),
+                    }
+                }
+                clock = Some(new_clock);
+            },
+            None => (),
+        }
+
+        // Save the game as it's played, so it can be resumed if the power is lost
+        // A game with too many moves to save isn't saved
+        let mut save_writer = chess2::save::SaveWriter::start(&mut flash_storage.save(), &settings, start_index, &game, clock.as_ref()).ok();
+
+        // The white and black teams are only warned once each game that their time is running out
+        let mut low_time_warned = [false; 2];
 
         // Game loop
         // Each loop represents one turn
-        // The loop will break once the game has finished
+        // The player's move is found by scanning the hall sensors, the computer's move by searching, then the move is played on the game (which checks it's legal) and shown on the leds and lcd
+        // The loop will break once the game has finished (see Game::play and Game::time_forfeit), or the player resigns or stops a demo
         'game: loop {
             lcd.clear(&mut delay);
+            embedded::led_grid::set_frame(0); // Turn off any leds left lit when the last turn ended early (e.g. the held piece's destinations)
 
-            let players_turn = player_white == board.whites_move; // Determine wether the current turn is for the player or computer to make
+            let board = game.board; // Board before the move is made
+
+            // Determine wether the current turn is for the player or computer to make
+            // In two player mode every turn is a player's turn, the computer only checks the moves, and in demo mode the computer makes every move
+            let players_turn;
+            match settings.mode() {
+                chess2::settings::Mode::Computer => players_turn = player_white == board.whites_move,
+                chess2::settings::Mode::TwoPlayer => players_turn = true,
+                chess2::settings::Mode::Demo => players_turn = false,
+            }
+            let demo = settings.mode() == chess2::settings::Mode::Demo;
 
             // Piece move for the chess engine and the physical board must be stored seperately
             // Because the physical board has a dynamic orientation for the teams, while the internal engine board representation has a static orientation for the white and black team perspective
             let mut piece_internal_move = algorithm::Move::new();
             let mut piece_physical_move = algorithm::Move::new();
 
-            physical_bitboard = embedded::read_board_halls(&mut grid_sr, &hall_sensor, &mut delay); // Get bitboard of pieces on the physical board before a piece has been moved
+            physical_bitboard = embedded::led_grid::read_board_halls(&mut grid); // Get bitboard of pieces on the physical board before a piece has been moved
+
+            // Get what the physical bitboard should be at the start of the turn
+            let mut expected_physical_bitboard = board.to_bitboard();
+            if !player_white {
+                expected_physical_bitboard = chess2::flip_bitboard(expected_physical_bitboard); // Flip the bitboard to physical board perspective
+            }
+
+            // If a piece was knocked over or a move wasn't finished the physical board no longer matches the game
+            // Guide the player back to the position of the game before the turn starts, otherwise every move after would be read wrong
+            // Nobody has to move the pieces in demo mode, so the game carries on without the physical board
+            if physical_bitboard != expected_physical_bitboard && !demo {
+                recover_board("Board mismatch", expected_physical_bitboard, &mut lcd, &mut grid, &mut delay);
+                physical_bitboard = expected_physical_bitboard;
+                lcd.clear(&mut delay);
+            }
+
+            // In an untimed game the last 2 moves are shown in place of whose turn it is, so the player can see the move that was just made
+            // They're written once a turn, writing them replays the game
+            let mut last_moves = Row::new();
+            chess2::lcd_text::write_last_moves(&mut last_moves, &game, &pieces_info).ok();
+
+            // Captured pieces and the material balance (see captures.rs), shown during the player's turn
+            let mut captures = Row::new();
+            chess2::captures::write_captures(&mut captures, &board, &pieces_info).ok();
 
             // Get move from player / computer
             if players_turn {
 
-                let mut init_physical_bitboard = physical_bitboard;
+                let player_in_check = board.is_check(&pieces_info);
+
+                // Keeps track of the pieces the player lifts and places, so captures, castling and en passant can be worked out when the player presses the button
+                let mut hall_tracker = chess2::hall_moves::HallTracker::new(physical_bitboard);
+                embedded::led_grid::take_events(&mut grid); // The tracker starts from the board as it is now, so the changes before the turn aren't followed
+
+                // While the player holds one of their pieces the bits it can legally move to are lit
+                let legal_destinations = chess2::hall_moves::legal_destinations(&board, player_white, &pieces_info);
+                let mut held_piece_bit: Option<usize> = None;
 
                 // Loop until the player has made a proper move
-                let mut piece_removed = false;
                 loop {
                     lcd.set_cursor(&mut delay, [0, 0]);
-                    lcd.print(&mut delay, "Players turn");
+
+                    // In a timed game the clocks are shown instead of the last moves, the team to move is still on the second row
+                    match clock.as_mut() {
+                        Some(clock) => {
+                            cycle_counter.update();
+                            clock.update(embedded::cycles_to_ms(cycle_counter.cycles, clock_mhz as u64));
+
+                            // Warn the player when their time is running out
+                            let team_index;
+                            if board.whites_move {
+                                team_index = 0;
+                            } else {
+                                team_index = 1;
+                            }
+                            if clock.low_time(board.whites_move) && !low_time_warned[team_index] {
+                                embedded::buzzer::play(Sound::LowTime);
+                                low_time_warned[team_index] = true;
+                            }
+
+                            // The player loses when their time runs out
+                            match clock.flagged() {
+                                Some(white_flagged) => {
+                                    game.time_forfeit(white_flagged);
+                                    show_time_forfeit(&game, &mut lcd, &mut input, &mut cycle_counter, &mut delay);
+                                    break 'game;
+                                },
+                                None => (),
+                            }
+
+                            let af = arrform!(32, "{}", clock.display());
+                            lcd.print(&mut delay, af.as_str());
+                        },
+                        None => lcd_print_turn(&mut lcd, &mut delay, &last_moves, "Players turn"),
+                    }
+                    // The second row takes turns showing the team to move and the captured pieces, once a piece has been captured
+                    // Both are padded to the width of the lcd, so switching between them doesn't need the lcd to be cleared
+                    cycle_counter.update();
                     lcd.set_cursor(&mut delay, [0, 1]);
-                    lcd.print(&mut delay, "(");
-                    lcd_print_team(&mut lcd, &mut delay, player_white);
-                    lcd.print(&mut delay, ")");
-
-                    let new_physical_bitboard = embedded::read_board_halls(&mut grid_sr, &hall_sensor, &mut delay); // Get bitboard of pieces on the physical board
-
-                    let piece_change = chess2::find_piece_change(physical_bitboard, new_physical_bitboard);
-
-                    // Keep track of pieces being removed from the board so pieces can be captured without throwing an error
-                    if piece_change == -1 {
-                        if !piece_removed {
-                            // Set the initial bitboard to the current bitboard if one piece is removed from the board
-                            // This allows the capture move to be detected
-                            // Only the first piece that is removed from the board can be captured
-                            init_physical_bitboard = new_physical_bitboard;
-                            piece_removed = true;
+                    if captures.len() > 0 && embedded::cycles_to_ms(cycle_counter.cycles, clock_mhz as u64) / STATUS_ROTATE_MS % 2 == 1 {
+                        let af = arrform!(32, "{:<16.16}", captures.as_str());
+                        lcd.print(&mut delay, af.as_str());
+                    } else {
+                        let team;
+                        if board.whites_move {
+                            team = "White";
+                        } else {
+                            team = "Black";
                         }
-                    } else if piece_change > -1 {
-                        init_physical_bitboard = physical_bitboard;
+
+                        // The hourglass shows the team's clock is running
+                        let running;
+                        if clock.is_some() {
+                            running = Glyph::Hourglass.char();
+                        } else {
+                            running = ' ';
+                        }
+
+                        // Tell the player if the computer put them in check
+                        let check;
+                        if player_in_check {
+                            check = "Check!";
+                        } else {
+                            check = "";
+                        }
+
+                        let af = arrform!(32, "({}){}{:>8}", team, running, check);
+                        lcd.print(&mut delay, af.as_str());
                     }
 
-                    let button_pressed = button.press(&mut cycle_counter);
+                    // Follow the pieces the player lifts and places, they are also printed for debugging the hall sensors
+                    // The events are filtered by the background scan (see hall_scan.rs), so a piece slid across the board doesn't lift and place every bit it passes over
+                    for event in embedded::led_grid::take_events(&mut grid) {
+                        hall_tracker.apply(event);
+                        match event {
+                            chess2::hall_moves::HallEvent::Lift(bit) => {
+                                log!("Lifted {}", chess2::bit_to_ccn(bit));
+
+                                // Only the first of the player's pieces to be lifted is shown, so lifting the rook while castling doesn't change the lit bits
+                                if held_piece_bit.is_none() && legal_destinations[bit] != 0 {
+                                    held_piece_bit = Some(bit);
+                                }
+                            },
+                            chess2::hall_moves::HallEvent::Place(bit) => {
+                                log!("Placed {}", chess2::bit_to_ccn(bit));
+
+                                match held_piece_bit {
+                                    // Putting the piece back where it was lifted from isn't a move
+                                    Some(held_bit) if held_bit == bit => held_piece_bit = None,
+
+                                    // Once the only change to the board is the held piece moving to the bit, flash the bit if the piece can't move there
+                                    // Other placements (e.g. the rook while castling) don't finish the move, so the destinations stay lit
+                                    Some(held_bit) if hall_tracker.bitboard() == physical_bitboard & !(1 << held_bit) | 1 << bit => {
+                                        if !chess2::bit_on(legal_destinations[held_bit], bit) {
+                                            flash_bitboard(1 << bit, led_strobe_us, &mut delay);
+                                        }
+                                        held_piece_bit = None;
+                                    },
+                                    _ => (),
+                                }
+                            },
+                        }
+                    }
 
-                    // When the button registers a long press open a menu to change the maximum time that the computer takes to search
-                    if button.long_press {
-                        lcd.clear(&mut delay);
+                    match held_piece_bit {
+                        Some(held_bit) => embedded::led_grid::set_frame(legal_destinations[held_bit]),
+                        None => embedded::led_grid::set_frame(0),
+                    }
 
-                        let mut increment_queued = false;
-                        let mut press_start_cycle: Option<u64> = None;
+                    // Every gesture of the buttons is mapped to an action in one place (see menu::game_action)
+                    let action = input.game_action(&mut cycle_counter, board.whites_move);
+
+                    // Open a menu to change the maximum time that the computer takes to search
+                    // Short presses or the encoder change the search time, a long press closes the menu
+                    if action == Some(GameAction::SearchTimeMenu) {
+                        lcd.clear(&mut delay);
                         loop {
                             lcd.set_cursor(&mut delay, [0, 0]);
                             lcd.print(&mut delay, "Engine search");
                             lcd.set_cursor(&mut delay, [0, 1]);
                     
-                            let af = arrform!(64, "{}s", max_search_times[search_time_index] / 1000);
+                            let af = arrform!(64, "{}s", settings.search_time_ms() / 1000);
                             lcd.print(&mut delay, af.as_str());
 
-                            let button_pressed = button.press(&mut cycle_counter);
-
-                            // Long press the button again to close the menu
-                            if button.long_press {
-                                break;
+                            // Turning the encoder also changes the search time, in either direction
+                            let steps;
+                            match input.gesture(&mut cycle_counter) {
+                                Some(gesture) if gesture.is_long() => break,
+                                Some(chess2::menu::Gesture::Short) => steps = 1,
+                                _ => steps = input.scroll(),
                             }
 
-                            // If the button is pressed increment the search_time_index
-                            if button_pressed {
-                                increment_queued = true;
-                                press_start_cycle = button.press_start_cycle;
-                            }
-
-                            // Queue increment of the search time until after the button has been released
-                            // This avoids the value updating while the user is trying to long press to exit the menu
-                            if increment_queued && press_start_cycle != button.press_start_cycle {
+                            if steps != 0 {
                                 lcd.clear(&mut delay);
-                                search_time_index += 1;
-                                if search_time_index > (max_search_times.len() - 1) {
-                                    search_time_index = 0;
-                                }
-                                increment_queued = false;
+                                let search_times = chess2::settings::SEARCH_TIMES.len() as i32;
+                                settings.search_time_index = (settings.search_time_index as i32 + steps).rem_euclid(search_times) as usize;
                             }
                         }
-                        button.consecutive_presses = 0; // Reset consecutive presses that may have been made while the user cycles through the search times
                         lcd.clear(&mut delay);
                     }
 
-                    // When the button is pressed greater than 9 times consecutevily resign
-                    if button.consecutive_presses > 9 {
+                    // Resign, the game ends without a result
+                    if action == Some(GameAction::Resign) {
                         break 'game;
                     }
 
-                    if button_pressed {
+                    // Take back the player's last move and the computer's reply
+                    // In two player mode only the last move is taken back, it was made by the other player
+                    // The player puts the pieces back where they were before the moves, then the turn starts again
+                    if action == Some(GameAction::Takeback) {
+                        let takeback_moves;
+                        if settings.mode() == chess2::settings::Mode::TwoPlayer {
+                            takeback_moves = 1;
+                        } else {
+                            takeback_moves = 2;
+                        }
 
-                        // Do nothing if the board has not changed
-                        if physical_bitboard == new_physical_bitboard {
+                        if game.len() < takeback_moves {
+                            lcd.clear(&mut delay);
+                            lcd.set_cursor(&mut delay, [0, 0]);
+                            lcd.print(&mut delay, "No move to undo");
+                            delay.delay_ms(1000u16);
+                            lcd.clear(&mut delay);
                             continue;
                         }
-                        
-                        let player_move = chess2::find_bitboard_move(init_physical_bitboard, new_physical_bitboard, &board, player_white);
 
-                        button.press(&mut cycle_counter);
+                        // Moves that have been played can allways be replayed, so undo can't fail here
+                        for _ in 0..takeback_moves {
+                            game.undo(&pieces_info).ok();
+                            save_game(&mut save_writer, &mut flash_storage, &game, clock.as_ref(), true);
+                        }
+
+                        let mut takeback_physical_bitboard = game.board.to_bitboard();
+                        if !player_white {
+                            takeback_physical_bitboard = chess2::flip_bitboard(takeback_physical_bitboard); // Flip the bitboard to physical board perspective
+                        }
+
+                        recover_board("Take back move", takeback_physical_bitboard, &mut lcd, &mut grid, &mut delay);
+                        input.press(&mut cycle_counter);
+                        continue 'game;
+                    }
+
+                    // Give the player a hint
+                    // The computer searches for the player's best move for the hint search time, then the move is printed with its score and its bits are flashed
+                    if action == Some(GameAction::Hint) {
+                        lcd.clear(&mut delay);
+                        lcd.set_cursor(&mut delay, [0, 0]);
+                        lcd.print(&mut delay, "Thinking...");
+
+                        let search_params = algorithm::SearchParams {
+                            max_depth: max_search_depth,
+                            max_elapsed_cycles: chess2::embedded::ms_to_cycles(settings.hint_time_ms(), clock_mhz as u64),
+                            ..algorithm::SearchParams::new()
+                        };
+
+                        STOP_SEARCH.store(false, Ordering::Relaxed);
+
+                        // Keep the value of the last completed iteration, it's the value of the hint
+                        let mut hint_value = 0;
+                        let mut thinking = chess2::thinking::Thinking::new();
+                        let mut save_value = |progress: &algorithm::SearchProgress| {
+                            if progress.completed {
+                                hint_value = progress.value;
+                            }
+                            if thinking.update(progress, clock_mhz as u64) {
+                                lcd_print_thinking(&mut lcd, &mut delay, &thinking, &board, &pieces_info);
+                            }
+                        };
+
+                        let hint = algorithm::gen_best_move(
+                            board,
+                            &search_params,
+                            &mut algorithm::SearchData {
+                                cycle_counter: &mut cycle_counter,
+                                start_cycles: 0,
+                                nodes: 0,
+                                stop: &STOP_SEARCH,
+                                progress: Some(&mut save_value),
+                                capture_history: &mut capture_history,
+                                hash_moves: &mut hash_moves,
+                                eval_cache: &mut eval_cache,
+                                pawn_cache: &mut pawn_cache,
+                                opening_heatmap: &opening_heatmap,
+                                pieces_info: &pieces_info,
+                            },
+                        );
+
+                        // The player allways has a legal move on their turn, otherwise the game would have ended
+                        match hint {
+                            Ok(hint_move) => {
+                                lcd.clear(&mut delay);
+                                lcd.set_cursor(&mut delay, [0, 0]);
+                                let af = arrform!(32, "Hint: {}{}{}", Glyph::piece(hint_move.initial_piece_coordinates.board_index).char(), chess2::bit_to_ccn(hint_move.initial_piece_coordinates.bit), chess2::bit_to_ccn(hint_move.final_piece_bit));
+                                lcd.print(&mut delay, af.as_str());
+                                lcd.set_cursor(&mut delay, [0, 1]);
+                                lcd_print_score(&mut lcd, &mut delay, hint_value);
+
+                                let mut hint_physical_move = hint_move;
+                                if !player_white {
+                                    hint_physical_move = hint_move.flip();
+                                }
+                                flash_bitboard(1 << hint_physical_move.initial_piece_coordinates.bit | 1 << hint_physical_move.final_piece_bit, led_strobe_us, &mut delay);
+                            },
+                            Err(_) => (),
+                        }
+
+                        lcd.clear(&mut delay);
+                        input.press(&mut cycle_counter);
+                        continue;
+                    }
+
+                    // Make the move the player has made on the board
+                    if action == Some(GameAction::MakeMove) {
+
+                        let player_move = hall_tracker.infer_move(&board, player_white);
+
+                        input.press(&mut cycle_counter);
 
                         match player_move {
 
                             // If the move was ok break the loop
-                            Ok(player_move) => {
+                            // The hall sensors can't see which piece a pawn was swapped for, so the player chooses it
+                            Ok(mut player_move) => {
+                                if player_move.initial_piece_coordinates.board_index % 6 == 0 && (player_move.final_piece_bit < 8 || player_move.final_piece_bit > 55) {
+                                    player_move.promotion = Some(choose_promotion(&mut lcd, &mut input, &mut cycle_counter, &mut delay));
+                                }
                                 piece_physical_move = player_move;
                                 break;
                             },
 
-                            // If there was an error with the move make the player revert the move so they can try again
-                            Err(pieces_moved) => {
-                                lcd.clear(&mut delay);
-                                lcd.set_cursor(&mut delay, [0, 0]);
-                                lcd.print(&mut delay, "Invalid move");
-                                lcd.set_cursor(&mut delay, [0, 1]);
-                                lcd.print(&mut delay, "Please revert");
+                            // Do nothing if the board has not changed, or the player is still holding a piece
+                            Err(chess2::hall_moves::InferenceError::NoMove) | Err(chess2::hall_moves::InferenceError::PieceLifted) => continue,
 
-                                show_bitboard_move(physical_bitboard, &mut grid_sr, &hall_sensor, led_strobe_us, &mut delay);
-                                lcd.clear(&mut delay);
-                                piece_removed = false;
-                                button.press(&mut cycle_counter);
+                            // If the changes to the board aren't a move make the player revert them so they can try again
+                            Err(chess2::hall_moves::InferenceError::Unrecognised) => {
+                                reject_move("Illegal move", physical_bitboard, hall_tracker.bitboard() ^ physical_bitboard, &mut lcd, &mut grid, led_strobe_us, &mut delay);
+                                hall_tracker = chess2::hall_moves::HallTracker::new(physical_bitboard);
+                                embedded::led_grid::take_events(&mut grid);
+                                held_piece_bit = None;
+                                input.press(&mut cycle_counter);
                             },
                         }                        
                     }
                 }
+                embedded::led_grid::set_frame(0);
             } else {
                 lcd.set_cursor(&mut delay, [0, 0]);
-                lcd.print(&mut delay, "Computers turn");
+                match clock {
+                    Some(clock) => {
+                        let af = arrform!(32, "{}", clock.display());
+                        lcd.print(&mut delay, af.as_str());
+                    },
+                    None => lcd_print_turn(&mut lcd, &mut delay, &last_moves, "Computers turn"),
+                }
                 lcd.set_cursor(&mut delay, [0, 1]);
                 lcd.print(&mut delay, "(");
-                lcd_print_team(&mut lcd, &mut delay, !player_white);
+                lcd_print_team(&mut lcd, &mut delay, board.whites_move);
                 lcd.print(&mut delay, ")");
+                if clock.is_some() {
+                    lcd.print_glyph(&mut delay, Glyph::Hourglass); // The team's clock is running
+                }
 
-                cycle_counter.update();
-                let start_cycles = cycle_counter.cycles;
-
-                // Generate a move which takes no longer than max_search_times[search_time_index] and has a maximum search depth of max_search_depth
-                piece_internal_move = algorithm::gen_best_move(
-                    true,
-                    &mut cycle_counter,
-                    &start_cycles,
-                    &chess2::embedded::ms_to_cycles(max_search_times[search_time_index], clock_mhz as u64),
-                    max_search_depth,
-                    0,
-                    0,
-                    algorithm::AlphaBeta::new(),
-                    &opening_heatmap,
-                    board,
-                    &pieces_info,
-                ).piece_move.unwrap();
+                // While the game is in the opening play moves from the opening book, they are instant and better than searched moves
+                let mut book_move = None;
+                if board.half_moves < chess2::opening_book::BOOK_MAX_HALF_MOVES {
+                    cycle_counter.update();
+                    book_move = chess2::opening_book::probe_book(&board, cycle_counter.cycles);
+                }
+
+                match book_move {
+                    Some(piece_move) => piece_internal_move = piece_move,
+                    None => {
+
+                        // Generate a move which takes no longer than the search time and has a maximum search depth of max_search_depth
+                        // In a timed game the search time comes from the computer's clock (see algorithm::move_time_ms), otherwise it's the search time from the settings
+                        let search_time_ms;
+                        match clock {
+                            Some(clock) => search_time_ms = clock.move_time_ms(board.whites_move),
+                            None => search_time_ms = settings.search_time_ms(),
+                        }
+
+                        // In demo mode each team searches to its own depth
+                        let search_depth;
+                        if demo {
+                            search_depth = settings.demo_depth(board.whites_move);
+                        } else {
+                            search_depth = max_search_depth;
+                        }
+
+                        let search_params = algorithm::SearchParams {
+                            max_depth: search_depth,
+                            max_elapsed_cycles: chess2::embedded::ms_to_cycles(search_time_ms, clock_mhz as u64),
+                            ..algorithm::SearchParams::new()
+                        };
+
+                        STOP_SEARCH.store(false, Ordering::Relaxed);
+
+                        // Nobody moves pieces while the computer is thinking, so the hall sensors aren't scanned during the search (showing the move scans them again)
+                        embedded::led_grid::set_scanning(false);
+
+                        // Replace the team with what the computer is thinking, the row is only printed when it changes so the search isn't slowed down
+                        let mut thinking = chess2::thinking::Thinking::new();
+                        let mut show_progress = |progress: &algorithm::SearchProgress| {
+                            if thinking.update(progress, clock_mhz as u64) {
+                                lcd_print_thinking(&mut lcd, &mut delay, &thinking, &board, &pieces_info);
+                            }
+                        };
+
+                        let best_move = algorithm::gen_best_move(
+                            board,
+                            &search_params,
+                            &mut algorithm::SearchData {
+                                cycle_counter: &mut cycle_counter,
+                                start_cycles: 0,
+                                nodes: 0,
+                                stop: &STOP_SEARCH,
+                                progress: Some(&mut show_progress),
+                                capture_history: &mut capture_history,
+                                hash_moves: &mut hash_moves,
+                                eval_cache: &mut eval_cache,
+                                pawn_cache: &mut pawn_cache,
+                                opening_heatmap: &opening_heatmap,
+                                pieces_info: &pieces_info,
+                            },
+                        );
+
+                        match best_move {
+                            Ok(piece_move) => piece_internal_move = piece_move,
+
+                            // The computer has no legal moves
+                            // new_turn normally ends the game before this can happen, this stops the firmware from hard faulting if it doesn't
+                            Err(game_end) => {
+                                embedded::buzzer::play(Sound::GameOver);
+                                lcd.clear(&mut delay);
+                                lcd.set_cursor(&mut delay, [0, 0]);
+                                match game_end {
+                                    algorithm::GameEnd::Checkmate => {
+                                        lcd.print(&mut delay, "Game over");
+                                        lcd.set_cursor(&mut delay, [0, 1]);
+                                        lcd_print_team(&mut lcd, &mut delay, !board.whites_move);
+                                        lcd.print(&mut delay, " team wins");
+                                    },
+                                    algorithm::GameEnd::Stalemate => {
+                                        lcd.print(&mut delay, "Game over (draw)");
+                                        lcd.set_cursor(&mut delay, [0, 1]);
+                                        lcd.print(&mut delay, "Stalemate");
+                                    },
+                                }
+
+                                // Once the player presses the button end the game
+                                loop {
+                                    if input.press(&mut cycle_counter) {
+                                        break 'game;
+                                    }
+                                }
+                            },
+                        }
+                    },
+                }
+            }
+
+            // The computer loses if its search used the last of its time
+            if !players_turn {
+                match clock.as_mut() {
+                    Some(clock) => {
+                        cycle_counter.update();
+                        clock.update(embedded::cycles_to_ms(cycle_counter.cycles, clock_mhz as u64));
+
+                        match clock.flagged() {
+                            Some(white_flagged) => {
+                                game.time_forfeit(white_flagged);
+                                show_time_forfeit(&game, &mut lcd, &mut input, &mut cycle_counter, &mut delay);
+                                break 'game;
+                            },
+                            None => (),
+                        }
+                    },
+                    None => (),
+                }
             }
 
             // Set piece_internal / piece_physical move (whichever hasn't been updated yet)
@@ -377,63 +990,64 @@ fn main() -> ! {
             } else {
 
                 // When the player is black the physical and internal boards are opposite orientations so the moves have to flipped
+                // infer_move allready found the board index of the player's piece on the internal board, so only the bits are flipped
                 if players_turn {
                     piece_internal_move = piece_physical_move.flip();
-                    piece_internal_move.initial_piece_coordinates.board_index = chess2::find_board_index(&board, piece_internal_move.initial_piece_coordinates.bit).unwrap(); // Update board index
                 } else {
                     piece_physical_move = piece_internal_move.flip();
                 } 
             }
 
-            // Get friendly and enemy kings
-            let friendly_king_index;
-            let enemy_king_index;
-            if board.whites_move {
-                friendly_king_index = 5;
-                enemy_king_index = 11;
-            } else {
-                friendly_king_index = 11;
-                enemy_king_index = 5;
-            }
+            use chess2::board::move_generator::TurnError;
 
-            let friendly_king = board_representation::BoardCoordinates {
-                board_index: friendly_king_index,
-                bit: chess2::find_bit_on(board.board[friendly_king_index], 0),
-            };
+            // Play the move, the game detects every way the game can end
+            let new_turn_board = game.play(&piece_internal_move, &pieces_info);
 
-            let enemy_king = board_representation::BoardCoordinates {
-                board_index: enemy_king_index,
-                bit: chess2::find_bit_on(board.board[enemy_king_index], 0),
-            };
+            match new_turn_board {
+                Ok(()) => {
 
-            use chess2::board::move_generator;
-            use move_generator::TurnError;
+                    // Add the increment of the team that moved and start the other team's time
+                    match clock.as_mut() {
+                        Some(clock) => {
+                            cycle_counter.update();
+                            clock.end_turn(embedded::cycles_to_ms(cycle_counter.cycles, clock_mhz as u64));
+                        },
+                        None => (),
+                    }
 
-            // Get new board after turn has been made
-            let team_bitboards = chess2::TeamBitboards::new(friendly_king.board_index, &board);
-            let enemy_attacks = move_generator::gen_enemy_attacks(&friendly_king, team_bitboards, &board, &pieces_info);
-            let new_turn_board = move_generator::new_turn(&piece_internal_move.initial_piece_coordinates, piece_internal_move.final_piece_bit, friendly_king, &enemy_king, &enemy_attacks, team_bitboards, board, &pieces_info);
+                    // Save the move with the time left after it, so the game can be resumed if the power is lost
+                    save_game(&mut save_writer, &mut flash_storage, &game, clock.as_ref(), false);
 
-            match new_turn_board {
-                Ok(new_board) => {
+                    // Checks have their own sound, so the player knows their king is in check
+                    if game.board.is_check(&pieces_info) {
+                        embedded::buzzer::play(Sound::Check);
+                    } else {
+                        embedded::buzzer::play(Sound::MoveMade);
+                    }
 
                     // Get what the phsysical bitboard should be after the turn is made
-                    let mut new_physical_bitboard = new_board.to_bitboard();
+                    let mut new_physical_bitboard = game.board.to_bitboard();
 
                     if !player_white {
                         new_physical_bitboard = chess2::flip_bitboard(new_physical_bitboard); // Flip the bitboard to physical board perspective
                     }
 
                     // Show computer move
+                    // A long press during a demo move stops the demo
                     if !players_turn {
-                        show_move(new_physical_bitboard, &piece_physical_move, &mut grid_sr, &hall_sensor, led_strobe_us, &mut delay)
+                        lcd_print_promotion(&mut lcd, &mut delay, &piece_internal_move);
+                        if demo {
+                            if show_demo_move(new_physical_bitboard, &piece_physical_move, &mut grid, &mut input, &mut cycle_counter, clock_mhz as u64) {
+                                break 'game;
+                            }
+                        } else {
+                            show_move(new_physical_bitboard, &piece_physical_move, &mut grid)
+                        }
                     }
-
-                    board = new_board;
                 },
                 Err(error) => {                    
                     match error {
-                        TurnError::Win => {
+                        TurnError::Checkmate | TurnError::KingOfTheHill | TurnError::ThreeCheck | TurnError::Stalemate | TurnError::FiftyMoveDraw | TurnError::InsufficientMaterial | TurnError::Repetition => {
 
                             // Show computer move
                             if !players_turn {
@@ -451,84 +1065,671 @@ fn main() -> ! {
                                     new_physical_bitboard = chess2::flip_bitboard(new_physical_bitboard); // Flip the bitboard to physical board perspective
                                 }
 
-                                show_move(new_physical_bitboard, &piece_physical_move, &mut grid_sr, &hall_sensor, led_strobe_us, &mut delay)
+                                lcd_print_promotion(&mut lcd, &mut delay, &piece_internal_move);
+                                if demo {
+                                    show_demo_move(new_physical_bitboard, &piece_physical_move, &mut grid, &mut input, &mut cycle_counter, clock_mhz as u64);
+                                } else {
+                                    show_move(new_physical_bitboard, &piece_physical_move, &mut grid)
+                                }
                             }
 
-                            // Print the winning team to the lcd
+                            embedded::buzzer::play(Sound::GameOver);
                             lcd.clear(&mut delay);
                             lcd.set_cursor(&mut delay, [0, 0]);
-                            lcd.print(&mut delay, "Game over");
-                            lcd.set_cursor(&mut delay, [0, 1]);
-                            lcd_print_team(&mut lcd, &mut delay, board.whites_move);
-                            lcd.print(&mut delay, " team wins");
+                            if error.is_win() {
 
-                            // Once the player presses the button end the game
-                            loop {
-                                if button.press(&mut cycle_counter) {
-                                    break 'game;
+                                // Print the winning team to the lcd
+                                lcd.print(&mut delay, "Game over");
+                                lcd.set_cursor(&mut delay, [0, 1]);
+                                lcd_print_team(&mut lcd, &mut delay, board.whites_move);
+                                lcd.print(&mut delay, " team wins");
+                            } else {
+
+                                // Print the reason for the draw to the lcd
+                                lcd.print(&mut delay, "Game over (draw)");
+                                lcd.set_cursor(&mut delay, [0, 1]);
+                                match error {
+                                    TurnError::Stalemate => lcd.print(&mut delay, "Stalemate"),
+                                    TurnError::FiftyMoveDraw => lcd.print(&mut delay, "Fifty move rule"),
+                                    TurnError::InsufficientMaterial => lcd.print(&mut delay, "Insufficient mat"),
+                                    TurnError::Repetition => lcd.print(&mut delay, "Repetition"),
+                                    _ => (),
                                 }
                             }
-                        },
-                        TurnError::Draw => {
-                            lcd.clear(&mut delay);
-                            lcd.set_cursor(&mut delay, [0, 0]);
-                            lcd.print(&mut delay, "Game over (draw)");
 
                             // Once the player presses the button end the game
                             loop {
-                                if button.press(&mut cycle_counter) {
+                                if input.press(&mut cycle_counter) {
                                     break 'game;
                                 }
                             }
                         },
 
                         // When there is an invalid move error make the player revert the turn and try again
-                        TurnError::InvalidMove => {
-                            lcd.clear(&mut delay);
-                            lcd.set_cursor(&mut delay, [0, 0]);
-                            lcd.print(&mut delay, "Invalid move");
-                            lcd.set_cursor(&mut delay, [0, 1]);
-                            lcd.print(&mut delay, "Please revert");
-
-                            show_bitboard_move(physical_bitboard, &mut grid_sr, &hall_sensor, led_strobe_us, &mut delay);
-                            button.press(&mut cycle_counter);
-                            continue;
-                        },
-                        TurnError::InvalidMoveCheck => {
-                            lcd.clear(&mut delay);
-                            lcd.set_cursor(&mut delay, [0, 0]);
-                            lcd.print(&mut delay, "King in check");
-                            lcd.set_cursor(&mut delay, [0, 1]);
-                            lcd.print(&mut delay, "Please revert");
+                        // The bits the piece moved from and to are flashed, as well as any other bits that changed (e.g. a captured piece)
+                        TurnError::InvalidMove | TurnError::InvalidMoveCheck => {
+                            let message;
+                            if error == TurnError::InvalidMoveCheck {
+                                message = "King in check";
+                            } else {
+                                message = "Illegal move";
+                            }
 
-                            show_bitboard_move(physical_bitboard, &mut grid_sr, &hall_sensor, led_strobe_us, &mut delay);
-                            button.press(&mut cycle_counter);
+                            let current_bitboard = embedded::led_grid::read_board_halls(&mut grid);
+                            let offending_bitboard = 1 << piece_physical_move.initial_piece_coordinates.bit | 1 << piece_physical_move.final_piece_bit | (current_bitboard ^ physical_bitboard);
+                            reject_move(message, physical_bitboard, offending_bitboard, &mut lcd, &mut grid, led_strobe_us, &mut delay);
+                            input.press(&mut cycle_counter);
                             continue;
                         },
                     }
                 },
             }
 
-            // Draw game based on half move clock after the move has taken place
-            // This is so checkmates made this move take priority over the half move draw
-            if board.half_move_clock >= 100 {
-                lcd.clear(&mut delay);
-                lcd.set_cursor(&mut delay, [0, 0]);
-                lcd.print(&mut delay, "Game over (draw)");
+            input.press(&mut cycle_counter);
+        }
+        embedded::led_grid::set_frame(0);
+
+        // The game has ended, so there is nothing to resume
+        // The result is saved with the game, so the game can still be sent from the menu until the next game starts
+        let finished;
+        match save_writer.as_mut() {
+            Some(writer) => finished = writer.finish(&mut flash_storage.save(), &game).is_ok(),
+            None => finished = false,
+        }
+        if !finished {
+            chess2::save::clear(&mut flash_storage.save()).ok();
+        }
+
+        // Count the result of the game, games without a result (e.g. a stopped demo) aren't stored
+        if game.result != chess2::pgn::GameResult::Unfinished {
+            statistics.add_game(&settings, game.result);
+            chess2::statistics::store(&mut flash_storage.records(), &statistics).ok();
+        }
+
+        // Print the game as PGN to the log (see log.rs), so every game (including two player games) can be saved from the debugger
+        print_pgn(&game, &pieces_info);
+
+        // Review the game with short searches, then show the result and the blunders until the player long presses the button
+        if game.len() > 0 {
+            lcd.clear(&mut delay);
+            lcd.set_cursor(&mut delay, [0, 0]);
+            lcd.print(&mut delay, "Reviewing game");
+
+            let search_params = algorithm::SearchParams {
+                max_elapsed_cycles: chess2::embedded::ms_to_cycles(REVIEW_SEARCH_MS, clock_mhz as u64),
+                ..algorithm::SearchParams::new()
+            };
+
+            STOP_SEARCH.store(false, Ordering::Relaxed);
+
+            let half_moves = game.len();
+            let mut print_progress = |half_move: usize| {
                 lcd.set_cursor(&mut delay, [0, 1]);
-                lcd.print(&mut delay, "Fifty move rule");
-                break 'game;
+                let af = arrform!(32, "Move {}/{}", half_move + 1, half_moves);
+                lcd.print(&mut delay, af.as_str());
+            };
+
+            let review = chess2::review::review_game(
+                &game,
+                &search_params,
+                &mut algorithm::SearchData {
+                    cycle_counter: &mut cycle_counter,
+                    start_cycles: 0,
+                    nodes: 0,
+                    stop: &STOP_SEARCH,
+                    progress: None,
+                    capture_history: &mut capture_history,
+                    hash_moves: &mut hash_moves,
+                    eval_cache: &mut eval_cache,
+                    pawn_cache: &mut pawn_cache,
+                    opening_heatmap: &opening_heatmap,
+                    pieces_info: &pieces_info,
+                },
+                &mut print_progress,
+            );
+
+            show_review(&game, &review, &pieces_info, &mut lcd, &mut input, &mut cycle_counter, clock_mhz as u64, &mut delay);
+        }
+    }
+}
+
+// Saves the last move of the game, or a move being taken back if undo is true (see save::SaveWriter)
+// If the game can't be saved the save is erased and the rest of the game isn't saved, so an older position of the game can't be resumed
+fn save_game(save_writer: &mut Option<chess2::save::SaveWriter>, flash_storage: &mut embedded::flash_storage::Flash, game: &chess2::game::Game, clock: Option<&chess2::clock::Clock>, undo: bool) {
+    let result;
+    match save_writer.as_mut() {
+        Some(writer) => {
+            if undo {
+                result = writer.push_undo(&mut flash_storage.save(), game, clock);
+            } else {
+                result = writer.push_move(&mut flash_storage.save(), game, clock);
             }
+        },
+        None => return,
+    }
+
+    if result.is_err() {
+        chess2::save::clear(&mut flash_storage.save()).ok();
+        *save_writer = None;
+    }
+}
+
+// Asks the player if they want to resume the saved game
+// Returns true if the button is long pressed, false if it's pressed
+fn ask_resume(lcd: &mut chess2::embedded::character_lcd::Lcd, input: &mut embedded::input::Input, cycle_counter: &mut embedded::cycle_counter::Counter, delay: &mut Delay) -> bool {
+    lcd.clear(delay);
+    lcd.set_cursor(delay, [0, 0]);
+    lcd.print(delay, "Resume game?");
+    lcd.set_cursor(delay, [0, 1]);
+    lcd.print(delay, "Hold yes, tap no");
+
+    loop {
+        match input.gesture(cycle_counter) {
+            Some(gesture) => return gesture.is_long(),
+            None => (),
+        }
+    }
+}
+
+// Runs the UCI engine (see uci.rs) over the serial port, so a chess GUI on a PC can use the engine on the board
+// Searches use the same heuristics and caches as the games on the board, and are no deeper than max_search_depth
+// Returns to the menu when the quit command is received, or the button is long pressed while the engine isn't searching
+fn run_uci<'b>(
+    serial: &mut embedded::serial::Serial,
+    lcd: &mut chess2::embedded::character_lcd::Lcd,
+    input: &mut embedded::input::Input,
+    cycle_counter: &mut embedded::cycle_counter::Counter,
+    capture_history: &mut algorithm::CaptureHistory,
+    hash_moves: &mut algorithm::HashMoves<'b>,
+    eval_cache: &mut chess2::evaluation::EvalCache<'b>,
+    pawn_cache: &mut chess2::evaluation::EvalCache<'b>,
+    opening_heatmap: &[[i16; 64]; 12],
+    pieces_info: &[chess2::piece::constants::PieceInfo; 12],
+    max_search_depth: usize,
+    clock_mhz: u64,
+    delay: &mut Delay,
+) {
+    use chess2::uci::Command;
+
+    lcd.clear(delay);
+    lcd.set_cursor(delay, [0, 0]);
+    lcd.print(delay, "UCI engine");
+    lcd.set_cursor(delay, [0, 1]);
+    lcd.print(delay, "(Hold to exit)");
+
+    let mut position = chess2::uci::Position {
+        board: board_representation::Board::start(),
+        ended: false,
+    };
+
+    loop {
+        match input.gesture(cycle_counter) {
+            Some(gesture) if gesture.is_long() => return,
+            _ => (),
+        }
+
+        let command;
+        match serial.read_line() {
+            Some(line) => command = chess2::uci::parse_command(line, pieces_info),
+            None => continue,
+        }
+
+        match command {
+            Ok(Command::Uci) => {
+                chess2::uci::write_id(serial).ok();
+            },
+            Ok(Command::IsReady) => {
+                serial.write_str("readyok\n").ok();
+            },
+            Ok(Command::NewGame) => {
+                *capture_history = algorithm::CaptureHistory::new();
+                position.board = board_representation::Board::start();
+                position.ended = false;
+            },
+            Ok(Command::Position(new_position)) => position = new_position,
+            Ok(Command::Go(go_params)) => {
+                let search_params = go_params.search_params(
+                    &algorithm::SearchParams {
+                        max_depth: max_search_depth,
+                        ..algorithm::SearchParams::new()
+                    },
+                    position.board.whites_move,
+                    clock_mhz,
+                );
+
+                lcd.set_cursor(delay, [0, 0]);
+                lcd.print(delay, "Thinking...     ");
+
+                STOP_SEARCH.store(false, Ordering::Relaxed);
+
+                // A position that has ended has no move to search for
+                let best_move;
+                if position.ended {
+                    best_move = None;
+                } else {
+                    let mut print_info = |progress: &algorithm::SearchProgress| {
+                        chess2::uci::write_info(serial, progress, clock_mhz).ok();
+                    };
+
+                    let result = algorithm::gen_best_move(
+                        position.board,
+                        &search_params,
+                        &mut algorithm::SearchData {
+                            cycle_counter: cycle_counter,
+                            start_cycles: 0,
+                            nodes: 0,
+                            stop: &STOP_SEARCH,
+                            progress: Some(&mut print_info),
+                            capture_history: capture_history,
+                            hash_moves: hash_moves,
+                            eval_cache: eval_cache,
+                            pawn_cache: pawn_cache,
+                            opening_heatmap: opening_heatmap,
+                            pieces_info: pieces_info,
+                        },
+                    );
+                    best_move = result.ok();
+                }
 
-            // Once the early and mid phases of the game are done reset the opening heatmap
-            // After this point no heatmap will affect the computer moves
-            if board.half_moves > 20 {
-                opening_heatmap = [[0i16; 64]; 12];
+                // The move of an infinite search is only sent once the search is stopped, even if it has reached its depth
+                if go_params.infinite {
+                    while !STOP_SEARCH.load(Ordering::Relaxed) {
+                        match input.gesture(cycle_counter) {
+                            Some(gesture) if gesture.is_long() => break,
+                            _ => (),
+                        }
+                    }
+                }
+
+                chess2::uci::write_best_move(serial, best_move.as_ref()).ok();
+
+                lcd.set_cursor(delay, [0, 0]);
+                match best_move {
+                    Some(piece_move) => {
+                        let af = arrform!(32, "Best: {}{}{}     ", Glyph::piece(piece_move.initial_piece_coordinates.board_index).char(), chess2::bit_to_ccn(piece_move.initial_piece_coordinates.bit), chess2::bit_to_ccn(piece_move.final_piece_bit));
+                        lcd.print(delay, af.as_str());
+                    },
+                    None => lcd.print(delay, "No legal moves  "),
+                }
+            },
+
+            // A stop is handled by the USART1 interrupt while the engine is searching, otherwise there is nothing to stop
+            Ok(Command::Stop) | Ok(Command::Unknown) => (),
+            Ok(Command::Quit) => return,
+            Err(()) => {
+                serial.write_str("info string invalid command\n").ok();
+            },
+        }
+    }
+}
+
+// Runs the debug console (see console.rs) over the serial port, for bringing up and debugging the board
+// The leds lit by the leds command stay lit until they are turned off, while the console waits for the next command
+// Returns to the menu when the quit command is received, or the button is long pressed while the console isn't searching
+fn run_console<'b>(
+    serial: &mut embedded::serial::Serial,
+    lcd: &mut chess2::embedded::character_lcd::Lcd,
+    grid: &mut embedded::led_grid::Grid,
+    input: &mut embedded::input::Input,
+    cycle_counter: &mut embedded::cycle_counter::Counter,
+    capture_history: &mut algorithm::CaptureHistory,
+    hash_moves: &mut algorithm::HashMoves<'b>,
+    eval_cache: &mut chess2::evaluation::EvalCache<'b>,
+    pawn_cache: &mut chess2::evaluation::EvalCache<'b>,
+    opening_heatmap: &[[i16; 64]; 12],
+    pieces_info: &[chess2::piece::constants::PieceInfo; 12],
+    max_search_depth: usize,
+    clock_mhz: u64,
+    delay: &mut Delay,
+) {
+    use chess2::console::Command;
+
+    lcd.clear(delay);
+    lcd.set_cursor(delay, [0, 0]);
+    lcd.print(delay, "Console");
+    lcd.set_cursor(delay, [0, 1]);
+    lcd.print(delay, "(Hold to exit)");
+
+    let mut board = board_representation::Board::start();
+    serial.write_str(chess2::console::HELP).ok();
+
+    loop {
+        match input.gesture(cycle_counter) {
+            Some(gesture) if gesture.is_long() => break,
+            _ => (),
+        }
+
+        let command;
+        match serial.read_line() {
+            Some(line) => command = chess2::console::parse_command(line),
+            None => continue,
+        }
+
+        match command {
+            Ok(Command::Fen(new_board)) => board = new_board,
+            Ok(Command::Display) => {
+                write!(serial, "{}\n", board).ok();
+            },
+            Ok(Command::Go(depth)) => {
+                // The depth is kept within 1..=max_search_depth like the uci frontend (see uci::GoParams::search_params), a depth of 0 would return no move
+                let search_params = algorithm::SearchParams {
+                    max_depth: core::cmp::max(core::cmp::min(depth.unwrap_or(max_search_depth), max_search_depth), 1),
+                    ..algorithm::SearchParams::new()
+                };
+
+                STOP_SEARCH.store(false, Ordering::Relaxed);
+
+                let mut print_info = |progress: &algorithm::SearchProgress| {
+                    chess2::uci::write_info(serial, progress, clock_mhz).ok();
+                };
+
+                let best_move = algorithm::gen_best_move(
+                    board,
+                    &search_params,
+                    &mut algorithm::SearchData {
+                        cycle_counter: cycle_counter,
+                        start_cycles: 0,
+                        nodes: 0,
+                        stop: &STOP_SEARCH,
+                        progress: Some(&mut print_info),
+                        capture_history: capture_history,
+                        hash_moves: hash_moves,
+                        eval_cache: eval_cache,
+                        pawn_cache: pawn_cache,
+                        opening_heatmap: opening_heatmap,
+                        pieces_info: pieces_info,
+                    },
+                );
+                chess2::uci::write_best_move(serial, best_move.ok().as_ref()).ok();
+            },
+            Ok(Command::Perft(depth)) => {
+                chess2::console::write_perft(serial, &board, depth, pieces_info).ok();
+            },
+            Ok(Command::Eval) => {
+                chess2::console::write_eval(serial, &board, pawn_cache, pieces_info).ok();
+            },
+            Ok(Command::Halls) => {
+                let physical_bitboard = embedded::led_grid::read_board_halls(grid);
+                chess2::console::write_bitboard(serial, physical_bitboard).ok();
+            },
+            Ok(Command::Leds(bitboard)) => embedded::led_grid::set_frame(bitboard),
+            Ok(Command::Help) => {
+                serial.write_str(chess2::console::HELP).ok();
+            },
+            Ok(Command::Quit) => break,
+            Err(()) => {
+                serial.write_str("Invalid command, type help for the commands\n").ok();
+            },
+        }
+    }
+
+    embedded::led_grid::set_frame(0); // Turn the leds off
+}
+
+// Prints a game as PGN to the log, with an empty line before and after it so games printed one after another are separate
+fn print_pgn(game: &chess2::game::Game, pieces_info: &[chess2::piece::constants::PieceInfo; 12]) {
+    let mut log_writer = chess2::log::LogWriter::new();
+    log!("");
+    game.record().write_pgn(&mut log_writer, pieces_info).ok();
+    log_writer.flush();
+    log!("");
+}
+
+// Sends the last saved game (see save::load_last) as PGN to the log, so a game can be archived after the board has been turned off
+// The game is the game in progress when the board was turned off, or the last game that was played
+fn export_game(flash_storage: &mut embedded::flash_storage::Flash, pieces_info: &[chess2::piece::constants::PieceInfo; 12], lcd: &mut chess2::embedded::character_lcd::Lcd, delay: &mut Delay) {
+    lcd.clear(delay);
+    lcd.set_cursor(delay, [0, 0]);
+    match chess2::save::load_last(&flash_storage.save(), pieces_info) {
+        Some(saved) => {
+            print_pgn(&saved.game, pieces_info);
+            lcd.print(delay, "Game sent");
+            lcd.set_cursor(delay, [0, 1]);
+            let af = arrform!(32, "{} moves {}", (saved.game.len() + 1) / 2, saved.game.result.to_str());
+            lcd.print(delay, af.as_str());
+        },
+        None => lcd.print(delay, "No saved game"),
+    }
+    delay.delay_ms(2000u16);
+}
+
+// Shows the moves of the last saved game as pages on the lcd, 2 full moves to a page
+fn show_move_history(flash_storage: &mut embedded::flash_storage::Flash, pieces_info: &[chess2::piece::constants::PieceInfo; 12], lcd: &mut chess2::embedded::character_lcd::Lcd, input: &mut embedded::input::Input, cycle_counter: &mut embedded::cycle_counter::Counter, clock_mhz: u64, delay: &mut Delay) {
+    let game;
+    match chess2::save::load_last(&flash_storage.save(), pieces_info) {
+        Some(saved) if saved.game.len() > 0 => game = saved.game,
+        _ => {
+            lcd.clear(delay);
+            lcd.set_cursor(delay, [0, 0]);
+            lcd.print(delay, "No saved game");
+            delay.delay_ms(2000u16);
+            return;
+        },
+    }
+
+    show_pages(chess2::lcd_text::move_pages(&game), lcd, input, cycle_counter, clock_mhz, delay, |page, top, bottom| {
+        chess2::lcd_text::write_move_page(top, bottom, &game, page, pieces_info).ok();
+    });
+}
+
+// Shows pages of 2 rows on the lcd until the player exits with a long press, see lcd_text::Pager for the gestures that move through the pages
+// write_page writes the rows of a page, rows longer than the lcd scroll across it while the page is shown
+// The pages are redrawn only when they change or a row scrolls, so nothing waits on the delay and no gesture is missed
+fn show_pages<F: FnMut(usize, &mut Row, &mut Row)>(pages: usize, lcd: &mut chess2::embedded::character_lcd::Lcd, input: &mut embedded::input::Input, cycle_counter: &mut embedded::cycle_counter::Counter, clock_mhz: u64, delay: &mut Delay, mut write_page: F) {
+    let mut pager = Pager::new(pages);
+    let mut rows = [Row::new(), Row::new()];
+    let mut marquees = [Marquee::new(0, 0), Marquee::new(0, 0)];
+    let mut redraw = true;
+
+    loop {
+        cycle_counter.update();
+        let now_ms = embedded::cycles_to_ms(cycle_counter.cycles, clock_mhz);
+
+        if redraw {
+            let [top, bottom] = &mut rows;
+            top.clear();
+            bottom.clear();
+            write_page(pager.page, top, bottom);
+
+            lcd.clear(delay);
+            for i in 0..2 {
+                marquees[i] = Marquee::new(rows[i].len(), now_ms);
+                lcd.set_cursor(delay, [0, i as u8]);
+                lcd.print(delay, marquees[i].window(rows[i].as_str()));
+            }
+            redraw = false;
+        }
+
+        // Scroll the rows that are longer than the lcd, the rest of the row is cleared in case the window got shorter
+        for i in 0..2 {
+            if marquees[i].tick(now_ms) {
+                lcd.set_cursor(delay, [0, i as u8]);
+                let af = arrform!(32, "{:<16}", marquees[i].window(rows[i].as_str()));
+                lcd.print(delay, af.as_str());
+            }
+        }
+
+        let action;
+        match input.gesture(cycle_counter) {
+            Some(gesture) => action = pager.input(gesture),
+            None => action = pager.scroll(input.scroll()),
+        }
+        match action {
+            PagerAction::Changed => redraw = true,
+            PagerAction::Exit => return,
+            PagerAction::None => (),
+        }
+    }
+}
+
+// Shows the review of a game (see review.rs) as pages on the lcd
+// The first page has the result and number of moves, the second the number of blunders of each team, then there is a page for every blunder with the best move and how much worse the blunder was
+// The last pages are the moves of the game, 2 full moves to a page
+fn show_review(game: &chess2::game::Game, review: &chess2::review::Review, pieces_info: &[chess2::piece::constants::PieceInfo; 12], lcd: &mut chess2::embedded::character_lcd::Lcd, input: &mut embedded::input::Input, cycle_counter: &mut embedded::cycle_counter::Counter, clock_mhz: u64, delay: &mut Delay) {
+    let summary_pages = 2 + review.len();
+    let pages = summary_pages + chess2::lcd_text::move_pages(game);
+
+    show_pages(pages, lcd, input, cycle_counter, clock_mhz, delay, |page, top, bottom| {
+        match page {
+            0 => {
+                write!(top, "Result {}", game.result.to_str()).ok();
+                write!(bottom, "{} moves", (game.len() + 1) / 2).ok();
+            },
+            1 => {
+                write!(top, "Blunders W{} B{}", review.blunder_count(true), review.blunder_count(false)).ok();
+                write!(bottom, "(Hold to exit)").ok();
+            },
+            _ if page < summary_pages => match review.blunder(page - 2) {
+                Some(blunder) => {
+                    // Black moves are numbered with ... the same as PGN
+                    let dots;
+                    if blunder.white {
+                        dots = ".";
+                    } else {
+                        dots = "...";
+                    }
+                    write!(top, "{}{} {}{}{}?", blunder.full_move_number, dots, Glyph::piece(blunder.played.initial_piece_coordinates.board_index).char(), chess2::bit_to_ccn(blunder.played.initial_piece_coordinates.bit), chess2::bit_to_ccn(blunder.played.final_piece_bit)).ok();
+                    write!(bottom, "Best {}{}{} -{}.{:02}", Glyph::piece(blunder.best.initial_piece_coordinates.board_index).char(), chess2::bit_to_ccn(blunder.best.initial_piece_coordinates.bit), chess2::bit_to_ccn(blunder.best.final_piece_bit), blunder.loss / 100, blunder.loss % 100).ok();
+                },
+                None => (),
+            },
+            _ => {
+                chess2::lcd_text::write_move_page(top, bottom, game, page - summary_pages, pieces_info).ok();
+            },
+        }
+    });
+}
+
+// Shows the statistics of the games played (see statistics.rs) as pages on the lcd
+// The first page has the number of games played, then there is a page for every difficulty with the player's wins, losses and draws against the computer
+fn show_statistics(statistics: &chess2::statistics::Statistics, lcd: &mut chess2::embedded::character_lcd::Lcd, input: &mut embedded::input::Input, cycle_counter: &mut embedded::cycle_counter::Counter, clock_mhz: u64, delay: &mut Delay) {
+    let pages = 1 + chess2::statistics::LEVELS;
+
+    show_pages(pages, lcd, input, cycle_counter, clock_mhz, delay, |page, top, bottom| {
+        match page {
+            0 => {
+                write!(top, "Games {}", statistics.games).ok();
+                write!(bottom, "(Hold to exit)").ok();
+            },
+            _ => {
+                write!(top, "Difficulty {}s", chess2::settings::SEARCH_TIMES[page - 1] / 1000).ok();
+                write!(bottom, "{}", statistics.level(page - 1)).ok();
+            },
+        }
+    });
+}
+
+// Shows how many pieces the hall sensors can see and lights the leds of them, so broken sensors or leds can be found
+// Every piece lifted and placed is printed to the log, so a flickering sensor shows up even if it's too quick to see on the leds
+// Exits when the button is long pressed
+fn run_diagnostics(lcd: &mut chess2::embedded::character_lcd::Lcd, grid: &mut embedded::led_grid::Grid, input: &mut embedded::input::Input, cycle_counter: &mut embedded::cycle_counter::Counter, delay: &mut Delay) {
+    lcd.clear(delay);
+    lcd.set_cursor(delay, [0, 1]);
+    lcd.print(delay, "(Hold to exit)");
+
+    embedded::led_grid::read_board_halls(grid);
+    embedded::led_grid::take_events(grid); // Only the changes made during the diagnostics are printed
+
+    let mut shown_bitboard = None; // Bitboard on the lcd and leds, they are only written when it changes
+    loop {
+        let physical_bitboard = embedded::led_grid::read_board_halls(grid);
+
+        for event in embedded::led_grid::take_events(grid) {
+            match event {
+                chess2::hall_moves::HallEvent::Lift(bit) => log!("Lifted {}", chess2::bit_to_ccn(bit)),
+                chess2::hall_moves::HallEvent::Place(bit) => log!("Placed {}", chess2::bit_to_ccn(bit)),
             }
+        }
+
+        if shown_bitboard != Some(physical_bitboard) {
+            lcd.set_cursor(delay, [0, 0]);
+            let af = arrform!(32, "Pieces: {}  ", chess2::bits_on(physical_bitboard));
+            lcd.print(delay, af.as_str());
 
-            button.press(&mut cycle_counter);
+            embedded::led_grid::set_frame(physical_bitboard);
+            shown_bitboard = Some(physical_bitboard);
+        }
+
+        match input.gesture(cycle_counter) {
+            Some(gesture) if gesture.is_long() => break,
+            _ => (),
         }
     }
+    embedded::led_grid::set_frame(0);
+}
+
+// Prints the result of a game that ended on time, and waits for the player to press the button
+fn show_time_forfeit(game: &chess2::game::Game, lcd: &mut chess2::embedded::character_lcd::Lcd, input: &mut embedded::input::Input, cycle_counter: &mut embedded::cycle_counter::Counter, delay: &mut Delay) {
+    embedded::buzzer::play(Sound::GameOver);
+    lcd.clear(delay);
+    lcd.set_cursor(delay, [0, 0]);
+    match game.result {
+        chess2::pgn::GameResult::WhiteWin | chess2::pgn::GameResult::BlackWin => {
+            lcd.print(delay, "Out of time");
+            lcd.set_cursor(delay, [0, 1]);
+            lcd_print_team(lcd, delay, game.result == chess2::pgn::GameResult::WhiteWin);
+            lcd.print(delay, " team wins");
+        },
+        _ => {
+
+            // The team with time left can't checkmate
+            lcd.print(delay, "Game over (draw)");
+            lcd.set_cursor(delay, [0, 1]);
+            lcd.print(delay, "Out of time");
+        },
+    }
+
+    loop {
+        if input.press(cycle_counter) {
+            break;
+        }
+    }
+}
+
+// Prints the value of a move (relative to the team that would make it) to the lcd, in pawns or as the number of moves until a checkmate
+fn lcd_print_score(lcd: &mut chess2::embedded::character_lcd::Lcd, delay: &mut Delay, value: i16) {
+    match algorithm::mate_moves(value) {
+        Some(mate_moves) => {
+            if mate_moves > 0 {
+                let af = arrform!(32, "Mate in {}", mate_moves);
+                lcd.print(delay, af.as_str());
+            } else {
+                let af = arrform!(32, "Mated in {}", -mate_moves);
+                lcd.print(delay, af.as_str());
+            }
+            return;
+        },
+        None => (),
+    }
+
+    let sign;
+    if value < 0 {
+        sign = "-";
+    } else {
+        sign = "+";
+    }
+    let af = arrform!(32, "Score {}{}.{:02}", sign, value.abs() / 100, value.abs() % 100);
+    lcd.print(delay, af.as_str());
+}
+
+// Prints what the computer is thinking (see thinking.rs) on the second row of the lcd, the rest of the row is cleared
+fn lcd_print_thinking(lcd: &mut chess2::embedded::character_lcd::Lcd, delay: &mut Delay, thinking: &chess2::thinking::Thinking, board: &board_representation::Board, pieces_info: &[chess2::piece::constants::PieceInfo; 12]) {
+    let mut row = Row::new();
+    thinking.write_row(&mut row, board, pieces_info).ok();
+    lcd.set_cursor(delay, [0, 1]);
+    let af = arrform!(32, "{:<16.16}", row.as_str());
+    lcd.print(delay, af.as_str());
+}
+
+// Prints the last moves of the game (see lcd_text::write_last_moves), or whose turn it is before any moves have been played
+// The row is padded to the width of the lcd so it doesn't have to be cleared, and cut off if the moves are longer
+fn lcd_print_turn(lcd: &mut chess2::embedded::character_lcd::Lcd, delay: &mut Delay, last_moves: &Row, turn: &str) {
+    if last_moves.len() == 0 {
+        lcd.print(delay, turn);
+    } else {
+        let af = arrform!(32, "{:<16.16}", last_moves.as_str());
+        lcd.print(delay, af.as_str());
+    }
 }
 
 // Prints team (white / black) to lcd
@@ -540,26 +1741,156 @@ fn lcd_print_team(lcd: &mut chess2::embedded::character_lcd::Lcd, delay: &mut De
     }
 }
 
+// Prints the piece to swap a pawn for on the second row of the lcd when the computer underpromotes
+// The hall sensors can only see where pieces are, so the player has to be told which piece to place
+fn lcd_print_promotion(lcd: &mut chess2::embedded::character_lcd::Lcd, delay: &mut Delay, piece_move: &chess2::algorithm::Move) {
+    let piece_name;
+    match piece_move.promotion {
+        Some(1) => piece_name = "Rook  ",
+        Some(2) => piece_name = "Knight",
+        Some(3) => piece_name = "Bishop",
+        _ => return,
+    }
+
+    lcd.set_cursor(delay, [0, 1]);
+    lcd.print(delay, "Promote: ");
+    lcd.print_glyph(delay, Glyph::piece(piece_move.promotion.unwrap_or(4)));
+    lcd.print(delay, piece_name);
+}
+
+// Asks the player which piece their pawn promotes to, returns the board index of the piece for the white team
+// Short presses or the encoder move through the pieces, a long press chooses the piece
+fn choose_promotion(lcd: &mut chess2::embedded::character_lcd::Lcd, input: &mut embedded::input::Input, cycle_counter: &mut embedded::cycle_counter::Counter, delay: &mut Delay) -> usize {
+    const PIECES: [(usize, &str); 4] = [(4, "Queen "), (1, "Rook  "), (3, "Bishop"), (2, "Knight")];
+
+    lcd.clear(delay);
+    lcd.set_cursor(delay, [0, 0]);
+    lcd.print(delay, "Promote to");
+
+    let mut index = 0;
+    loop {
+        lcd.set_cursor(delay, [0, 1]);
+        lcd.print_glyph(delay, Glyph::piece(PIECES[index].0));
+        lcd.print(delay, " ");
+        lcd.print(delay, PIECES[index].1);
+
+        let steps;
+        match input.gesture(cycle_counter) {
+            Some(gesture) if gesture.is_long() => break,
+            Some(chess2::menu::Gesture::Short) => steps = 1,
+            _ => steps = input.scroll(),
+        }
+        index = (index as i32 + steps).rem_euclid(PIECES.len() as i32) as usize;
+    }
+
+    lcd.clear(delay);
+    PIECES[index].0
+}
+
 // Only exits once the physical bitboard equals the desired bitboard
 // Lights leds to show the user what pieces they need to move to do this
-fn show_bitboard_move<T: InputPin>(desired_bitboard: u64, grid_sr: &mut embedded::ShiftRegister, hall_sensor: &T, led_strobe_us: u32, delay: &mut Delay) {
+fn show_bitboard_move(desired_bitboard: u64, grid: &mut embedded::led_grid::Grid) {
     
-    let mut current_bitboard = embedded::read_board_halls(grid_sr, hall_sensor, delay); // Get bitboard of pieces on the physical board
+    let mut current_bitboard = embedded::led_grid::read_board_halls(grid); // Get bitboard of pieces on the physical board
     while current_bitboard != desired_bitboard {
-        current_bitboard = embedded::read_board_halls(grid_sr, hall_sensor, delay);
-        embedded::leds_from_bitboard(grid_sr, delay, desired_bitboard ^ current_bitboard, led_strobe_us, true);
+        embedded::led_grid::set_frame(desired_bitboard ^ current_bitboard);
+        current_bitboard = embedded::led_grid::read_board_halls(grid);
     }
+    embedded::led_grid::set_frame(0);
+}
+
+// Only exits once the physical bitboard equals the expected bitboard
+// Used when the physical board has to be changed to a position other than after a move (e.g. it got out of sync with the game, or a move was taken back)
+// The message is printed to the lcd, with the number of pieces that have to be added and removed under it, and the bits of them are lit
+fn recover_board(message: &str, expected_bitboard: u64, lcd: &mut chess2::embedded::character_lcd::Lcd, grid: &mut embedded::led_grid::Grid, delay: &mut Delay) {
+    lcd.clear(delay);
+    lcd.set_cursor(delay, [0, 0]);
+    lcd.print(delay, message);
+
+    let mut shown_changes = None; // Number of pieces to add and remove that are on the lcd, the lcd is only written when they change because writing is slow
+    loop {
+        let current_bitboard = embedded::led_grid::read_board_halls(grid); // Get bitboard of pieces on the physical board
+        if current_bitboard == expected_bitboard {
+            break;
+        }
+
+        let changes = (chess2::bits_on(expected_bitboard & !current_bitboard), chess2::bits_on(current_bitboard & !expected_bitboard));
+        if shown_changes != Some(changes) {
+            lcd.set_cursor(delay, [0, 1]);
+            let af = arrform!(32, "Add {} Remove {}  ", changes.0, changes.1);
+            lcd.print(delay, af.as_str());
+            shown_changes = Some(changes);
+        }
+
+        embedded::led_grid::set_frame(expected_bitboard ^ current_bitboard);
+    }
+    embedded::led_grid::set_frame(0);
+}
+
+// Tells the player their move can't be played, and only exits once the board has been put back to how it was before the move
+// The message is printed to the lcd and the offending bits are flashed, then the bits that still have to be fixed are lit until the board matches
+fn reject_move(message: &str, expected_bitboard: u64, offending_bitboard: u64, lcd: &mut chess2::embedded::character_lcd::Lcd, grid: &mut embedded::led_grid::Grid, led_strobe_us: u32, delay: &mut Delay) {
+    embedded::buzzer::play(Sound::IllegalMove);
+    lcd.clear(delay);
+    lcd.set_cursor(delay, [0, 0]);
+    lcd.print(delay, message);
+    lcd.set_cursor(delay, [0, 1]);
+    lcd.print(delay, "Please revert");
+
+    flash_bitboard(offending_bitboard, led_strobe_us, delay);
+    show_bitboard_move(expected_bitboard, grid);
+    lcd.clear(delay);
+}
+
+// Flashes the leds of a bitboard 3 times, to warn the player about the bits
+fn flash_bitboard(bitboard: u64, led_strobe_us: u32, delay: &mut Delay) {
+    for _ in 0..3 {
+        embedded::led_grid::set_frame(bitboard);
+        delay.delay_us(led_strobe_us);
+        embedded::led_grid::set_frame(0);
+        delay.delay_us(led_strobe_us);
+    }
+}
+
+// Lights the bits of a move in demo mode until the move has been made on the board, or DEMO_MOVE_TIMEOUT_MS has passed
+// Nobody has to be at the board during a demo, so after the timeout the game carries on without the physical board
+// Returns true if the button was long pressed, to stop the demo
+fn show_demo_move(desired_bitboard: u64, piece_physical_move: &chess2::algorithm::Move, grid: &mut embedded::led_grid::Grid, input: &mut embedded::input::Input, cycle_counter: &mut embedded::cycle_counter::Counter, clock_mhz: u64) -> bool {
+    cycle_counter.update();
+    let start_cycles = cycle_counter.cycles;
+    let timeout_cycles = embedded::ms_to_cycles(DEMO_MOVE_TIMEOUT_MS, clock_mhz);
+
+    embedded::led_grid::set_frame(1 << piece_physical_move.initial_piece_coordinates.bit | 1 << piece_physical_move.final_piece_bit);
+
+    let mut stopped = false;
+    while cycle_counter.cycles - start_cycles < timeout_cycles {
+        if embedded::led_grid::read_board_halls(grid) == desired_bitboard {
+            break;
+        }
+
+        // Checking the button also updates the cycle counter for the timeout
+        match input.gesture(cycle_counter) {
+            Some(gesture) if gesture.is_long() => {
+                stopped = true;
+                break;
+            },
+            _ => (),
+        }
+    }
+
+    embedded::led_grid::set_frame(0);
+    stopped
 }
 
 // Only exits once the piece_physical move has been made on the board
-fn show_move<T: InputPin>(desired_bitboard: u64, piece_physical_move: &chess2::algorithm::Move, grid_sr: &mut embedded::ShiftRegister, hall_sensor: &T, led_strobe_us: u32, delay: &mut Delay) {
-    let current_bitboard = embedded::read_board_halls(grid_sr, hall_sensor, delay); // Get bitboard of pieces on the physical board
+fn show_move(desired_bitboard: u64, piece_physical_move: &chess2::algorithm::Move, grid: &mut embedded::led_grid::Grid) {
+    let current_bitboard = embedded::led_grid::read_board_halls(grid); // Get bitboard of pieces on the physical board
 
     // If the bit where the piece has to move is allready occupied then the piece is performing a capture
     // When this happens make the player remove the capture piece first
     if chess2::bit_on(current_bitboard, piece_physical_move.final_piece_bit) {
-        show_bitboard_move(desired_bitboard ^ 1 << piece_physical_move.final_piece_bit, grid_sr, hall_sensor, led_strobe_us, delay);
+        show_bitboard_move(desired_bitboard ^ 1 << piece_physical_move.final_piece_bit, grid);
     }
 
-    show_bitboard_move(desired_bitboard, grid_sr, hall_sensor, led_strobe_us, delay);
+    show_bitboard_move(desired_bitboard, grid);
 }
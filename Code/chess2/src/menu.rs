@@ -0,0 +1,331 @@
+// Menu of the character lcd, for setting up a game and changing the settings of the board
+// The menu only keeps track of what is selected, so it works with anything that can show 2 rows of text and read one button
+// The firmware prints the current screen (Menu::title and Menu::value) and passes the gestures of the button to Menu::input
+//
+// Controls:
+//   Short press: moves to the next screen, or the next option while an option is being changed
+//   Double press: moves to the previous screen, or stops changing an option
+//   Long press or hold: opens the screen (starts a game or the UCI engine, sends or shows the moves of the last game, shows or resets the statistics, runs the diagnostics or console, or starts changing an option), or stops changing an option
+//   Triple press: does nothing
+//   Turning the rotary encoder (if the board has one): moves through the screens, or the options while an option is being changed (see Menu::scroll)
+//
+// Controls during a game (see game_action):
+//   Short press: makes the move on the board
+//   Double press: shows a hint
+//   Triple press: takes back the last move
+//   Long press: opens the search time menu
+//   Hold: resigns
+//   When a pawn is promoted: short presses or the encoder move through the pieces, a long press chooses the piece (see main.rs)
+//
+// Boards can have more buttons (see ButtonRole), the main button can still do everything:
+//   Confirm: opens the screen or stops changing an option in the menus, the same as the main button during a game
+//   Back: moves to the previous screen or stops changing an option in the menus, takes back the last move during a game (hold to resign)
+//   White and black clock: makes the move during a game, only the button of the team to move does anything
+
+use crate::clock;
+use crate::settings::{self, Settings};
+use crate::variant;
+
+// Ways the button can be pressed (see embedded::button::Button::gesture)
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Gesture {
+    Short,
+    Double,
+    Triple,
+    Long, // Held down then released
+    Hold, // Held down for longer than a long press, returned while the button is still held
+}
+
+impl Gesture {
+    // Returns true for long presses and holds
+    // Holding the button past the hold time doesn't make a long press, so screens that only need one long gesture use this
+    pub fn is_long(&self) -> bool {
+        *self == Gesture::Long || *self == Gesture::Hold
+    }
+}
+
+// Buttons a board can have
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ButtonRole {
+    Main,
+    Confirm,
+    Back,
+    WhiteClock,
+    BlackClock,
+}
+
+// Every button role, in the order of their values
+pub const BUTTON_ROLES: [ButtonRole; 5] = [ButtonRole::Main, ButtonRole::Confirm, ButtonRole::Back, ButtonRole::WhiteClock, ButtonRole::BlackClock];
+
+// Returns the gesture of the main button that a gesture of a button is the same as outside of a game, None if the button does nothing there
+pub fn menu_gesture(role: ButtonRole, gesture: Gesture) -> Option<Gesture> {
+    match role {
+        ButtonRole::Main => Some(gesture),
+        ButtonRole::Confirm => Some(Gesture::Long),
+        ButtonRole::Back => Some(Gesture::Double),
+        ButtonRole::WhiteClock | ButtonRole::BlackClock => None,
+    }
+}
+
+// What a gesture does during a game, the gestures are mapped to actions here so the controls are all in one place
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum GameAction {
+    MakeMove, // Infers the move made on the board (see hall_moves::HallTracker::infer_move)
+    Hint,
+    Takeback,
+    SearchTimeMenu, // Changes the time the computer searches for
+    Resign,
+}
+
+// Returns the action of a gesture of a button during a game, None if the gesture does nothing
+pub fn game_action(role: ButtonRole, gesture: Gesture, whites_move: bool) -> Option<GameAction> {
+    match (role, gesture) {
+        (ButtonRole::Main, _) | (ButtonRole::Confirm, _) => match gesture {
+            Gesture::Short => Some(GameAction::MakeMove),
+            Gesture::Double => Some(GameAction::Hint),
+            Gesture::Triple => Some(GameAction::Takeback),
+            Gesture::Long => Some(GameAction::SearchTimeMenu),
+            Gesture::Hold => Some(GameAction::Resign),
+        },
+        (ButtonRole::Back, Gesture::Hold) => Some(GameAction::Resign),
+        (ButtonRole::Back, _) => Some(GameAction::Takeback),
+        (ButtonRole::WhiteClock, _) if whites_move => Some(GameAction::MakeMove),
+        (ButtonRole::BlackClock, _) if !whites_move => Some(GameAction::MakeMove),
+        (ButtonRole::WhiteClock, _) | (ButtonRole::BlackClock, _) => None,
+    }
+}
+
+// Screens of the menu
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Screen {
+    NewGame,
+    Mode,
+    Colour,
+    Difficulty,
+    HintTime, // Time the computer searches for a hint (see main.rs)
+    WhiteDepth, // Search depth of the white team in demo mode
+    BlackDepth, // Search depth of the black team in demo mode
+    TimeControl,
+    Variant,
+    LedBrightness,
+    Sound, // Turns the buzzer on or off (see sound.rs)
+    UciEngine, // Lets a chess GUI on a PC use the engine over the serial port (see uci.rs)
+    ExportGame, // Sends the last saved game as PGN (see save::load_last)
+    MoveHistory, // Shows the moves of the last saved game as pages (see lcd_text::write_move_row)
+    Statistics, // Shows the results of the games played (see statistics.rs)
+    ResetStatistics,
+    Diagnostics, // Shows the pieces the hall sensors can see, for checking the sensors and leds
+    Console, // Debug console over the serial port (see console.rs)
+}
+
+// Every screen, in the order they are shown
+pub const SCREENS: [Screen; 18] = [Screen::NewGame, Screen::Mode, Screen::Colour, Screen::Difficulty, Screen::HintTime, Screen::WhiteDepth, Screen::BlackDepth, Screen::TimeControl, Screen::Variant, Screen::LedBrightness, Screen::Sound, Screen::UciEngine, Screen::ExportGame, Screen::MoveHistory, Screen::Statistics, Screen::ResetStatistics, Screen::Diagnostics, Screen::Console];
+
+impl Screen {
+    // Title of the screen, short enough for one row of the lcd with the editing marker (see Menu::title)
+    pub fn title(&self) -> &'static str {
+        match self {
+            Screen::NewGame => "New game",
+            Screen::Mode => "Mode",
+            Screen::Colour => "Colour",
+            Screen::Difficulty => "Difficulty",
+            Screen::HintTime => "Hint search",
+            Screen::WhiteDepth => "Demo white",
+            Screen::BlackDepth => "Demo black",
+            Screen::TimeControl => "Time control",
+            Screen::Variant => "Variant",
+            Screen::LedBrightness => "Led brightness",
+            Screen::Sound => "Sound",
+            Screen::UciEngine => "UCI engine",
+            Screen::ExportGame => "Export game",
+            Screen::MoveHistory => "Move history",
+            Screen::Statistics => "Statistics",
+            Screen::ResetStatistics => "Reset stats",
+            Screen::Diagnostics => "Diagnostics",
+            Screen::Console => "Console",
+        }
+    }
+}
+
+// Changes the setting of a screen to its next option, or its previous option if backwards is true, the options wrap around
+fn next_option(settings: &mut Settings, screen: Screen, backwards: bool) {
+    let step = |index: usize, len: usize| {
+        if backwards {
+            (index + len - 1) % len
+        } else {
+            (index + 1) % len
+        }
+    };
+
+    match screen {
+        Screen::Mode => settings.mode_index = step(settings.mode_index, settings::MODES.len()),
+        Screen::Colour => settings.player_white = !settings.player_white,
+        Screen::Sound => settings.sound = !settings.sound,
+        Screen::Difficulty => settings.search_time_index = step(settings.search_time_index, settings::SEARCH_TIMES.len()),
+        Screen::WhiteDepth => settings.demo_depths[0] = step(settings.demo_depths[0] - 1, settings::MAX_DEMO_DEPTH) + 1,
+        Screen::BlackDepth => settings.demo_depths[1] = step(settings.demo_depths[1] - 1, settings::MAX_DEMO_DEPTH) + 1,
+        Screen::HintTime => settings.hint_time_index = step(settings.hint_time_index, settings::HINT_TIMES.len()),
+        Screen::TimeControl => settings.time_control_index = step(settings.time_control_index, clock::TIME_CONTROLS.len()),
+        Screen::Variant => settings.variant_index = step(settings.variant_index, variant::VARIANTS.len()),
+        Screen::LedBrightness => settings.led_brightness_index = step(settings.led_brightness_index, settings::LED_BRIGHTNESS_LEVELS.len()),
+        Screen::NewGame | Screen::UciEngine | Screen::ExportGame | Screen::MoveHistory | Screen::Statistics | Screen::ResetStatistics | Screen::Diagnostics | Screen::Console => (),
+    }
+}
+
+// What the caller has to do after a gesture
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum MenuAction {
+    None, // Only the menu changed, the screen has to be printed again
+    StartGame,
+    UciEngine,
+    ExportGame,
+    MoveHistory,
+    Statistics,
+    ResetStatistics,
+    Diagnostics,
+    Console,
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Menu {
+    pub screen_index: usize, // Index of the current screen in SCREENS
+    pub editing: bool, // True while the option of the current screen is being changed
+}
+
+impl Menu {
+    pub fn new() -> Self {
+        Menu {
+            screen_index: 0,
+            editing: false,
+        }
+    }
+
+    pub fn screen(&self) -> Screen {
+        SCREENS[self.screen_index]
+    }
+
+    // Updates the menu and settings from a gesture of the button
+    pub fn input(&mut self, gesture: Gesture, settings: &mut Settings) -> MenuAction {
+        let screen = self.screen();
+
+        if self.editing {
+            match gesture {
+                Gesture::Short => next_option(settings, screen, false),
+                Gesture::Double | Gesture::Long | Gesture::Hold => self.editing = false,
+                Gesture::Triple => (),
+            }
+            return MenuAction::None;
+        }
+
+        match gesture {
+            Gesture::Short => self.screen_index = (self.screen_index + 1) % SCREENS.len(),
+            Gesture::Double => self.screen_index = (self.screen_index + SCREENS.len() - 1) % SCREENS.len(),
+            Gesture::Triple => (),
+            Gesture::Long | Gesture::Hold => match screen {
+                Screen::NewGame => return MenuAction::StartGame,
+                Screen::UciEngine => return MenuAction::UciEngine,
+                Screen::ExportGame => return MenuAction::ExportGame,
+                Screen::MoveHistory => return MenuAction::MoveHistory,
+                Screen::Statistics => return MenuAction::Statistics,
+                Screen::ResetStatistics => return MenuAction::ResetStatistics,
+                Screen::Diagnostics => return MenuAction::Diagnostics,
+                Screen::Console => return MenuAction::Console,
+                _ => self.editing = true,
+            },
+        }
+        MenuAction::None
+    }
+
+    // Updates the menu and settings from the steps a rotary encoder was turned (see encoder.rs), positive steps are clockwise
+    // Moves through the screens, or the options while an option is being changed, in either direction
+    pub fn scroll(&mut self, steps: i32, settings: &mut Settings) {
+        let screen = self.screen();
+        for _ in 0..steps.abs() {
+            if self.editing {
+                next_option(settings, screen, steps < 0);
+            } else if steps > 0 {
+                self.screen_index = (self.screen_index + 1) % SCREENS.len();
+            } else {
+                self.screen_index = (self.screen_index + SCREENS.len() - 1) % SCREENS.len();
+            }
+        }
+    }
+
+    // Returns the first row of the screen, the title with a marker while its option is being changed
+    pub fn title(&self) -> Title {
+        Title {
+            screen: self.screen(),
+            editing: self.editing,
+        }
+    }
+
+    // Returns the second row of the screen, the option of a setting or how to open the screen
+    pub fn value<'a>(&self, settings: &'a Settings) -> Value<'a> {
+        Value {
+            screen: self.screen(),
+            settings: settings,
+        }
+    }
+}
+
+// First row of a screen (see Menu::title)
+pub struct Title {
+    screen: Screen,
+    editing: bool,
+}
+
+impl core::fmt::Display for Title {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(self.screen.title())?;
+        if self.editing {
+            f.write_str(" *")?;
+        }
+        Ok(())
+    }
+}
+
+// Second row of a screen (see Menu::value)
+pub struct Value<'a> {
+    screen: Screen,
+    settings: &'a Settings,
+}
+
+impl<'a> core::fmt::Display for Value<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        match self.screen {
+            Screen::NewGame => f.write_str("(Hold to start)"),
+            Screen::Mode => f.write_str(self.settings.mode().name()),
+            Screen::Colour => {
+                if self.settings.player_white {
+                    f.write_str("White")
+                } else {
+                    f.write_str("Black")
+                }
+            },
+            Screen::Difficulty => write!(f, "{}s search", self.settings.search_time_ms() / 1000),
+            Screen::WhiteDepth => write!(f, "Depth {}", self.settings.demo_depths[0]),
+            Screen::BlackDepth => write!(f, "Depth {}", self.settings.demo_depths[1]),
+            Screen::HintTime => write!(f, "{}.{}s search", self.settings.hint_time_ms() / 1000, self.settings.hint_time_ms() % 1000 / 100),
+            Screen::TimeControl => match self.settings.time_control() {
+                Some(time_control) => write!(f, "{}", time_control),
+                None => f.write_str("Untimed"),
+            },
+            Screen::Variant => f.write_str(self.settings.variant().name()),
+            Screen::LedBrightness => write!(f, "{}%", self.settings.led_brightness()),
+            Screen::Sound => {
+                if self.settings.sound {
+                    f.write_str("On")
+                } else {
+                    f.write_str("Off")
+                }
+            },
+            Screen::UciEngine => f.write_str("(Hold to start)"),
+            Screen::ExportGame => f.write_str("(Hold to send)"),
+            Screen::MoveHistory => f.write_str("(Hold to show)"),
+            Screen::Statistics => f.write_str("(Hold to show)"),
+            Screen::ResetStatistics => f.write_str("(Hold to reset)"),
+            Screen::Diagnostics => f.write_str("(Hold to run)"),
+            Screen::Console => f.write_str("(Hold to start)"),
+        }
+    }
+}
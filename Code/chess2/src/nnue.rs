@@ -0,0 +1,90 @@
+use crate::board::board_representation;
+
+// Small NNUE style neural network evaluation, used instead of the classical evaluation with the nnue feature
+// The network is 768 -> NNUE_HIDDEN -> 1, with one input for every piece (board index) on every bit
+// The hidden layer (the accumulator) is stored on the board and updated by new_turn, so only the pieces that moved are added or removed
+// https://www.chessprogramming.org/NNUE
+
+// Number of hidden neurons, 768 x 16 int8 input weights is 12 KB of flash
+pub const NNUE_HIDDEN: usize = 16;
+pub const NNUE_INPUTS: usize = 768;
+
+// Quantisation scales
+// Input weights, input biases and hidden activations are stored multiplied by NNUE_SCALE, so an activation of NNUE_SCALE is 1.0
+// Output weights are also multiplied by NNUE_SCALE, and the output bias by NNUE_SCALE * NNUE_SCALE
+pub const NNUE_SCALE: i32 = 64;
+
+// The network output is multiplied by this to get centipawns
+pub const NNUE_OUTPUT_SCALE: i32 = 512;
+
+// Network weights, NNUE_INPUT_WEIGHTS, NNUE_INPUT_BIASES, NNUE_OUTPUT_WEIGHTS and NNUE_OUTPUT_BIAS
+// Generated on a host by nnue_trainer (std and nnue features)
+include!("nnue_weights.rs");
+
+// Adds (or removes) the input weights of a piece on a bit to the accumulator
+fn apply_input(accumulator: &mut [i16; NNUE_HIDDEN], board_index: usize, bit: usize, add: bool) {
+    let weights = &NNUE_INPUT_WEIGHTS[board_index * 64 + bit];
+    for i in 0..NNUE_HIDDEN {
+        if add {
+            accumulator[i] += weights[i] as i16;
+        } else {
+            accumulator[i] -= weights[i] as i16;
+        }
+    }
+}
+
+// Calculates the accumulator of a board from scratch
+// Used when a board is made without new_turn (e.g. from a fen)
+pub fn refresh_accumulator(board: &mut board_representation::Board) {
+    board.accumulator = NNUE_INPUT_BIASES;
+
+    for i in 0..12 {
+        let mut bitboard = board.board[i];
+        while bitboard != 0 {
+            let bit = bitboard.trailing_zeros() as usize;
+            apply_input(&mut board.accumulator, i, bit, true);
+            bitboard &= bitboard - 1; // Turn off the lowest bit
+        }
+    }
+}
+
+// Updates the accumulator of a board after a move, from the bitboards of the board before the move
+// Only the pieces that changed are added or removed, so captures, castling, en passant and promotions are all handled
+pub fn update_accumulator(initial_bitboards: &[u64; 13], board: &mut board_representation::Board) {
+    for i in 0..12 {
+        let changed = initial_bitboards[i] ^ board.board[i];
+        if changed == 0 {
+            continue;
+        }
+
+        let mut added = changed & board.board[i];
+        while added != 0 {
+            let bit = added.trailing_zeros() as usize;
+            apply_input(&mut board.accumulator, i, bit, true);
+            added &= added - 1; // Turn off the lowest bit
+        }
+
+        let mut removed = changed & initial_bitboards[i];
+        while removed != 0 {
+            let bit = removed.trailing_zeros() as usize;
+            apply_input(&mut board.accumulator, i, bit, false);
+            removed &= removed - 1; // Turn off the lowest bit
+        }
+    }
+}
+
+// Returns the network evaluation of the board relative to the white team
+pub fn evaluate(board: &board_representation::Board) -> i16 {
+    let mut output = NNUE_OUTPUT_BIAS;
+
+    for i in 0..NNUE_HIDDEN {
+        // Clipped ReLU activation (0.0 to 1.0)
+        let activation = (board.accumulator[i] as i32).max(0).min(NNUE_SCALE);
+        output += activation * NNUE_OUTPUT_WEIGHTS[i] as i32;
+    }
+
+    // Keep the evaluation well away from mate values
+    let limit = crate::algorithm::MATE_VALUE as i32 / 2;
+    let evaluation = output * NNUE_OUTPUT_SCALE / (NNUE_SCALE * NNUE_SCALE);
+    evaluation.max(-limit).min(limit) as i16
+}
@@ -0,0 +1,234 @@
+use std::fmt::Write;
+
+use crate::board::board_representation;
+use crate::nnue::{NNUE_HIDDEN, NNUE_INPUTS, NNUE_OUTPUT_SCALE, NNUE_SCALE};
+
+// Trains the neural network evaluation and exports it as the source of nnue_weights.rs
+// Only available with the std and nnue features
+// The network is trained in floating point with the same structure as the quantised network in nnue.rs
+
+// Evaluations are converted to expected results (0 - 1) with a logistic curve, an evaluation of this many centipawns is a 10 to 1 advantage
+const SIGMOID_SCALE: f32 = 400.0;
+
+// Largest weight that can be stored in an i8 after quantisation
+const MAX_WEIGHT: f32 = 127.0 / NNUE_SCALE as f32;
+
+pub struct Network {
+    pub input_weights: Vec<[f32; NNUE_HIDDEN]>, // Weights of every input (board index * 64 + bit)
+    pub input_biases: [f32; NNUE_HIDDEN],
+    pub output_weights: [f32; NNUE_HIDDEN],
+    pub output_bias: f32,
+}
+
+// A position to train on, the inputs (pieces) that are on and the expected result (1 white win, 0.5 draw, 0 black win)
+pub struct TrainingSample {
+    pub inputs: Vec<usize>,
+    pub target: f32,
+}
+
+// Xorshift pseudo random number generator
+fn next_random(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+fn sigmoid(evaluation: f32) -> f32 {
+    1.0 / (1.0 + 10f32.powf(-evaluation / SIGMOID_SCALE))
+}
+
+// Returns the inputs that are on for a board
+pub fn inputs(board: &board_representation::Board) -> Vec<usize> {
+    let mut inputs = Vec::new();
+    for i in 0..12 {
+        let mut bitboard = board.board[i];
+        while bitboard != 0 {
+            inputs.push(i * 64 + bitboard.trailing_zeros() as usize);
+            bitboard &= bitboard - 1; // Turn off the lowest bit
+        }
+    }
+    inputs
+}
+
+impl Network {
+    // Creates a network with small random weights
+    pub fn new(seed: u64) -> Self {
+        let mut state = seed.max(1);
+        let mut random_weight = || (next_random(&mut state) % 2001) as f32 / 1000.0 - 1.0;
+
+        let mut input_weights = vec![[0.0; NNUE_HIDDEN]; NNUE_INPUTS];
+        for weights in input_weights.iter_mut() {
+            for weight in weights.iter_mut() {
+                *weight = random_weight() * 0.1;
+            }
+        }
+
+        let mut output_weights = [0.0; NNUE_HIDDEN];
+        for weight in output_weights.iter_mut() {
+            *weight = random_weight() * 0.5;
+        }
+
+        Network {
+            input_weights: input_weights,
+            input_biases: [0.5; NNUE_HIDDEN], // Start with every hidden neuron in the middle of its activation range
+            output_weights: output_weights,
+            output_bias: 0.0,
+        }
+    }
+
+    // Returns the hidden layer before activation, and the network output (centipawns / NNUE_OUTPUT_SCALE)
+    fn forward(&self, inputs: &[usize]) -> ([f32; NNUE_HIDDEN], f32) {
+        let mut hidden = self.input_biases;
+        for &input in inputs {
+            for i in 0..NNUE_HIDDEN {
+                hidden[i] += self.input_weights[input][i];
+            }
+        }
+
+        let mut output = self.output_bias;
+        for i in 0..NNUE_HIDDEN {
+            output += hidden[i].max(0.0).min(1.0) * self.output_weights[i];
+        }
+
+        (hidden, output)
+    }
+
+    // Returns the evaluation of a board in centipawns relative to the white team
+    pub fn evaluate(&self, board: &board_representation::Board) -> f32 {
+        self.forward(&inputs(board)).1 * NNUE_OUTPUT_SCALE as f32
+    }
+
+    // Trains the network on one sample with gradient descent, minimising the squared error of the expected result
+    fn train_sample(&mut self, sample: &TrainingSample, learning_rate: f32) {
+        let (hidden, output) = self.forward(&sample.inputs);
+        let prediction = sigmoid(output * NNUE_OUTPUT_SCALE as f32);
+
+        // Gradient of the error with respect to the network output
+        let gradient = 2.0 * (prediction - sample.target) * prediction * (1.0 - prediction)
+            * core::f32::consts::LN_10 / SIGMOID_SCALE * NNUE_OUTPUT_SCALE as f32;
+
+        for i in 0..NNUE_HIDDEN {
+            let activation = hidden[i].max(0.0).min(1.0);
+
+            // The clipped ReLU only passes the gradient when the neuron isn't clipped
+            if hidden[i] > 0.0 && hidden[i] < 1.0 {
+                let hidden_gradient = gradient * self.output_weights[i] * learning_rate;
+                self.input_biases[i] -= hidden_gradient;
+                for &input in &sample.inputs {
+                    self.input_weights[input][i] = (self.input_weights[input][i] - hidden_gradient).max(-MAX_WEIGHT).min(MAX_WEIGHT);
+                }
+            }
+
+            self.output_weights[i] = (self.output_weights[i] - gradient * activation * learning_rate).max(-MAX_WEIGHT).min(MAX_WEIGHT);
+        }
+        self.output_bias -= gradient * learning_rate;
+    }
+
+    // Returns the mean squared error of the expected results
+    pub fn error(&self, samples: &[TrainingSample]) -> f32 {
+        let mut total = 0.0;
+        for sample in samples {
+            let difference = sigmoid(self.forward(&sample.inputs).1 * NNUE_OUTPUT_SCALE as f32) - sample.target;
+            total += difference * difference;
+        }
+        total / samples.len() as f32
+    }
+
+    // Trains the network for a number of epochs, shuffling the samples every epoch
+    // progress is called after every epoch with the epoch number and the error
+    pub fn train(&mut self, samples: &mut [TrainingSample], epochs: usize, learning_rate: f32, seed: u64, progress: &mut dyn FnMut(usize, f32)) {
+        let mut state = seed.max(1);
+
+        for epoch in 0..epochs {
+            for i in (1..samples.len()).rev() {
+                let j = (next_random(&mut state) % (i as u64 + 1)) as usize;
+                samples.swap(i, j);
+            }
+
+            for sample in samples.iter() {
+                self.train_sample(sample, learning_rate);
+            }
+
+            progress(epoch, self.error(samples));
+        }
+    }
+
+    // Returns the source of nnue_weights.rs, with the weights quantised to the scales in nnue.rs
+    pub fn weights_source(&self) -> String {
+        let quantise = |weight: f32, scale: f32| (weight * scale).round();
+        let scale = NNUE_SCALE as f32;
+
+        let mut source = String::new();
+        writeln!(source, "// Generated by nnue_trainer, do not edit").unwrap();
+        writeln!(source, "pub const NNUE_INPUT_WEIGHTS: [[i8; NNUE_HIDDEN]; NNUE_INPUTS] = [").unwrap();
+        for weights in &self.input_weights {
+            let values: Vec<String> = weights.iter().map(|weight| (quantise(*weight, scale).max(-127.0).min(127.0) as i8).to_string()).collect();
+            writeln!(source, "    [{}],", values.join(", ")).unwrap();
+        }
+        writeln!(source, "];").unwrap();
+
+        let biases: Vec<String> = self.input_biases.iter().map(|bias| (quantise(*bias, scale) as i16).to_string()).collect();
+        writeln!(source, "pub const NNUE_INPUT_BIASES: [i16; NNUE_HIDDEN] = [{}];", biases.join(", ")).unwrap();
+
+        let weights: Vec<String> = self.output_weights.iter().map(|weight| (quantise(*weight, scale).max(-127.0).min(127.0) as i8).to_string()).collect();
+        writeln!(source, "pub const NNUE_OUTPUT_WEIGHTS: [i8; NNUE_HIDDEN] = [{}];", weights.join(", ")).unwrap();
+
+        writeln!(source, "pub const NNUE_OUTPUT_BIAS: i32 = {};", quantise(self.output_bias, scale * scale) as i32).unwrap();
+        source
+    }
+}
+
+// Returns training samples from positions with game results (see tuner::parse_positions)
+pub fn samples_from_results(positions: &[crate::tuner::TrainingPosition]) -> Vec<TrainingSample> {
+    let mut samples = Vec::new();
+    for position in positions {
+        samples.push(TrainingSample {
+            inputs: inputs(&position.board),
+            target: position.result as f32,
+        });
+    }
+    samples
+}
+
+// Returns training samples with the classical evaluation as the expected result
+// Used to train a network without game results, which starts it off with the knowledge of the classical evaluation
+pub fn samples_from_classical(boards: &[board_representation::Board], pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Vec<TrainingSample> {
+    let mut pawn_cache = crate::evaluation::EvalCache::new(&mut []);
+
+    let mut samples = Vec::new();
+    for board in boards {
+        let evaluation = crate::evaluation::classical_evaluation(board, &mut pawn_cache, &crate::evaluation::WEIGHTS, pieces_info);
+        samples.push(TrainingSample {
+            inputs: inputs(board),
+            target: sigmoid(evaluation as f32),
+        });
+    }
+    samples
+}
+
+// Returns boards from games of random legal moves from the starting position
+pub fn random_boards(count: usize, seed: u64, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Vec<board_representation::Board> {
+    let start = board_representation::Board::from_fen("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1").unwrap();
+    let mut state = seed.max(1);
+
+    let mut boards = Vec::new();
+    let mut board = start;
+    while boards.len() < count {
+        let mut next_boards = Vec::new();
+        crate::board::perft::legal_moves(board, pieces_info, |_, _, _, new_board| {
+            next_boards.push(new_board);
+        });
+
+        // Start a new game when the game ends by checkmate or stalemate, or after 200 half moves
+        if next_boards.len() == 0 || board.half_moves >= 200 {
+            board = start;
+            continue;
+        }
+
+        board = next_boards[(next_random(&mut state) % next_boards.len() as u64) as usize];
+        boards.push(board);
+    }
+
+    boards
+}
@@ -0,0 +1,774 @@
+// Generated by nnue_trainer, do not edit
+pub const NNUE_INPUT_WEIGHTS: [[i8; NNUE_HIDDEN]; NNUE_INPUTS] = [
+    [4, 4, 1, -3, 4, 0, -6, -2, -2, 2, 2, 4, 0, 2, 3, 6],
+    [3, 3, -1, 0, 0, -2, 6, 3, 6, 6, 3, 0, 0, -6, -6, -6],
+    [1, 6, -6, 0, 0, 5, 4, 5, 1, -5, 0, -6, 0, -4, -1, -5],
+    [-4, 1, 2, -2, -3, 1, 6, 0, 0, 6, -1, -3, -1, 0, 3, 3],
+    [0, 2, 1, -5, -1, -4, -2, 2, -4, -1, 6, -1, 4, -6, 3, 5],
+    [-2, 0, 0, -6, 4, 4, 1, 1, 1, -4, -1, 6, 0, 3, -5, -5],
+    [-1, 4, 5, 4, 4, 1, -1, 3, -6, -2, 0, 5, -3, -6, -2, 1],
+    [-3, 0, 5, -3, 0, 5, -4, 4, -5, 2, -2, -3, -3, -2, 2, -1],
+    [-7, -3, -4, 1, 1, 4, -1, -1, -4, -9, -6, 2, 4, 3, 3, -5],
+    [-5, -3, -2, 6, 1, 3, -4, -7, -5, -7, 0, 1, -2, 8, 0, -3],
+    [-5, -4, 6, -1, -2, 7, 3, -6, 5, -9, -4, 1, 9, -3, 5, 3],
+    [-4, -4, -3, 9, 3, 4, -3, -8, -3, -2, 2, 4, -3, 4, 7, -2],
+    [1, 2, 5, 7, 3, 4, -2, -7, 2, -4, -4, -3, -1, 0, 6, -5],
+    [-2, -4, 8, 1, 1, 2, -3, -8, 4, -7, -4, -4, 2, 6, 1, 5],
+    [1, -1, 1, 1, 2, 2, -4, -4, 4, -10, -5, -5, 5, 3, 8, -4],
+    [2, 1, -2, 7, 3, 4, -4, -3, 1, -10, 3, 4, 2, -5, 5, -6],
+    [3, 0, 9, 0, 1, 4, -11, -3, -5, -2, -2, -2, 5, 5, -4, -1],
+    [0, 1, 2, 7, 1, 7, -4, -1, -3, -1, -1, -2, 7, -3, 2, 1],
+    [-3, -1, 7, 4, -1, 5, -8, -6, 0, 1, 2, 3, 4, -1, 1, 2],
+    [3, -4, -3, 4, 3, 4, -7, -4, 2, -1, -3, 5, 0, -4, 5, 5],
+    [-9, -1, -6, 1, 2, 4, -7, 1, -6, -2, -3, 0, 7, 3, 4, 1],
+    [-7, -4, 3, -2, 0, 6, -5, -1, -5, -5, -4, -5, -1, -3, 2, 2],
+    [-7, -4, -4, 5, 1, 6, -8, 4, 1, -4, -5, -3, -2, 2, -3, -5],
+    [0, -5, 5, -2, 5, 4, -8, -3, -3, 1, 0, -1, 8, 1, 4, 1],
+    [-7, 0, 4, -1, 2, 2, -7, -2, -6, 1, 1, 1, 3, 3, 0, 2],
+    [-5, 4, -2, 2, 3, 3, -3, -1, -1, -2, -2, 2, -4, 1, 2, 0],
+    [-1, 1, 8, 2, 4, 2, -6, 1, 3, -3, 2, -4, 9, -3, 1, -3],
+    [-4, 5, -5, 0, 2, 2, -4, -3, -6, 0, -3, 4, 2, -2, 5, 0],
+    [-2, 5, -1, 4, 5, 2, -3, -1, -3, -1, -5, 2, -4, 1, -1, 5],
+    [0, -3, 5, 3, -1, 2, -4, -7, -4, -4, -2, 0, -6, -3, -5, -1],
+    [-3, -5, -1, 4, 3, 3, -3, 1, 2, -4, -1, 2, -4, -2, 3, 3],
+    [-1, -1, 6, -4, 4, 2, -6, -1, -7, -1, 0, 4, 2, 5, 1, 1],
+    [-4, -2, 1, 2, 1, 2, -6, 1, -2, 0, -2, 0, 3, 0, 1, 2],
+    [-6, 2, 5, 2, 0, 2, -2, -4, 0, 0, -3, -3, -1, -4, 1, -1],
+    [3, 1, 6, 2, 0, 2, -4, 0, 0, -3, -1, 1, -3, 7, 4, -6],
+    [-1, 5, -2, 1, -1, 1, -3, -4, -1, -3, -5, 1, 1, 2, 0, -7],
+    [0, 4, 7, 3, 1, 1, -3, -2, -4, -4, -2, -2, -2, 0, -1, -4],
+    [4, 3, -2, 3, 2, 3, -3, -1, 1, -2, -5, 4, 2, 0, 1, -5],
+    [-4, 4, 1, -2, -2, 3, -1, -3, -4, -3, -2, -2, 6, 7, 4, 4],
+    [-2, 4, 4, 3, -2, 2, -4, -1, -1, -1, -4, 1, 4, 5, -3, 5],
+    [-2, -4, -4, 4, 3, 2, -6, -1, 4, -3, 2, 2, -2, -2, -1, -5],
+    [-4, -2, 2, 4, 1, 2, -2, -2, -2, -1, 1, 3, -4, 1, 3, -6],
+    [0, 0, 6, 1, 0, 2, -5, 0, -3, 0, -1, 1, 5, 1, 3, -5],
+    [-2, -2, -1, 2, -1, 1, -3, -3, -2, -3, -3, -4, -1, -2, 1, 4],
+    [-1, 0, -1, 0, 4, 2, -4, 1, 0, -4, -1, -2, 2, 5, 4, -3],
+    [-2, 4, -3, 1, -1, 2, -4, -3, -1, -5, -1, -2, -1, -3, 1, 4],
+    [-3, 0, 0, -1, 4, 3, -1, -3, -1, -2, -2, 1, -4, -2, 4, 1],
+    [-4, -2, -1, 3, 4, 2, -3, 0, 3, -3, 1, -4, -3, 5, -1, 1],
+    [-3, 3, -5, 3, 4, 2, -6, 2, -5, -4, -2, 1, -4, -5, 1, 2],
+    [-2, -3, 3, 3, -1, 1, -3, -2, -4, -2, -2, -5, 0, -2, 6, -5],
+    [1, 4, 0, -1, 2, 2, -4, -5, -2, -1, -3, -1, 4, 4, 0, -1],
+    [-4, 2, -2, -3, 3, 2, -7, -2, -1, 0, -4, -3, 2, -3, 2, 1],
+    [-1, 2, 0, 1, -2, 2, -7, 1, -4, -4, -1, 0, 1, 0, 0, 2],
+    [-6, -6, 0, 1, -1, 2, -4, -2, 6, -5, 0, 3, 4, -2, -2, -7],
+    [0, 1, 2, 0, 3, 3, -3, -3, 3, -2, 1, 0, 2, 5, 3, -5],
+    [-1, 3, 1, 2, 2, 2, -4, -4, 4, -3, -2, -4, -6, 2, -3, -1],
+    [-5, -5, 2, -1, 1, -1, -2, -6, 5, -6, 0, -4, -1, 6, 1, 3],
+    [-5, 2, 4, -1, 1, 0, 0, 3, 3, 5, 1, -3, -4, -1, -5, -2],
+    [3, -3, -6, 2, 6, 6, 2, 2, -1, 5, -2, -6, 3, 5, -2, -3],
+    [6, -1, -5, 1, -3, -4, -1, 4, -1, 3, 1, 1, 3, 4, -1, -3],
+    [0, 1, 2, 2, -2, -1, 5, -3, -1, -6, 2, 4, 5, 3, -1, 6],
+    [4, -2, 5, 1, 0, -2, 2, 0, -4, 3, -2, 4, 0, -4, 1, -3],
+    [-1, 1, 2, 6, -5, -5, 5, 3, 2, -4, -5, 3, -3, -6, 5, 2],
+    [-5, 6, 1, -1, 3, -4, -6, -3, 1, 5, 0, -4, -5, 2, 0, -6],
+    [-7, -4, 1, 4, 13, 7, -9, -10, 1, -12, -3, 8, -3, 6, 5, 1],
+    [-5, 6, 3, 5, 7, 12, -5, -10, 0, -9, -10, 5, -2, 3, 9, -4],
+    [-6, 0, -3, 12, 12, 10, -5, -5, -4, -8, -7, -3, 10, -3, 9, 5],
+    [-3, -3, 6, 13, 6, 11, -9, -5, -4, -11, -6, 1, 2, -1, 2, -1],
+    [-8, -3, -3, 5, 8, 11, -5, -12, -7, -12, -4, 3, 2, 3, 4, -2],
+    [-5, -1, -2, 9, 5, 8, -10, -5, 2, -11, -10, 5, 8, 8, 1, 6],
+    [-5, -1, 2, 8, 12, 7, -1, -15, -6, -6, -8, 0, 9, -2, 10, -2],
+    [0, 1, 2, 7, 11, 11, -8, -6, 2, -11, -11, 6, 1, -2, 3, -3],
+    [-9, 2, -1, 6, 5, 6, -7, -15, 2, -11, -4, 0, 2, 2, 9, 1],
+    [-11, 1, 4, 10, 7, 8, -7, -10, 1, -10, -5, 8, -1, 2, 4, -2],
+    [-7, -2, -2, 16, 3, 6, -7, -11, 3, -9, -9, 0, 3, 4, 4, 4],
+    [-1, -5, 8, 13, 8, 6, -8, -12, -6, -6, -12, -2, 0, 8, 3, 2],
+    [-2, -1, 8, 13, 2, 9, -10, -14, 1, -8, -4, 2, 7, 5, 3, -2],
+    [-5, 4, 6, 11, 8, 15, -3, -15, 0, -9, -1, 2, 2, -2, 1, 0],
+    [-1, -1, -4, 11, 5, 4, -9, -7, 5, -16, -11, 8, 6, 2, 4, -1],
+    [-1, 4, 7, 5, 5, 10, -1, -14, -1, -16, 0, 6, 6, 4, 7, -2],
+    [-4, -6, 6, 13, 8, 12, -8, -4, 0, -8, -7, 4, 1, -2, 9, 5],
+    [-5, -1, -1, 5, 7, 7, -7, -13, 2, -11, -9, 3, 5, 1, 9, 5],
+    [-8, 1, 1, 5, 9, 5, -4, -12, -6, -8, -13, 6, 6, 1, 5, -3],
+    [-4, 6, 3, 8, 9, 11, 0, -12, 1, -12, -2, 1, 6, -2, 7, 1],
+    [-1, -5, 5, 3, 9, 10, -9, -6, -6, -16, -10, 3, 6, -3, 3, 1],
+    [-1, -5, -4, 11, 9, 10, -3, -7, 4, -17, -9, 1, 5, -2, 2, 1],
+    [-5, -4, 7, 5, 2, 9, -5, -9, -3, -14, -11, 7, 9, 0, 5, 0],
+    [-3, -1, 3, 12, 12, 11, -2, -13, -4, -5, -13, 1, 2, 1, 1, 2],
+    [-5, 6, 5, 7, 6, 11, -9, -10, -6, -12, -1, -3, 7, 4, 3, 5],
+    [-6, 1, 4, 11, 2, 8, -10, -6, -4, -15, -8, 4, -2, 9, -2, -3],
+    [-1, -2, 2, 12, 4, 9, -7, -14, -6, -11, -1, 6, 4, 7, -1, 2],
+    [-9, -2, 2, 11, 10, 10, -7, -14, 3, -5, 0, 6, -1, 2, 7, -1],
+    [-8, -5, -3, 2, 6, 10, -10, -14, -1, -10, -8, 0, 8, 3, 5, -4],
+    [-8, -3, 6, 7, 8, 11, -3, -5, -1, -13, -2, 6, 1, 5, 9, 4],
+    [-2, -5, -1, 13, 10, 4, -5, -9, -6, -9, -7, 8, -1, 5, 9, -3],
+    [0, 0, 3, 9, 8, 5, -10, -3, -7, -14, -7, 8, 2, 3, 10, -1],
+    [-7, -3, 3, 12, 6, 4, -10, -10, -5, -12, -1, -3, 0, 3, 9, 2],
+    [-8, -3, -3, 11, 11, 6, -6, -12, 3, -10, -2, 8, 2, 4, 0, -4],
+    [-8, 1, 0, 8, 11, 14, -7, -7, 5, -9, -4, 0, 1, 3, 7, 2],
+    [-5, 2, 2, 7, 9, 9, -2, -6, -5, -14, -7, 4, 5, 5, 6, 2],
+    [-5, 3, 1, 5, 7, 11, -7, -12, 4, -9, -6, 8, 5, 6, 6, -4],
+    [-6, 3, -3, 13, 7, 4, -10, -9, 3, -8, -10, 4, -1, 3, 9, 6],
+    [-8, -6, -3, 12, 8, 8, -4, -7, -5, -8, -6, 8, -2, 8, 9, 5],
+    [-5, -2, 7, 7, 6, 10, -6, -11, -2, -9, -10, 5, 1, -1, 2, 5],
+    [-6, -2, 4, 6, 5, 7, -10, -10, 4, -14, -6, -1, 1, 0, 7, -2],
+    [-6, 3, -1, 8, 6, 8, -9, -12, 1, -11, -6, -1, 0, 2, 9, -1],
+    [-11, -2, 3, 11, 5, 12, -5, -11, 4, -9, -5, 1, 1, 2, 4, -2],
+    [-5, 1, -1, 10, 10, 5, -11, -7, -3, -12, -6, 7, 2, 9, 3, -3],
+    [-4, -1, 7, 3, 8, 9, -6, -6, -3, -15, -8, 0, 2, -3, 9, -6],
+    [-10, 0, 7, 12, 6, 10, -7, -9, 2, -6, -10, 8, 2, -3, 2, 6],
+    [-4, 6, -1, 14, 4, 4, -10, -10, -5, -14, -3, 2, 3, -1, 5, -6],
+    [-4, -6, 1, 4, 5, 6, -9, -13, -5, -15, -6, 3, -1, 3, 2, 0],
+    [-4, -4, 5, 9, 4, 5, -10, -9, -1, -11, -7, 6, 3, 6, 4, -2],
+    [0, -3, 7, 5, 5, 8, -9, -11, 2, -11, -9, 6, -1, 4, 9, 5],
+    [-9, 5, -2, 7, 9, 7, -8, -10, -6, -12, -4, 0, 6, 4, -1, -4],
+    [-1, -1, 5, 9, 3, 8, -10, -6, -6, -12, -10, 5, 2, 6, 6, 2],
+    [-9, 3, -1, 13, 10, 4, -9, -13, 3, -9, -2, 9, -2, 5, 2, 1],
+    [-3, -6, -4, 11, 3, 9, -7, -12, -4, -14, -8, 7, -4, -2, -1, -4],
+    [-6, 0, -1, 6, 10, 5, -8, -12, 1, -11, -9, -3, -2, 0, 7, 1],
+    [-11, -6, 3, 5, 10, 5, -8, -9, 1, -11, -4, 4, -1, -1, 8, 2],
+    [-6, -1, 1, 8, 5, 6, -10, -10, 3, -11, -9, 4, 9, 9, -1, 2],
+    [-6, 5, 0, 11, 8, 6, -10, -7, -3, -11, -8, 2, 6, 1, 0, 3],
+    [-6, 5, 3, 6, 4, 7, -9, -10, -4, -13, -3, 6, 5, 6, 5, 0],
+    [-1, 5, 1, 8, 6, 8, -8, -12, 3, -14, -6, 4, 6, 4, 4, 3],
+    [-3, -2, 8, 5, 5, 5, -11, -7, -1, -17, -5, 6, 4, 2, 4, -2],
+    [-7, -5, 7, 7, 7, 7, -9, -8, 2, -10, -5, 5, 4, 6, 5, -5],
+    [-6, -6, 5, 10, 9, 5, -10, -7, -1, -10, -11, 3, 2, -1, 1, -4],
+    [-9, -6, -1, 9, 8, 5, -8, -11, 1, -11, -7, -1, 2, 1, 2, 0],
+    [-3, 1, 5, 0, 3, 5, -3, -7, -5, -3, -1, 8, 0, 0, 9, -4],
+    [-6, 5, -2, 4, 0, 2, -4, -1, -6, -9, -5, 3, 5, 7, 3, -3],
+    [-5, 0, 2, 6, 8, 5, -2, -2, 1, -6, -3, -1, -2, -2, 6, 5],
+    [-1, -3, -2, 11, 6, 5, -3, -7, -2, -3, 2, 4, -2, 2, 6, -1],
+    [1, -6, 5, 8, 0, 3, -5, -2, -4, -8, -2, 3, 7, 1, 1, -3],
+    [-1, -5, 6, 0, 1, 3, -3, -2, -6, -11, -7, 7, -1, 6, 3, -5],
+    [-9, 4, -2, 10, 2, 1, 1, -8, 0, -2, -4, 0, 1, 2, 3, 4],
+    [0, -2, 0, 7, 2, 8, 1, -2, -1, -5, -2, 5, 3, -5, 6, 4],
+    [-1, 0, -2, 8, 2, 3, -4, -6, 0, -8, -1, 4, 4, 3, -2, 5],
+    [1, -4, 0, 8, 4, -1, 0, -6, 2, -10, -5, 2, 1, -2, 2, 3],
+    [-2, -3, 6, 4, 5, 8, -1, -1, 6, -10, -5, -4, 2, 7, -1, 3],
+    [-8, 5, -4, 9, 9, 2, 0, 0, 5, -11, -4, -4, -1, 6, 1, -1],
+    [-4, -2, 0, 3, 2, 5, 0, -9, 2, -6, -8, 5, 5, -2, 8, -4],
+    [-1, -6, -4, 1, 7, 8, -2, -7, -5, -10, 1, 5, 0, -3, 9, 0],
+    [-3, -3, 7, 2, 9, -1, -8, -3, -7, -7, -2, 4, 2, 1, 3, 2],
+    [-6, 6, 2, 8, 5, 0, -6, -1, -3, -8, 1, 5, 1, -3, 6, -3],
+    [-5, -1, 4, 10, -2, 4, 1, 0, -3, -8, -8, 1, 1, 7, 0, 4],
+    [-1, 4, 2, 4, 0, 7, -2, -2, -4, -12, -6, 8, 7, 2, -2, 1],
+    [-10, 5, 7, 13, 4, 2, -3, -2, 0, -4, -10, 1, 1, -3, 0, 5],
+    [-3, 3, -2, 11, 0, 9, -1, -9, -5, -3, -8, 3, 2, 3, 1, -5],
+    [-6, -6, -4, 6, 9, 3, -2, -9, -6, -6, 0, 5, 1, 7, -1, -3],
+    [0, 6, 4, 5, 11, 7, -2, -6, -5, -8, -8, 1, -5, -3, 0, -5],
+    [-8, 1, -4, 7, 1, 5, 1, -6, -5, -6, -7, 7, -1, 6, -1, 4],
+    [-7, -6, 0, 1, 4, -3, -6, -2, 5, -6, -7, 5, 5, 7, 8, -5],
+    [-6, -1, 1, 10, 3, -1, -6, -8, 6, -8, 0, 5, -3, -3, -3, -4],
+    [0, 5, -4, 4, 9, 1, -5, -4, -2, -7, -6, 3, 6, 3, 7, 6],
+    [-5, 6, 5, 8, 5, 4, -2, -1, -2, -9, -3, -3, 6, -4, 9, -6],
+    [-1, -4, 1, 8, 5, 2, -4, -9, -6, -5, -4, 3, -1, -1, 8, -1],
+    [-7, 2, 5, 5, 3, 2, -3, -3, -8, -12, -2, 4, 5, -3, 3, 3],
+    [-9, 5, -3, 1, 9, 4, -3, -11, 2, -5, -3, 7, -4, 4, -1, 1],
+    [-3, -3, 8, 1, 6, 2, -5, -6, 0, -8, -4, 5, -6, 6, 8, -4],
+    [1, -4, 6, 1, 9, 1, -3, -4, -1, -8, -7, 3, 4, 6, -4, 4],
+    [-2, 6, -3, 7, 5, 2, -3, -4, -5, -7, 1, 4, 3, 3, 7, 2],
+    [1, 5, -2, 2, 7, 4, -2, -3, 2, -13, -7, 1, -2, 1, 4, 4],
+    [2, -1, -2, 1, 11, 4, -3, -3, -6, -10, -6, 7, 5, 2, 4, 2],
+    [-8, 0, 6, -1, 7, 3, -3, -10, -4, -7, 1, 5, 2, 3, 4, 0],
+    [0, 4, 3, 4, 8, 2, -3, -2, -5, -13, 0, 3, 7, 4, 2, 6],
+    [-10, 2, 6, 6, 4, 3, -3, -8, 0, -8, -5, -2, -3, -2, -1, 3],
+    [-5, 6, -4, 9, 6, 8, -3, -5, -1, -5, -3, 6, 1, -4, 5, 2],
+    [-5, 0, 2, 3, 6, 3, -2, -6, -3, -6, -3, -3, 4, 3, 0, -5],
+    [0, -4, -4, 4, 4, 2, -3, -6, 4, -12, -1, -3, 4, 5, 0, 4],
+    [-8, -4, 0, 4, 3, 5, -1, -7, 4, -6, -2, -2, 3, 5, 8, 6],
+    [-7, -6, 3, 6, 3, 2, -5, -3, -6, -10, -5, -2, 5, 3, 0, 0],
+    [-8, 1, -4, -1, 5, 3, -3, -10, 4, -12, -5, 2, -3, 2, 1, -4],
+    [-7, -5, -1, 6, 4, 3, -3, -9, -6, -5, -5, 1, 0, 8, -1, -1],
+    [-2, -1, -2, 4, 8, 3, -3, -8, 2, -10, -5, -3, 2, -2, 2, 0],
+    [-2, 3, 2, 3, 3, 4, -2, -5, -1, -9, -5, -5, 5, 5, 8, -6],
+    [-5, 5, 0, 2, 0, 3, -2, -7, 4, -7, -4, 1, 1, 3, 7, 3],
+    [-4, -6, 1, 9, 1, 3, -3, -6, 2, -5, -2, 1, 2, -6, 5, 6],
+    [-1, 2, -3, 4, 9, 8, 1, 1, -2, -10, -4, 3, 7, -4, 1, 5],
+    [-10, -4, 7, 3, 10, 2, -2, -6, 0, -6, 0, 4, 1, -3, 1, 0],
+    [-2, -5, 6, 2, 6, 3, -3, -9, -2, -10, -1, -3, -2, 6, -1, 3],
+    [0, 1, 4, 8, 9, 3, -5, -4, 1, -5, -2, -3, 1, 4, 7, 0],
+    [-4, -1, -3, 9, 5, 6, -2, -6, 0, -7, -3, -1, -3, 6, 3, 4],
+    [-4, -3, 7, 2, 9, 5, -6, -4, 4, -6, -2, -2, 4, 7, -1, -4],
+    [-2, 2, -3, 9, 1, 4, -4, -3, -3, -3, -8, 5, 7, 0, -2, 1],
+    [0, 3, -2, 5, 4, 6, -4, -3, 2, -10, 2, 5, 5, 0, -1, 4],
+    [-3, -3, -5, 3, 6, 2, -3, -4, 0, -8, -4, -4, 4, 2, 4, -2],
+    [-2, -1, -3, 3, 5, 9, -3, -6, -2, -4, -3, 2, 4, 4, 2, -1],
+    [-2, -1, 6, 4, 8, 4, -3, -6, 5, -6, 0, 2, 4, -4, 4, -1],
+    [-2, -6, -1, -1, 10, 2, -3, -3, -3, -7, -5, 1, 4, 8, 3, 0],
+    [-2, -5, -1, 10, 2, 7, -2, -6, -5, -4, -3, 4, 1, -3, 0, 6],
+    [-1, -1, 5, 5, 6, 2, -4, -2, -4, -5, -4, -1, 3, 5, 4, -2],
+    [-5, 0, 6, 8, -1, 5, 1, -3, -4, -8, -3, 1, 0, -4, 1, 6],
+    [-3, 5, -2, 9, 1, 4, 3, -8, 6, -8, -1, -1, -3, 5, 7, 3],
+    [-4, 4, -4, 10, 4, 0, -4, -4, -7, -7, -9, -1, -2, -5, 6, -2],
+    [-1, 4, 3, 4, 5, 1, -5, -6, -5, -7, -8, 3, 4, -1, 1, -2],
+    [-5, 0, 2, 0, 4, 7, -6, -9, -7, -3, -5, 7, -3, 1, 7, -5],
+    [1, -3, -4, 8, 10, 2, -8, -8, 3, -4, -10, -3, 0, 1, -1, 3],
+    [-1, -4, 5, 8, 2, 10, 0, -3, -3, -7, -7, 3, 2, 5, -4, -4],
+    [1, -1, 4, 1, 3, 7, -4, -10, 3, -6, -4, 2, 6, 3, 2, 0],
+    [0, -4, -3, 6, 7, 4, -2, -11, -3, -1, -10, 5, 6, 3, -4, 2],
+    [-5, -3, -2, 2, 10, 6, -2, -7, -5, -8, 3, -3, 6, 3, 3, -3],
+    [1, 0, 4, 7, 6, 1, -4, -6, -3, -6, -4, 4, 8, 3, 5, -5],
+    [-7, 1, 4, 3, 8, 1, -5, -7, -5, -8, -3, 3, -6, 5, -1, 2],
+    [-6, 6, 0, 6, 2, 3, -3, -4, 4, -13, -2, -2, 1, 2, 5, -3],
+    [2, -2, 2, 0, 7, 11, -3, -7, -3, -6, 0, 1, 6, 6, 5, -5],
+    [-9, 2, -1, 8, 8, 3, -2, -9, -5, -5, 1, 0, 0, -4, 6, -3],
+    [0, 3, 8, 8, 2, 1, -4, -9, -7, -7, -5, -3, 4, 0, 7, -6],
+    [1, 1, 1, 7, 4, 7, 1, -3, -6, -5, -8, 2, 5, 6, 7, 4],
+    [1, -3, 5, 7, 5, 2, -4, -6, 3, -12, -1, 5, 0, 1, 2, -4],
+    [0, 5, 5, 4, 2, 4, -3, -5, -3, -8, -10, 2, 8, 0, 2, -4],
+    [-5, 4, 5, 12, 5, 2, -5, -2, -4, -4, -10, 7, -4, 3, 1, -1],
+    [-4, 0, 5, 4, 1, 4, -4, -11, 5, -5, -5, 6, 5, -3, 9, -2],
+    [-2, 6, -5, 2, 2, 2, -2, -6, 0, -11, -9, 3, 9, 7, 4, -6],
+    [-7, 5, 3, 6, 0, 6, 0, -7, 0, -6, -5, 3, 4, 6, 3, 5],
+    [-5, -2, 4, 8, 7, 3, -2, -8, 0, -6, -2, 4, 2, -1, -1, 3],
+    [-4, 4, 9, 0, 4, 3, -4, -11, -3, -11, 0, -3, 3, -2, -1, -1],
+    [1, 1, 8, 9, 1, 4, -3, -5, -6, -10, -3, 5, 2, -4, 2, 6],
+    [-2, 1, 1, 10, 7, 2, -4, -4, -2, -7, -8, 0, 1, -1, 2, 2],
+    [-1, -4, 0, 4, 1, 4, -4, -13, 4, -10, -3, 2, -4, 1, 4, -6],
+    [1, 0, 0, 7, 4, 11, -1, -6, 3, -7, -8, 5, 1, 3, -3, 2],
+    [-4, 2, -1, 3, 2, 3, -4, -11, -5, -9, -9, 2, 0, 3, -2, -3],
+    [1, -4, 0, 7, 2, 11, -2, -4, -1, -6, -4, 4, 7, 6, 6, 1],
+    [-6, 2, 4, 5, 7, 2, -2, -10, 1, -9, -1, -2, -3, -2, 2, 3],
+    [-6, 2, 5, 8, -3, 4, -3, -8, 4, -3, -8, 6, 3, 4, -1, 6],
+    [2, 3, -1, 10, 5, 4, -3, -4, 4, -11, -2, 0, 8, 2, -1, -3],
+    [1, -5, -3, 9, 5, 2, -5, -10, 3, -4, -4, 1, 5, 1, 7, -1],
+    [-5, 2, -4, 9, 5, 1, -5, -1, -3, -11, -7, 3, -2, -4, 5, 0],
+    [-9, 0, 8, 4, 1, 4, -4, -6, -6, -12, 0, -2, -1, 9, -1, 4],
+    [-9, -3, 4, 7, 5, 3, -5, -4, -5, -3, -6, 5, -2, 7, 3, 4],
+    [1, -1, 3, 4, 7, 1, -5, -5, -5, -10, -8, 6, 2, -1, 0, -6],
+    [4, 3, 2, 0, 8, 6, -3, -7, -7, -7, -8, 6, -2, 1, 6, -6],
+    [-4, 1, -2, 6, 2, 1, -5, -3, -4, -6, -6, 3, 6, 5, 10, 4],
+    [1, 5, -4, 3, -1, 2, -4, -9, -5, -12, -9, 2, -1, -2, 4, -4],
+    [-1, 0, 5, 4, 5, 0, -5, -11, -3, -3, -5, 3, 6, 5, 7, 4],
+    [-5, 6, -1, 8, 11, 2, -7, -3, 4, -10, -1, 4, -2, 2, -1, -1],
+    [-2, 4, 5, 5, 5, 2, -5, -4, -1, -11, -4, -1, 1, 5, 2, -6],
+    [0, -4, 6, 5, 3, 2, -5, -9, 3, -9, -9, 4, 2, 6, -1, 1],
+    [2, -5, -1, 5, 7, 4, -5, -7, 4, -10, -7, 4, -3, 0, 2, -6],
+    [-8, 3, -2, 6, 4, 7, -4, -3, 1, -8, -3, -2, 5, 6, 1, -6],
+    [-5, -6, -1, 8, 7, 2, -4, -8, 0, -2, -6, 4, 7, 1, -3, 3],
+    [-7, 2, 0, 0, 10, 2, -5, -6, 3, -4, -9, 4, 5, 1, -1, 2],
+    [-6, 0, 3, 9, 1, 2, -4, -2, -4, -12, -2, 2, -1, 4, 3, -4],
+    [-5, 0, 0, 8, 2, 9, -5, 0, -6, -5, -7, 2, -2, 5, 7, 2],
+    [1, -4, -1, 7, 4, 2, -5, -7, -2, -8, -7, 7, 1, 0, 0, -7],
+    [-1, 0, 6, 2, 6, 2, -5, -8, -5, -9, -3, -1, 3, -1, 6, 4],
+    [-7, 4, 7, 7, 7, 2, -4, 0, -2, -7, -6, 5, -4, 5, 2, 5],
+    [-5, -1, -2, 11, 3, 2, -3, -4, 2, -9, -2, 3, 4, 4, 1, -1],
+    [-2, 5, 4, 4, 6, 4, -3, -7, 4, -6, -2, 1, 6, 0, 8, 3],
+    [0, 0, -3, 6, 6, 1, -3, -6, -5, -6, -3, 2, 2, 9, 6, 0],
+    [1, -4, 1, 7, 7, 3, -6, -4, -4, -6, 1, 7, 4, 2, 9, 2],
+    [-5, -4, -3, 5, 3, 1, -5, -5, 2, -9, -5, 3, 3, -1, 3, 0],
+    [-1, 2, -3, 4, 1, 5, -3, -8, -1, -5, -8, 5, 4, 1, 8, 3],
+    [2, 2, 4, 5, 10, 8, -8, -1, 4, -9, 1, 3, 1, -1, 3, 5],
+    [-2, -3, 3, 2, 6, 1, -4, -8, -2, -9, -2, 2, 1, 1, 3, 2],
+    [-1, -1, 7, 0, 5, 7, -4, -8, -2, -11, 1, -2, 5, 0, 1, 0],
+    [-2, 5, -3, 8, 4, 6, -3, 0, -5, -9, -6, -4, -1, 0, 4, 4],
+    [-12, 0, 10, 10, 16, 11, -7, -19, 0, -20, -9, 10, 8, -2, 11, 4],
+    [-12, -3, 9, 14, 13, 17, -9, -13, -5, -21, -7, 10, 1, 3, 12, 3],
+    [-12, 4, 6, 11, 17, 20, -11, -14, 1, -19, -14, 5, 0, 3, 5, -5],
+    [-14, -4, -2, 18, 7, 8, -14, -15, -3, -23, -13, 10, 4, 5, 4, 2],
+    [-14, 1, 8, 19, 9, 17, -8, -15, 1, -17, -9, 3, 3, 5, 13, 2],
+    [-13, -4, 6, 15, 10, 14, -8, -17, -3, -20, -14, 1, 0, 6, 9, 1],
+    [-9, -2, 1, 19, 12, 19, -11, -10, -7, -21, -7, 10, 8, 10, 2, -6],
+    [-14, 6, 8, 9, 16, 10, -10, -16, -3, -19, -15, 8, 1, 8, 10, 2],
+    [-4, 3, 9, 18, 15, 13, -11, -19, -7, -23, -6, 5, 5, -1, 1, 2],
+    [-13, 5, 6, 10, 7, 13, -11, -20, -3, -20, -16, -2, 0, 9, 14, 0],
+    [-10, -3, 0, 9, 17, 15, -8, -17, 2, -25, -8, 5, 3, 4, 8, -2],
+    [-15, 5, 7, 20, 8, 16, -10, -19, -4, -17, -9, 0, 2, 4, 11, 2],
+    [-6, 6, 4, 16, 16, 12, -14, -17, -7, -18, -9, 3, 10, 3, 8, 2],
+    [-10, 4, 4, 18, 15, 13, -8, -15, 1, -22, -5, 0, 1, 10, 13, -2],
+    [-10, -4, 1, 19, 10, 12, -15, -10, 4, -24, -12, 7, 4, 4, 11, -4],
+    [-10, -6, 6, 16, 7, 14, -12, -10, -7, -24, -8, 8, 6, 6, 12, 4],
+    [-8, 2, 5, 15, 10, 16, -13, -20, 2, -20, -12, 4, 1, -1, 10, -2],
+    [-5, -2, 2, 17, 9, 14, -13, -19, 2, -19, -15, 3, 6, 6, 11, 4],
+    [-9, 1, 1, 22, 12, 15, -12, -15, 2, -22, -8, 5, 4, 1, 9, -2],
+    [-3, 4, 1, 20, 17, 16, -9, -19, 3, -19, -10, -1, 7, 6, 3, -3],
+    [-14, 1, 0, 17, 9, 17, -10, -12, 0, -16, -16, 11, 12, 1, 15, -5],
+    [-13, 6, 1, 15, 8, 11, -13, -19, 1, -18, -15, 9, 6, 7, 9, -3],
+    [-12, 1, 3, 20, 10, 15, -11, -12, 0, -17, -11, 3, 9, 8, 14, 0],
+    [-3, -2, 0, 18, 16, 16, -12, -14, -5, -19, -8, 1, 8, 7, 12, 1],
+    [-9, 1, 11, 12, 8, 13, -14, -12, 2, -25, -12, 8, 9, 4, 8, -1],
+    [-10, 6, 10, 8, 18, 15, -12, -10, -8, -24, -8, 4, 8, 10, 6, -5],
+    [-6, -6, 2, 11, 10, 13, -11, -18, 2, -29, -10, 8, 1, 2, 4, 5],
+    [-8, 2, 11, 20, 15, 19, -10, -13, -3, -18, -11, 9, 7, -1, 2, -1],
+    [-11, -5, 1, 15, 11, 11, -14, -20, -8, -17, -11, 5, 12, 10, 6, 3],
+    [-6, -3, 8, 14, 18, 13, -13, -17, 2, -16, -14, 8, 4, 1, 13, -5],
+    [-11, 1, 12, 17, 18, 13, -14, -11, -5, -15, -15, -1, 4, 1, 13, 5],
+    [-6, 1, 5, 20, 16, 13, -12, -15, 4, -23, -8, 0, 0, 2, 4, 2],
+    [-10, 0, 2, 14, 15, 14, -12, -15, -1, -20, -14, 5, 3, 7, 4, -2],
+    [-16, 0, 9, 13, 6, 13, -13, -9, 0, -23, -14, 6, 9, 9, 11, -3],
+    [-11, -3, 12, 20, 9, 13, -15, -15, 0, -20, -9, 5, 0, 1, 8, 1],
+    [-6, -1, 7, 13, 11, 15, -13, -19, -1, -20, -15, 9, 5, 7, 3, -3],
+    [-12, 1, 5, 21, 10, 17, -10, -13, 4, -22, -13, -1, 6, 3, 3, 2],
+    [-6, 4, 5, 18, 18, 14, -12, -9, -1, -18, -16, 8, 7, 10, 4, 2],
+    [-9, 0, 8, 19, 12, 14, -12, -9, 4, -24, -12, 1, 10, -2, 5, 3],
+    [-6, 3, 6, 16, 12, 15, -11, -11, -1, -22, -15, 6, 3, 3, 10, -1],
+    [-14, -4, 7, 10, 16, 14, -13, -11, -4, -21, -11, 1, 7, 2, 9, -4],
+    [-17, -5, 5, 13, 10, 13, -14, -21, 0, -19, -8, 7, 5, 5, 4, -6],
+    [-7, -4, 3, 18, 17, 14, -11, -13, 2, -21, -15, 5, -1, 1, 6, 3],
+    [-11, 2, 10, 18, 15, 14, -14, -15, 4, -20, -8, 0, 2, 9, 7, -3],
+    [-11, 1, 5, 17, 8, 13, -13, -20, -2, -20, -15, -1, 3, 3, 1, -3],
+    [-9, -5, 9, 18, 18, 13, -15, -14, -2, -16, -12, 0, 6, 9, 4, -5],
+    [-14, -4, 6, 15, 13, 13, -13, -18, -3, -19, -8, 10, 0, 3, 8, 6],
+    [-14, -5, 0, 15, 15, 14, -11, -15, -3, -13, -10, 8, 10, 9, 13, -7],
+    [-12, -5, 2, 9, 16, 13, -15, -15, -7, -22, -7, 2, 7, 12, 4, -2],
+    [-11, -1, 3, 13, 17, 13, -13, -21, 1, -15, -9, 8, 0, 3, 11, -1],
+    [-11, -1, 2, 16, 18, 14, -14, -18, 1, -14, -8, 6, 3, 9, 12, 0],
+    [-7, -5, 8, 19, 10, 15, -14, -18, -3, -15, -11, 8, 1, 8, 11, -2],
+    [-13, -5, 2, 15, 6, 15, -15, -16, -2, -24, -7, 0, 9, 4, 11, -2],
+    [-14, 4, 0, 10, 12, 14, -14, -13, -8, -24, -7, -1, 10, 10, 12, -2],
+    [-6, -6, 10, 13, 19, 16, -12, -20, 3, -18, -11, 2, 1, 0, 6, 6],
+    [-6, 6, -1, 16, 9, 11, -15, -17, -5, -25, -6, 4, 4, 10, 7, -2],
+    [-6, -6, 6, 18, 15, 18, -14, -12, 0, -19, -6, 5, 0, 4, 12, -3],
+    [-12, 1, 0, 17, 14, 15, -13, -12, 0, -22, -14, 1, -1, 1, 4, 1],
+    [-7, 3, 4, 11, 18, 15, -13, -18, -6, -18, -6, 8, 9, 5, 11, -1],
+    [-8, 0, 8, 11, 13, 14, -12, -14, -3, -24, -10, -2, 6, 10, 9, 2],
+    [-13, 0, 1, 17, 10, 14, -12, -14, -6, -22, -9, 6, 2, 9, 4, -5],
+    [-9, -1, 10, 17, 14, 13, -14, -16, 2, -17, -7, 0, 11, 10, 7, -1],
+    [-12, -3, 9, 18, 5, 14, -13, -14, 1, -20, -12, 2, 9, 5, 7, -1],
+    [-8, 4, 8, 14, 18, 11, -11, -17, 1, -14, -13, 3, 9, 8, 7, -1],
+    [0, 0, -2, -4, 1, -3, 1, 3, -4, -2, -7, -6, -5, 2, -3, -3],
+    [1, -2, -6, -5, 0, 2, 1, -2, 1, -3, 2, 3, -6, 5, -6, 2],
+    [-1, -2, 4, 1, -4, -1, -4, -1, 0, 1, 4, -3, -5, -4, 0, -2],
+    [-5, -1, -5, 3, 6, 0, 4, 3, 2, 2, 4, -4, -1, -3, 3, -4],
+    [-5, -6, -2, 0, 3, 4, 5, 6, 1, -1, 4, 0, -5, 3, 1, -5],
+    [0, 6, -1, 1, -5, 4, 1, 0, 3, 5, -3, -5, 4, 5, -3, 6],
+    [2, 6, -6, -2, -3, 0, 1, -2, 1, -3, 4, 1, 1, -3, -2, -2],
+    [4, 5, 2, -1, 0, -2, -2, -2, -6, 3, 4, 3, -2, 2, -6, 1],
+    [1, -2, -3, 5, -3, -4, 1, -2, -5, 1, 3, 0, -6, -3, 4, -2],
+    [5, -5, 4, 3, 3, -6, 5, 5, 3, -6, -3, -6, -5, 5, -4, 6],
+    [-4, 2, -2, -7, -6, -1, -1, -6, -5, 3, 2, 5, 6, 1, 5, -3],
+    [4, 5, 5, -4, -2, -3, -5, -1, -1, -2, 4, 0, 5, -2, 0, 1],
+    [4, 6, -3, 5, -4, 1, 4, -7, -3, 3, 1, 2, -2, 6, -4, 0],
+    [6, 0, -7, -2, 4, -2, 5, -3, -4, 0, 0, 1, 4, -3, 4, 3],
+    [4, 2, -3, 1, -5, -2, -1, 0, 4, -4, 3, -5, 6, -2, -5, -4],
+    [0, 3, 0, -4, -1, -5, -3, -3, 1, 2, 0, -1, -6, 5, 6, 3],
+    [0, 0, -1, 2, -5, 2, 1, 5, 1, 0, 2, 7, 3, 5, -3, 1],
+    [3, -6, -2, -2, -2, -2, 4, 1, -3, -5, -2, -1, 1, -3, 2, -6],
+    [5, 6, -3, 4, 5, -5, 6, 2, 4, -2, 2, -1, 1, -2, 3, 6],
+    [-1, -5, 3, -5, 2, 4, -4, 1, -1, 5, -6, 2, -3, 5, -4, 3],
+    [4, -2, 2, 3, 2, 3, 4, -1, 4, -1, 2, -3, -4, 4, -3, -1],
+    [-2, 0, 6, -4, 6, -1, -2, 3, -5, 0, 2, -4, 2, -1, 1, -2],
+    [-2, 3, 3, 0, 0, -4, 1, -2, 4, 1, -1, -6, 1, 1, -3, 6],
+    [4, -3, 6, -5, 4, -2, -2, 5, 2, 0, -5, -3, 3, 4, -3, -1],
+    [6, 2, -6, -3, 5, 3, 5, -6, 2, 4, -5, 1, -3, -4, -4, -4],
+    [3, -1, 1, 1, -6, -1, 1, -5, -4, 2, -2, 0, -4, -3, 4, 5],
+    [1, -4, -7, 1, 2, 4, 4, 5, 3, -4, 0, 1, -3, 1, 0, 6],
+    [0, -1, -4, 0, 7, -3, 1, -4, 0, 2, 4, 1, 7, -2, -2, 3],
+    [5, -6, 3, 3, 1, 4, 3, 0, 5, 3, 1, 3, 3, 1, 0, -5],
+    [-5, 3, 3, 5, 2, -1, -5, 2, 6, 3, 2, -3, -2, -4, -2, 5],
+    [-2, 5, 4, -5, 2, -1, 0, -3, -6, 4, 1, 4, 4, -5, -2, 6],
+    [-1, 0, -3, 4, -3, 3, 2, 0, 4, 5, -2, 1, -2, 5, -5, 4],
+    [3, -1, 5, -4, -4, 2, 1, -3, 2, 1, 4, -4, -1, 5, 6, 0],
+    [3, -4, 6, 3, -3, -1, 5, -4, -2, -1, 4, -3, 4, -1, -6, -1],
+    [1, -5, -3, -2, -2, 1, -4, -3, -2, 2, -2, -4, -5, 5, -2, -5],
+    [-1, -3, -4, -4, -2, 1, -3, -3, -8, 2, -1, -5, 4, 1, -1, 6],
+    [2, -1, -2, 0, 0, 2, -4, 5, 6, -5, 5, 4, -1, -1, -1, 6],
+    [0, -6, -3, -4, -1, 2, -4, -7, -3, 2, 5, 5, -5, 3, -2, 3],
+    [2, 3, 5, -5, 1, -3, -2, 2, -4, 2, -1, 6, 5, 0, 1, 5],
+    [1, -3, 4, 5, 5, -3, 0, 6, -6, 5, -2, -3, -5, -1, 5, -3],
+    [-3, -2, 3, -5, -4, -1, 3, -1, 1, -1, -4, 2, -6, 2, 1, -1],
+    [-4, 5, 5, 0, 0, 0, -4, -3, 3, 5, 2, -4, -1, 1, -6, -3],
+    [-4, 5, -1, 2, -1, 1, -4, 6, 2, 3, -4, 1, -3, -5, 3, 0],
+    [5, 0, -4, 2, 2, -1, -8, 5, 1, -4, 3, 2, -5, 0, 0, -4],
+    [5, 4, 3, -2, 1, 2, -5, 1, -2, 2, -3, 2, -2, 2, -2, 2],
+    [2, -4, 1, 3, 0, 2, -5, 4, 3, 3, -3, 5, -7, 5, -1, 1],
+    [-3, 6, 5, -2, -4, 0, -1, 5, -6, 2, -1, 3, 3, -2, 5, 4],
+    [-3, -6, 2, -1, 0, 2, 3, 7, 2, -1, -3, 3, -1, 3, -4, -3],
+    [6, -3, 6, -5, -2, -3, 0, 0, -2, -3, 2, -1, 2, 1, 6, -3],
+    [2, 5, 1, 3, 1, -4, 2, -4, -1, 3, 3, 6, -2, 4, -3, -1],
+    [-1, -5, -2, -2, -1, 0, -1, -5, 3, -1, 3, 0, -3, 4, -5, -2],
+    [-4, -5, 2, -2, 1, 1, 0, -2, -5, 1, 3, -1, -6, -1, -5, 1],
+    [-5, -2, 0, -1, -3, 1, 0, -1, 2, 1, 4, 2, -2, -4, 4, 3],
+    [2, 0, -1, -3, -1, 1, 1, 1, 4, -3, -3, 4, -3, 0, -2, 6],
+    [3, -2, -2, -3, 4, -2, 4, 2, -1, -4, 1, 5, 5, -1, -3, 2],
+    [-3, 3, 4, 1, 0, 4, 3, 4, 0, -1, 5, 5, 3, -6, -5, 5],
+    [4, -6, -1, -6, 0, -5, 2, -4, 6, -1, -4, 3, -5, 3, 5, 2],
+    [5, 1, -4, -2, 2, 3, 1, 3, 1, -2, -4, 3, -8, -5, 5, -1],
+    [-3, -1, -4, 2, -3, -2, 1, 4, 0, -3, -5, -4, -1, 1, -5, 4],
+    [3, 4, 2, -3, 1, 0, 2, 0, 2, -3, -2, 5, -1, -2, -2, -5],
+    [-1, 2, 2, -2, 0, -1, 2, 0, 7, -1, 0, -1, -3, -1, 2, -2],
+    [-1, 2, -6, 0, -5, 0, 4, 0, -1, -2, -2, 2, -1, -1, 0, -7],
+    [1, 0, 4, -2, 1, -1, 4, -3, 0, -2, 2, 2, 2, -4, -5, 0],
+    [-1, 5, -9, -3, -4, -3, 1, -3, 4, -4, -3, -2, -1, 3, -3, -2],
+    [3, 1, 4, 3, 3, -3, -1, -5, 4, 5, -2, 0, 1, 1, -2, -6],
+    [5, 5, 4, -3, 0, 0, -2, -2, 1, 4, 3, 0, -4, -6, 5, 5],
+    [4, 2, 2, -2, 4, -1, 1, 6, -5, -1, -3, -2, 3, 5, -2, 6],
+    [-3, -6, 0, -3, 5, 4, -3, -3, 0, 3, 4, 0, 5, -4, -3, 1],
+    [2, 2, -1, 4, 5, 2, -2, 2, 3, -4, -1, -2, 0, -4, -2, 6],
+    [-3, 2, 5, -6, -5, -1, 0, 5, 6, 3, 0, 2, 6, 1, -4, -3],
+    [4, 6, -3, 3, -6, -3, 1, 2, -5, 5, -6, 5, 4, -6, -2, 6],
+    [-4, -2, 3, 1, -2, -3, -2, -2, 0, -1, -1, 2, -1, -4, -3, -4],
+    [2, -1, -3, -3, -2, -4, 4, 0, -3, 1, -1, 3, -4, -6, 0, -6],
+    [-1, 0, 0, -1, -3, -3, 2, 2, -2, 6, 1, 2, -2, 1, 0, -1],
+    [4, -5, -4, -5, -1, -4, 1, -1, -1, 2, 0, 1, 1, 1, -1, -8],
+    [-1, 4, 5, -1, -3, -5, 4, -1, 1, 2, 0, 0, 4, -7, -5, -2],
+    [1, -5, -2, 1, -2, -4, 6, 3, 6, 2, -3, -1, 1, -4, -1, -3],
+    [-3, 0, 0, 1, -2, -3, 2, 1, -1, 3, 5, -3, -1, -2, -3, -2],
+    [4, 2, -5, -3, -2, -2, 1, 5, -5, 2, -2, -2, 4, 0, 2, -3],
+    [1, 2, -2, 2, -4, -4, 3, 2, -1, 2, 3, -1, 2, 1, -2, 0],
+    [5, -5, 0, -1, -1, -4, 3, 5, 6, 1, -3, 0, 4, 1, 0, -1],
+    [3, -6, -2, 0, 1, -2, 1, 2, -2, 5, -1, 2, 0, 1, -3, -4],
+    [4, -6, 2, -3, 0, -4, 1, 4, 1, 3, -1, 2, 3, -1, 3, -3],
+    [3, 1, 0, -1, 1, -3, 2, 3, -4, 3, 0, -1, 2, -4, 2, -5],
+    [0, -4, 1, 0, -3, -2, 3, 1, 2, 3, 4, 3, 1, 0, 1, -2],
+    [2, 5, 5, -5, 0, -3, 3, 1, -4, 1, 4, 4, 4, -4, 0, 2],
+    [-3, 0, -5, -2, -5, -1, 2, 4, 4, 3, 2, 2, 7, 4, -1, -1],
+    [0, 1, 7, 2, 0, -5, 1, 2, 3, 5, 4, 3, -6, -3, 0, 0],
+    [1, -6, 2, -2, 0, -4, 4, 3, -4, 3, 0, 5, -3, 0, 0, -6],
+    [5, -4, 0, 1, 1, -2, 3, -1, -1, 4, 3, -5, -2, -2, -4, -1],
+    [2, -3, 1, -1, -2, -3, 2, 1, 0, 3, 1, -3, -2, 7, 0, -6],
+    [3, 3, -1, -3, -2, -3, 1, 1, -3, 2, 1, -1, 4, -9, 2, 1],
+    [4, 1, 5, 0, -2, -2, 2, 4, -6, 5, 1, 0, 4, -3, -1, -1],
+    [-2, 0, -1, -4, -2, -4, 2, -1, 2, 1, 2, -4, -2, -3, -3, 4],
+    [4, -2, 1, -2, -6, -1, 3, 2, -4, 2, 0, -1, 1, 3, 0, -6],
+    [0, 3, -1, -2, 2, -5, 1, 1, 3, 3, -1, -1, -5, 3, -3, -2],
+    [-1, 2, -5, 1, -2, -4, 4, 0, 4, 4, 0, -3, 5, -1, 0, -6],
+    [3, -1, 0, -2, -4, -2, 4, -2, 2, 3, 2, 0, -1, 0, -1, -6],
+    [6, -6, -5, -6, 2, -4, 3, 4, 5, -2, 1, -4, 2, 0, 2, -7],
+    [3, 1, -2, -1, -3, -3, 2, 1, 3, 4, 1, 3, 0, 3, -2, -6],
+    [1, -6, -2, -1, 2, -3, 1, 4, 4, 3, 2, 1, -9, 7, -1, -5],
+    [-1, -7, 1, 1, -3, -2, 5, 0, 5, 2, 7, -5, -1, -7, 1, 5],
+    [0, 4, -2, -2, -3, -1, 4, 2, 4, 3, 3, -4, 4, 1, 3, -1],
+    [-1, 5, -4, -3, 3, -7, 3, 3, -3, 1, -1, 2, -7, -7, -3, 3],
+    [4, 4, -10, -4, 0, -7, 6, -4, 4, 1, 2, 4, -8, 2, -1, 6],
+    [3, -3, -3, -3, 0, -7, 7, 4, -2, 1, 4, 7, 2, 2, -3, -5],
+    [0, 5, -4, 0, -3, -8, 8, -2, 4, 5, 5, -5, 0, 8, -1, 5],
+    [-1, 3, 2, -4, 1, -7, 6, 6, 4, 4, -2, -1, 2, -7, 3, -1],
+    [3, -4, -9, -2, 0, -8, 5, 0, 4, -1, 6, -6, 3, -4, -2, 4],
+    [-2, 4, 0, -3, 2, -7, 7, 6, -3, 2, 5, 4, -3, 0, 1, 1],
+    [7, -2, -2, 2, 2, -7, 7, 5, 6, 2, -1, -1, 0, 3, -8, 5],
+    [-1, -2, 2, -3, 3, -3, 10, 5, -4, 3, -2, 3, -11, -5, 0, 5],
+    [-2, 2, -3, -5, -3, -7, 2, 6, 2, 6, -1, 3, -6, 0, -5, -1],
+    [-3, -3, -3, -7, -2, -6, 4, 5, 6, 3, 2, -4, -2, -4, -6, -5],
+    [0, -3, 1, -3, -3, 0, 8, 3, -5, 8, 6, -7, -1, 2, -4, -3],
+    [-2, -4, 0, -6, -3, -1, 7, 4, 3, 5, 9, -2, -5, -4, 0, -4],
+    [0, 5, -2, -11, -8, -1, 4, 1, -4, 5, 5, -2, -5, 4, -3, 0],
+    [-4, 0, 2, -6, -8, -5, -2, 5, -1, 5, 3, 3, -6, -7, -5, 3],
+    [0, 5, -2, -6, 0, -4, 10, 2, 1, 7, 0, -3, -4, -6, -6, -6],
+    [2, -5, 3, -4, -3, -4, 6, 4, -1, 6, 6, -3, 3, -3, 0, -2],
+    [6, 4, 1, -6, 2, 3, -3, -4, -3, -3, 3, 2, -3, -5, -5, 0],
+    [6, 5, -3, -3, 4, -3, 1, 3, -4, 6, -2, 5, 6, 5, 5, -4],
+    [3, 0, -2, 1, 0, 0, -1, 5, 4, -1, -3, 4, 4, 6, -4, 0],
+    [-1, -4, -4, 2, 0, -4, 0, 0, 5, 4, 2, 6, 0, 4, 1, 5],
+    [0, 4, 6, 1, -4, 2, 4, -2, -1, -1, -3, 3, -5, 6, 2, -4],
+    [6, -4, 5, 1, -3, -5, 6, 3, -6, 4, -6, 2, -3, 5, -2, 4],
+    [-5, 2, -6, 6, -4, -2, 3, 2, 4, 0, 0, 3, 2, 4, 3, -4],
+    [0, -2, 0, 0, 1, -5, 1, 1, 3, 6, -5, -3, -4, -2, -3, -4],
+    [10, -6, 0, -6, -8, -10, 4, 8, 3, 13, 5, 1, -10, 3, -5, -8],
+    [9, 6, -4, -4, -10, -9, 5, 10, 0, 13, 4, -3, -7, 2, 0, -5],
+    [6, -5, -3, -7, -13, -8, 6, 11, 6, 9, 7, -1, 2, 0, -6, -5],
+    [0, -1, -3, -6, -12, -10, 3, 9, 2, 13, 6, -5, -2, -6, -4, -2],
+    [5, 1, -1, -12, -5, -7, 6, 6, -3, 14, 3, 0, -7, -9, -8, -6],
+    [3, 2, -9, -13, -8, -9, 6, 8, -1, 10, 11, 3, -1, 0, -2, 3],
+    [7, 6, -3, -7, -6, -8, 4, 11, 0, 14, 4, -1, -8, -1, -1, -4],
+    [4, 0, -5, -7, -9, -8, 4, 8, -6, 12, 6, -2, -6, -1, -6, -3],
+    [9, 2, -3, -9, -7, -9, 4, 11, -2, 13, 3, 3, -4, -3, 1, 0],
+    [5, 2, -9, -8, -11, -9, 3, 8, 1, 10, 8, -6, 3, 1, -8, 5],
+    [3, 2, -7, -9, -12, -6, 7, 9, 4, 13, -2, 1, -5, -2, -8, -5],
+    [6, -2, -4, -12, -5, -8, 4, 3, -3, 14, 9, -4, -4, -5, -2, -6],
+    [6, 4, -8, -6, -10, -9, 10, 8, 3, 9, 8, -6, 1, -8, -2, 0],
+    [5, 4, -5, -11, -8, -9, 5, 3, -4, 10, 9, -3, -9, -6, -7, -4],
+    [3, -4, -6, -14, -9, -10, 4, 5, -3, 13, 2, -8, -3, -1, -2, 4],
+    [3, -4, -1, -5, -8, -9, 5, 9, -4, 14, 9, -2, -7, -5, -2, 3],
+    [2, 3, 1, -8, -6, -10, 4, 10, 7, 12, 2, -2, -7, -7, -9, -1],
+    [5, -3, -3, -12, -10, -9, 7, 8, 2, 8, 2, -9, 0, -7, -4, 2],
+    [9, -1, -8, -10, -6, -7, 4, 9, 2, 12, 7, -1, 0, 0, -2, 2],
+    [1, -5, -1, -8, -7, -11, 4, 8, 4, 13, 7, -5, -4, -9, -4, -4],
+    [1, 5, -2, -8, -9, -9, 4, 11, -1, 10, 9, -3, -1, 1, -7, -3],
+    [5, -3, -3, -3, -8, -7, 7, 11, 0, 14, 4, -9, -1, -7, -7, 6],
+    [2, -4, -5, -12, -4, -8, 7, 9, -3, 11, 5, -4, -5, 0, -8, 5],
+    [4, 4, 2, -7, -12, -7, 6, 11, -3, 13, 3, -3, 1, 0, -5, 5],
+    [2, -5, -9, -9, -3, -10, 5, 13, -3, 12, 5, 4, -6, -6, -4, -5],
+    [9, -4, -1, -5, -7, -4, 4, 13, 7, 12, 7, -3, 1, -6, -7, -4],
+    [1, -5, -4, -11, -11, -5, 7, 6, 2, 15, 7, -2, -4, 1, 1, -4],
+    [3, 2, -6, -7, -10, -9, 4, 15, 3, 6, 10, -9, 2, -8, 0, 1],
+    [2, -6, -5, -9, -9, -9, 9, 8, 0, 12, 1, 1, -7, -8, -2, 3],
+    [4, 0, 0, -7, -2, -8, 5, 11, -1, 16, 7, -7, -8, -1, 0, 5],
+    [8, 2, -7, -9, -10, -4, 6, 8, 5, 14, 5, -4, 2, -7, 1, -4],
+    [9, 4, -5, -11, -3, -8, 5, 5, 3, 15, 5, -1, -5, -5, 0, 4],
+    [6, 3, -5, -2, -6, -7, 7, 9, 4, 16, 9, 2, -2, -6, -4, -3],
+    [7, 3, -7, -4, -7, -13, 3, 7, 1, 16, 2, -5, 0, -4, -5, 2],
+    [11, -5, -5, -5, -14, -10, 6, 11, -4, 9, 3, -1, -5, -4, 0, -2],
+    [6, -3, 0, -8, -4, -5, 6, 6, -3, 16, 8, -8, -4, -6, -6, 6],
+    [4, -4, -1, -6, -7, -4, 7, 9, -4, 17, 5, 0, -1, -9, -11, 6],
+    [5, -4, -3, -8, -4, -8, 2, 7, 4, 16, 7, -6, -3, -8, -1, 1],
+    [4, -4, -6, -9, -6, -10, 6, 2, 4, 14, 4, -9, -8, 3, -10, -3],
+    [3, -4, -2, -5, -11, -8, 7, 12, 1, 9, 8, -7, -9, 1, -1, -3],
+    [2, -5, -6, -12, -1, -11, 5, 13, -5, 13, 2, 0, -7, 2, -10, -4],
+    [10, -3, -9, -7, -3, -10, 4, 12, -2, 8, 9, -1, -3, -7, -8, -6],
+    [11, 5, -6, -6, -2, -5, 9, 12, 3, 13, 7, -8, 2, 1, -4, 1],
+    [7, 2, -7, -12, -10, -4, 11, 4, 2, 11, 6, 1, -2, -7, -4, 3],
+    [0, 3, 2, -4, -10, -11, 13, 7, 6, 12, 9, -2, 0, -8, -4, 3],
+    [0, -3, -5, -11, -13, -11, 7, 7, 8, 9, 10, -3, 2, 0, 1, 4],
+    [4, -2, 0, -3, -13, -7, 9, 13, -3, 10, 3, -6, 1, -7, -9, 0],
+    [1, 0, -5, -3, -7, -3, 5, 11, -5, 15, 10, -7, -9, -5, -5, 5],
+    [5, 0, -8, -3, -6, -7, 9, 5, 1, 15, 9, -6, -2, -8, -5, 3],
+    [6, 2, -2, -8, -13, -12, 10, 7, 4, 10, 2, 1, -4, 0, -5, -2],
+    [7, 6, 2, -16, -5, -10, 6, 7, -5, 12, 5, 1, 2, -2, -9, 1],
+    [4, 3, 1, -12, -9, -9, 8, 5, -5, 11, 11, 0, -3, 2, -8, 4],
+    [2, -1, -9, -9, -10, -5, 4, 5, -3, 15, 11, 2, -4, -1, -9, 1],
+    [3, 2, -2, -11, -7, -5, 9, 14, 5, 11, 2, -1, -3, -8, -5, -1],
+    [5, 5, 1, -11, -11, -7, 9, 15, -5, 9, 1, -4, 1, -9, -2, -1],
+    [7, 1, -6, -9, -10, -5, 12, 4, 4, 11, 4, -8, -7, 1, -8, -4],
+    [4, 5, 0, -9, -5, -6, 14, 7, 3, 15, 2, 1, -2, -10, -4, 5],
+    [9, 1, 0, -4, -13, -11, 3, 9, 2, 17, 2, 0, 2, 0, 0, -1],
+    [0, 2, -2, -11, -3, -5, 13, 15, 0, 11, 3, -5, -3, 3, -10, 3],
+    [4, -5, -4, -5, -5, -4, 12, 14, 7, 18, 5, 2, 2, 0, 0, 2],
+    [3, 0, -5, -11, -4, -7, 11, 13, 6, 9, 10, 2, -6, 0, 0, 4],
+    [2, 3, -4, -7, -11, -12, 3, 11, 3, 9, 7, -7, -5, -7, -1, 5],
+    [1, -5, -7, -6, -12, -11, 6, 14, 4, 11, 1, 2, -7, -5, -1, 3],
+    [6, -6, 0, -6, -15, -8, 8, 7, 2, 14, 4, 2, -1, -5, -1, 4],
+    [5, 1, -3, -3, -4, -1, 0, 2, 6, 6, 8, 0, 6, -4, -6, 0],
+    [-1, 3, 0, -2, -4, -6, 1, 4, 3, 7, 3, -2, -7, -4, -5, -2],
+    [2, 4, 5, -8, -4, -2, 5, 9, 6, 3, 5, -6, 2, 1, -2, 1],
+    [7, -6, -4, -3, -3, -7, 1, 7, -3, 7, 1, -1, 3, -3, -1, 2],
+    [-2, 2, -5, -5, -9, -1, 5, 1, 1, 10, 7, -4, 1, 5, 1, 0],
+    [8, -2, 4, 0, -2, -6, 0, 1, 7, 9, 0, -1, -5, -5, -8, -4],
+    [4, -5, -1, -6, -2, -5, 2, 3, 3, 5, 2, -1, 1, -5, -5, 0],
+    [-1, -1, -8, -5, -2, -3, -1, 2, 3, 10, 4, -1, 2, 4, -4, -4],
+    [4, -6, 4, -5, -3, -3, 3, 4, -2, 6, 6, -6, -5, 3, 0, 3],
+    [0, -2, -8, -3, -2, -6, 3, 8, 6, 7, 1, 1, 1, -6, -4, -2],
+    [1, 3, 1, -8, -1, -4, 4, 8, 5, 7, 3, -1, 4, -1, -5, 0],
+    [3, 3, 5, -5, -7, -7, 2, 2, 4, 10, 4, -6, -5, -1, 1, -4],
+    [5, -1, -4, -5, -3, -8, 2, 4, -1, 4, 6, -2, -4, -6, -6, 6],
+    [7, -4, -6, -4, -7, -5, 4, 3, 2, 11, 2, -6, 4, 4, 5, -5],
+    [-4, 5, -3, -2, -9, -2, 2, 6, -1, 10, 4, 2, -5, -1, 0, -4],
+    [-3, 6, 0, -6, -6, -6, 1, 2, 4, 6, 2, 4, 0, -5, -9, -1],
+    [5, 3, -2, -2, -2, -5, 2, 6, 5, 8, 1, -3, 1, -5, -1, -4],
+    [-1, -1, 4, -5, -7, -7, 2, 10, 7, 4, 8, 2, 1, -3, -7, 6],
+    [3, -1, -4, -4, -7, -6, 1, 3, 5, 9, 5, -1, -3, 1, -4, -4],
+    [0, 1, 1, -9, -8, 0, 5, 7, 6, 4, 3, -4, -7, -2, -7, 0],
+    [-1, -4, 3, -10, -5, -4, 5, 9, 0, 10, -1, 3, 3, 5, -7, -4],
+    [7, 4, 0, -10, -5, -5, 3, 2, 0, 7, 8, 2, -1, 2, -2, -5],
+    [3, -1, 1, -4, -2, -6, 4, 9, 5, 5, 11, 1, -5, -4, 2, 6],
+    [5, 2, 4, -4, -2, -5, 2, 6, 4, 6, 6, -1, 3, -1, -4, -3],
+    [4, 0, -4, -4, 0, -3, 3, 5, 4, 8, 4, -4, -5, 2, -4, -3],
+    [1, -2, 0, -3, -6, -4, 10, 3, 4, 6, 12, 3, -4, 2, -1, -1],
+    [3, 1, -4, -9, -1, -6, 1, 5, 1, 13, 3, -1, 1, 1, 2, -6],
+    [1, -2, -5, -10, 0, -5, 3, 7, -6, 4, 11, -4, -3, -1, -5, -5],
+    [2, 0, -3, -8, -8, -6, 3, 3, -2, 10, 9, 4, -1, 3, 2, -4],
+    [7, 6, -3, -5, -3, -6, 2, 0, 0, 6, 7, -7, 0, -9, -7, 0],
+    [3, 2, -4, -5, -1, -9, 2, 2, 0, 7, 5, -1, -4, -5, -7, 5],
+    [3, 1, 1, -5, -11, -5, 2, 5, 1, 4, 2, -2, 1, 2, -3, -1],
+    [-1, -1, -7, -7, -1, -4, 2, 2, 5, 8, 1, -3, -6, -4, -4, -3],
+    [3, -3, 5, -1, -7, -4, 4, 8, 6, 9, 6, 1, -3, -5, 0, -2],
+    [0, -5, 1, -3, -3, -5, 0, 4, -4, 10, 11, -1, -4, -2, -8, 1],
+    [8, 2, 1, -7, -9, -8, 1, 6, 6, 7, 3, 2, 2, 1, -3, -3],
+    [4, -3, -2, -1, -8, -5, 2, 4, 2, 13, 8, 0, 0, 3, 2, 3],
+    [-1, 6, -3, -8, -2, -5, 3, 8, -1, 7, 8, 1, 0, 3, -9, 0],
+    [2, 6, 0, -2, -2, -6, 1, 3, 1, 10, 10, -6, -4, -5, -4, -5],
+    [0, 6, 2, 0, -4, -7, -1, 10, -4, 8, 5, 3, -7, -2, -2, -6],
+    [-1, -1, -1, -9, -1, 0, 0, 1, -4, 5, 9, -6, -7, -7, -9, -1],
+    [-3, 0, -8, -4, 0, -5, 2, 3, 6, 12, 8, -3, -2, -8, 0, -2],
+    [0, 0, 4, 0, -6, -4, 4, 11, -5, 9, 4, -7, -6, -3, -6, 1],
+    [6, -5, -4, -11, -1, -7, 1, 4, 3, 10, 2, -4, 2, -4, 1, -1],
+    [2, 0, -3, -10, -9, -4, 4, 2, 5, 11, 1, 0, 2, -1, 0, -4],
+    [0, 6, 0, -5, -3, -5, 2, 9, 6, 8, 7, 4, -4, -1, -1, 2],
+    [3, 4, -6, -12, 0, -4, 3, 7, -3, 8, -1, -7, 3, -4, 1, -4],
+    [0, 0, -5, -8, -2, -2, 10, 6, -2, 2, 5, 2, 2, 4, -8, -3],
+    [3, 1, -7, -1, 1, -2, 5, 7, 4, 8, 6, 1, -6, -2, 4, 5],
+    [-1, 4, -1, -8, -6, 1, 1, 8, 7, 9, -2, -7, -2, 0, -1, 4],
+    [5, 0, -5, -10, -2, -3, 6, 5, 2, 6, 2, -4, -4, 1, -1, 2],
+    [9, 2, 3, -7, -4, -1, 7, 9, 7, 3, 4, 2, -2, 3, -7, -5],
+    [4, 4, -7, -4, -10, -5, 3, 6, 1, 4, 1, -4, -6, 1, -7, 6],
+    [0, -2, 2, -10, -9, -5, 2, 7, -5, 2, 9, 0, -4, 0, -1, 3],
+    [6, 4, -3, -4, -9, -6, 2, 5, 3, 4, 1, 0, -5, 4, -2, -6],
+    [1, -6, 3, 0, -6, -4, 3, 3, -4, 11, 3, 3, -3, -5, -6, 5],
+    [8, -1, -1, -3, -8, 1, 3, 0, -1, 8, 4, 0, -1, -4, -2, -4],
+    [3, -6, -7, -3, -1, -2, -2, 10, 1, 6, 0, -3, -5, 4, -6, -5],
+    [6, -3, 2, -2, -4, -5, 3, 5, 1, 10, 0, -3, 2, 2, -4, -2],
+    [-1, -1, -3, -6, -4, 0, 2, 9, 5, 5, 9, 2, -3, -6, 3, 2],
+    [4, 6, -8, 1, -1, -5, 5, 2, -4, 9, 3, 4, -6, -8, -8, 3],
+    [8, 2, -6, -1, -6, 0, 5, 9, -1, 5, 3, 1, 0, 1, -2, 7],
+    [0, -2, -2, -1, -5, -9, 4, 3, 3, 6, -1, -7, 1, -4, -3, -5],
+    [10, -2, -2, -7, -3, -1, 4, 6, -6, 6, -1, -4, -5, 3, 4, -4],
+    [10, 2, 0, -3, -1, -7, -1, 5, 2, 8, 3, -6, -2, 0, 0, -4],
+    [3, -4, -2, -3, 0, -8, 1, 7, -3, 13, 0, 2, -6, -6, 1, 0],
+    [3, -4, -3, -7, -5, -5, 1, 4, 1, 7, 5, 3, 0, -3, -4, -2],
+    [-3, -2, -8, -2, -8, -5, 5, 10, 1, 5, 3, -7, 4, -8, -1, 4],
+    [1, -6, -3, -9, -4, -2, 3, 1, -5, 11, 2, -2, 0, -7, -3, 2],
+    [0, -7, -4, -4, -5, -7, -1, 3, 0, 9, 6, 0, -2, 0, -4, 0],
+    [4, 0, -7, -10, -7, 0, 0, 3, 4, 4, 9, 2, -3, -5, -2, -5],
+    [8, 5, -1, -1, -9, -5, 1, 0, 1, 11, 0, -3, -3, -8, -2, 0],
+    [-1, -1, 1, -9, -2, -6, 4, 10, 2, 3, 8, -5, -2, -3, 2, 1],
+    [3, 6, -4, -7, -6, -5, 1, 5, -3, 9, 2, 1, -1, -8, -1, 0],
+    [6, 6, -5, -2, -5, -6, 2, 1, -3, 12, 7, 4, 2, 2, -4, -4],
+    [0, 2, -5, -8, -5, -5, 2, 4, -3, 9, 3, 1, -2, -3, -6, 3],
+    [5, 3, 1, -2, -5, -7, 0, 6, 1, 9, -2, -3, -6, -1, -7, 2],
+    [5, -1, -7, -3, -8, 0, 1, 8, -2, 9, -2, 2, 1, -5, -6, 2],
+    [5, -5, -2, -6, -2, -6, 1, 6, -2, 10, 2, -5, 2, -7, -2, 2],
+    [0, -3, 5, -7, -9, -2, 9, 6, -4, 7, 0, -5, -3, 0, -3, -6],
+    [5, -4, -3, -3, -2, -5, 2, 3, 0, 8, 9, -6, -3, 1, -2, 5],
+    [7, -3, -1, -4, -1, -6, 1, 10, 4, 8, 4, 0, -4, -6, 2, -5],
+    [0, 6, 6, -5, -7, -4, 3, 8, -2, 6, 7, -4, -5, -6, -4, 1],
+    [-1, 0, -1, -3, -7, -7, 0, 6, 1, 10, 5, 0, -4, -1, -6, 0],
+    [4, 0, 0, -9, -3, -6, 2, 4, 7, 8, 4, -3, -1, 4, -8, 3],
+    [-2, 4, -1, -7, -10, -7, 0, 10, 0, 3, 6, -6, -1, 0, -4, 3],
+    [0, 2, 2, -6, -5, -3, 7, 7, -2, 5, 6, -4, -7, -4, -6, 3],
+    [-1, 4, -3, -8, -5, -6, 1, 2, 5, 10, 5, 2, -4, 1, -4, 1],
+    [1, 0, 3, -7, -5, -8, 0, 5, -1, 6, 8, 0, 3, -8, -2, 3],
+    [6, -2, 2, -5, -9, -3, 3, 6, -3, 7, 1, 2, -5, -4, -4, 1],
+    [-2, -3, -2, -4, -3, -6, 1, 8, 5, 13, -2, 1, -7, -6, -5, 0],
+    [8, 4, 0, -5, 0, -7, 1, 12, -1, 6, 0, -3, -7, 0, -4, -5],
+    [6, 2, -7, -8, -6, -6, 0, 9, 5, 4, 2, -1, -2, 5, -6, -5],
+    [1, -2, -3, -8, -8, -4, 2, 4, -5, 12, 1, 3, -7, -6, 2, 2],
+    [8, 3, -3, 0, -4, -1, 3, 4, 4, 12, 3, -4, -2, 0, -8, 0],
+    [1, -2, 0, -3, -8, -5, 1, 7, -2, 5, 10, -3, 2, -6, 0, -3],
+    [8, -4, -3, -2, -5, -6, -1, 9, 3, 10, 0, 2, 2, -9, 3, 6],
+    [1, 2, -4, -1, -6, -7, 0, 8, 5, 10, 0, -4, -5, -5, -3, -5],
+    [3, 4, 0, -3, -11, -3, 1, 3, 3, 6, 8, -6, -6, 0, -6, 6],
+    [6, -6, -8, -6, -5, -7, 2, 7, 6, 8, 5, 4, -3, 3, -1, 4],
+    [-1, -2, -2, -5, -7, -4, 3, 10, 0, 5, 9, -8, -4, -6, 3, 1],
+    [8, -3, -2, -2, -4, -3, 2, 3, 3, 10, 2, -3, -7, -4, -7, -2],
+    [6, -1, -1, -7, -3, -5, 1, 6, -3, 6, 8, -5, 2, -2, -6, -6],
+    [0, 5, -3, -5, -11, -5, 4, 4, -4, 8, 3, -2, -3, -1, 0, 5],
+    [7, 4, 4, -10, 0, -4, 1, 2, -4, 12, 2, 2, -1, 0, -7, 0],
+    [2, -4, -3, -8, -6, -4, 0, 7, 0, 11, 4, 1, 2, -6, 4, 0],
+    [9, 5, -2, 0, -8, -5, 3, 8, 2, 7, -2, -5, -4, -4, -3, 4],
+    [6, -5, 2, -1, -9, -3, 2, 11, 6, 12, -2, 1, -3, -5, 2, 3],
+    [8, -3, 3, -2, -7, -7, 2, 9, 2, 5, 7, 0, 0, -5, -3, -3],
+    [3, 5, -1, -8, 0, -4, 6, 4, 0, 11, 8, 0, 5, -6, -2, 2],
+    [4, -6, 0, -3, -7, -4, 4, 6, 4, 6, 4, -5, -4, -6, -2, -3],
+    [1, -6, -4, -9, -8, -6, 0, 5, 2, 3, 5, 1, -4, -2, -5, 5],
+    [1, -2, -5, -5, -4, -1, 9, 3, -4, 8, 5, 0, 0, -5, -1, 4],
+    [3, -3, -1, -12, -1, -5, 3, 6, -4, 10, 4, 0, 2, 3, -2, 6],
+    [6, -6, -1, -11, -3, -4, 1, 2, -4, 10, -1, 2, 0, -6, -5, -5],
+    [1, -4, 1, -6, -2, -4, 8, 2, -1, 9, 9, -8, -6, 1, 0, -1],
+    [9, 1, -8, -3, -2, -1, 9, 4, 2, 13, 1, -2, -1, 3, 0, 6],
+    [9, 0, 0, -5, -8, -4, 2, 3, -2, 12, 5, 0, 1, -1, 2, 4],
+    [-1, 4, -1, -8, -10, -3, 0, 7, 7, 5, 4, -4, -3, -2, -2, -3],
+    [1, -4, -6, -1, -4, -7, 1, 6, 7, 11, 3, -6, 0, 4, -5, 2],
+    [9, -2, 0, -6, 2, -6, 5, 5, -2, 5, 6, -2, -1, -1, -6, -2],
+    [-1, -4, 4, -7, 0, -4, 5, 4, -4, 12, 7, -7, 3, -7, 2, 1],
+    [9, 0, -1, 0, -10, -5, -1, 5, 5, 14, -2, 0, 4, 4, -2, -4],
+    [4, -4, 1, -3, -8, -6, 2, 8, 4, 8, -2, 2, -6, 2, -5, 3],
+    [4, 3, 2, -3, -8, -6, 4, 4, 3, 9, 3, -3, 3, 2, -5, 6],
+    [2, 3, 3, -9, -8, -4, 5, 9, 5, 5, -3, -6, -4, -6, 3, -3],
+    [0, 4, 2, -3, -4, -7, 6, 7, 2, 9, 7, 3, -1, -6, 1, 2],
+    [1, -6, -6, -2, -4, -1, 8, 8, -5, 4, 7, -4, -2, -5, -4, 4],
+    [13, -1, 1, -17, -17, -11, 17, 17, 5, 16, 8, -3, -1, 0, -7, 2],
+    [5, -2, -10, -16, -9, -15, 13, 16, 6, 18, 14, -3, -6, -3, -11, -1],
+    [9, 1, -1, -17, -17, -16, 11, 13, 2, 19, 10, -5, -4, -2, -8, 1],
+    [6, 4, -4, -16, -7, -14, 10, 17, 2, 20, 10, -3, -8, -9, -13, -5],
+    [11, 2, -5, -17, -9, -15, 11, 13, 4, 17, 14, -8, -11, -4, -10, -6],
+    [9, 2, -7, -16, -10, -14, 13, 15, 4, 23, 12, -4, 0, -4, -3, 0],
+    [15, -6, -10, -12, -11, -16, 12, 20, 4, 22, 6, 1, -8, -1, -2, 3],
+    [13, -5, -3, -17, -12, -19, 8, 13, 8, 19, 9, -1, -2, -2, -11, 2],
+    [7, -6, -10, -21, -7, -11, 11, 19, 7, 17, 13, -5, -8, 1, -5, 5],
+    [10, -4, -2, -15, -14, -14, 12, 18, 5, 17, 14, -9, -4, -2, -5, -6],
+    [11, 5, -2, -11, -16, -16, 10, 13, 2, 24, 10, -3, -8, -2, -10, -5],
+    [12, -3, -1, -18, -8, -17, 12, 14, -2, 19, 17, -3, -7, -9, -4, 1],
+    [13, 2, 0, -16, -12, -16, 10, 11, 4, 15, 15, -9, -9, -6, -14, 4],
+    [4, -3, -7, -19, -15, -11, 12, 14, 1, 21, 11, -2, -11, -1, -7, -5],
+    [15, 0, -3, -11, -14, -16, 13, 14, 2, 20, 10, -3, -1, -12, -8, -4],
+    [14, 3, -3, -17, -16, -7, 14, 12, -2, 21, 14, -1, -9, -4, -7, 7],
+    [6, 5, -7, -15, -8, -13, 14, 12, 4, 25, 9, -1, -8, -7, -8, -6],
+    [11, -4, -2, -12, -14, -16, 8, 21, 4, 18, 11, -8, -5, -9, -4, 2],
+    [11, -6, -3, -11, -8, -17, 11, 21, 7, 19, 12, -9, -8, 0, -7, 3],
+    [7, 5, -10, -10, -11, -15, 10, 19, -1, 20, 15, 0, -7, -7, -9, -3],
+    [6, 4, -3, -18, -12, -15, 10, 13, 9, 23, 16, 2, -5, -2, -5, 3],
+    [10, 0, -5, -10, -12, -14, 12, 17, 0, 19, 16, -3, -9, 1, -13, 5],
+    [10, -1, -2, -14, -8, -15, 12, 21, 0, 21, 7, -1, -8, -9, -9, 3],
+    [4, 6, -2, -11, -16, -13, 12, 19, -1, 19, 18, -1, -12, -2, -3, -1],
+    [7, 5, -4, -9, -16, -17, 8, 14, 6, 21, 13, -9, -1, -5, -11, -5],
+    [5, -6, -5, -16, -6, -15, 13, 17, 4, 22, 15, -6, -10, -3, -6, 5],
+    [13, 2, -3, -9, -16, -15, 9, 9, 7, 26, 9, -6, -6, -7, -11, -3],
+    [10, -4, 0, -20, -14, -15, 10, 20, 4, 17, 7, -8, -5, -1, -7, 2],
+    [9, -4, -7, -13, -17, -13, 10, 18, 6, 16, 10, -10, -8, -1, -11, -5],
+    [14, 1, 1, -11, -16, -13, 12, 15, -4, 23, 9, -10, -7, -7, -4, 5],
+    [11, -1, -4, -17, -10, -15, 10, 19, 7, 16, 11, -8, -7, -2, -12, -5],
+    [11, 4, -3, -9, -15, -16, 10, 14, 6, 23, 6, -6, -3, -10, -11, 4],
+    [7, -5, -6, -20, -11, -13, 12, 20, -3, 17, 13, -8, -2, 0, -3, 3],
+    [10, 2, -6, -18, -16, -13, 12, 19, 6, 16, 10, 1, 0, 0, -12, 2],
+    [3, 1, -1, -16, -12, -13, 15, 19, -2, 21, 11, -1, -6, 0, -13, -2],
+    [4, -4, -10, -19, -12, -12, 12, 17, -3, 17, 12, -1, -10, -10, -11, 2],
+    [14, 6, -9, -13, -17, -11, 14, 19, -3, 15, 17, -1, -8, -1, -4, 1],
+    [10, 5, 2, -11, -13, -14, 9, 18, 7, 21, 12, -3, -10, -5, -10, 1],
+    [12, -4, -4, -17, -14, -14, 9, 18, 2, 21, 8, -3, 0, -3, -4, 2],
+    [7, -2, -3, -18, -12, -15, 9, 16, 4, 22, 10, -1, -4, -2, -8, 0],
+    [13, -4, -5, -14, -10, -14, 14, 20, 3, 17, 7, -8, -6, -10, -5, 4],
+    [8, -4, -3, -13, -17, -14, 14, 19, 5, 14, 16, -4, -10, -1, -6, -3],
+    [6, 5, 0, -12, -14, -16, 9, 15, 5, 19, 16, -6, -5, -11, -14, -3],
+    [8, 6, -4, -13, -13, -15, 11, 15, 0, 22, 15, -4, -8, -5, -8, 1],
+    [6, -1, -7, -17, -7, -14, 11, 18, -4, 19, 11, -8, -8, -10, -13, -4],
+    [12, -5, -5, -11, -18, -17, 8, 22, 1, 16, 9, -1, -8, -2, -5, 0],
+    [8, -5, -8, -16, -14, -16, 9, 10, 6, 20, 14, -5, -1, -11, -9, 5],
+    [11, 1, -2, -16, -12, -15, 11, 18, 4, 16, 13, -10, -8, -4, -6, 5],
+    [8, 3, -4, -13, -13, -15, 11, 11, 3, 26, 8, -7, -4, 0, -10, 6],
+    [5, 5, -7, -17, -17, -11, 15, 15, 1, 18, 16, -7, -5, 0, -4, 1],
+    [13, 0, -4, -11, -6, -9, 13, 17, 8, 22, 14, -10, 0, -7, -13, -3],
+    [13, -5, -1, -13, -11, -13, 14, 13, 0, 21, 18, -11, -4, 0, -6, 6],
+    [14, 3, -8, -14, -9, -17, 10, 14, 0, 22, 16, -3, 0, -4, -3, -5],
+    [11, -2, -8, -20, -16, -13, 14, 17, 7, 13, 13, -1, -4, -2, -4, 3],
+    [4, -4, 0, -11, -11, -9, 18, 20, -2, 21, 16, -10, -8, -1, -9, -4],
+    [8, -2, -7, -9, -13, -13, 14, 13, 2, 24, 11, -5, -1, -8, -12, 1],
+    [13, 1, -11, -15, -16, -17, 12, 10, -1, 15, 6, -10, -10, -3, -12, 5],
+    [6, -6, -9, -14, -12, -18, 6, 16, -1, 25, 14, 0, -10, 1, -2, -5],
+    [10, -3, -10, -15, -16, -8, 13, 15, 7, 22, 7, -5, -8, 0, -7, -2],
+    [9, -6, -9, -11, -17, -15, 9, 14, 7, 22, 10, 0, -5, -6, -6, 1],
+    [6, 6, -4, -14, -12, -14, 19, 16, -1, 20, 13, -6, -2, -3, -10, -3],
+    [5, -1, -12, -10, -15, -10, 14, 15, -2, 20, 7, -11, -10, -11, -13, -6],
+    [4, -1, -11, -13, -10, -14, 8, 11, 5, 26, 14, -7, -12, 1, -10, 2],
+    [10, -5, -12, -15, -12, -13, 11, 16, 5, 16, 15, -3, -3, -2, -10, -1],
+    [-1, 2, -4, 1, 4, -1, -3, -2, 4, 2, 5, 5, -2, -4, 5, 1],
+    [2, 6, 3, -6, 1, 0, -6, -4, 4, 0, 5, -2, 2, 2, -4, 6],
+    [3, 6, -1, -4, 2, 0, -1, -1, -2, 1, 3, 1, 5, 2, 2, 5],
+    [5, -1, -1, -3, 2, 1, -4, 2, 0, -3, -1, -5, 3, -1, 2, -5],
+    [1, 1, 1, -1, -3, 0, -2, 1, -5, 1, 2, 3, 3, 0, 6, 1],
+    [-2, 6, 3, -1, -2, 1, -3, -1, 0, 4, 4, 4, 3, 1, 2, -4],
+    [0, -3, -6, -1, 1, 0, -7, 0, 1, 3, 2, 3, 1, -1, 3, 1],
+    [5, -4, -5, 4, -1, -2, -8, 4, 1, -1, -2, -2, 1, 1, -4, 0],
+    [4, -2, 3, -3, 0, 4, 1, -6, 4, 0, 3, -1, -5, 2, -4, 4],
+    [1, -2, -4, -4, -1, -1, 1, -6, 2, 0, -1, -1, 1, -2, 0, 1],
+    [2, 6, 4, 0, -2, 0, -1, 2, 3, -3, 0, 1, -7, -5, 1, -4],
+    [0, 6, -3, 0, 0, -1, -1, -4, 0, 1, 5, -2, 0, -1, 4, -4],
+    [-2, 3, 0, 1, -2, -2, -1, -1, 1, -1, 1, -4, 3, -4, -1, -2],
+    [1, 2, -4, 0, -2, 0, 0, 0, 2, -1, 1, 3, -3, 2, 2, -4],
+    [3, 5, 6, 4, -1, 2, -4, -1, 6, 5, 0, -5, 0, -1, -1, 0],
+    [4, 3, -5, -1, 6, 0, -3, 2, -5, 3, 0, 2, -3, 2, 3, -4],
+    [6, -4, -6, 1, 5, -5, -4, -1, -2, -1, 2, -1, 3, -1, 3, 3],
+    [-2, 5, 4, 3, 3, -3, 3, -2, -2, 2, 4, 5, -2, -3, 1, -3],
+    [-4, -1, -1, 5, 3, -6, 2, 3, 1, 5, -5, 4, 4, -2, -3, -2],
+    [-2, 1, -6, 1, 4, -5, 2, -3, 6, 5, -2, -5, 2, 3, 0, 0],
+    [-6, 2, -6, 3, 6, -2, 2, -1, 4, 3, 2, 6, -1, -2, -7, -1],
+    [2, 6, -3, 4, 0, -3, 2, -4, -4, 1, 2, 3, 4, -5, -8, -6],
+    [4, 1, 1, 0, 4, 0, 0, 0, 0, 2, 3, 6, 1, -1, 5, -4],
+    [5, 0, -6, 0, 5, -2, -4, -2, 4, 2, -2, -3, 6, 2, -4, -3],
+    [-3, -1, 2, -3, 4, 3, -3, -5, 1, 5, 0, 4, -7, -4, -4, -3],
+    [-3, 5, 3, -3, -2, -2, 1, -1, -5, -1, 4, 3, -1, -2, 5, -5],
+    [2, 0, 3, 0, 5, -4, 0, -5, -5, 5, 6, -4, 6, 3, 1, 2],
+    [-1, -6, 2, 3, -1, 1, 2, 0, -2, 4, -3, -3, 4, 0, -1, -6],
+    [-1, -2, -6, -2, -3, -3, 4, 0, -3, -2, -4, 2, 4, 7, -3, 4],
+    [1, -2, 4, 4, -4, 2, 2, -4, 5, 3, 4, -5, 0, -6, 3, -1],
+    [5, 6, 5, 4, -7, 4, 1, -3, -1, 1, 3, 0, 4, -6, 4, 3],
+    [-5, 6, 0, 3, -4, -2, -4, 3, 1, 3, -4, 2, -1, -4, 7, 3],
+    [3, 3, -5, -1, 5, 2, 2, 1, -4, -2, 2, -3, 1, -2, 2, 5],
+    [-3, 3, 3, 1, -3, 4, 4, 2, 0, -3, 6, 0, 2, 5, 0, 1],
+    [-4, -2, -7, -5, 3, 3, -1, 1, 1, 0, -4, -6, 6, -6, -5, 1],
+    [3, -3, 2, -3, 4, 5, 3, 4, -6, 1, 3, -5, 1, -5, 5, 3],
+    [7, -1, -3, 2, -1, -4, -1, -2, -6, 3, 0, 4, -2, 4, 5, 0],
+    [4, -2, 4, 2, -4, -5, 2, -1, -4, -3, 0, 0, -4, -2, 7, -6],
+    [2, 2, -4, 5, 4, 2, 3, 1, -1, 4, -1, 4, -3, 5, -2, -5],
+    [-2, -3, 1, -3, -3, -1, -1, -3, -2, 0, -4, 0, 4, -4, -4, 3],
+    [-4, -3, 4, -5, 2, -6, -2, 4, -5, -4, -5, 1, -3, 4, -4, 6],
+    [-5, -2, 0, 2, 1, -1, 4, -2, -5, 3, -4, -7, -3, 3, -1, 1],
+    [1, 0, -3, -1, 0, 2, -4, 4, 4, 1, 2, -1, 3, -1, 5, 2],
+    [3, 0, -4, 1, 2, -5, -4, -3, 4, 3, 2, 6, 1, -1, 2, -5],
+    [2, -2, 4, 1, -7, -6, -3, 0, 5, -2, -2, -1, -1, -5, 5, 0],
+    [-4, 2, 2, -4, -2, 1, -3, 1, 0, 2, -3, 0, -4, -2, -1, -4],
+    [1, 2, -1, -4, -3, 2, 0, -1, -1, -6, -1, -2, -4, -6, 2, 6],
+    [-2, -2, 6, 4, -1, 4, 1, 2, 2, 1, -2, -2, 0, -4, -5, 3],
+    [-5, -3, 4, 4, 5, -1, 5, 7, -2, -2, 4, 4, -5, -4, 3, -5],
+    [-3, -3, 0, 0, -5, -4, -4, 1, 1, -1, 0, -6, 2, 3, 5, 5],
+    [-4, -1, 3, -3, -1, -6, -2, -3, 3, 4, -2, 4, 6, -2, 2, -6],
+    [-5, 2, 4, -5, 1, 5, 6, -4, -6, 7, -4, -5, -3, -6, 6, -5],
+    [-5, -5, -6, 1, -4, -1, -2, 1, -5, 2, -4, -1, 6, -5, -2, -1],
+    [2, -1, -1, 1, 6, -6, 2, 6, 2, -3, 3, 4, 0, 1, 5, -4],
+    [-3, -4, 4, 5, 3, -1, 3, -1, -4, 6, 6, 5, 5, -5, 4, 2],
+    [-1, -4, -4, 5, 6, -4, -2, 4, 3, -2, 5, -1, -3, 5, -1, -1],
+    [0, 4, 4, -5, -3, -5, -6, -2, 4, -2, -6, -6, -5, 5, 0, -1],
+    [-5, 5, -4, -5, -4, 3, -2, 4, -1, 0, -6, 6, -1, 5, -1, 2],
+    [4, 5, 5, 2, 2, -1, -1, -4, -1, 1, 4, -2, -1, -5, -2, 6],
+    [-6, 2, -4, -5, 1, 4, -1, -3, 2, 5, 2, 4, 6, -3, 2, -4],
+    [3, -1, -2, -7, 4, 2, 5, -3, 0, 0, -2, 3, 4, 5, -5, -2],
+    [-5, -5, -2, 4, -1, -3, 2, -2, 5, 3, -6, -1, -6, -6, 6, 5],
+    [-2, -1, -3, 4, 1, -3, 2, -2, -4, 4, 2, 1, 1, 6, 6, 6],
+    [7, -2, 5, 1, -2, 1, -3, 4, 2, -3, 2, 5, 4, 1, 3, 2],
+];
+pub const NNUE_INPUT_BIASES: [i16; NNUE_HIDDEN] = [32, 26, 32, 30, 33, 30, 27, 33, 29, 29, 32, 29, 31, 32, 30, 27];
+pub const NNUE_OUTPUT_WEIGHTS: [i8; NNUE_HIDDEN] = [-39, 0, 21, 63, 54, 54, -44, -67, -8, -87, -48, 21, 23, 21, 34, 4];
+pub const NNUE_OUTPUT_BIAS: i32 = -46;
@@ -0,0 +1,424 @@
+use crate::algorithm::Move;
+use crate::board::board_representation;
+
+// Converts a square name (e.g. "e4") to a bit
+pub fn square_to_bit(square: &str) -> Result<usize, ()> {
+    let bytes = square.as_bytes();
+    if bytes.len() != 2 {
+        return Err(());
+    }
+
+    let file = bytes[0].wrapping_sub(b'a') as usize;
+    let rank = bytes[1].wrapping_sub(b'1') as usize;
+    if file > 7 || rank > 7 {
+        return Err(());
+    }
+
+    Ok(file + (7 - rank) * 8)
+}
+
+// Returns the promotion piece (board index for the white team) of an uppercase piece letter
+fn promotion_piece(c: char) -> Option<usize> {
+    match c {
+        'R' => Some(1),
+        'N' => Some(2),
+        'B' => Some(3),
+        'Q' => Some(4),
+        _ => None,
+    }
+}
+
+// Writes a move in standard algebraic notation (e.g. "Nbd7", "exd5", "O-O", "e8=Q+") for the board it is played on
+// The file or rank of the moving piece is only added when another piece of the same type can move to the same square
+// Returns an error if the move isn't legal on the board, or if writing fails
+pub fn write_san<W: core::fmt::Write>(out: &mut W, piece_move: &Move, board: &board_representation::Board, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Result<(), ()> {
+    use crate::board::move_generator::{make_move_unadjudicated, check_and_mate};
+
+    let piece = piece_move.initial_piece_coordinates;
+    let final_bit = piece_move.final_piece_bit;
+
+    // Make the move first, so illegal moves are rejected before anything is written
+    // The game isn't adjudicated so there is a board to find checks on, a checking move is written with a + even if it draws or wins a variant
+    let check;
+    let mate;
+    match make_move_unadjudicated(&piece, final_bit, piece_move.promotion, *board, pieces_info) {
+        Ok(new_board) => {
+            let (new_board_check, no_moves) = check_and_mate(&new_board, pieces_info);
+            check = new_board_check;
+            mate = new_board_check && no_moves;
+        },
+        Err(_) => return Err(()), // Only invalid moves return an error when the game isn't adjudicated
+    }
+
+    let piece_type = piece.board_index % 6;
+    let capture = crate::bit_on(board.to_bitboard(), final_bit) || (piece_type == 0 && Some(final_bit) == board.en_passant_target);
+
+    if piece_type == 5 && (final_bit as i8 - piece.bit as i8).abs() == 2 {
+        // Castling
+        if final_bit > piece.bit {
+            out.write_str("O-O").map_err(|_| ())?;
+        } else {
+            out.write_str("O-O-O").map_err(|_| ())?;
+        }
+    } else if piece_type == 0 {
+        // Pawns have no letter, captures start with the file the pawn came from
+        if capture {
+            write!(out, "{}x", &crate::bit_to_ccn(piece.bit)[..1]).map_err(|_| ())?;
+        }
+        out.write_str(crate::bit_to_ccn(final_bit)).map_err(|_| ())?;
+
+        // Pawns promote to queens unless the move has another promotion piece
+        if final_bit < 8 || final_bit > 55 {
+            let promotion = piece_move.promotion.unwrap_or(4);
+            write!(out, "={}", &"PRNBQK"[promotion..promotion + 1]).map_err(|_| ())?;
+        }
+    } else {
+        // Find other pieces of the same type that can move to the same square
+        let mut ambiguous = false;
+        let mut same_file = false;
+        let mut same_rank = false;
+        crate::board::perft::legal_moves(*board, pieces_info, |other_piece, other_move_bit, _, _| {
+            if other_piece.board_index == piece.board_index && other_move_bit == final_bit && other_piece.bit != piece.bit {
+                ambiguous = true;
+                if other_piece.bit % 8 == piece.bit % 8 {
+                    same_file = true;
+                }
+                if other_piece.bit / 8 == piece.bit / 8 {
+                    same_rank = true;
+                }
+            }
+        });
+
+        write!(out, "{}", &"PRNBQK"[piece_type..piece_type + 1]).map_err(|_| ())?;
+
+        // The file is used if it is different from the other pieces, then the rank, and both if neither is enough
+        let square = crate::bit_to_ccn(piece.bit);
+        if ambiguous && (!same_file || same_rank) {
+            out.write_str(&square[..1]).map_err(|_| ())?;
+        }
+        if ambiguous && same_file {
+            out.write_str(&square[1..]).map_err(|_| ())?;
+        }
+
+        if capture {
+            out.write_str("x").map_err(|_| ())?;
+        }
+        out.write_str(crate::bit_to_ccn(final_bit)).map_err(|_| ())?;
+    }
+
+    if mate {
+        out.write_str("#").map_err(|_| ())?;
+    } else if check {
+        out.write_str("+").map_err(|_| ())?;
+    }
+
+    Ok(())
+}
+
+// Parses a move in standard algebraic notation (e.g. "Nbd7", "exd5", "O-O", "e8=N+")
+// Returns an error if the move isn't legal on the board or is ambiguous
+// A promotion without the piece is taken as a queen promotion
+pub fn parse_san(san: &str, board: &board_representation::Board, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Result<Move, ()> {
+    // Moves typed over a serial console can have any characters, only ascii can be sliced safely below
+    if !san.is_ascii() {
+        return Err(());
+    }
+
+    // Remove check, mate and annotation symbols
+    let san = san.trim_end_matches(|c| c == '+' || c == '#' || c == '!' || c == '?');
+
+    let index_offset;
+    if board.whites_move {
+        index_offset = 0;
+    } else {
+        index_offset = 6;
+    }
+    let king_index = 5 + index_offset;
+
+    // Castling moves the king 2 squares
+    if san == "O-O" || san == "0-0" || san == "O-O-O" || san == "0-0-0" {
+        let king_bit = crate::find_bit_on(board.board[king_index], 64);
+        if king_bit > 63 {
+            return Err(());
+        }
+
+        let final_bit;
+        if san.len() == 3 {
+            final_bit = king_bit + 2;
+        } else {
+            final_bit = king_bit.checked_sub(2).ok_or(())?;
+        }
+        return find_legal_move(board, pieces_info, |piece, piece_move_bit, _| piece.board_index == king_index && piece_move_bit == final_bit);
+    }
+
+    let mut chars: &str = san;
+
+    // Piece type, pawns have no letter
+    let board_index;
+    match chars.chars().next() {
+        Some('R') => board_index = 1 + index_offset,
+        Some('N') => board_index = 2 + index_offset,
+        Some('B') => board_index = 3 + index_offset,
+        Some('Q') => board_index = 4 + index_offset,
+        Some('K') => board_index = 5 + index_offset,
+        Some(_) => board_index = index_offset,
+        None => return Err(()),
+    }
+    if board_index != index_offset {
+        chars = &chars[1..];
+    }
+
+    // Promotions, the piece can be written with or without an = (e.g. "e8=N" or "e8N")
+    // None if no promotion piece is written, a promotion without one is a queen promotion
+    let mut promotion = None;
+    match chars.find('=') {
+        Some(i) => {
+            if chars.len() != i + 2 {
+                return Err(());
+            }
+            promotion = Some(promotion_piece(chars.as_bytes()[i + 1] as char).ok_or(())?);
+            chars = &chars[..i];
+        },
+        None => {
+            if board_index == index_offset && chars.len() > 2 {
+                match promotion_piece(chars.as_bytes()[chars.len() - 1] as char) {
+                    Some(piece) => {
+                        promotion = Some(piece);
+                        chars = &chars[..chars.len() - 1];
+                    },
+                    None => (),
+                }
+            }
+        },
+    }
+
+    // The last 2 characters are the final square
+    if chars.len() < 2 {
+        return Err(());
+    }
+    let final_bit = square_to_bit(&chars[chars.len() - 2..])?;
+    chars = &chars[..chars.len() - 2];
+
+    // Anything left is the file and / or rank of the moving piece, and the capture symbol
+    let mut from_file = None;
+    let mut from_rank = None;
+    for c in chars.chars() {
+        match c {
+            'a'..='h' => from_file = Some(c as usize - 'a' as usize),
+            '1'..='8' => from_rank = Some(7 - (c as usize - '1' as usize)), // Rank as a row of the board
+            'x' => (),
+            _ => return Err(()),
+        }
+    }
+
+    // A promotion piece can only be written for a promotion (e.g. "e4=N" isn't e4)
+    find_legal_move(board, pieces_info, |piece, piece_move_bit, move_promotion| {
+        piece.board_index == board_index
+            && piece_move_bit == final_bit
+            && ((move_promotion.is_none() && promotion.is_none()) || (move_promotion.is_some() && move_promotion == promotion.or(Some(4))))
+            && (from_file.is_none() || from_file == Some(piece.bit % 8))
+            && (from_rank.is_none() || from_rank == Some(piece.bit / 8))
+    })
+}
+
+// Parses a move in long algebraic notation as used by UCI (e.g. "e2e4", "e7e8q")
+// Castling is written as the king moving 2 squares (e.g. "e1g1")
+// Returns an error if the move isn't legal on the board
+// A promotion without the piece is taken as a queen promotion
+pub fn parse_long_algebraic(text: &str, board: &board_representation::Board, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Result<Move, ()> {
+    if !text.is_ascii() || (text.len() != 4 && text.len() != 5) {
+        return Err(());
+    }
+
+    let initial_bit = square_to_bit(&text[..2])?;
+    let final_bit = square_to_bit(&text[2..4])?;
+
+    // The promotion piece is lowercase, only pawns moving to the last row can have one
+    let mut promotion = None;
+    if text.len() == 5 {
+        let c = text.as_bytes()[4] as char;
+        if !c.is_ascii_lowercase() {
+            return Err(());
+        }
+        promotion = Some(promotion_piece(c.to_ascii_uppercase()).ok_or(())?);
+    }
+
+    find_legal_move(board, pieces_info, |piece, piece_move_bit, move_promotion| {
+        piece.bit == initial_bit
+            && piece_move_bit == final_bit
+            && (move_promotion == promotion || (promotion.is_none() && move_promotion == Some(4)))
+    })
+}
+
+// Writes a move in long algebraic notation as used by UCI (e.g. "e2e4", "e7e8q")
+// Pawns moving to the last row always have the promotion piece, a move without one is a queen promotion
+pub fn write_long_algebraic<W: core::fmt::Write>(out: &mut W, piece_move: &Move) -> Result<(), ()> {
+    let piece = piece_move.initial_piece_coordinates;
+    let final_bit = piece_move.final_piece_bit;
+    write!(out, "{}{}", crate::bit_to_ccn(piece.bit), crate::bit_to_ccn(final_bit)).map_err(|_| ())?;
+
+    if piece.board_index % 6 == 0 && (final_bit < 8 || final_bit > 55) {
+        let promotion = piece_move.promotion.unwrap_or(4);
+        write!(out, "{}", &"prnbqk"[promotion..promotion + 1]).map_err(|_| ())?;
+    }
+    Ok(())
+}
+
+// Parses a move in either long algebraic notation or standard algebraic notation
+// Used where a move is typed in, so either form can be used
+pub fn parse_move(text: &str, board: &board_representation::Board, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Result<Move, ()> {
+    match parse_long_algebraic(text, board, pieces_info) {
+        Ok(piece_move) => Ok(piece_move),
+        Err(()) => parse_san(text, board, pieces_info),
+    }
+}
+
+// Returns the only legal move that matches (given the piece, final bit and promotion piece), or an error if there are none or more than one
+fn find_legal_move<F: Fn(&board_representation::BoardCoordinates, usize, Option<usize>) -> bool>(board: &board_representation::Board, pieces_info: &[crate::piece::constants::PieceInfo; 12], matches: F) -> Result<Move, ()> {
+    let mut found = None;
+    let mut matching_moves = 0;
+
+    crate::board::perft::legal_moves(*board, pieces_info, |piece, piece_move_bit, promotion, _| {
+        if matches(&piece, piece_move_bit, promotion) {
+            let mut piece_move = Move::new();
+            piece_move.initial_piece_coordinates = piece;
+            piece_move.final_piece_bit = piece_move_bit;
+            piece_move.promotion = promotion;
+
+            found = Some(piece_move);
+            matching_moves += 1;
+        }
+    });
+
+    if matching_moves != 1 {
+        return Err(());
+    }
+    found.ok_or(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Writes a move given in long algebraic notation as SAN
+    fn san(fen: &str, long_algebraic: &str) -> crate::lcd_text::Row {
+        let pieces_info = crate::piece::constants::gen();
+        let board = board_representation::Board::from_fen(fen).unwrap();
+        let piece_move = parse_long_algebraic(long_algebraic, &board, &pieces_info).unwrap();
+
+        let mut row = crate::lcd_text::Row::new();
+        write_san(&mut row, &piece_move, &board, &pieces_info).unwrap();
+        row
+    }
+
+    #[test]
+    fn san_checks() {
+        assert_eq!(san("4k3/8/8/8/8/8/8/R3K3 w - - 0 1", "a1a8").as_str(), "Ra8+");
+        assert_eq!(san("6k1/5ppp/8/8/8/8/8/R5K1 w - - 0 1", "a1a8").as_str(), "Ra8#");
+
+        // Checking moves that also draw the game still get a +
+        assert_eq!(san("4k3/8/5p2/3N4/8/8/8/2K5 w - - 0 1", "d5f6").as_str(), "Nxf6+"); // Insufficient material
+        assert_eq!(san("4k3/8/8/8/8/8/8/R3K3 w - - 99 80", "a1a8").as_str(), "Ra8+"); // Fifty move rule
+    }
+
+    #[test]
+    fn san_disambiguation() {
+        assert_eq!(san("4k3/8/8/8/8/8/4K3/R6R w - - 0 1", "a1d1").as_str(), "Rad1");
+        assert_eq!(san("4k3/8/8/R7/8/8/4K3/R7 w - - 0 1", "a1a3").as_str(), "R1a3");
+        assert_eq!(san("4k3/8/8/8/8/Q7/6K1/Q1Q5 w - - 0 1", "a1b2").as_str(), "Qa1b2");
+
+        // Pieces of another type moving to the same square don't make a move ambiguous
+        assert_eq!(san("4k3/8/8/8/8/8/4K3/R2N4 w - - 0 1", "d1c3").as_str(), "Nc3");
+    }
+
+    #[test]
+    fn san_captures_promotions_and_castling() {
+        assert_eq!(san("4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1", "e4d5").as_str(), "exd5");
+        assert_eq!(san("4k3/8/8/3p4/8/2N5/8/4K3 w - - 0 1", "c3d5").as_str(), "Nxd5");
+        assert_eq!(san("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6").as_str(), "exd6"); // En passant
+
+        assert_eq!(san("8/4P3/8/8/8/8/k7/4K3 w - - 0 1", "e7e8n").as_str(), "e8=N");
+        assert_eq!(san("8/4P3/8/8/8/8/k7/4K3 w - - 0 1", "e7e8q").as_str(), "e8=Q");
+
+        assert_eq!(san("4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1", "e1g1").as_str(), "O-O");
+        assert_eq!(san("4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1", "e1c1").as_str(), "O-O-O");
+    }
+
+    // Parses a move and checks it against the move in long algebraic notation, or that it's rejected if expected is None
+    fn assert_parse(parse: fn(&str, &board_representation::Board, &[crate::piece::constants::PieceInfo; 12]) -> Result<Move, ()>, fen: &str, text: &str, expected: Option<&str>) {
+        let pieces_info = crate::piece::constants::gen();
+        let board = board_representation::Board::from_fen(fen).unwrap();
+
+        match parse(text, &board, &pieces_info) {
+            Ok(piece_move) => {
+                let mut row = crate::lcd_text::Row::new();
+                write_long_algebraic(&mut row, &piece_move).unwrap();
+                assert_eq!(Some(row.as_str()), expected, "{}", text);
+            },
+            Err(()) => assert_eq!(None, expected, "{}", text),
+        }
+    }
+
+    #[test]
+    fn parse_san_moves() {
+        let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert_parse(parse_san, start, "e4", Some("e2e4"));
+        assert_parse(parse_san, start, "Nf3", Some("g1f3"));
+        assert_parse(parse_san, start, "Nf3!?", Some("g1f3"));
+        assert_parse(parse_san, start, "e5", None);
+        assert_parse(parse_san, start, "Ke2", None);
+        assert_parse(parse_san, start, "e4é", None);
+
+        assert_parse(parse_san, "4k3/8/8/3p4/4P3/8/8/4K3 w - - 0 1", "exd5", Some("e4d5"));
+        assert_parse(parse_san, "4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "exd6", Some("e5d6"));
+
+        // Disambiguation, an ambiguous move is rejected
+        assert_parse(parse_san, "4k3/8/8/8/8/8/4K3/R6R w - - 0 1", "Rad1", Some("a1d1"));
+        assert_parse(parse_san, "4k3/8/8/8/8/8/4K3/R6R w - - 0 1", "Rd1", None);
+        assert_parse(parse_san, "4k3/8/8/R7/8/8/4K3/R7 w - - 0 1", "R1a3", Some("a1a3"));
+        assert_parse(parse_san, "4k3/8/8/8/8/Q7/6K1/Q1Q5 w - - 0 1", "Qa1b2", Some("a1b2"));
+
+        assert_parse(parse_san, "4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1", "O-O", Some("e1g1"));
+        assert_parse(parse_san, "4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1", "O-O-O", Some("e1c1"));
+        assert_parse(parse_san, "4k3/8/8/8/8/8/8/R3K2R w - - 0 1", "O-O", None);
+    }
+
+    #[test]
+    fn parse_san_promotions() {
+        let fen = "8/4P3/8/8/8/8/k7/4K3 w - - 0 1";
+        assert_parse(parse_san, fen, "e8=N", Some("e7e8n"));
+        assert_parse(parse_san, fen, "e8N", Some("e7e8n"));
+        assert_parse(parse_san, fen, "e8=Q+", Some("e7e8q"));
+        assert_parse(parse_san, fen, "e8", Some("e7e8q"));
+        assert_parse(parse_san, fen, "e8=K", None);
+
+        // A promotion piece can only be written for a promotion
+        let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert_parse(parse_san, start, "e4=N", None);
+        assert_parse(parse_san, start, "e4=Q", None);
+        assert_parse(parse_san, start, "Nf3=Q", None);
+    }
+
+    #[test]
+    fn parse_long_algebraic_moves() {
+        let start = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1";
+        assert_parse(parse_long_algebraic, start, "e2e4", Some("e2e4"));
+        assert_parse(parse_long_algebraic, start, "g1f3", Some("g1f3"));
+        assert_parse(parse_long_algebraic, start, "e2e5", None);
+        assert_parse(parse_long_algebraic, start, "e2e4q", None);
+        assert_parse(parse_long_algebraic, start, "e2", None);
+        assert_parse(parse_long_algebraic, start, "i2i4", None);
+
+        let fen = "8/4P3/8/8/8/8/k7/4K3 w - - 0 1";
+        assert_parse(parse_long_algebraic, fen, "e7e8n", Some("e7e8n"));
+        assert_parse(parse_long_algebraic, fen, "e7e8", Some("e7e8q"));
+        assert_parse(parse_long_algebraic, fen, "e7e8N", None);
+        assert_parse(parse_long_algebraic, fen, "e7e8k", None);
+
+        assert_parse(parse_long_algebraic, "4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1", "e1g1", Some("e1g1"));
+
+        // parse_move takes either notation
+        assert_parse(parse_move, start, "e2e4", Some("e2e4"));
+        assert_parse(parse_move, start, "Nc3", Some("b1c3"));
+    }
+}
@@ -0,0 +1,188 @@
+use crate::algorithm::Move;
+use crate::board::board_representation;
+
+// The book is only probed for this many half moves, after that the engine searches every move
+pub const BOOK_MAX_HALF_MOVES: i16 = 20;
+
+// One move from a book position
+// Moves are in the internal board orientation (white at the bottom)
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct BookEntry {
+    pub key: u64, // Zobrist hash of the position (see zobrist::hash)
+    pub initial_bit: u8,
+    pub final_bit: u8,
+    pub weight: u8, // Moves with a higher weight are played more often
+}
+
+// Main lines of common openings (Ruy Lopez, Italian, Petrov, Sicilian, French, Caro-Kann, Queens Gambit, Slav, Kings Indian, English and Reti)
+// Entries are sorted by key so a position's moves are next to each other and can be found with a binary search
+// The keys are generated with the zobrist keys in zobrist::KEYS, if the keys or the hash function change the book has to be regenerated
+pub const BOOK: [BookEntry; 109] = [
+    BookEntry { key: 0x00E1CA11D62E2BDF, initial_bit: 4, final_bit: 6, weight: 5 }, // e8g8
+    BookEntry { key: 0x028EB6174F50F7DA, initial_bit: 11, final_bit: 19, weight: 4 }, // d7d6
+    BookEntry { key: 0x03BAB62686E9AAE3, initial_bit: 51, final_bit: 35, weight: 2 }, // d2d4
+    BookEntry { key: 0x072342918050A465, initial_bit: 6, final_bit: 21, weight: 2 }, // g8f6
+    BookEntry { key: 0x07E46591C8C02025, initial_bit: 62, final_bit: 45, weight: 2 }, // g1f3
+    BookEntry { key: 0x0813561C62A313B5, initial_bit: 10, final_bit: 18, weight: 3 }, // c7c6
+    BookEntry { key: 0x0813561C62A313B5, initial_bit: 10, final_bit: 26, weight: 9 }, // c7c5
+    BookEntry { key: 0x0813561C62A313B5, initial_bit: 12, final_bit: 20, weight: 3 }, // e7e6
+    BookEntry { key: 0x0813561C62A313B5, initial_bit: 12, final_bit: 28, weight: 12 }, // e7e5
+    BookEntry { key: 0x0839C13D191DD1AC, initial_bit: 62, final_bit: 45, weight: 9 }, // g1f3
+    BookEntry { key: 0x08E62A5FC41A8EFF, initial_bit: 57, final_bit: 42, weight: 2 }, // b1c3
+    BookEntry { key: 0x08F36F70BE4B67A7, initial_bit: 58, final_bit: 30, weight: 2 }, // c1g5
+    BookEntry { key: 0x08F36F70BE4B67A7, initial_bit: 58, final_bit: 37, weight: 4 }, // c1f4
+    BookEntry { key: 0x091A7DC0763131C6, initial_bit: 57, final_bit: 42, weight: 6 }, // b1c3
+    BookEntry { key: 0x095AF51DDE63D312, initial_bit: 5, final_bit: 12, weight: 3 }, // f8e7
+    BookEntry { key: 0x0A3911E4D90C43A2, initial_bit: 6, final_bit: 21, weight: 3 }, // g8f6
+    BookEntry { key: 0x10419172BDBE9FB5, initial_bit: 50, final_bit: 34, weight: 8 }, // c2c4
+    BookEntry { key: 0x108BE75F5BCBEA70, initial_bit: 5, final_bit: 12, weight: 5 }, // f8e7
+    BookEntry { key: 0x1137381D41B2B79D, initial_bit: 62, final_bit: 45, weight: 12 }, // g1f3
+    BookEntry { key: 0x1A63E0A6A2EBFFAB, initial_bit: 10, final_bit: 18, weight: 3 }, // c7c6
+    BookEntry { key: 0x1A63E0A6A2EBFFAB, initial_bit: 12, final_bit: 20, weight: 5 }, // e7e6
+    BookEntry { key: 0x1DEAA00E5BD1ADFD, initial_bit: 51, final_bit: 35, weight: 4 }, // d2d4
+    BookEntry { key: 0x1F71EF0AEAAED161, initial_bit: 1, final_bit: 18, weight: 10 }, // b8c6
+    BookEntry { key: 0x1F71EF0AEAAED161, initial_bit: 6, final_bit: 21, weight: 2 }, // g8f6
+    BookEntry { key: 0x2AB787494C385A62, initial_bit: 51, final_bit: 35, weight: 3 }, // d2d4
+    BookEntry { key: 0x2B18CEDFA7DC89E8, initial_bit: 6, final_bit: 21, weight: 6 }, // g8f6
+    BookEntry { key: 0x36B4D303D47FEED3, initial_bit: 12, final_bit: 28, weight: 3 }, // e7e5
+    BookEntry { key: 0x36EE94D3A5FBFF62, initial_bit: 51, final_bit: 35, weight: 3 }, // d2d4
+    BookEntry { key: 0x381A5C394F7AB5C0, initial_bit: 28, final_bit: 45, weight: 2 }, // e5f3
+    BookEntry { key: 0x393E24DB13E6A7AF, initial_bit: 34, final_bit: 27, weight: 2 }, // c4d5
+    BookEntry { key: 0x39DA1EF1F3CF8DBC, initial_bit: 51, final_bit: 35, weight: 6 }, // d2d4
+    BookEntry { key: 0x3A2D3FAF62754572, initial_bit: 1, final_bit: 18, weight: 2 }, // b8c6
+    BookEntry { key: 0x3A7AE58370D6085E, initial_bit: 42, final_bit: 36, weight: 3 }, // c3e4
+    BookEntry { key: 0x3AEA18AF2AE5C132, initial_bit: 50, final_bit: 34, weight: 8 }, // c2c4
+    BookEntry { key: 0x3CC4A1C51B04F356, initial_bit: 57, final_bit: 42, weight: 4 }, // b1c3
+    BookEntry { key: 0x3F0D43695EA4C578, initial_bit: 57, final_bit: 42, weight: 4 }, // b1c3
+    BookEntry { key: 0x424B1440163703D2, initial_bit: 2, final_bit: 29, weight: 3 }, // c8f5
+    BookEntry { key: 0x47C3D73E36970A26, initial_bit: 4, final_bit: 6, weight: 4 }, // e8g8
+    BookEntry { key: 0x47FFCC9371EE0EDB, initial_bit: 61, final_bit: 25, weight: 6 }, // f1b5
+    BookEntry { key: 0x47FFCC9371EE0EDB, initial_bit: 61, final_bit: 34, weight: 4 }, // f1c4
+    BookEntry { key: 0x4B35FDE8A29A2553, initial_bit: 5, final_bit: 12, weight: 6 }, // f8e7
+    BookEntry { key: 0x4C20BC32E2AD5758, initial_bit: 48, final_bit: 32, weight: 3 }, // a2a4
+    BookEntry { key: 0x5132AA0262401D7C, initial_bit: 45, final_bit: 35, weight: 3 }, // f3d4
+    BookEntry { key: 0x58E8CA4DFCD9B134, initial_bit: 28, final_bit: 35, weight: 4 }, // e5d4
+    BookEntry { key: 0x61062CAB53764419, initial_bit: 57, final_bit: 42, weight: 3 }, // b1c3
+    BookEntry { key: 0x62A31C36F9359AC8, initial_bit: 54, final_bit: 46, weight: 2 }, // g2g3
+    BookEntry { key: 0x62B6DE647A362765, initial_bit: 21, final_bit: 27, weight: 2 }, // f6d5
+    BookEntry { key: 0x65CAB5AA98DB105D, initial_bit: 6, final_bit: 21, weight: 3 }, // g8f6
+    BookEntry { key: 0x667BF601BE242A2E, initial_bit: 5, final_bit: 26, weight: 4 }, // f8c5
+    BookEntry { key: 0x6FB5ED0AEB3046AB, initial_bit: 11, final_bit: 27, weight: 3 }, // d7d5
+    BookEntry { key: 0x7153F78F9401B363, initial_bit: 12, final_bit: 20, weight: 4 }, // e7e6
+    BookEntry { key: 0x7153F78F9401B363, initial_bit: 14, final_bit: 22, weight: 4 }, // g7g6
+    BookEntry { key: 0x73ECFE9002F3E3AB, initial_bit: 11, final_bit: 27, weight: 3 }, // d7d5
+    BookEntry { key: 0x7993A6307118B55D, initial_bit: 27, final_bit: 36, weight: 3 }, // d5e4
+    BookEntry { key: 0x7C1BA7E81D73293F, initial_bit: 6, final_bit: 21, weight: 5 }, // g8f6
+    BookEntry { key: 0x7CD874B254C79175, initial_bit: 26, final_bit: 35, weight: 6 }, // c5d4
+    BookEntry { key: 0x7D5F3F31BAB5E119, initial_bit: 57, final_bit: 42, weight: 2 }, // b1c3
+    BookEntry { key: 0x800A8E7AE7F1E64F, initial_bit: 52, final_bit: 44, weight: 5 }, // e2e3
+    BookEntry { key: 0x842B15CFEBB2CE98, initial_bit: 27, final_bit: 34, weight: 3 }, // d5c4
+    BookEntry { key: 0x856F8D164C7406BE, initial_bit: 62, final_bit: 45, weight: 3 }, // g1f3
+    BookEntry { key: 0x887AF405A9B8AA0A, initial_bit: 6, final_bit: 21, weight: 8 }, // g8f6
+    BookEntry { key: 0x887AF405A9B8AA0A, initial_bit: 11, final_bit: 27, weight: 8 }, // d7d5
+    BookEntry { key: 0x8BB13DA578CA48E8, initial_bit: 45, final_bit: 35, weight: 6 }, // f3d4
+    BookEntry { key: 0x8C7D58928AB0AC3C, initial_bit: 2, final_bit: 29, weight: 3 }, // c8f5
+    BookEntry { key: 0x8CBCCF62B1DAC6B4, initial_bit: 36, final_bit: 46, weight: 3 }, // e4g3
+    BookEntry { key: 0x8EC5D53E65861B08, initial_bit: 4, final_bit: 6, weight: 4 }, // e8g8
+    BookEntry { key: 0x9872065502716B98, initial_bit: 5, final_bit: 12, weight: 6 }, // f8e7
+    BookEntry { key: 0x99369E8CA5B7A3BE, initial_bit: 57, final_bit: 42, weight: 5 }, // b1c3
+    BookEntry { key: 0x99DB9C386259DF2D, initial_bit: 36, final_bit: 28, weight: 3 }, // e4e5
+    BookEntry { key: 0x9F1827E6945691DA, initial_bit: 50, final_bit: 34, weight: 2 }, // c2c4
+    BookEntry { key: 0xA55EA7A0B647FA17, initial_bit: 51, final_bit: 35, weight: 2 }, // d2d4
+    BookEntry { key: 0xA5BB5C6BA8C40ECF, initial_bit: 58, final_bit: 30, weight: 5 }, // c1g5
+    BookEntry { key: 0xA65BE3154E4DC4E1, initial_bit: 26, final_bit: 35, weight: 3 }, // c5d4
+    BookEntry { key: 0xAA0D60DD5E592BFB, initial_bit: 21, final_bit: 11, weight: 3 }, // f6d7
+    BookEntry { key: 0xAC6AF8BB270F9A15, initial_bit: 12, final_bit: 28, weight: 2 }, // e7e5
+    BookEntry { key: 0xB30C8F00CF337E5F, initial_bit: 62, final_bit: 45, weight: 4 }, // g1f3
+    BookEntry { key: 0xB883CC30BB164499, initial_bit: 8, final_bit: 16, weight: 6 }, // a7a6
+    BookEntry { key: 0xB8A6D728E6C163E9, initial_bit: 6, final_bit: 21, weight: 3 }, // g8f6
+    BookEntry { key: 0xBA17E807CEB03FEE, initial_bit: 11, final_bit: 19, weight: 2 }, // d7d6
+    BookEntry { key: 0xBBD7AACF72050792, initial_bit: 1, final_bit: 18, weight: 3 }, // b8c6
+    BookEntry { key: 0xBBD7AACF72050792, initial_bit: 11, final_bit: 19, weight: 6 }, // d7d6
+    BookEntry { key: 0xBC6A4E292D6C37AD, initial_bit: 58, final_bit: 30, weight: 3 }, // c1g5
+    BookEntry { key: 0xBDC3D4444D448318, initial_bit: 4, final_bit: 6, weight: 2 }, // e8g8
+    BookEntry { key: 0xC17C123A81AE8893, initial_bit: 57, final_bit: 42, weight: 3 }, // b1c3
+    BookEntry { key: 0xC44A5B8DEE668A0D, initial_bit: 6, final_bit: 21, weight: 4 }, // g8f6
+    BookEntry { key: 0xC4BD95D3357E4EEB, initial_bit: 21, final_bit: 36, weight: 2 }, // f6e4
+    BookEntry { key: 0xC6D114895F19F691, initial_bit: 45, final_bit: 28, weight: 2 }, // f3e5
+    BookEntry { key: 0xC7E57E4D234352AC, initial_bit: 52, final_bit: 36, weight: 4 }, // e2e4
+    BookEntry { key: 0xC9C94EF82F48C938, initial_bit: 25, final_bit: 32, weight: 6 }, // b5a4
+    BookEntry { key: 0xCA355685310ADED8, initial_bit: 11, final_bit: 27, weight: 2 }, // d7d5
+    BookEntry { key: 0xCD789E460EB0B6C3, initial_bit: 50, final_bit: 34, weight: 2 }, // c2c4
+    BookEntry { key: 0xCD789E460EB0B6C3, initial_bit: 51, final_bit: 35, weight: 16 }, // d2d4
+    BookEntry { key: 0xCD789E460EB0B6C3, initial_bit: 52, final_bit: 36, weight: 27 }, // e2e4
+    BookEntry { key: 0xCD789E460EB0B6C3, initial_bit: 62, final_bit: 45, weight: 2 }, // g1f3
+    BookEntry { key: 0xCFCFD39B39E88A21, initial_bit: 11, final_bit: 27, weight: 4 }, // d7d5
+    BookEntry { key: 0xD0BA8643C3861636, initial_bit: 6, final_bit: 21, weight: 6 }, // g8f6
+    BookEntry { key: 0xD399EA676CBB6452, initial_bit: 57, final_bit: 42, weight: 3 }, // b1c3
+    BookEntry { key: 0xDA207A0DE6604FF9, initial_bit: 5, final_bit: 14, weight: 4 }, // f8g7
+    BookEntry { key: 0xDD2501A0686D2D93, initial_bit: 57, final_bit: 42, weight: 3 }, // b1c3
+    BookEntry { key: 0xDE449E127D7707D5, initial_bit: 6, final_bit: 21, weight: 2 }, // g8f6
+    BookEntry { key: 0xDEED51C342B8C0E6, initial_bit: 50, final_bit: 42, weight: 4 }, // c2c3
+    BookEntry { key: 0xE05CCDE3114FE6DE, initial_bit: 19, final_bit: 27, weight: 2 }, // d6d5
+    BookEntry { key: 0xE3598956E945D828, initial_bit: 51, final_bit: 35, weight: 3 }, // d2d4
+    BookEntry { key: 0xEC3744A4CEF5BB47, initial_bit: 8, final_bit: 16, weight: 6 }, // a7a6
+    BookEntry { key: 0xF0B1C478A405D394, initial_bit: 11, final_bit: 27, weight: 2 }, // d7d5
+    BookEntry { key: 0xF20689A5935DEF76, initial_bit: 62, final_bit: 45, weight: 4 }, // g1f3
+    BookEntry { key: 0xF2B8355C126BAE18, initial_bit: 60, final_bit: 62, weight: 6 }, // e1g1
+    BookEntry { key: 0xF52F31134F2D4A33, initial_bit: 29, final_bit: 22, weight: 3 }, // f5g6
+    BookEntry { key: 0xFE0A411BBDE9BD0C, initial_bit: 12, final_bit: 20, weight: 2 }, // e7e6
+];
+
+// Returns a move from the opening book for the board, or None if the position isn't in the book
+// Positions with multiple moves pick one randomly based on the move weights, seed is used as the random seed (e.g. the cycle count)
+pub fn probe_book(board: &board_representation::Board, seed: u64) -> Option<Move> {
+    let key = board.hash;
+
+    // Find the range of entries for the position
+    let start = BOOK.partition_point(|entry| entry.key < key);
+    let mut end = start;
+    while end < BOOK.len() && BOOK[end].key == key {
+        end += 1;
+    }
+
+    if start == end {
+        return None;
+    }
+
+    let mut total_weight = 0;
+    for i in start..end {
+        total_weight += BOOK[i].weight as u64;
+    }
+
+    // Xorshift the seed so close seeds don't pick the same move
+    let mut x = seed ^ key;
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+
+    let mut pick = x % total_weight;
+    for i in start..end {
+        let entry = BOOK[i];
+
+        if pick < entry.weight as u64 {
+            let initial_bit = entry.initial_bit as usize;
+
+            // The piece has to be on the board in case the key collided with another position
+            match crate::find_board_index(board, initial_bit) {
+                Ok(board_index) => {
+                    if crate::board_index_white(board_index) != board.whites_move {
+                        return None;
+                    }
+
+                    let mut piece_move = Move::new();
+                    piece_move.initial_piece_coordinates = board_representation::BoardCoordinates {
+                        board_index: board_index,
+                        bit: initial_bit,
+                    };
+                    piece_move.final_piece_bit = entry.final_bit as usize;
+                    return Some(piece_move);
+                },
+                Err(()) => return None,
+            }
+        }
+        pick -= entry.weight as u64;
+    }
+
+    None
+}
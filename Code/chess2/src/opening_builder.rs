@@ -0,0 +1,114 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+// Builds the opening heatmap and the opening book from PGN games
+// Only available with the std feature, the output is Rust source which is pasted into the firmware
+
+// Half moves of every game which are counted in the heatmap
+// The same as the number of half moves the search uses the heatmap for
+pub const HEATMAP_HALF_MOVES: i16 = crate::algorithm::HEATMAP_HALF_MOVES;
+
+pub struct OpeningStats {
+    pub heatmap: [[u32; 64]; 12], // Number of times a piece (board index) moved to a bit
+    pub book: BTreeMap<(u64, u8, u8), u32>, // Number of times a move (initial bit, final bit) was played in a position (zobrist hash)
+    pub games: usize, // Number of games added
+}
+
+impl OpeningStats {
+    pub fn new() -> Self {
+        OpeningStats {
+            heatmap: [[0; 64]; 12],
+            book: BTreeMap::new(),
+            games: 0,
+        }
+    }
+
+    // Adds every game in a PGN database
+    // A game stops being counted at the first move which can't be parsed
+    pub fn add_pgn(&mut self, pgn: &str, pieces_info: &[crate::piece::constants::PieceInfo; 12]) {
+        for game in crate::pgn::import_pgn(pgn, pieces_info) {
+            self.add_game(&game);
+        }
+    }
+
+    // Adds the moves of one game
+    pub fn add_game(&mut self, game: &crate::pgn::ImportedGame) {
+        for (piece_move, board) in game.moves.iter().zip(game.positions.iter()) {
+            if board.half_moves >= HEATMAP_HALF_MOVES.max(crate::opening_book::BOOK_MAX_HALF_MOVES) {
+                break;
+            }
+
+            let board_index = piece_move.initial_piece_coordinates.board_index;
+            let initial_bit = piece_move.initial_piece_coordinates.bit;
+            let final_bit = piece_move.final_piece_bit;
+
+            if board.half_moves < HEATMAP_HALF_MOVES {
+                self.heatmap[board_index][final_bit] += 1;
+            }
+
+            if board.half_moves < crate::opening_book::BOOK_MAX_HALF_MOVES {
+                let key = board.hash;
+                *self.book.entry((key, initial_bit as u8, final_bit as u8)).or_insert(0) += 1;
+            }
+        }
+
+        self.games += 1;
+    }
+
+    // Returns the heatmap as Rust source for the opening_heatmap array in the firmware
+    // Counts larger than an i16 are clamped
+    pub fn heatmap_source(&self) -> String {
+        let mut source = String::from("[");
+        for i in 0..12 {
+            if i > 0 {
+                source += ", ";
+            }
+            source += "[";
+            for bit in 0..64 {
+                if bit > 0 {
+                    source += ", ";
+                }
+                write!(source, "{}", self.heatmap[i][bit].min(i16::MAX as u32)).unwrap();
+            }
+            source += "]";
+        }
+        source += "]";
+        source
+    }
+
+    // Returns the book as Rust source for opening_book::BOOK
+    // Moves played less than min_count times are left out, weights are scaled so the most played move in each position has a weight of 255
+    pub fn book_source(&self, min_count: u32) -> String {
+        // Highest count in every position
+        let mut max_counts: BTreeMap<u64, u32> = BTreeMap::new();
+        for (&(key, _, _), &count) in &self.book {
+            let max_count = max_counts.entry(key).or_insert(0);
+            *max_count = (*max_count).max(count);
+        }
+
+        let mut lines = Vec::new();
+        for (&(key, initial_bit, final_bit), &count) in &self.book {
+            if count < min_count {
+                continue;
+            }
+
+            let weight = (count as u64 * 255 / max_counts[&key] as u64).max(1);
+            lines.push(format!(
+                "    BookEntry {{ key: 0x{:016X}, initial_bit: {}, final_bit: {}, weight: {} }}, // {}{}\n",
+                key,
+                initial_bit,
+                final_bit,
+                weight,
+                crate::bit_to_ccn(initial_bit as usize),
+                crate::bit_to_ccn(final_bit as usize),
+            ));
+        }
+
+        let mut source = format!("pub const BOOK: [BookEntry; {}] = [\n", lines.len());
+        for line in lines {
+            source += &line;
+        }
+        source += "];\n";
+        source
+    }
+}
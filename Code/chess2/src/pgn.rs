@@ -0,0 +1,444 @@
+use core::fmt::Write;
+
+use crate::algorithm::Move;
+use crate::board::board_representation;
+
+// Records the moves of a game so it can be saved as PGN
+// Works without an allocator, the PGN is written to anything that implements core::fmt::Write (a UART on the board, a String or file on a host)
+// https://www.chessprogramming.org/Portable_Game_Notation
+
+// Most half moves a record can hold, every move is stored in 3 bytes
+pub const MAX_RECORD_HALF_MOVES: usize = 512;
+
+// Movetext lines are wrapped before this many characters, as recommended by the PGN standard
+const MAX_LINE_LENGTH: usize = 80;
+
+// A record from any position other than the starting position has SetUp and FEN headers (e.g. games played at odds)
+use crate::board::board_representation::START_FEN;
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum GameResult {
+    WhiteWin,
+    BlackWin,
+    Draw,
+    Unfinished,
+}
+
+impl GameResult {
+    // Returns the result of a game that ended with a turn error, made by the team that moved
+    pub fn from_turn_error(error: crate::board::move_generator::TurnError, white_moved: bool) -> Self {
+        use crate::board::move_generator::TurnError;
+
+        match error {
+            TurnError::Checkmate | TurnError::KingOfTheHill | TurnError::ThreeCheck => {
+                if white_moved {
+                    GameResult::WhiteWin
+                } else {
+                    GameResult::BlackWin
+                }
+            },
+            _ => {
+                if error.is_draw() {
+                    GameResult::Draw
+                } else {
+                    GameResult::Unfinished
+                }
+            },
+        }
+    }
+
+    // Returns the result as it is written in PGN
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            GameResult::WhiteWin => "1-0",
+            GameResult::BlackWin => "0-1",
+            GameResult::Draw => "1/2-1/2",
+            GameResult::Unfinished => "*",
+        }
+    }
+}
+
+pub struct GameRecord<'a> {
+    // Seven tag roster headers, unknown values are written as "?"
+    pub event: &'a str,
+    pub site: &'a str,
+    pub date: &'a str, // YYYY.MM.DD
+    pub round: &'a str,
+    pub white: &'a str,
+    pub black: &'a str,
+    pub result: GameResult,
+
+    start: board_representation::Board, // Board the game started from
+    moves: [(u8, u8, u8); MAX_RECORD_HALF_MOVES], // Initial bit, final bit and promotion piece (0 if there is none) of every move
+    half_moves: usize, // Number of moves in the record
+}
+
+impl<'a> GameRecord<'a> {
+    // Creates an empty record of a game starting from a board
+    pub fn new(start: board_representation::Board) -> Self {
+        GameRecord {
+            event: "?",
+            site: "?",
+            date: "????.??.??",
+            round: "?",
+            white: "?",
+            black: "?",
+            result: GameResult::Unfinished,
+            start: start,
+            moves: [(0, 0, 0); MAX_RECORD_HALF_MOVES],
+            half_moves: 0,
+        }
+    }
+
+    // Adds a move to the record
+    // Moves aren't checked until the PGN is written, returns an error if the record is full
+    pub fn push(&mut self, piece_move: &Move) -> Result<(), ()> {
+        if self.half_moves == MAX_RECORD_HALF_MOVES {
+            return Err(());
+        }
+
+        self.moves[self.half_moves] = (piece_move.initial_piece_coordinates.bit as u8, piece_move.final_piece_bit as u8, piece_move.promotion.unwrap_or(0) as u8);
+        self.half_moves += 1;
+        Ok(())
+    }
+
+    // Returns the number of moves in the record
+    pub fn len(&self) -> usize {
+        self.half_moves
+    }
+
+    // Returns true if the record has no moves
+    pub fn is_empty(&self) -> bool {
+        self.half_moves == 0
+    }
+
+    // Writes the game as PGN
+    // Returns an error if a move in the record isn't legal, or if writing fails
+    pub fn write_pgn<W: core::fmt::Write>(&self, out: &mut W, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Result<(), ()> {
+        let headers = [("Event", self.event), ("Site", self.site), ("Date", self.date), ("Round", self.round), ("White", self.white), ("Black", self.black), ("Result", self.result.to_str())];
+        for (name, value) in headers {
+            writeln!(out, "[{} \"{}\"]", name, value).map_err(|_| ())?;
+        }
+
+        // Games that don't start from the starting position need the position they started from
+        let mut fen = FenBuffer::new();
+        self.start.write_fen(&mut fen).map_err(|_| ())?;
+        if fen.as_str() != START_FEN {
+            write!(out, "[SetUp \"1\"]\n[FEN \"{}\"]\n", fen.as_str()).map_err(|_| ())?;
+        }
+        out.write_str("\n").map_err(|_| ())?;
+
+        let mut board = self.start;
+        let mut line_length = 0;
+        for i in 0..self.half_moves {
+            let mut text = SanBuffer::new();
+
+            // Move numbers are written before white moves, and before the first move if black moves first
+            let full_moves = board.full_move_number();
+            if board.whites_move {
+                write!(text, "{}. ", full_moves).map_err(|_| ())?;
+            } else if i == 0 {
+                write!(text, "{}... ", full_moves).map_err(|_| ())?;
+            }
+
+            let piece = board_representation::BoardCoordinates {
+                board_index: crate::find_board_index(&board, self.moves[i].0 as usize)?,
+                bit: self.moves[i].0 as usize,
+            };
+            let mut piece_move = Move::new();
+            piece_move.initial_piece_coordinates = piece;
+            piece_move.final_piece_bit = self.moves[i].1 as usize;
+            if self.moves[i].2 != 0 {
+                piece_move.promotion = Some(self.moves[i].2 as usize);
+            }
+            crate::notation::write_san(&mut text, &piece_move, &board, pieces_info)?;
+
+            write_word(out, text.as_str(), &mut line_length).map_err(|_| ())?;
+
+            // Only the last move can end the game
+            match crate::board::move_generator::make_move(&piece, piece_move.final_piece_bit, piece_move.promotion, board, pieces_info) {
+                Ok(new_board) => board = new_board,
+                Err(_) => {
+                    if i != self.half_moves - 1 {
+                        return Err(());
+                    }
+                },
+            }
+        }
+
+        write_word(out, self.result.to_str(), &mut line_length).map_err(|_| ())?;
+        out.write_str("\n").map_err(|_| ())
+    }
+}
+
+// Writes a word of movetext, starting a new line if it doesn't fit on the current line
+fn write_word<W: core::fmt::Write>(out: &mut W, word: &str, line_length: &mut usize) -> core::fmt::Result {
+    if *line_length > 0 && *line_length + 1 + word.len() > MAX_LINE_LENGTH {
+        out.write_str("\n")?;
+        *line_length = 0;
+    }
+    if *line_length > 0 {
+        out.write_str(" ")?;
+        *line_length += 1;
+    }
+    out.write_str(word)?;
+    *line_length += word.len();
+    Ok(())
+}
+
+// Fixed size string buffers, so a word can be measured before it is written without an allocator
+// The longest move is a move number and a move with both disambiguation characters (e.g. "512. Qa1xb2+")
+// The longest FEN is about 90 characters
+type SanBuffer = StrBuffer<16>;
+type FenBuffer = StrBuffer<96>;
+
+struct StrBuffer<const N: usize> {
+    bytes: [u8; N],
+    length: usize,
+}
+
+impl<const N: usize> StrBuffer<N> {
+    fn new() -> Self {
+        StrBuffer {
+            bytes: [0; N],
+            length: 0,
+        }
+    }
+
+    fn as_str(&self) -> &str {
+        // Only complete strs are written to the buffer so it is always valid utf8
+        core::str::from_utf8(&self.bytes[..self.length]).unwrap_or("")
+    }
+}
+
+impl<const N: usize> core::fmt::Write for StrBuffer<N> {
+    fn write_str(&mut self, s: &str) -> core::fmt::Result {
+        if self.length + s.len() > N {
+            return Err(core::fmt::Error);
+        }
+
+        self.bytes[self.length..self.length + s.len()].copy_from_slice(s.as_bytes());
+        self.length += s.len();
+        Ok(())
+    }
+}
+
+// A game read from PGN, only available with the std feature
+// Used by the opening builder and the tuner to get positions from game databases
+#[cfg(feature = "std")]
+pub struct ImportedGame {
+    pub tags: Vec<(String, String)>, // Tag pairs in the order they were written
+    pub result: GameResult,
+    pub moves: Vec<Move>, // Moves which could be replayed, a game stops at the first move which can't be parsed or isn't legal
+    pub positions: Vec<board_representation::Board>, // Board before every move, followed by the board after the last move unless the last move ended the game
+}
+
+#[cfg(feature = "std")]
+impl ImportedGame {
+    // Returns the value of a tag, e.g. tag("White")
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        for (tag_name, value) in &self.tags {
+            if tag_name == name {
+                return Some(value);
+            }
+        }
+        None
+    }
+}
+
+// Reads every game in a PGN database and replays the movetext into boards
+// Comments ({} and ;), variations, move numbers and annotations (NAGs, !, ?) are skipped
+// Games with a FEN tag start from that position, games with a FEN which can't be parsed are left out
+#[cfg(feature = "std")]
+pub fn import_pgn(pgn: &str, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Vec<ImportedGame> {
+    let mut games = Vec::new();
+
+    let mut tags = Vec::new();
+    let mut sans: Vec<String> = Vec::new();
+    let mut result = None;
+
+    let mut in_comment = false; // In a {} comment, these can't be nested
+    let mut variation_depth = 0; // Depth of nested () variations
+    for line in pgn.lines() {
+        let line = line.trim();
+
+        // Escaped lines are ignored
+        if line.starts_with('%') {
+            continue;
+        }
+
+        // Tag pairs outside of movetext, a tag pair after movetext starts a new game
+        if !in_comment && variation_depth == 0 && line.starts_with('[') {
+            if !sans.is_empty() {
+                finish_game(&mut games, &mut tags, &mut sans, &mut result, pieces_info);
+            }
+
+            match parse_tag(line) {
+                Some(tag) => tags.push(tag),
+                None => (),
+            }
+            continue;
+        }
+
+        let mut token = String::new();
+        for c in line.chars().chain(core::iter::once(' ')) {
+            if in_comment {
+                if c == '}' {
+                    in_comment = false;
+                }
+                continue;
+            }
+
+            match c {
+                '{' => in_comment = true,
+                '(' => variation_depth += 1,
+                ')' => {
+                    if variation_depth > 0 {
+                        variation_depth -= 1;
+                    }
+                },
+                ';' => break, // Rest of line comment
+                ' ' | '\t' | '\r' => (),
+                _ => {
+                    if variation_depth == 0 {
+                        token.push(c);
+                    }
+                    continue;
+                },
+            }
+
+            // Anything other than a character of a token ends the token, a result ends the game
+            end_token(&token, &mut sans, &mut result);
+            token.clear();
+            if result.is_some() {
+                finish_game(&mut games, &mut tags, &mut sans, &mut result, pieces_info);
+            }
+        }
+
+        // Every token is ended by the chained space, except a token directly before a ; comment
+        end_token(&token, &mut sans, &mut result);
+        if result.is_some() {
+            finish_game(&mut games, &mut tags, &mut sans, &mut result, pieces_info);
+        }
+    }
+
+    // The last game can be missing its result
+    if !tags.is_empty() || !sans.is_empty() {
+        finish_game(&mut games, &mut tags, &mut sans, &mut result, pieces_info);
+    }
+    games
+}
+
+// Replays a game and starts the next one
+#[cfg(feature = "std")]
+fn finish_game(
+    games: &mut Vec<ImportedGame>,
+    tags: &mut Vec<(String, String)>,
+    sans: &mut Vec<String>,
+    result: &mut Option<GameResult>,
+    pieces_info: &[crate::piece::constants::PieceInfo; 12]
+) {
+    match replay(core::mem::take(tags), sans, *result, pieces_info) {
+        Some(game) => games.push(game),
+        None => (),
+    }
+    sans.clear();
+    *result = None;
+}
+
+// Adds a token of movetext to the moves or the result of a game
+#[cfg(feature = "std")]
+fn end_token(token: &str, sans: &mut Vec<String>, result: &mut Option<GameResult>) {
+    match token {
+        "" => (),
+        "1-0" => *result = Some(GameResult::WhiteWin),
+        "0-1" => *result = Some(GameResult::BlackWin),
+        "1/2-1/2" => *result = Some(GameResult::Draw),
+        "*" => *result = Some(GameResult::Unfinished),
+        _ => {
+            match move_from_token(token) {
+                Some(san) => sans.push(String::from(san)),
+                None => (),
+            }
+        },
+    }
+}
+
+// Returns the name and value of a tag pair line, e.g. [White "Carlsen, Magnus"]
+#[cfg(feature = "std")]
+fn parse_tag(line: &str) -> Option<(String, String)> {
+    let line = line.strip_prefix('[')?.strip_suffix(']')?;
+    let (name, value) = line.split_once(' ')?;
+    let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+    Some((String::from(name), value.replace("\\\"", "\"").replace("\\\\", "\\")))
+}
+
+// Returns the move in a movetext token, or None if the token is only a move number or a NAG
+#[cfg(feature = "std")]
+fn move_from_token(token: &str) -> Option<&str> {
+    if token.starts_with('$') {
+        return None;
+    }
+
+    // Castling can be written with zeros, which would be removed with the move number
+    if token == "0-0" || token == "0-0-0" {
+        return Some(token);
+    }
+
+    // Move numbers can be attached to the move (e.g. "1.e4")
+    let token = token.trim_start_matches(|c: char| c.is_ascii_digit() || c == '.');
+    if token.is_empty() {
+        return None;
+    }
+    Some(token)
+}
+
+// Replays the SAN moves of a game from its starting position
+// The result comes from the movetext, or the Result tag if the movetext had none
+#[cfg(feature = "std")]
+fn replay(tags: Vec<(String, String)>, sans: &[String], result: Option<GameResult>, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Option<ImportedGame> {
+    let mut game = ImportedGame {
+        tags: tags,
+        result: GameResult::Unfinished,
+        moves: Vec::new(),
+        positions: Vec::new(),
+    };
+
+    let mut fen = START_FEN;
+    match game.tag("FEN") {
+        Some(tag_fen) => fen = tag_fen,
+        None => (),
+    }
+    let mut board = board_representation::Board::from_fen(fen).ok()?;
+
+    match result {
+        Some(result) => game.result = result,
+        None => {
+            match game.tag("Result") {
+                Some("1-0") => game.result = GameResult::WhiteWin,
+                Some("0-1") => game.result = GameResult::BlackWin,
+                Some("1/2-1/2") => game.result = GameResult::Draw,
+                _ => (),
+            }
+        },
+    }
+
+    game.positions.push(board);
+    for san in sans {
+        let piece_move;
+        match crate::notation::parse_san(san, &board, pieces_info) {
+            Ok(parsed_move) => piece_move = parsed_move,
+            Err(()) => break,
+        }
+        game.moves.push(piece_move);
+
+        // There is no board after a move which ends the game
+        match crate::board::move_generator::make_move(&piece_move.initial_piece_coordinates, piece_move.final_piece_bit, piece_move.promotion, board, pieces_info) {
+            Ok(new_board) => board = new_board,
+            Err(_) => break,
+        }
+        game.positions.push(board);
+    }
+
+    Some(game)
+}
@@ -9,6 +9,115 @@ pub mod constants {
         pub value: i8, // Material value of the piece
     }
 
+    // Piece square tables, the value (centipawns) of a piece being on a square
+    // Tables are from the white teams perspective (bit 0 is a8), black pieces use the vertically mirrored bit
+    // Pawns and kings have separate middlegame and endgame tables, the evaluation blends them based on the game phase
+    // https://www.chessprogramming.org/Simplified_Evaluation_Function
+    const PAWN_MIDDLEGAME_TABLE: [i16; 64] = [
+          0,   0,   0,   0,   0,   0,   0,   0,
+         50,  50,  50,  50,  50,  50,  50,  50,
+         10,  10,  20,  30,  30,  20,  10,  10,
+          5,   5,  10,  25,  25,  10,   5,   5,
+          0,   0,   0,  20,  20,   0,   0,   0,
+          5,  -5, -10,   0,   0, -10,  -5,   5,
+          5,  10,  10, -20, -20,  10,  10,   5,
+          0,   0,   0,   0,   0,   0,   0,   0,
+    ];
+
+    // Passed pawns are much stronger in the endgame, so pawns are rewarded for advancing on every file
+    const PAWN_ENDGAME_TABLE: [i16; 64] = [
+          0,   0,   0,   0,   0,   0,   0,   0,
+         80,  80,  80,  80,  80,  80,  80,  80,
+         50,  50,  50,  50,  50,  50,  50,  50,
+         30,  30,  30,  30,  30,  30,  30,  30,
+         20,  20,  20,  20,  20,  20,  20,  20,
+         10,  10,  10,  10,  10,  10,  10,  10,
+         10,  10,  10,  10,  10,  10,  10,  10,
+          0,   0,   0,   0,   0,   0,   0,   0,
+    ];
+
+    const ROOK_TABLE: [i16; 64] = [
+          0,   0,   0,   0,   0,   0,   0,   0,
+          5,  10,  10,  10,  10,  10,  10,   5,
+         -5,   0,   0,   0,   0,   0,   0,  -5,
+         -5,   0,   0,   0,   0,   0,   0,  -5,
+         -5,   0,   0,   0,   0,   0,   0,  -5,
+         -5,   0,   0,   0,   0,   0,   0,  -5,
+         -5,   0,   0,   0,   0,   0,   0,  -5,
+          0,   0,   0,   5,   5,   0,   0,   0,
+    ];
+
+    const KNIGHT_TABLE: [i16; 64] = [
+        -50, -40, -30, -30, -30, -30, -40, -50,
+        -40, -20,   0,   0,   0,   0, -20, -40,
+        -30,   0,  10,  15,  15,  10,   0, -30,
+        -30,   5,  15,  20,  20,  15,   5, -30,
+        -30,   0,  15,  20,  20,  15,   0, -30,
+        -30,   5,  10,  15,  15,  10,   5, -30,
+        -40, -20,   0,   5,   5,   0, -20, -40,
+        -50, -40, -30, -30, -30, -30, -40, -50,
+    ];
+
+    const BISHOP_TABLE: [i16; 64] = [
+        -20, -10, -10, -10, -10, -10, -10, -20,
+        -10,   0,   0,   0,   0,   0,   0, -10,
+        -10,   0,   5,  10,  10,   5,   0, -10,
+        -10,   5,   5,  10,  10,   5,   5, -10,
+        -10,   0,  10,  10,  10,  10,   0, -10,
+        -10,  10,  10,  10,  10,  10,  10, -10,
+        -10,   5,   0,   0,   0,   0,   5, -10,
+        -20, -10, -10, -10, -10, -10, -10, -20,
+    ];
+
+    const QUEEN_TABLE: [i16; 64] = [
+        -20, -10, -10,  -5,  -5, -10, -10, -20,
+        -10,   0,   0,   0,   0,   0,   0, -10,
+        -10,   0,   5,   5,   5,   5,   0, -10,
+         -5,   0,   5,   5,   5,   5,   0,  -5,
+          0,   0,   5,   5,   5,   5,   0,  -5,
+        -10,   5,   5,   5,   5,   5,   0, -10,
+        -10,   0,   5,   0,   0,   0,   0, -10,
+        -20, -10, -10,  -5,  -5, -10, -10, -20,
+    ];
+
+    // The king stays behind its pawns in the middlegame
+    const KING_MIDDLEGAME_TABLE: [i16; 64] = [
+        -30, -40, -40, -50, -50, -40, -40, -30,
+        -30, -40, -40, -50, -50, -40, -40, -30,
+        -30, -40, -40, -50, -50, -40, -40, -30,
+        -30, -40, -40, -50, -50, -40, -40, -30,
+        -20, -30, -30, -40, -40, -30, -30, -20,
+        -10, -20, -20, -20, -20, -20, -20, -10,
+         20,  20,   0,   0,   0,   0,  20,  20,
+         20,  30,  10,   0,   0,  10,  30,  20,
+    ];
+
+    // The king moves to the center in the endgame
+    const KING_ENDGAME_TABLE: [i16; 64] = [
+        -50, -40, -30, -20, -20, -30, -40, -50,
+        -30, -20, -10,   0,   0, -10, -20, -30,
+        -30, -10,  20,  30,  30,  20, -10, -30,
+        -30, -10,  30,  40,  40,  30, -10, -30,
+        -30, -10,  30,  40,  40,  30, -10, -30,
+        -30, -10,  20,  30,  30,  20, -10, -30,
+        -30, -30,   0,   0,   0,   0, -30, -30,
+        -50, -30, -30, -30, -30, -30, -30, -50,
+    ];
+
+    // Indexed by [board index % 6][bit]
+    pub const MIDDLEGAME_PIECE_SQUARE_TABLES: [[i16; 64]; 6] = [PAWN_MIDDLEGAME_TABLE, ROOK_TABLE, KNIGHT_TABLE, BISHOP_TABLE, QUEEN_TABLE, KING_MIDDLEGAME_TABLE];
+    pub const ENDGAME_PIECE_SQUARE_TABLES: [[i16; 64]; 6] = [PAWN_ENDGAME_TABLE, ROOK_TABLE, KNIGHT_TABLE, BISHOP_TABLE, QUEEN_TABLE, KING_ENDGAME_TABLE];
+
+    // Returns the middlegame and endgame piece square table values of a piece (board index) on a bit
+    pub fn piece_square_values(board_index: usize, bit: usize) -> [i16; 2] {
+        let mut table_bit = bit;
+        if !crate::board_index_white(board_index) {
+            table_bit ^= 56; // Mirror the row for the black team
+        }
+
+        [MIDDLEGAME_PIECE_SQUARE_TABLES[board_index % 6][table_bit], ENDGAME_PIECE_SQUARE_TABLES[board_index % 6][table_bit]]
+    }
+
     pub fn gen() -> [PieceInfo; 12] {
         // How much the piece bitboard bit changes when a move is made in a direction
         const KNIGHT_MOVES: [i8; 8] = [-17, -15, -6, 10, 17, 15, 6, -10];
@@ -0,0 +1,147 @@
+use crate::algorithm::Move;
+use crate::board::board_representation;
+
+// Reader for Polyglot .bin opening books
+// Books are loaded from disk so this is only available with the std feature
+// http://hgm.nubati.net/book_format.html
+
+// One 16 byte entry of a book
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct PolyglotEntry {
+    pub key: u64, // zobrist::polyglot_hash of the position
+    pub raw_move: u16, // Move encoded as to file, to row, from file, from row, promotion piece (3 bits each, starting from the lowest bit)
+    pub weight: u16,
+    pub learn: u32,
+}
+
+pub struct PolyglotBook {
+    entries: Vec<PolyglotEntry>, // Sorted by key
+}
+
+impl PolyglotBook {
+
+    // Reads a book from a .bin file
+    pub fn load(path: &std::path::Path) -> std::io::Result<Self> {
+        let bytes = std::fs::read(path)?;
+        Ok(PolyglotBook::from_bytes(&bytes))
+    }
+
+    // Reads a book from the contents of a .bin file, a trailing partial entry is ignored
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let mut entries = Vec::with_capacity(bytes.len() / 16);
+
+        for chunk in bytes.chunks_exact(16) {
+            entries.push(PolyglotEntry {
+                key: u64::from_be_bytes(chunk[0..8].try_into().unwrap()),
+                raw_move: u16::from_be_bytes(chunk[8..10].try_into().unwrap()),
+                weight: u16::from_be_bytes(chunk[10..12].try_into().unwrap()),
+                learn: u32::from_be_bytes(chunk[12..16].try_into().unwrap()),
+            });
+        }
+
+        // Books should allready be sorted, but the probe relies on it
+        entries.sort_by_key(|entry| entry.key);
+
+        PolyglotBook {
+            entries: entries,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // Returns every entry for the board
+    pub fn entries(&self, board: &board_representation::Board) -> &[PolyglotEntry] {
+        let key = crate::zobrist::polyglot_hash(board);
+
+        let start = self.entries.partition_point(|entry| entry.key < key);
+        let end = self.entries.partition_point(|entry| entry.key <= key);
+        &self.entries[start..end]
+    }
+
+    // Returns a move from the book for the board, or None if the position isn't in the book
+    // Positions with multiple moves pick one randomly based on the move weights, seed is used as the random seed
+    pub fn probe(&self, board: &board_representation::Board, seed: u64) -> Option<Move> {
+        let entries = self.entries(board);
+
+        let mut total_weight = 0;
+        for entry in entries {
+            total_weight += entry.weight as u64;
+        }
+        if total_weight == 0 {
+            return None;
+        }
+
+        // Xorshift the seed so close seeds don't pick the same move
+        let mut x = seed ^ entries[0].key;
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+
+        let mut pick = x % total_weight;
+        for entry in entries {
+            if pick < entry.weight as u64 {
+                return decode_move(entry.raw_move, board);
+            }
+            pick -= entry.weight as u64;
+        }
+
+        None
+    }
+}
+
+// Converts a Polyglot move to a move on the board
+// Returns None if the piece isn't on the board
+pub fn decode_move(raw_move: u16, board: &board_representation::Board) -> Option<Move> {
+    let raw_move = raw_move as usize;
+
+    // Polyglot rows start at rank 1, the board starts at rank 8
+    let final_bit = (7 - (raw_move >> 3 & 7)) * 8 + (raw_move & 7);
+    let initial_bit = (7 - (raw_move >> 9 & 7)) * 8 + (raw_move >> 6 & 7);
+
+    // Polyglot promotion pieces are none, knight, bishop, rook, queen
+    let promotion;
+    match raw_move >> 12 & 7 {
+        0 => promotion = None,
+        1 => promotion = Some(2),
+        2 => promotion = Some(3),
+        3 => promotion = Some(1),
+        4 => promotion = Some(4),
+        _ => return None,
+    }
+
+    let board_index;
+    match crate::find_board_index(board, initial_bit) {
+        Ok(index) => board_index = index,
+        Err(()) => return None,
+    }
+
+    if crate::board_index_white(board_index) != board.whites_move {
+        return None;
+    }
+
+    // Polyglot castles are the king capturing it's own rook, on the board the king moves 2 squares towards the rook
+    let mut final_piece_bit = final_bit;
+    let castle = (board_index == 5 && crate::bit_on(board.board[1], final_bit)) || (board_index == 11 && crate::bit_on(board.board[7], final_bit));
+    if castle {
+        if final_bit > initial_bit {
+            final_piece_bit = initial_bit + 2;
+        } else {
+            final_piece_bit = initial_bit - 2;
+        }
+    }
+
+    let mut piece_move = Move::new();
+    piece_move.initial_piece_coordinates = board_representation::BoardCoordinates {
+        board_index: board_index,
+        bit: initial_bit,
+    };
+    piece_move.final_piece_bit = final_piece_bit;
+    piece_move.promotion = promotion;
+    Some(piece_move)
+}
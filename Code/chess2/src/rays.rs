@@ -0,0 +1,83 @@
+// Ray lookups for sliding pieces, used for pin detection, check evasions and static exchange evaluation
+// Full between and line tables ([[u64; 64]; 64] each) are 32kB, half of the flash on the STM32F103
+// So only the rays from every bit in each direction are stored (4kB), and between and line are computed from them
+
+// Column and row deltas of each direction, in the same order as the bit deltas in DIRECTIONS
+const DIRECTION_DELTAS: [[i8; 2]; 8] = [[0, -1], [0, 1], [-1, 0], [1, 0], [-1, -1], [1, 1], [1, -1], [-1, 1]];
+
+// Directions as bit deltas, rook directions (columns and rows) then bishop directions (diagonals)
+// A direction and its opposite are next to each other, so the opposite of direction i is i ^ 1
+pub const DIRECTIONS: [i8; 8] = [-8, 8, -1, 1, -9, 9, -7, 7];
+
+// Bitboard of the bits a piece on a bit slides over in a direction on an empty board, indexed by [direction][bit]
+// Generated at compile time so there is no runtime initialisation
+pub const RAYS: [[u64; 64]; 8] = gen_rays();
+
+const fn gen_rays() -> [[u64; 64]; 8] {
+    let mut rays = [[0; 64]; 8];
+
+    let mut direction = 0;
+    while direction < 8 {
+        let mut bit = 0;
+        while bit < 64 {
+            let mut column = (bit % 8) as i8 + DIRECTION_DELTAS[direction][0];
+            let mut row = (bit / 8) as i8 + DIRECTION_DELTAS[direction][1];
+
+            // Move in the direction until the edge of the board
+            while column >= 0 && column < 8 && row >= 0 && row < 8 {
+                rays[direction][bit] |= 1 << (row * 8 + column);
+                column += DIRECTION_DELTAS[direction][0];
+                row += DIRECTION_DELTAS[direction][1];
+            }
+            bit += 1;
+        }
+        direction += 1;
+    }
+
+    rays
+}
+
+// Returns the index of the direction from one bit to another (see DIRECTIONS), None if the bits aren't on the same column, row or diagonal
+pub const fn direction(from_bit: usize, to_bit: usize) -> Option<usize> {
+    let mut direction = 0;
+    while direction < 8 {
+        if RAYS[direction][from_bit] & (1 << to_bit) != 0 {
+            return Some(direction);
+        }
+        direction += 1;
+    }
+    None
+}
+
+// Returns a bitboard of the bits between two bits (not including either bit)
+// Empty if the bits aren't on the same column, row or diagonal
+pub const fn between(from_bit: usize, to_bit: usize) -> u64 {
+    match direction(from_bit, to_bit) {
+        Some(direction) => RAYS[direction][from_bit] & !RAYS[direction][to_bit] & !(1 << to_bit),
+        None => 0,
+    }
+}
+
+// Returns a bitboard of the whole column, row or diagonal that goes through two bits (including both bits), from one edge of the board to the other
+// Empty if the bits aren't on the same column, row or diagonal
+pub const fn line(from_bit: usize, to_bit: usize) -> u64 {
+    match direction(from_bit, to_bit) {
+        Some(direction) => RAYS[direction][from_bit] | RAYS[direction ^ 1][from_bit] | 1 << from_bit,
+        None => 0,
+    }
+}
+
+// Returns the first bit that is on in a bitboard when moving from a bit in a direction, None if there are no bits on the ray
+pub fn first_on_ray(from_bit: usize, direction: usize, bitboard: u64) -> Option<usize> {
+    let blockers = RAYS[direction][from_bit] & bitboard;
+    if blockers == 0 {
+        return None;
+    }
+
+    // Positive directions move towards higher bits, so the closest bit is the lowest one
+    if DIRECTIONS[direction] > 0 {
+        Some(blockers.trailing_zeros() as usize)
+    } else {
+        Some(63 - blockers.leading_zeros() as usize)
+    }
+}
@@ -0,0 +1,157 @@
+// Small records kept in one page of storage, e.g. the settings (see settings.rs) and statistics (see statistics.rs)
+// Records are appended to a log, the last valid record of each kind is the current one
+// Storing a record only writes the new record, the page is only erased when it's full, which spreads the wear over the whole page
+// When the page is full it's erased and the current record of every kind is written back before the new record
+//
+// Record:
+//   Header (4 bytes): kind, version of the layout of the payload, length of the payload, 0
+//   Payload: padded to an even length
+//   CRC (2 bytes): CRC-16 of the header and payload, written last so a record cut off by the power being lost isn't read
+// Erased storage reads as 0xff, so a kind of 0xff is the end of the log
+
+use crate::storage::Storage;
+
+// Size of the page of records in bytes, 1 page of the STM32F103's flash
+pub const RECORDS_SIZE: usize = 1024;
+
+// Most bytes the payload of a record can have
+pub const MAX_PAYLOAD: usize = 64;
+
+// Kinds of records, every kind has to be less than RECORD_KINDS
+pub const SETTINGS_RECORD: u8 = 0;
+pub const STATISTICS_RECORD: u8 = 1;
+pub const RECORD_KINDS: usize = 2;
+
+const HEADER_SIZE: usize = 4;
+const CRC_SIZE: usize = 2;
+const END: u8 = 0xff; // Erased storage
+
+// Returns the size of a record in the log
+fn record_size(length: usize) -> usize {
+    HEADER_SIZE + (length + 1) / 2 * 2 + CRC_SIZE
+}
+
+// Returns the CRC-16 (CCITT) of some bytes
+fn crc16(bytes: &[u8]) -> u16 {
+    let mut crc: u16 = 0xffff;
+    for byte in bytes {
+        crc ^= (*byte as u16) << 8;
+        for _ in 0..8 {
+            if crc & 0x8000 != 0 {
+                crc = crc << 1 ^ 0x1021;
+            } else {
+                crc <<= 1;
+            }
+        }
+    }
+    crc
+}
+
+// Offsets of the current record of each kind, and the offset of the end of the log
+struct Log {
+    current: [Option<usize>; RECORD_KINDS],
+    end: usize, // RECORDS_SIZE if the page is full, or a record is damaged and nothing can be written after it
+}
+
+// Reads a record from the log into buffer (the header, padded payload and CRC)
+// Returns the size of the record, or None if there is no valid record at the offset
+fn read_record<S: Storage>(storage: &S, offset: usize, buffer: &mut [u8; HEADER_SIZE + MAX_PAYLOAD + CRC_SIZE]) -> Option<usize> {
+    storage.read(offset, &mut buffer[..HEADER_SIZE]).ok()?;
+    let kind = buffer[0] as usize;
+    let length = buffer[2] as usize;
+    if kind >= RECORD_KINDS || length > MAX_PAYLOAD || offset + record_size(length) > RECORDS_SIZE {
+        return None;
+    }
+
+    let size = record_size(length);
+    storage.read(offset + HEADER_SIZE, &mut buffer[HEADER_SIZE..size]).ok()?;
+    let crc = u16::from_le_bytes([buffer[size - 2], buffer[size - 1]]);
+    if crc != crc16(&buffer[..HEADER_SIZE + length]) {
+        return None;
+    }
+    Some(size)
+}
+
+fn scan<S: Storage>(storage: &S) -> Log {
+    let mut log = Log {
+        current: [None; RECORD_KINDS],
+        end: RECORDS_SIZE,
+    };
+
+    let mut buffer = [0; HEADER_SIZE + MAX_PAYLOAD + CRC_SIZE];
+    let mut offset = 0;
+    while offset + HEADER_SIZE <= RECORDS_SIZE {
+        match storage.read(offset, &mut buffer[..1]) {
+            Ok(()) if buffer[0] == END => {
+                log.end = offset;
+                break;
+            },
+            Ok(()) => (),
+            Err(()) => break,
+        }
+
+        match read_record(storage, offset, &mut buffer) {
+            Some(size) => {
+                log.current[buffer[0] as usize] = Some(offset);
+                offset += size;
+            },
+            None => break,
+        }
+    }
+    log
+}
+
+// Reads the current record of a kind into payload
+// Returns the version of the layout and the length of the payload, None if there is no record of the kind
+pub fn load<S: Storage>(storage: &S, kind: u8, payload: &mut [u8; MAX_PAYLOAD]) -> Option<(u8, usize)> {
+    let offset = scan(storage).current.get(kind as usize).copied().flatten()?;
+
+    let mut buffer = [0; HEADER_SIZE + MAX_PAYLOAD + CRC_SIZE];
+    read_record(storage, offset, &mut buffer)?;
+    let length = buffer[2] as usize;
+    payload[..length].copy_from_slice(&buffer[HEADER_SIZE..HEADER_SIZE + length]);
+    Some((buffer[1], length))
+}
+
+// Stores a record, it becomes the current record of its kind
+// Returns an error if the kind or payload is too big, or the storage can't be written
+pub fn store<S: Storage>(storage: &mut S, kind: u8, version: u8, payload: &[u8]) -> Result<(), ()> {
+    if kind as usize >= RECORD_KINDS || payload.len() > MAX_PAYLOAD {
+        return Err(());
+    }
+
+    let mut buffer = [0; HEADER_SIZE + MAX_PAYLOAD + CRC_SIZE];
+    buffer[0] = kind;
+    buffer[1] = version;
+    buffer[2] = payload.len() as u8;
+    buffer[HEADER_SIZE..HEADER_SIZE + payload.len()].copy_from_slice(payload);
+    let size = record_size(payload.len());
+    let crc = crc16(&buffer[..HEADER_SIZE + payload.len()]);
+    buffer[size - 2..size].copy_from_slice(&crc.to_le_bytes());
+
+    let log = scan(storage);
+    let mut end = log.end;
+
+    // Make space by erasing the page and writing back the current records of the other kinds
+    if end + size > RECORDS_SIZE {
+        let mut current = [[0; HEADER_SIZE + MAX_PAYLOAD + CRC_SIZE]; RECORD_KINDS];
+        let mut current_sizes = [0; RECORD_KINDS];
+        for i in 0..RECORD_KINDS {
+            match log.current[i] {
+                Some(offset) if i != kind as usize => current_sizes[i] = read_record(storage, offset, &mut current[i]).unwrap_or(0),
+                _ => (),
+            }
+        }
+
+        storage.erase()?;
+        end = 0;
+        for i in 0..RECORD_KINDS {
+            if current_sizes[i] != 0 {
+                storage.write(end, &current[i][..current_sizes[i]])?;
+                end += current_sizes[i];
+            }
+        }
+    }
+
+    storage.write(end, &buffer[..size])
+}
@@ -0,0 +1,178 @@
+// Review of a finished game
+// Every move of the game is checked with a short search, a move is a blunder if the search finds a move that is much better for the team that moved
+// The value of the played move is found by searching the board after it (one depth shallower), so both values come from searches of the same depth
+// The searches are short so the review only takes a few seconds on the board, which means it only finds mistakes that lose material or a mate
+
+use crate::algorithm::{self, Move, SearchData, SearchParams};
+use crate::board::move_generator::TurnError;
+use crate::game::Game;
+
+// Depth of the search of every move, the search of the board after the played move is one depth shallower
+pub const REVIEW_DEPTH: usize = 3;
+
+// A move is a blunder if the best move is worth this much more (centipawns) than it
+pub const BLUNDER_MARGIN: i16 = 200;
+
+// Values are capped at this (centipawns) before being compared
+// Once a team is winning by this much a move that wins by less isn't a blunder, e.g. not taking the fastest mate
+const VALUE_CAP: i16 = 1000;
+
+// Most blunders a review keeps, a review still counts every blunder of a game
+pub const MAX_REVIEW_BLUNDERS: usize = 16;
+
+// A move of the game where the search found a much better move
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Blunder {
+    pub half_move: usize, // Index of the move in the game, 0 is the first move
+    pub full_move_number: i16, // Full move number of the move, as it is written in PGN
+    pub white: bool, // True if the white team made the move
+    pub played: Move,
+    pub best: Move, // Best move found by the search
+    pub loss: i16, // Value (centipawns) the played move is worse than the best move, after the values are capped
+}
+
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Review {
+    pub half_moves: usize, // Number of moves that were reviewed
+    pub blunder_counts: [usize; 2], // Number of blunders of the white and black teams
+    blunders: [Option<Blunder>; MAX_REVIEW_BLUNDERS], // First blunders of the game, in the order they were played
+    blunders_len: usize,
+}
+
+impl Review {
+    pub fn new() -> Self {
+        Review {
+            half_moves: 0,
+            blunder_counts: [0; 2],
+            blunders: [None; MAX_REVIEW_BLUNDERS],
+            blunders_len: 0,
+        }
+    }
+
+    // Returns the number of blunders of a team
+    pub fn blunder_count(&self, white: bool) -> usize {
+        if white {
+            self.blunder_counts[0]
+        } else {
+            self.blunder_counts[1]
+        }
+    }
+
+    // Returns the number of blunders that were kept, at most MAX_REVIEW_BLUNDERS
+    pub fn len(&self) -> usize {
+        self.blunders_len
+    }
+
+    // Returns true if no blunders were kept
+    pub fn is_empty(&self) -> bool {
+        self.blunders_len == 0
+    }
+
+    // Returns a kept blunder, 0 is the first blunder of the game
+    pub fn blunder(&self, index: usize) -> Option<Blunder> {
+        if index >= self.blunders_len {
+            return None;
+        }
+        self.blunders[index]
+    }
+
+    fn push(&mut self, blunder: Blunder) {
+        if blunder.white {
+            self.blunder_counts[0] += 1;
+        } else {
+            self.blunder_counts[1] += 1;
+        }
+
+        if self.blunders_len < MAX_REVIEW_BLUNDERS {
+            self.blunders[self.blunders_len] = Some(blunder);
+            self.blunders_len += 1;
+        }
+    }
+}
+
+// Reviews every move of a game
+// The search params set the time or node budget of each search, their max_depth is replaced with REVIEW_DEPTH
+// Progress is called with the index of every move before it is reviewed, e.g. to update a display while the review runs
+// A search that runs out of budget still gives the value of its last completed iteration, so the review always finishes
+pub fn review_game(game: &Game, search_params: &SearchParams, search_data: &mut SearchData, progress: &mut dyn FnMut(usize)) -> Review {
+    let mut review = Review::new();
+    let search_params = SearchParams {
+        max_depth: REVIEW_DEPTH,
+        ..*search_params
+    };
+    let reply_search_params = SearchParams {
+        max_depth: REVIEW_DEPTH - 1,
+        ..search_params
+    };
+
+    let mut board = *game.start();
+    for i in 0..game.len() {
+        progress(i);
+
+        let played;
+        match game.piece_move(i) {
+            Some(piece_move) => played = piece_move,
+            None => break,
+        }
+
+        let played_board = crate::board::move_generator::make_move(&played.initial_piece_coordinates, played.final_piece_bit, played.promotion, board, search_data.pieces_info);
+
+        // The team that moved has a legal move, so the search always finds one
+        let (best, best_value);
+        match algorithm::search_best(board, &search_params, search_data) {
+            Ok((piece_move, value)) => {
+                best = piece_move;
+                best_value = value.clamp(-VALUE_CAP, VALUE_CAP);
+            },
+            Err(_) => break,
+        }
+
+        // Value of the played move for the team that moved, from the value of the best reply for the other team
+        let played_value;
+        match played_board {
+            Ok(new_board) => {
+                if same_move(&played, &best) {
+                    played_value = best_value;
+                } else {
+                    match algorithm::search_best(new_board, &reply_search_params, search_data) {
+                        Ok((_, value)) => played_value = -value.clamp(-VALUE_CAP, VALUE_CAP),
+                        Err(algorithm::GameEnd::Checkmate) => played_value = VALUE_CAP,
+                        Err(algorithm::GameEnd::Stalemate) => played_value = 0,
+                    }
+                }
+            },
+
+            // Only the last move can end the game, moves that win the game can't be improved on and draws are worth 0
+            Err(TurnError::Checkmate) | Err(TurnError::KingOfTheHill) | Err(TurnError::ThreeCheck) => played_value = VALUE_CAP,
+            Err(TurnError::InvalidMove) | Err(TurnError::InvalidMoveCheck) => break,
+            Err(_) => played_value = 0,
+        }
+
+        review.half_moves = i + 1;
+        if best_value - played_value >= BLUNDER_MARGIN {
+            review.push(Blunder {
+                half_move: i,
+                full_move_number: board.full_move_number(),
+                white: board.whites_move,
+                played: played,
+                best: best,
+                loss: best_value - played_value,
+            });
+        }
+
+        match played_board {
+            Ok(new_board) => board = new_board,
+            Err(_) => break,
+        }
+    }
+
+    review
+}
+
+// Returns true if two moves move the same piece to the same bit with the same promotion
+// A pawn promotes to a queen without a promotion piece, so no promotion piece and a queen (board index 4) are the same promotion
+fn same_move(a: &Move, b: &Move) -> bool {
+    a.initial_piece_coordinates.bit == b.initial_piece_coordinates.bit
+        && a.final_piece_bit == b.final_piece_bit
+        && a.promotion.unwrap_or(4) == b.promotion.unwrap_or(4)
+}
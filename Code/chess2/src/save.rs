@@ -0,0 +1,479 @@
+// Saving the game in progress, so it can be resumed after the power is lost
+// The save is a log of entries written one after another, the settings and starting position first then every move as it is played
+// Flash can only be written once between erases, so appending to a log means the save is only erased once per game (or when the log fills up), which spreads the wear over the whole save
+// The save is kept in storage that reads, writes and erases (see storage.rs), the firmware uses the last 2 pages of flash (see memory.x)
+// When a game ends its result is written to the save, so the game isn't resumed but can still be read (e.g. to send it as PGN) until the next game starts
+//
+// Entries (erased storage reads as 0xff, so the first entry with a tag of 0xff is the end of the log):
+//   Start (16 bytes): tag, starting board index (see Board::indexed_start), then the settings
+//   Move (8 bytes): tag, initial bit and final bit and promotion packed into 2 bytes, then the time the team that moved had left (ms, u32::MAX in an untimed game)
+//   Undo (8 bytes): tag, takes back the last move in the log
+//   Result (8 bytes): tag, result of the game (see result_to_byte), the game has ended and there are no entries after it
+// The last byte of every entry is COMMIT, it's written last so an entry cut off by the power being lost isn't read
+
+use crate::algorithm::Move;
+use crate::board::board_representation;
+use crate::game::Game;
+use crate::pgn::GameResult;
+use crate::settings::{self, Settings};
+use crate::storage::Storage;
+
+// Size of the save in bytes, 2 pages of the STM32F103's flash
+pub const SAVE_SIZE: usize = 2048;
+
+const START_ENTRY_SIZE: usize = 16;
+const ENTRY_SIZE: usize = 8;
+
+const TAG_START: u8 = b'S';
+const TAG_MOVE: u8 = b'M';
+const TAG_UNDO: u8 = b'U';
+const TAG_RESULT: u8 = b'R';
+const TAG_END: u8 = 0xff; // Erased storage
+const COMMIT: u8 = 0xa5;
+
+// Most moves a save can hold, longer games stop being saved
+pub const MAX_SAVED_HALF_MOVES: usize = (SAVE_SIZE - START_ENTRY_SIZE) / ENTRY_SIZE;
+
+// A game read from a save
+pub struct SavedGame {
+    pub settings: Settings,
+    pub start_index: usize, // Index of the starting board (see Board::indexed_start)
+    pub game: Game, // The game with every saved move replayed
+    pub remaining_ms: [Option<u64>; 2], // Time the white and black teams had left after their last moves, None if the team hasn't moved or the game is untimed
+}
+
+// Reads the saved game so it can be resumed, None if there is no save, the save can't be replayed, or the game has ended
+pub fn load<S: Storage>(storage: &S, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Option<SavedGame> {
+    let (saved_game, ended) = read(storage, pieces_info)?;
+    if ended || saved_game.game.end.is_some() {
+        return None;
+    }
+    Some(saved_game)
+}
+
+// Reads the last game that was saved, including a game that has ended, None if there is no save or the save can't be replayed
+// The game has the result it ended with, or GameResult::Unfinished if it's still in progress or was stopped
+pub fn load_last<S: Storage>(storage: &S, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Option<SavedGame> {
+    let (saved_game, _) = read(storage, pieces_info)?;
+    Some(saved_game)
+}
+
+// Replays the saved game, returns the game and true if the save has a result entry
+fn read<S: Storage>(storage: &S, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Option<(SavedGame, bool)> {
+    let mut start_entry = [0; START_ENTRY_SIZE];
+    storage.read(0, &mut start_entry).ok()?;
+    if start_entry[0] != TAG_START || start_entry[START_ENTRY_SIZE - 1] != COMMIT {
+        return None;
+    }
+
+    let start_index = start_entry[1] as usize;
+    let settings = Settings::from_bytes(&start_entry[2..START_ENTRY_SIZE - 1])?;
+    let mut start = board_representation::Board::indexed_start(start_index)?;
+    start.variant = settings.variant();
+
+    let mut saved_game = SavedGame {
+        settings: settings,
+        start_index: start_index,
+        game: Game::new(start),
+        remaining_ms: [None; 2],
+    };
+
+    let mut ended = false;
+    let mut offset = START_ENTRY_SIZE;
+    while offset + ENTRY_SIZE <= SAVE_SIZE && !ended {
+        let mut entry = [0; ENTRY_SIZE];
+        storage.read(offset, &mut entry).ok()?;
+        if entry[0] == TAG_END || entry[ENTRY_SIZE - 1] != COMMIT {
+            break;
+        }
+
+        match entry[0] {
+            TAG_MOVE => {
+                let white = saved_game.game.board.whites_move;
+                let piece_move = unpack_move(u16::from_le_bytes([entry[1], entry[2]]), &saved_game.game.board)?;
+
+                // The move that ended a game is still played, play only returns the way the game ended
+                match saved_game.game.play(&piece_move, pieces_info) {
+                    Ok(()) => (),
+                    Err(_) if saved_game.game.end.is_some() => (),
+                    Err(_) => return None,
+                }
+
+                let remaining_ms = u32::from_le_bytes([entry[3], entry[4], entry[5], entry[6]]);
+                if remaining_ms != u32::MAX {
+                    saved_game.remaining_ms[team_index(white)] = Some(remaining_ms as u64);
+                }
+            },
+            TAG_UNDO => saved_game.game.undo(pieces_info).ok()?,
+            TAG_RESULT => {
+                saved_game.game.result = byte_to_result(entry[1])?;
+                ended = true;
+            },
+            _ => return None,
+        }
+        offset += ENTRY_SIZE;
+    }
+
+    Some((saved_game, ended))
+}
+
+// Erases the save, so there is no game to resume
+pub fn clear<S: Storage>(storage: &mut S) -> Result<(), ()> {
+    storage.erase()
+}
+
+// Writes a game to the save as it's played
+// The writer keeps the offset of the end of the log, so only the new entries have to be written
+// When the log is full it's started again with only the moves of the game, the settings and starting board are kept for this
+pub struct SaveWriter {
+    offset: usize, // Offset of the end of the log
+    half_moves: usize, // Number of moves of the game in the log
+    settings: Settings,
+    start_index: usize,
+}
+
+impl SaveWriter {
+    // Erases the save and writes a game to it, with every move that has been played so far
+    // Used when a game starts or is resumed, and when the log is full (the new log has no undo entries, so it's shorter)
+    // The time each team has left is saved with its last move
+    // Returns an error if the game has too many moves to save, the save isn't changed
+    pub fn start<S: Storage>(storage: &mut S, settings: &Settings, start_index: usize, game: &Game, clock: Option<&crate::clock::Clock>) -> Result<Self, ()> {
+        if game.len() > MAX_SAVED_HALF_MOVES {
+            return Err(());
+        }
+        storage.erase()?;
+
+        let mut start_entry = [0; START_ENTRY_SIZE];
+        start_entry[0] = TAG_START;
+        start_entry[1] = start_index as u8;
+        start_entry[2..2 + settings::SETTINGS_BYTES].copy_from_slice(&settings.to_bytes());
+        start_entry[START_ENTRY_SIZE - 1] = COMMIT;
+        storage.write(0, &start_entry)?;
+
+        let mut writer = SaveWriter {
+            offset: START_ENTRY_SIZE,
+            half_moves: 0,
+            settings: *settings,
+            start_index: start_index,
+        };
+
+        let mut whites_move = game.start().whites_move;
+        for i in 0..game.len() {
+            match game.piece_move(i) {
+                Some(piece_move) => writer.write_entry(storage, TAG_MOVE, pack_move(&piece_move), remaining_ms(clock, whites_move))?,
+                None => return Err(()),
+            }
+            writer.half_moves += 1;
+            whites_move = !whites_move;
+        }
+        Ok(writer)
+    }
+
+    // Saves the result of a game that has ended, so it isn't resumed
+    // The move that ended the game is saved first if it hasn't been (it's saved without the time left, the game can't be resumed)
+    // Returns an error if the result can't be written, the save should then be cleared so the game isn't resumed
+    pub fn finish<S: Storage>(&mut self, storage: &mut S, game: &Game) -> Result<(), ()> {
+        if self.half_moves + 1 == game.len() {
+            match game.piece_move(self.half_moves) {
+                Some(piece_move) => self.write_entry(storage, TAG_MOVE, pack_move(&piece_move), u32::MAX)?,
+                None => return Err(()),
+            }
+            self.half_moves += 1;
+        }
+        if self.half_moves != game.len() {
+            return Err(());
+        }
+
+        self.write_entry(storage, TAG_RESULT, result_to_byte(game.result) as u16, u32::MAX)
+    }
+
+    // Saves the last move of the game, after it has been played and the clock has been updated
+    // Returns an error if the game has too many moves to save
+    pub fn push_move<S: Storage>(&mut self, storage: &mut S, game: &Game, clock: Option<&crate::clock::Clock>) -> Result<(), ()> {
+        let piece_move;
+        match game.piece_move(game.len().wrapping_sub(1)) {
+            Some(last_move) => piece_move = last_move,
+            None => return Err(()),
+        }
+
+        // The team that made the last move is the team that isn't to move now
+        let white = !game.board.whites_move;
+        match self.write_entry(storage, TAG_MOVE, pack_move(&piece_move), remaining_ms(clock, white)) {
+            Ok(()) => {
+                self.half_moves += 1;
+                Ok(())
+            },
+            Err(()) => self.restart(storage, game, clock),
+        }
+    }
+
+    // Saves a move being taken back, after the game has taken it back
+    // Returns an error if the game has too many moves to save, the same as push_move
+    pub fn push_undo<S: Storage>(&mut self, storage: &mut S, game: &Game, clock: Option<&crate::clock::Clock>) -> Result<(), ()> {
+        match self.write_entry(storage, TAG_UNDO, 0, u32::MAX) {
+            Ok(()) => {
+                self.half_moves -= 1;
+                Ok(())
+            },
+            Err(()) => self.restart(storage, game, clock),
+        }
+    }
+
+    // Starts the log again with only the moves of the game, when there isn't space for another entry
+    fn restart<S: Storage>(&mut self, storage: &mut S, game: &Game, clock: Option<&crate::clock::Clock>) -> Result<(), ()> {
+        *self = SaveWriter::start(storage, &self.settings, self.start_index, game, clock)?;
+        Ok(())
+    }
+
+    // Appends an entry to the log, returns an error if the log is full
+    fn write_entry<S: Storage>(&mut self, storage: &mut S, tag: u8, packed_move: u16, remaining_ms: u32) -> Result<(), ()> {
+        if self.offset + ENTRY_SIZE > SAVE_SIZE {
+            return Err(());
+        }
+
+        let mut entry = [0; ENTRY_SIZE];
+        entry[0] = tag;
+        entry[1..3].copy_from_slice(&packed_move.to_le_bytes());
+        entry[3..7].copy_from_slice(&remaining_ms.to_le_bytes());
+        entry[ENTRY_SIZE - 1] = COMMIT;
+        storage.write(self.offset, &entry)?;
+
+        self.offset += ENTRY_SIZE;
+        Ok(())
+    }
+}
+
+// Returns the time a team has left to save with its move, u32::MAX in an untimed game
+fn remaining_ms(clock: Option<&crate::clock::Clock>, white: bool) -> u32 {
+    match clock {
+        Some(clock) => core::cmp::min(clock.remaining_ms(white), u32::MAX as u64 - 1) as u32,
+        None => u32::MAX,
+    }
+}
+
+// Results are saved as one byte, the result of a game that was stopped before it ended is GameResult::Unfinished
+fn result_to_byte(result: GameResult) -> u8 {
+    match result {
+        GameResult::WhiteWin => 0,
+        GameResult::BlackWin => 1,
+        GameResult::Draw => 2,
+        GameResult::Unfinished => 3,
+    }
+}
+
+fn byte_to_result(byte: u8) -> Option<GameResult> {
+    match byte {
+        0 => Some(GameResult::WhiteWin),
+        1 => Some(GameResult::BlackWin),
+        2 => Some(GameResult::Draw),
+        3 => Some(GameResult::Unfinished),
+        _ => None,
+    }
+}
+
+fn team_index(white: bool) -> usize {
+    if white {
+        0
+    } else {
+        1
+    }
+}
+
+// Packs a move into 2 bytes, the initial bit (6 bits), final bit (6 bits), and promotion (3 bits, 0 if there is none)
+// The board index isn't saved, it's the piece on the initial bit when the move is replayed
+fn pack_move(piece_move: &Move) -> u16 {
+    let promotion;
+    match piece_move.promotion {
+        Some(index) => promotion = index as u16 + 1,
+        None => promotion = 0,
+    }
+    piece_move.initial_piece_coordinates.bit as u16 | (piece_move.final_piece_bit as u16) << 6 | promotion << 12
+}
+
+fn unpack_move(packed_move: u16, board: &board_representation::Board) -> Option<Move> {
+    let initial_bit = (packed_move & 0x3f) as usize;
+    let mut piece_move = Move::new();
+    piece_move.initial_piece_coordinates = board_representation::BoardCoordinates {
+        board_index: board.piece_on(initial_bit)?,
+        bit: initial_bit,
+    };
+    piece_move.final_piece_bit = (packed_move >> 6 & 0x3f) as usize;
+    match packed_move >> 12 {
+        0 => (),
+        promotion => piece_move.promotion = Some(promotion as usize - 1),
+    }
+    Some(piece_move)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Storage kept in ram, which checks that writes are made the way flash needs them to be
+    struct RamStorage {
+        bytes: [u8; SAVE_SIZE],
+        erases: usize,
+    }
+
+    impl RamStorage {
+        fn new() -> Self {
+            RamStorage {
+                bytes: [0xff; SAVE_SIZE],
+                erases: 0,
+            }
+        }
+    }
+
+    impl Storage for RamStorage {
+        fn read(&self, offset: usize, buffer: &mut [u8]) -> Result<(), ()> {
+            buffer.copy_from_slice(&self.bytes[offset..offset + buffer.len()]);
+            Ok(())
+        }
+
+        fn write(&mut self, offset: usize, data: &[u8]) -> Result<(), ()> {
+            assert!(offset % 2 == 0 && data.len() % 2 == 0);
+            // Flash can only be written once between erases
+            assert!(self.bytes[offset..offset + data.len()].iter().all(|byte| *byte == 0xff));
+            self.bytes[offset..offset + data.len()].copy_from_slice(data);
+            Ok(())
+        }
+
+        fn erase(&mut self) -> Result<(), ()> {
+            self.bytes = [0xff; SAVE_SIZE];
+            self.erases += 1;
+            Ok(())
+        }
+    }
+
+    fn play(game: &mut Game, text: &str, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Result<(), crate::board::move_generator::TurnError> {
+        let piece_move = crate::notation::parse_move(text, &game.board, pieces_info).unwrap();
+        game.play(&piece_move, pieces_info)
+    }
+
+    fn new_game() -> Game {
+        Game::new(board_representation::Board::indexed_start(0).unwrap())
+    }
+
+    #[test]
+    fn round_trip() {
+        let pieces_info = crate::piece::constants::gen();
+        let mut storage = RamStorage::new();
+        let mut settings = Settings::new();
+        settings.time_control_index = 1;
+        settings.player_white = false;
+
+        let mut game = new_game();
+        let mut clock = crate::clock::Clock::new(settings.time_control().unwrap(), true, 0);
+        let mut writer = SaveWriter::start(&mut storage, &settings, 0, &game, Some(&clock)).unwrap();
+
+        // Nothing has been played yet
+        let saved_game = load(&storage, &pieces_info).unwrap();
+        assert_eq!(saved_game.game.len(), 0);
+        assert_eq!(saved_game.remaining_ms, [None, None]);
+
+        let mut now_ms = 0;
+        for text in ["e4", "e5", "Nf3", "Nc6", "Bb5"] {
+            play(&mut game, text, &pieces_info).unwrap();
+            now_ms += 3000;
+            clock.end_turn(now_ms);
+            writer.push_move(&mut storage, &game, Some(&clock)).unwrap();
+        }
+
+        // Bb5 is taken back
+        game.undo(&pieces_info).unwrap();
+        writer.push_undo(&mut storage, &game, Some(&clock)).unwrap();
+
+        let saved_game = load(&storage, &pieces_info).unwrap();
+        assert_eq!(saved_game.settings, settings);
+        assert_eq!(saved_game.start_index, 0);
+        assert_eq!(saved_game.game.len(), 4);
+        assert_eq!(saved_game.game.board.hash, game.board.hash);
+        assert_eq!(saved_game.game.board.whites_move, true);
+
+        // Time is saved with each move, so the time of the move that was taken back is still read
+        assert_eq!(saved_game.remaining_ms, [Some(clock.remaining_ms(true)), Some(clock.remaining_ms(false))]);
+        assert_eq!(saved_game.remaining_ms[1], Some(300000 - 6000));
+    }
+
+    #[test]
+    fn full_log() {
+        let pieces_info = crate::piece::constants::gen();
+        let mut storage = RamStorage::new();
+        let settings = Settings::new();
+
+        let mut game = new_game();
+        let mut writer = SaveWriter::start(&mut storage, &settings, 0, &game, None).unwrap();
+        assert_eq!(storage.erases, 1);
+
+        play(&mut game, "e4", &pieces_info).unwrap();
+        writer.push_move(&mut storage, &game, None).unwrap();
+
+        // Every move that is taken back adds 2 entries to the log without making the game longer, so the log fills up and is started again
+        for _ in 0..MAX_SAVED_HALF_MOVES {
+            play(&mut game, "e5", &pieces_info).unwrap();
+            writer.push_move(&mut storage, &game, None).unwrap();
+            game.undo(&pieces_info).unwrap();
+            writer.push_undo(&mut storage, &game, None).unwrap();
+        }
+        assert!(storage.erases > 1);
+
+        play(&mut game, "c5", &pieces_info).unwrap();
+        writer.push_move(&mut storage, &game, None).unwrap();
+
+        let saved_game = load(&storage, &pieces_info).unwrap();
+        assert_eq!(saved_game.game.len(), 2);
+        assert_eq!(saved_game.game.board.hash, game.board.hash);
+    }
+
+    #[test]
+    fn torn_entry() {
+        let pieces_info = crate::piece::constants::gen();
+        let mut storage = RamStorage::new();
+        let settings = Settings::new();
+
+        let mut game = new_game();
+        let mut writer = SaveWriter::start(&mut storage, &settings, 0, &game, None).unwrap();
+        for text in ["d4", "d5"] {
+            play(&mut game, text, &pieces_info).unwrap();
+            writer.push_move(&mut storage, &game, None).unwrap();
+        }
+
+        // The power is lost before d5's COMMIT byte is written
+        storage.bytes[START_ENTRY_SIZE + 2 * ENTRY_SIZE - 1] = 0xff;
+
+        let saved_game = load(&storage, &pieces_info).unwrap();
+        assert_eq!(saved_game.game.len(), 1);
+        assert_eq!(saved_game.game.board.whites_move, false);
+
+        // A torn start entry is no save at all
+        storage.bytes[START_ENTRY_SIZE - 1] = 0xff;
+        assert!(load(&storage, &pieces_info).is_none());
+        assert!(load_last(&storage, &pieces_info).is_none());
+    }
+
+    #[test]
+    fn result_entry() {
+        let pieces_info = crate::piece::constants::gen();
+        let mut storage = RamStorage::new();
+        let settings = Settings::new();
+
+        let mut game = new_game();
+        let mut writer = SaveWriter::start(&mut storage, &settings, 0, &game, None).unwrap();
+        for text in ["f3", "e5", "g4"] {
+            play(&mut game, text, &pieces_info).unwrap();
+            writer.push_move(&mut storage, &game, None).unwrap();
+        }
+
+        // The move that ends the game is saved by finish
+        assert!(play(&mut game, "Qh4", &pieces_info).is_err());
+        assert_eq!(game.result, GameResult::BlackWin);
+        writer.finish(&mut storage, &game).unwrap();
+
+        // A game that has ended isn't resumed, but can still be read
+        assert!(load(&storage, &pieces_info).is_none());
+        let saved_game = load_last(&storage, &pieces_info).unwrap();
+        assert_eq!(saved_game.game.len(), 4);
+        assert_eq!(saved_game.game.result, GameResult::BlackWin);
+        assert!(saved_game.game.end.is_some());
+    }
+}
@@ -0,0 +1,171 @@
+// Settings of the board, chosen from the menu (see menu.rs) and kept in storage so they are the same after the board is turned off
+// Every setting is stored as an index into its list of options, so a layout only has to change when a setting is added or removed
+// A stored layout of another version, or with an option this firmware doesn't have, isn't loaded and the default settings are used instead
+
+use crate::clock;
+use crate::record;
+use crate::storage::Storage;
+use crate::variant;
+
+// Who plays the game
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Mode {
+    Computer, // The player plays against the computer
+    TwoPlayer, // Two players play each other, the computer only checks their moves, runs the clocks and ends the game
+    Demo, // The computer plays both teams, for showing the board off and testing the leds and hall sensors
+}
+
+// Every mode, in the order they are shown
+pub const MODES: [Mode; 3] = [Mode::Computer, Mode::TwoPlayer, Mode::Demo];
+
+impl Mode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Mode::Computer => "Vs computer",
+            Mode::TwoPlayer => "Two players",
+            Mode::Demo => "Demo",
+        }
+    }
+}
+
+// Maximum times (ms) the computer can take to search for a move, longer searches make the computer harder to beat
+pub const SEARCH_TIMES: [u64; 8] = [1000, 3000, 5000, 10000, 20000, 30000, 50000, 100000];
+
+// Deepest search depth each team can be given in demo mode, the same as the computer's search depth in other modes
+pub const MAX_DEMO_DEPTH: usize = 6;
+
+// Times (ms) the computer searches for when the player asks for a hint
+pub const HINT_TIMES: [u64; 4] = [500, 1000, 2000, 5000];
+
+// Brightness levels of the leds, as the percent of the time a lit led is on
+pub const LED_BRIGHTNESS_LEVELS: [u8; 4] = [25, 50, 75, 100];
+
+// Settings that can be changed from the menu
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Settings {
+    pub mode_index: usize, // Index of the mode in MODES
+    pub player_white: bool, // Team of the player, in two player mode the team at the bottom of the board
+    pub search_time_index: usize, // Index of the computer's maximum search time in SEARCH_TIMES
+    pub hint_time_index: usize, // Index of the search time of hints in HINT_TIMES
+    pub demo_depths: [usize; 2], // Search depths of the white and black teams in demo mode (1 - MAX_DEMO_DEPTH), so one team can be stronger than the other
+    pub time_control_index: usize, // Index of the time control in clock::TIME_CONTROLS
+    pub variant_index: usize, // Index of the variant in variant::VARIANTS
+    pub led_brightness_index: usize, // Index of the led brightness in LED_BRIGHTNESS_LEVELS
+    pub sound: bool, // True if the buzzer plays sounds (see sound.rs)
+}
+
+impl Settings {
+    pub fn new() -> Self {
+        Settings {
+            mode_index: 0,
+            player_white: true,
+            search_time_index: 2,
+            hint_time_index: 1,
+            demo_depths: [MAX_DEMO_DEPTH, MAX_DEMO_DEPTH],
+            time_control_index: 0,
+            variant_index: 0,
+            led_brightness_index: LED_BRIGHTNESS_LEVELS.len() - 1,
+            sound: true,
+        }
+    }
+
+    pub fn mode(&self) -> Mode {
+        MODES[self.mode_index]
+    }
+
+    pub fn search_time_ms(&self) -> u64 {
+        SEARCH_TIMES[self.search_time_index]
+    }
+
+    // Returns the search depth of a team in demo mode
+    pub fn demo_depth(&self, white: bool) -> usize {
+        if white {
+            self.demo_depths[0]
+        } else {
+            self.demo_depths[1]
+        }
+    }
+
+    pub fn hint_time_ms(&self) -> u64 {
+        HINT_TIMES[self.hint_time_index]
+    }
+
+    // Returns the time control of the game, None for an untimed game
+    pub fn time_control(&self) -> Option<clock::TimeControl> {
+        clock::TIME_CONTROLS[self.time_control_index]
+    }
+
+    pub fn variant(&self) -> variant::Variant {
+        variant::VARIANTS[self.variant_index]
+    }
+
+    pub fn led_brightness(&self) -> u8 {
+        LED_BRIGHTNESS_LEVELS[self.led_brightness_index]
+    }
+
+    // Returns the settings as bytes, one byte for each setting in the order of the fields
+    pub fn to_bytes(&self) -> [u8; SETTINGS_BYTES] {
+        [
+            self.mode_index as u8,
+            self.player_white as u8,
+            self.search_time_index as u8,
+            self.hint_time_index as u8,
+            self.demo_depths[0] as u8,
+            self.demo_depths[1] as u8,
+            self.time_control_index as u8,
+            self.variant_index as u8,
+            self.led_brightness_index as u8,
+            self.sound as u8,
+        ]
+    }
+
+    // Creates settings from the bytes of to_bytes
+    // Returns None if there aren't enough bytes or a setting is out of range, e.g. bytes from firmware with more options
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < SETTINGS_BYTES {
+            return None;
+        }
+
+        let value = |i: usize, min: usize, len: usize| -> Option<usize> {
+            let value = bytes[i] as usize;
+            if value >= min && value < len {
+                Some(value)
+            } else {
+                None
+            }
+        };
+
+        Some(Settings {
+            mode_index: value(0, 0, MODES.len())?,
+            player_white: value(1, 0, 2)? == 1,
+            search_time_index: value(2, 0, SEARCH_TIMES.len())?,
+            hint_time_index: value(3, 0, HINT_TIMES.len())?,
+            demo_depths: [value(4, 1, MAX_DEMO_DEPTH + 1)?, value(5, 1, MAX_DEMO_DEPTH + 1)?],
+            time_control_index: value(6, 0, clock::TIME_CONTROLS.len())?,
+            variant_index: value(7, 0, variant::VARIANTS.len())?,
+            led_brightness_index: value(8, 0, LED_BRIGHTNESS_LEVELS.len())?,
+            sound: value(9, 0, 2)? == 1,
+        })
+    }
+}
+
+// Number of bytes of Settings::to_bytes
+pub const SETTINGS_BYTES: usize = 10;
+
+// Version of the layout of Settings::to_bytes, has to change when the layout changes
+const SETTINGS_VERSION: u8 = 2;
+
+// Reads the stored settings, None if no settings have been stored or they can't be read (see Settings::from_bytes)
+pub fn load<S: Storage>(storage: &S) -> Option<Settings> {
+    let mut payload = [0; record::MAX_PAYLOAD];
+    let (version, length) = record::load(storage, record::SETTINGS_RECORD, &mut payload)?;
+    if version != SETTINGS_VERSION {
+        return None;
+    }
+    Settings::from_bytes(&payload[..length])
+}
+
+// Stores the settings, so they are loaded the next time the board is turned on
+pub fn store<S: Storage>(storage: &mut S, settings: &Settings) -> Result<(), ()> {
+    record::store(storage, record::SETTINGS_RECORD, SETTINGS_VERSION, &settings.to_bytes())
+}
@@ -0,0 +1,113 @@
+// Sounds of the buzzer, each sound is a short pattern of notes so the sounds can be told apart without looking at the board
+// The patterns are played by a Player, which only keeps track of the note that should be playing
+// The firmware ticks the player from a timer interrupt and sets the buzzer's tone when it changes (see embedded::buzzer), so playing a sound never stalls the game
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Sound {
+    MoveMade, // A move was made, by the player or the computer
+    IllegalMove,
+    Check,
+    LowTime, // The clock of the team to move has little time left (see clock::LOW_TIME_MS)
+    GameOver,
+}
+
+// A tone played for a time, a frequency of 0 is a rest
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Note {
+    pub frequency_hz: u16,
+    pub duration_ms: u16,
+}
+
+const fn note(frequency_hz: u16, duration_ms: u16) -> Note {
+    Note {
+        frequency_hz: frequency_hz,
+        duration_ms: duration_ms,
+    }
+}
+
+// A short high blip
+const MOVE_MADE: [Note; 1] = [note(2000, 40)];
+
+// Two low buzzes
+const ILLEGAL_MOVE: [Note; 3] = [note(220, 150), note(0, 60), note(220, 150)];
+
+// Two quick rising notes
+const CHECK: [Note; 3] = [note(1500, 60), note(0, 30), note(2500, 80)];
+
+// Three quick beeps
+const LOW_TIME: [Note; 5] = [note(3000, 50), note(0, 50), note(3000, 50), note(0, 50), note(3000, 50)];
+
+// A falling run of notes
+const GAME_OVER: [Note; 4] = [note(1047, 150), note(784, 150), note(659, 150), note(523, 400)];
+
+impl Sound {
+    pub fn pattern(&self) -> &'static [Note] {
+        match self {
+            Sound::MoveMade => &MOVE_MADE,
+            Sound::IllegalMove => &ILLEGAL_MOVE,
+            Sound::Check => &CHECK,
+            Sound::LowTime => &LOW_TIME,
+            Sound::GameOver => &GAME_OVER,
+        }
+    }
+}
+
+// Plays a pattern of notes, tick has to be called regularly with the time since it was last called
+pub struct Player {
+    pattern: &'static [Note],
+    index: usize, // Index of the playing note in pattern, the pattern has finished once this is its length
+    remaining_ms: u16, // Time left of the playing note
+    started: bool, // True when a pattern has been played but tick hasn't returned its first note yet
+}
+
+impl Player {
+    // Const so the firmware's player can be a static, the timer interrupt that plays the notes uses it
+    pub const fn new() -> Self {
+        Player {
+            pattern: &[],
+            index: 0,
+            remaining_ms: 0,
+            started: false,
+        }
+    }
+
+    // Starts playing a sound, a sound that is already playing is stopped
+    pub fn play(&mut self, sound: Sound) {
+        self.pattern = sound.pattern();
+        self.index = 0;
+        self.remaining_ms = self.pattern[0].duration_ms;
+        self.started = true;
+    }
+
+    // Returns true until the last note of the pattern has finished
+    pub fn playing(&self) -> bool {
+        self.index < self.pattern.len()
+    }
+
+    // Moves the pattern on by elapsed_ms, returns the frequency the buzzer has to play when it changes, 0 to stop the buzzer
+    // Notes are moved on by at most one note each tick, so the ticks should be shorter than the shortest note
+    pub fn tick(&mut self, elapsed_ms: u16) -> Option<u16> {
+        if !self.playing() {
+            return None;
+        }
+
+        if self.started {
+            self.started = false;
+            return Some(self.pattern[self.index].frequency_hz);
+        }
+
+        if self.remaining_ms > elapsed_ms {
+            self.remaining_ms -= elapsed_ms;
+            return None;
+        }
+
+        self.index += 1;
+        match self.pattern.get(self.index) {
+            Some(note) => {
+                self.remaining_ms = note.duration_ms;
+                Some(note.frequency_hz)
+            },
+            None => Some(0),
+        }
+    }
+}
@@ -0,0 +1,220 @@
+// Typed squares, files, ranks and pieces for users of the engine library
+// Internally the engine uses raw bit numbers (0 is a8, 63 is h1) and board indexes (0 - 11), which are easy to mix up
+// These types convert to and from the raw layout, so code outside the engine doesn't need to know it
+
+// A square on the board, stored as its bit number (0 is a8, 63 is h1)
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Square(u8);
+
+impl Square {
+    // Returns the square of a bit number, None if the bit is off the board
+    pub const fn new(bit: usize) -> Option<Square> {
+        if bit > 63 {
+            return None;
+        }
+        Some(Square(bit as u8))
+    }
+
+    // Returns the square on a file and rank
+    pub const fn from_file_rank(file: File, rank: Rank) -> Square {
+        Square(((7 - rank.index()) * 8 + file.index()) as u8)
+    }
+
+    // Returns the square of a square name (e.g. "e4"), an error if the name isn't a square
+    pub fn from_algebraic(text: &str) -> Result<Square, ()> {
+        Ok(Square(crate::notation::square_to_bit(text)? as u8))
+    }
+
+    // Returns the name of the square (e.g. "e4")
+    pub fn to_algebraic(self) -> &'static str {
+        crate::bit_to_ccn(self.bit())
+    }
+
+    // Returns the bit number of the square
+    pub const fn bit(self) -> usize {
+        self.0 as usize
+    }
+
+    // Returns a bitboard with only the square's bit on
+    pub const fn bitboard(self) -> u64 {
+        1 << self.0
+    }
+
+    pub const fn file(self) -> File {
+        FILES[self.0 as usize % 8]
+    }
+
+    pub const fn rank(self) -> Rank {
+        RANKS[7 - self.0 as usize / 8]
+    }
+}
+
+// Prints the square as a file and rank (e.g. e4)
+impl core::fmt::Display for Square {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(self.to_algebraic())
+    }
+}
+
+// Columns of the board, a is the column of bit 0
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum File {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+}
+
+const FILES: [File; 8] = [File::A, File::B, File::C, File::D, File::E, File::F, File::G, File::H];
+
+impl File {
+    // Returns the file of an index, 0 is the a file
+    pub const fn from_index(index: usize) -> Option<File> {
+        if index > 7 {
+            return None;
+        }
+        Some(FILES[index])
+    }
+
+    // Returns the index of the file (the column of its bits), 0 is the a file
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    // Returns a bitboard of every bit on the file
+    pub const fn bitboard(self) -> u64 {
+        crate::bitboard::A_COLUMN << self.index()
+    }
+}
+
+// Rows of the board, the first rank is the white team's back row (bits 56 - 63)
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Rank {
+    First,
+    Second,
+    Third,
+    Fourth,
+    Fifth,
+    Sixth,
+    Seventh,
+    Eighth,
+}
+
+const RANKS: [Rank; 8] = [Rank::First, Rank::Second, Rank::Third, Rank::Fourth, Rank::Fifth, Rank::Sixth, Rank::Seventh, Rank::Eighth];
+
+impl Rank {
+    // Returns the rank of an index, 0 is the first rank
+    pub const fn from_index(index: usize) -> Option<Rank> {
+        if index > 7 {
+            return None;
+        }
+        Some(RANKS[index])
+    }
+
+    // Returns the index of the rank, 0 is the first rank
+    // Bit rows count the other way, row 0 is the eighth rank
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    // Returns a bitboard of every bit on the rank
+    pub const fn bitboard(self) -> u64 {
+        0xFF << ((7 - self.index()) * 8)
+    }
+}
+
+// Teams of the pieces
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Colour {
+    White,
+    Black,
+}
+
+impl Colour {
+    // Converts the bools the engine uses for teams (e.g. Board::whites_move)
+    pub const fn from_white(white: bool) -> Colour {
+        if white {
+            Colour::White
+        } else {
+            Colour::Black
+        }
+    }
+
+    pub const fn is_white(self) -> bool {
+        match self {
+            Colour::White => true,
+            Colour::Black => false,
+        }
+    }
+
+    pub const fn opposite(self) -> Colour {
+        match self {
+            Colour::White => Colour::Black,
+            Colour::Black => Colour::White,
+        }
+    }
+}
+
+// Types of pieces, in the same order as the bitboards of each team
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum PieceKind {
+    Pawn,
+    Rook,
+    Knight,
+    Bishop,
+    Queen,
+    King,
+}
+
+const PIECE_KINDS: [PieceKind; 6] = [PieceKind::Pawn, PieceKind::Rook, PieceKind::Knight, PieceKind::Bishop, PieceKind::Queen, PieceKind::King];
+
+impl PieceKind {
+    // Returns the piece kind of a board index of either team, None if the index isn't a piece bitboard
+    pub const fn from_board_index(board_index: usize) -> Option<PieceKind> {
+        if board_index > 11 {
+            return None;
+        }
+        Some(PIECE_KINDS[board_index % 6])
+    }
+
+    // Returns the board index of the piece kind for the white team, which is how promotion pieces are stored (see Move::promotion)
+    pub const fn white_board_index(self) -> usize {
+        self as usize
+    }
+}
+
+// A piece of a team
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Piece {
+    pub colour: Colour,
+    pub kind: PieceKind,
+}
+
+impl Piece {
+    pub const fn new(colour: Colour, kind: PieceKind) -> Self {
+        Piece {
+            colour: colour,
+            kind: kind,
+        }
+    }
+
+    // Returns the piece of a board index, None if the index isn't a piece bitboard
+    pub const fn from_board_index(board_index: usize) -> Option<Piece> {
+        match PieceKind::from_board_index(board_index) {
+            Some(kind) => Some(Piece::new(Colour::from_white(board_index < 6), kind)),
+            None => None,
+        }
+    }
+
+    // Returns the index of the piece's bitboard in a board
+    pub const fn board_index(self) -> usize {
+        match self.colour {
+            Colour::White => self.kind as usize,
+            Colour::Black => self.kind as usize + 6,
+        }
+    }
+}
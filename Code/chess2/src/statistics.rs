@@ -0,0 +1,121 @@
+// Statistics of the games played on the board, kept in storage as a record (see record.rs)
+// Wins, losses and draws against the computer are counted for every difficulty (see settings::SEARCH_TIMES), from the player's side
+// Every game with a result is counted in the games played, including two player and demo games
+
+use crate::pgn::GameResult;
+use crate::record;
+use crate::settings::{self, Mode, Settings};
+use crate::storage::Storage;
+
+// Number of difficulties the results are kept for
+pub const LEVELS: usize = settings::SEARCH_TIMES.len();
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Statistics {
+    pub games: u16, // Games played
+    pub results: [[u16; 3]; LEVELS], // Wins, losses and draws of the player against the computer at each difficulty
+}
+
+impl Statistics {
+    pub fn new() -> Self {
+        Statistics {
+            games: 0,
+            results: [[0; 3]; LEVELS],
+        }
+    }
+
+    // Counts a game that has ended, with the settings it was played with
+    // Unfinished games (e.g. a demo that was stopped) aren't counted
+    pub fn add_game(&mut self, settings: &Settings, result: GameResult) {
+        if result == GameResult::Unfinished {
+            return;
+        }
+        self.games = self.games.saturating_add(1);
+
+        if settings.mode() != Mode::Computer {
+            return;
+        }
+
+        let result_index;
+        match (result, settings.player_white) {
+            (GameResult::WhiteWin, true) | (GameResult::BlackWin, false) => result_index = 0,
+            (GameResult::WhiteWin, false) | (GameResult::BlackWin, true) => result_index = 1,
+            _ => result_index = 2,
+        }
+
+        let count = &mut self.results[settings.search_time_index][result_index];
+        *count = count.saturating_add(1);
+    }
+
+    // Returns the wins, losses and draws at a difficulty
+    pub fn level(&self, search_time_index: usize) -> Level {
+        Level {
+            wins: self.results[search_time_index][0],
+            losses: self.results[search_time_index][1],
+            draws: self.results[search_time_index][2],
+        }
+    }
+
+    // Returns the statistics as bytes, the games played then the results of every difficulty (little endian u16s)
+    pub fn to_bytes(&self) -> [u8; STATISTICS_BYTES] {
+        let mut bytes = [0; STATISTICS_BYTES];
+        bytes[0..2].copy_from_slice(&self.games.to_le_bytes());
+        for level in 0..LEVELS {
+            for i in 0..3 {
+                let offset = 2 + (level * 3 + i) * 2;
+                bytes[offset..offset + 2].copy_from_slice(&self.results[level][i].to_le_bytes());
+            }
+        }
+        bytes
+    }
+
+    // Creates statistics from the bytes of to_bytes, None if the number of bytes is wrong
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() != STATISTICS_BYTES {
+            return None;
+        }
+
+        let mut statistics = Statistics::new();
+        statistics.games = u16::from_le_bytes([bytes[0], bytes[1]]);
+        for level in 0..LEVELS {
+            for i in 0..3 {
+                let offset = 2 + (level * 3 + i) * 2;
+                statistics.results[level][i] = u16::from_le_bytes([bytes[offset], bytes[offset + 1]]);
+            }
+        }
+        Some(statistics)
+    }
+}
+
+// Results at one difficulty, printed as "W3 L2 D1"
+pub struct Level {
+    pub wins: u16,
+    pub losses: u16,
+    pub draws: u16,
+}
+
+impl core::fmt::Display for Level {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "W{} L{} D{}", self.wins, self.losses, self.draws)
+    }
+}
+
+// Number of bytes of Statistics::to_bytes
+pub const STATISTICS_BYTES: usize = 2 + LEVELS * 3 * 2;
+
+// Version of the layout of Statistics::to_bytes, has to change when the layout changes
+const STATISTICS_VERSION: u8 = 1;
+
+// Reads the stored statistics, None if no statistics have been stored or they can't be read
+pub fn load<S: Storage>(storage: &S) -> Option<Statistics> {
+    let mut payload = [0; record::MAX_PAYLOAD];
+    let (version, length) = record::load(storage, record::STATISTICS_RECORD, &mut payload)?;
+    if version != STATISTICS_VERSION {
+        return None;
+    }
+    Statistics::from_bytes(&payload[..length])
+}
+
+pub fn store<S: Storage>(storage: &mut S, statistics: &Statistics) -> Result<(), ()> {
+    record::store(storage, record::STATISTICS_RECORD, STATISTICS_VERSION, &statistics.to_bytes())
+}
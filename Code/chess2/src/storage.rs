@@ -0,0 +1,11 @@
+// Storage that keeps its data while the power is off, e.g. a region of the STM32F103's flash (see embedded::flash_storage)
+// Used by the saved game (see save.rs) and the records of the settings and statistics (see record.rs)
+// Erased storage reads as 0xff, and bytes can only be written once between erases
+pub trait Storage {
+    fn read(&self, offset: usize, buffer: &mut [u8]) -> Result<(), ()>;
+
+    // Writes always have an even length at an even offset (the STM32F103 writes flash 2 bytes at a time)
+    fn write(&mut self, offset: usize, data: &[u8]) -> Result<(), ()>;
+
+    fn erase(&mut self) -> Result<(), ()>; // Erases the whole storage
+}
@@ -0,0 +1,87 @@
+// Row of the lcd shown while the computer is searching, so the player can see the board is thinking and hasn't hung
+// It has a spinner, the depth being searched, and the best move and score of the last completed iteration (e.g. "/d6 Nf3 +0.35")
+// The row is updated from the search's progress reports (see algorithm::SearchProgress), which are made during iterations as well as after them
+
+use crate::algorithm::{self, Move, SearchProgress};
+use crate::board::board_representation;
+
+// Frames of the spinner, the HD44780's character rom has a yen sign in place of a backslash so it only turns through 3 frames
+const SPINNER: [char; 3] = ['|', '/', '-'];
+
+// Time (ms) each frame of the spinner is shown
+const SPINNER_FRAME_MS: u64 = 250;
+
+pub struct Thinking {
+    depth: usize, // Depth of the last progress report
+    best: Option<(Move, i16)>, // Best move and value (relative to the team to move) of the last completed iteration
+    frame: usize, // Index of the spinner frame in SPINNER
+}
+
+impl Thinking {
+    pub fn new() -> Self {
+        Thinking {
+            depth: 0,
+            best: None,
+            frame: 0,
+        }
+    }
+
+    // Updates the row from a progress report, returns true if the row changed and has to be printed again
+    // The spinner turns with the time the search has taken, so it only changes every SPINNER_FRAME_MS however often the search reports
+    pub fn update(&mut self, progress: &SearchProgress, clock_mhz: u64) -> bool {
+        let elapsed_ms = progress.elapsed_cycles / (clock_mhz * 1000);
+        let frame = (elapsed_ms / SPINNER_FRAME_MS) as usize % SPINNER.len();
+
+        let mut best = self.best;
+        if progress.completed {
+            match progress.best_move {
+                Some(best_move) => best = Some((best_move, progress.value)),
+                None => (),
+            }
+        }
+
+        let changed = frame != self.frame || progress.depth != self.depth || best != self.best;
+        self.frame = frame;
+        self.depth = progress.depth;
+        self.best = best;
+        changed
+    }
+
+    // Writes the row, the best move is written in standard algebraic notation so the board has to be the board being searched
+    // The row can be longer than the lcd with a long move and score, the end of the score is cut off when it's printed
+    pub fn write_row<W: core::fmt::Write>(&self, out: &mut W, board: &board_representation::Board, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Result<(), ()> {
+        write!(out, "{}d{}", SPINNER[self.frame], self.depth).map_err(|_| ())?;
+
+        match self.best {
+            Some((best_move, value)) => {
+                out.write_str(" ").map_err(|_| ())?;
+                crate::notation::write_san(out, &best_move, board, pieces_info)?;
+                out.write_str(" ").map_err(|_| ())?;
+                write_score(out, value)?;
+            },
+            None => (),
+        }
+        Ok(())
+    }
+}
+
+// Writes a value in pawns (e.g. "+0.35"), or as the moves until a checkmate (e.g. "M3" or "-M3") if the value is a checkmate
+fn write_score<W: core::fmt::Write>(out: &mut W, value: i16) -> Result<(), ()> {
+    match algorithm::mate_moves(value) {
+        Some(mate_moves) => {
+            if mate_moves < 0 {
+                return write!(out, "-M{}", -mate_moves).map_err(|_| ());
+            }
+            return write!(out, "M{}", mate_moves).map_err(|_| ());
+        },
+        None => (),
+    }
+
+    let sign;
+    if value < 0 {
+        sign = "-";
+    } else {
+        sign = "+";
+    }
+    write!(out, "{}{}.{:02}", sign, value.abs() / 100, value.abs() % 100).map_err(|_| ())
+}
@@ -0,0 +1,237 @@
+use std::fmt::Write;
+
+use crate::board::board_representation;
+use crate::evaluation::{EvalCache, EvalWeights};
+
+// Texel tuning of the evaluation weights
+// Only available with the std feature, the output is the source of eval_weights.rs
+// https://www.chessprogramming.org/Texel%27s_Tuning_Method
+
+pub struct TrainingPosition {
+    pub board: board_representation::Board,
+    pub result: f64, // Result of the game the position is from, 1 white win, 0.5 draw, 0 black win
+}
+
+// Parses training positions, one per line
+// A line is a fen followed by the result, either as "1-0", "0-1", "1/2-1/2" or a number (1.0, 0.5, 0.0)
+// E.g. the quiet-labeled.epd format: <fen> c9 "1/2-1/2";
+// Lines which can't be parsed are skipped
+pub fn parse_positions(text: &str) -> Vec<TrainingPosition> {
+    let mut positions = Vec::new();
+
+    for line in text.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 4 {
+            continue;
+        }
+
+        let result;
+        match parse_result(line, fields[fields.len() - 1]) {
+            Some(line_result) => result = line_result,
+            None => continue,
+        }
+
+        // Only the first 4 fen fields, the move clocks are often replaced by opcodes
+        let fen = fields[..4].join(" ");
+        match board_representation::Board::from_fen(&fen) {
+            Ok(board) => positions.push(TrainingPosition {
+                board: board,
+                result: result,
+            }),
+            Err(_) => continue,
+        }
+    }
+
+    positions
+}
+
+// Half moves at the start of every game which aren't used as training positions, these are mostly book moves
+pub const PGN_SKIP_HALF_MOVES: i16 = 16;
+
+// Returns training positions from the games in a PGN database, every position is labeled with the result of its game
+// Unfinished games, positions in the opening and positions where the team to move is in check are left out
+pub fn positions_from_pgn(pgn: &str, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Vec<TrainingPosition> {
+    use crate::pgn::GameResult;
+
+    let mut positions = Vec::new();
+    for game in crate::pgn::import_pgn(pgn, pieces_info) {
+        let result;
+        match game.result {
+            GameResult::WhiteWin => result = 1.0,
+            GameResult::BlackWin => result = 0.0,
+            GameResult::Draw => result = 0.5,
+            GameResult::Unfinished => continue,
+        }
+
+        for board in game.positions {
+            if board.half_moves < PGN_SKIP_HALF_MOVES || crate::board::move_generator::in_check(&board, pieces_info) {
+                continue;
+            }
+
+            positions.push(TrainingPosition {
+                board: board,
+                result: result,
+            });
+        }
+    }
+
+    positions
+}
+
+// Returns the result of a training position line
+fn parse_result(line: &str, last_field: &str) -> Option<f64> {
+    if line.contains("1/2-1/2") {
+        return Some(0.5);
+    } else if line.contains("1-0") {
+        return Some(1.0);
+    } else if line.contains("0-1") {
+        return Some(0.0);
+    }
+
+    match last_field.trim_matches(|c| c == ';' || c == '"' || c == '[' || c == ']').parse::<f64>() {
+        Ok(result) => {
+            if result >= 0.0 && result <= 1.0 {
+                return Some(result);
+            }
+            None
+        },
+        Err(_) => None,
+    }
+}
+
+// Returns the tunable weights as a list
+// Weights that can never be used (passed pawns on the back ranks, pawn and king mobility) are left out
+pub fn params(weights: &EvalWeights) -> Vec<i16> {
+    let mut params = vec![weights.doubled_pawn, weights.isolated_pawn, weights.backward_pawn];
+    params.extend_from_slice(&weights.passed_pawn[1..7]);
+    params.extend_from_slice(&weights.mobility[1..5]);
+    params.extend_from_slice(&[weights.bishop_pair, weights.rook_open_file, weights.rook_semi_open_file, weights.knight_outpost, weights.threat, weights.hanging_piece, weights.tempo]);
+    params
+}
+
+// Sets the tunable weights from a list made by params
+pub fn set_params(weights: &mut EvalWeights, params: &[i16]) {
+    weights.doubled_pawn = params[0];
+    weights.isolated_pawn = params[1];
+    weights.backward_pawn = params[2];
+    weights.passed_pawn[1..7].copy_from_slice(&params[3..9]);
+    weights.mobility[1..5].copy_from_slice(&params[9..13]);
+    weights.bishop_pair = params[13];
+    weights.rook_open_file = params[14];
+    weights.rook_semi_open_file = params[15];
+    weights.knight_outpost = params[16];
+    weights.threat = params[17];
+    weights.hanging_piece = params[18];
+    weights.tempo = params[19];
+}
+
+// Converts an evaluation into an expected result (win probability for white)
+fn sigmoid(evaluation: f64, k: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-k * evaluation / 400.0))
+}
+
+// Returns the mean squared error between the results of the positions and the expected results from their evaluations
+pub fn error(positions: &[TrainingPosition], weights: &EvalWeights, k: f64, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> f64 {
+    // No pawn cache, as its evaluations would be from different weights
+    let mut pawn_cache = EvalCache::new(&mut []);
+
+    let mut total = 0.0;
+    for position in positions {
+        let evaluation = crate::evaluation::evaluate_with_weights(&position.board, &mut pawn_cache, weights, pieces_info);
+        let difference = position.result - sigmoid(evaluation as f64, k);
+        total += difference * difference;
+    }
+
+    total / positions.len() as f64
+}
+
+// Finds the scaling constant K of the sigmoid which gives the lowest error for the current weights
+pub fn find_k(positions: &[TrainingPosition], weights: &EvalWeights, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> f64 {
+    let mut best_k = 1.0;
+    let mut best_error = error(positions, weights, best_k, pieces_info);
+
+    // Search with smaller steps around the best K
+    let mut step = 0.5;
+    for _ in 0..6 {
+        let centre = best_k;
+        for i in -4..5 {
+            let k = centre + i as f64 * step;
+            if k <= 0.0 {
+                continue;
+            }
+
+            let k_error = error(positions, weights, k, pieces_info);
+            if k_error < best_error {
+                best_error = k_error;
+                best_k = k;
+            }
+        }
+        step /= 4.0;
+    }
+
+    best_k
+}
+
+// Tunes the weights with a local search, every weight is moved by one until no change lowers the error
+// progress is called after every iteration with the iteration number and the error
+pub fn tune(
+    positions: &[TrainingPosition],
+    initial_weights: &EvalWeights,
+    k: f64,
+    max_iterations: usize,
+    pieces_info: &[crate::piece::constants::PieceInfo; 12],
+    progress: &mut dyn FnMut(usize, f64),
+) -> EvalWeights {
+    let mut weights = *initial_weights;
+    let mut best_params = params(&weights);
+    let mut best_error = error(positions, &weights, k, pieces_info);
+
+    for iteration in 0..max_iterations {
+        let mut improved = false;
+
+        for i in 0..best_params.len() {
+            for delta in [1, -1] {
+                let mut new_params = best_params.clone();
+                new_params[i] += delta;
+                set_params(&mut weights, &new_params);
+
+                let new_error = error(positions, &weights, k, pieces_info);
+                if new_error < best_error {
+                    best_error = new_error;
+                    best_params = new_params;
+                    improved = true;
+                    break;
+                }
+            }
+        }
+
+        set_params(&mut weights, &best_params);
+        progress(iteration, best_error);
+
+        if !improved {
+            break;
+        }
+    }
+
+    weights
+}
+
+// Returns the source of eval_weights.rs
+pub fn weights_source(weights: &EvalWeights) -> String {
+    let mut source = String::new();
+    writeln!(source, "pub const WEIGHTS: EvalWeights = EvalWeights {{").unwrap();
+    writeln!(source, "    doubled_pawn: {},", weights.doubled_pawn).unwrap();
+    writeln!(source, "    isolated_pawn: {},", weights.isolated_pawn).unwrap();
+    writeln!(source, "    backward_pawn: {},", weights.backward_pawn).unwrap();
+    writeln!(source, "    passed_pawn: {:?},", weights.passed_pawn).unwrap();
+    writeln!(source, "    mobility: {:?},", weights.mobility).unwrap();
+    writeln!(source, "    bishop_pair: {},", weights.bishop_pair).unwrap();
+    writeln!(source, "    rook_open_file: {},", weights.rook_open_file).unwrap();
+    writeln!(source, "    rook_semi_open_file: {},", weights.rook_semi_open_file).unwrap();
+    writeln!(source, "    knight_outpost: {},", weights.knight_outpost).unwrap();
+    writeln!(source, "    threat: {},", weights.threat).unwrap();
+    writeln!(source, "    hanging_piece: {},", weights.hanging_piece).unwrap();
+    writeln!(source, "    tempo: {},", weights.tempo).unwrap();
+    writeln!(source, "}};").unwrap();
+    source
+}
@@ -0,0 +1,376 @@
+// Universal Chess Interface (UCI), so a chess GUI on a PC can use the engine
+// This only parses the commands and writes the responses, the frontend reads the lines, runs the searches and sends the output
+// The firmware reads and writes the lines over a UART (see embedded::serial), a host frontend can use stdin and stdout
+// Supports uci, isready, ucinewgame, position, go, stop and quit, other commands are ignored as the protocol says they should be
+// https://www.chessprogramming.org/UCI
+
+use crate::algorithm::{self, Move, SearchParams, SearchProgress};
+use crate::board::board_representation::Board;
+use crate::board::move_generator::{self, TurnError};
+
+// Longest line that can be read, longer lines are dropped
+// A position command with every move of a game is the longest line, each move adds 5 characters
+pub const MAX_LINE_LENGTH: usize = 1024;
+
+// Collects received bytes into lines, so a frontend that reads a byte at a time can parse whole commands
+pub struct LineBuffer {
+    bytes: [u8; MAX_LINE_LENGTH],
+    len: usize,
+    complete: bool, // The bytes are a complete line that has been returned, the next byte starts a new line
+    overflowed: bool, // The line is longer than MAX_LINE_LENGTH, it's dropped when it ends
+}
+
+impl LineBuffer {
+    pub fn new() -> Self {
+        LineBuffer {
+            bytes: [0; MAX_LINE_LENGTH],
+            len: 0,
+            complete: false,
+            overflowed: false,
+        }
+    }
+
+    // Adds a received byte, returns the line once a line ending is received
+    // Lines can end with \n, \r or \r\n, empty lines and lines that aren't UTF-8 aren't returned
+    pub fn push(&mut self, byte: u8) -> Option<&str> {
+        if self.complete {
+            self.len = 0;
+            self.complete = false;
+            self.overflowed = false;
+        }
+
+        match byte {
+            b'\n' | b'\r' => {
+                if self.len == 0 && !self.overflowed {
+                    return None;
+                }
+                self.complete = true;
+                if self.overflowed {
+                    return None;
+                }
+                self.line()
+            },
+            _ => {
+                if self.len < MAX_LINE_LENGTH {
+                    self.bytes[self.len] = byte;
+                    self.len += 1;
+                } else {
+                    self.overflowed = true;
+                }
+                None
+            },
+        }
+    }
+
+    // Returns the last line that push returned, None while a line is being received
+    pub fn line(&self) -> Option<&str> {
+        if !self.complete || self.overflowed {
+            return None;
+        }
+        core::str::from_utf8(&self.bytes[..self.len]).ok()
+    }
+}
+
+pub enum Command {
+    Uci,
+    IsReady,
+    NewGame,
+    Position(Position),
+    Go(GoParams),
+    Stop,
+    Quit,
+    Unknown, // A command that isn't supported, it's ignored
+}
+
+// Position from a position command
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Position {
+    pub board: Board, // Board of the team to move, if a move ended the game this is the board before the move
+    pub ended: bool, // True if a move of the command ended the game, so there is no move to search for
+}
+
+// Limits of a search from a go command, None if the limit wasn't given
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct GoParams {
+    pub depth: Option<usize>,
+    pub nodes: Option<u64>,
+    pub move_time_ms: Option<u64>,
+    pub remaining_ms: [Option<u64>; 2], // Time the white and black teams have left (wtime and btime)
+    pub increment_ms: [Option<u64>; 2], // Increments of the white and black teams (winc and binc)
+    pub infinite: bool, // Search until stop is received
+}
+
+impl GoParams {
+    pub fn new() -> Self {
+        GoParams {
+            depth: None,
+            nodes: None,
+            move_time_ms: None,
+            remaining_ms: [None; 2],
+            increment_ms: [None; 2],
+            infinite: false,
+        }
+    }
+
+    // Returns the search params for the search, made from the frontend's params (e.g. the heuristics it uses)
+    // The depth is never more than the max_depth of the frontend's params, so the search can't use more stack than the frontend has
+    // The time is the move time, or a share of the time left on the clock of the team to move (see algorithm::move_time_ms)
+    // Without a time or an infinite search only the depth and nodes limit the search
+    pub fn search_params(&self, params: &SearchParams, whites_move: bool, clock_mhz: u64) -> SearchParams {
+        let team_index;
+        if whites_move {
+            team_index = 0;
+        } else {
+            team_index = 1;
+        }
+
+        let mut search_params = *params;
+        match self.depth {
+            Some(depth) => search_params.max_depth = core::cmp::max(core::cmp::min(depth, params.max_depth), 1),
+            None => (),
+        }
+        match self.nodes {
+            Some(nodes) => search_params.max_nodes = nodes,
+            None => search_params.max_nodes = u64::MAX,
+        }
+
+        let move_time_ms;
+        match (self.move_time_ms, self.remaining_ms[team_index]) {
+            _ if self.infinite => move_time_ms = None,
+            (Some(ms), _) => move_time_ms = Some(ms),
+            (None, Some(remaining_ms)) => move_time_ms = Some(algorithm::move_time_ms(remaining_ms, self.increment_ms[team_index].unwrap_or(0), 0)),
+            (None, None) => move_time_ms = None,
+        }
+        match move_time_ms {
+            Some(ms) => search_params.max_elapsed_cycles = crate::embedded::ms_to_cycles(ms, clock_mhz),
+            None => search_params.max_elapsed_cycles = u64::MAX,
+        }
+
+        search_params
+    }
+}
+
+// Parses a line from the GUI
+// Returns an error if a position or go command can't be parsed, e.g. an illegal move or an invalid FEN
+pub fn parse_command(line: &str, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Result<Command, ()> {
+    let mut words = line.split_whitespace();
+    match words.next() {
+        Some("uci") => Ok(Command::Uci),
+        Some("isready") => Ok(Command::IsReady),
+        Some("ucinewgame") => Ok(Command::NewGame),
+        Some("position") => Ok(Command::Position(parse_position(line.trim_start()["position".len()..].trim(), pieces_info)?)),
+        Some("go") => Ok(Command::Go(parse_go(words)?)),
+        Some("stop") => Ok(Command::Stop),
+        Some("quit") => Ok(Command::Quit),
+        _ => Ok(Command::Unknown),
+    }
+}
+
+// Parses the arguments of a position command, "startpos" or "fen <fen>", then optionally "moves" and the moves in long algebraic notation
+fn parse_position(arguments: &str, pieces_info: &[crate::piece::constants::PieceInfo; 12]) -> Result<Position, ()> {
+    let (board_text, moves_text);
+    match arguments.find("moves") {
+        Some(index) => {
+            board_text = arguments[..index].trim();
+            moves_text = &arguments[index + "moves".len()..];
+        },
+        None => {
+            board_text = arguments;
+            moves_text = "";
+        },
+    }
+
+    let mut position = Position {
+        board: Board::start(),
+        ended: false,
+    };
+    if board_text != "startpos" {
+        match board_text.strip_prefix("fen") {
+            Some(fen) => position.board = Board::from_fen(fen.trim()).map_err(|_| ())?,
+            None => return Err(()),
+        }
+    }
+
+    for text in moves_text.split_whitespace() {
+        if position.ended {
+            return Err(());
+        }
+
+        let piece_move = crate::notation::parse_long_algebraic(text, &position.board, pieces_info)?;
+        match move_generator::make_move(&piece_move.initial_piece_coordinates, piece_move.final_piece_bit, piece_move.promotion, position.board, pieces_info) {
+            Ok(board) => position.board = board,
+            Err(TurnError::InvalidMove) | Err(TurnError::InvalidMoveCheck) => return Err(()),
+            Err(_) => position.ended = true,
+        }
+    }
+
+    Ok(position)
+}
+
+// Parses the arguments of a go command, limits that aren't supported (e.g. movestogo, ponder) are skipped
+fn parse_go<'a, I: Iterator<Item = &'a str>>(mut words: I) -> Result<GoParams, ()> {
+    let mut go_params = GoParams::new();
+    while let Some(word) = words.next() {
+        match word {
+            "infinite" => go_params.infinite = true,
+            "depth" => go_params.depth = Some(parse_number(words.next())? as usize),
+            "nodes" => go_params.nodes = Some(parse_number(words.next())?),
+            "movetime" => go_params.move_time_ms = Some(parse_number(words.next())?),
+            "wtime" => go_params.remaining_ms[0] = Some(parse_number(words.next())?),
+            "btime" => go_params.remaining_ms[1] = Some(parse_number(words.next())?),
+            "winc" => go_params.increment_ms[0] = Some(parse_number(words.next())?),
+            "binc" => go_params.increment_ms[1] = Some(parse_number(words.next())?),
+            _ => (),
+        }
+    }
+    Ok(go_params)
+}
+
+// Parses the number after a go limit, a negative time (GUIs send these once a clock has run out) is taken as 0
+fn parse_number(word: Option<&str>) -> Result<u64, ()> {
+    match word {
+        Some(word) if word.starts_with('-') => word[1..].parse::<u64>().map(|_| 0).map_err(|_| ()),
+        Some(word) => word.parse::<u64>().map_err(|_| ()),
+        None => Err(()),
+    }
+}
+
+// Writes the response to uci, the name of the engine then uciok
+pub fn write_id<W: core::fmt::Write>(out: &mut W) -> Result<(), ()> {
+    out.write_str("id name chess2\nid author Hardware7253\nuciok\n").map_err(|_| ())
+}
+
+// Writes an info line for a completed iteration of a search (see algorithm::SearchProgress)
+// Values within 100 half moves of a checkmate are written as the number of moves until the checkmate
+// Nothing is written for the progress reports made while an iteration is being searched, they don't have a value or move yet
+pub fn write_info<W: core::fmt::Write>(out: &mut W, progress: &SearchProgress, clock_mhz: u64) -> Result<(), ()> {
+    if !progress.completed {
+        return Ok(());
+    }
+
+    write!(out, "info depth {} score ", progress.depth).map_err(|_| ())?;
+
+    match algorithm::mate_moves(progress.value) {
+        Some(mate_moves) => write!(out, "mate {}", mate_moves).map_err(|_| ())?,
+        None => write!(out, "cp {}", progress.value).map_err(|_| ())?,
+    }
+
+    write!(out, " nodes {} time {}", progress.nodes, progress.elapsed_cycles / (clock_mhz * 1000)).map_err(|_| ())?;
+    match progress.best_move {
+        Some(piece_move) => {
+            out.write_str(" pv ").map_err(|_| ())?;
+            crate::notation::write_long_algebraic(out, &piece_move)?;
+        },
+        None => (),
+    }
+    out.write_str("\n").map_err(|_| ())
+}
+
+// Writes the best move found by a search, or the null move 0000 if there is no legal move
+pub fn write_best_move<W: core::fmt::Write>(out: &mut W, piece_move: Option<&Move>) -> Result<(), ()> {
+    out.write_str("bestmove ").map_err(|_| ())?;
+    match piece_move {
+        Some(piece_move) => crate::notation::write_long_algebraic(out, piece_move)?,
+        None => out.write_str("0000").map_err(|_| ())?,
+    }
+    out.write_str("\n").map_err(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(line: &str) -> Result<Position, ()> {
+        let pieces_info = crate::piece::constants::gen();
+        match parse_command(line, &pieces_info)? {
+            Command::Position(position) => Ok(position),
+            _ => panic!("{} isn't a position command", line),
+        }
+    }
+
+    fn go(line: &str) -> Result<GoParams, ()> {
+        let pieces_info = crate::piece::constants::gen();
+        match parse_command(line, &pieces_info)? {
+            Command::Go(go_params) => Ok(go_params),
+            _ => panic!("{} isn't a go command", line),
+        }
+    }
+
+    #[test]
+    fn startpos() {
+        let start = position("position startpos").unwrap();
+        assert_eq!(start.board.hash, Board::start().hash);
+        assert!(!start.ended);
+
+        let after_moves = position("position startpos moves e2e4 e7e5 g1f3").unwrap();
+        let board = Board::from_fen("rnbqkbnr/pppp1ppp/8/4p3/4P3/5N2/PPPP1PPP/RNBQKB1R b KQkq - 1 2").unwrap();
+        assert_eq!(after_moves.board.hash, board.hash);
+        assert_eq!(after_moves.board.whites_move, false);
+    }
+
+    #[test]
+    fn fen_with_moves() {
+        let fen = position("position fen 4k3/8/8/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+        assert_eq!(fen.board.hash, Board::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1").unwrap().hash);
+
+        let after_moves = position("position fen 4k3/8/8/8/8/8/4P3/4K3 w - - 0 1 moves e2e4 e8d7").unwrap();
+        let board = Board::from_fen("8/3k4/8/8/4P3/8/8/4K3 w - - 1 2").unwrap();
+        assert_eq!(after_moves.board.hash, board.hash);
+        assert!(!after_moves.ended);
+
+        // A move that ends the game leaves the board before it, and no moves can come after it
+        let mate = position("position startpos moves f2f3 e7e5 g2g4 d8h4").unwrap();
+        assert!(mate.ended);
+        assert!(position("position startpos moves f2f3 e7e5 g2g4 d8h4 e1f2").is_err());
+
+        assert!(position("position fen 4k3/8/8/8/8/8/8/4K3 x - - 0 1").is_err());
+        assert!(position("position").is_err());
+    }
+
+    #[test]
+    fn illegal_move() {
+        // A pawn can't move 3 squares, a king can't move into check, and a move needs a piece on its first square
+        assert!(position("position startpos moves e2e5").is_err());
+        assert!(position("position fen 4k3/8/8/8/8/8/3r4/4K3 w - - 0 1 moves e1e2").is_err());
+        assert!(position("position startpos moves e3e4").is_err());
+        assert!(position("position startpos moves e2e4 e2e4").is_err());
+    }
+
+    #[test]
+    fn negative_time() {
+        let go_params = go("go wtime -1500 btime 20000 winc 0 binc 100").unwrap();
+        assert_eq!(go_params.remaining_ms, [Some(0), Some(20000)]);
+        assert_eq!(go_params.increment_ms, [Some(0), Some(100)]);
+
+        assert!(go("go wtime -").is_err());
+        assert!(go("go wtime").is_err());
+        assert!(go("go movetime ten").is_err());
+
+        // Unsupported limits are skipped
+        let go_params = go("go movestogo 20 movetime 500").unwrap();
+        assert_eq!(go_params.move_time_ms, Some(500));
+    }
+
+    #[test]
+    fn depth_clamping() {
+        let params = SearchParams::new();
+
+        // The depth is never more than the frontend's max depth, or less than 1
+        let search_params = go("go depth 100").unwrap().search_params(&params, true, 72);
+        assert_eq!(search_params.max_depth, params.max_depth);
+        let search_params = go("go depth 0").unwrap().search_params(&params, true, 72);
+        assert_eq!(search_params.max_depth, 1);
+        let search_params = go("go depth 3").unwrap().search_params(&params, true, 72);
+        assert_eq!(search_params.max_depth, 3);
+
+        // Without limits only the frontend's depth limits the search
+        let search_params = go("go").unwrap().search_params(&params, true, 72);
+        assert_eq!(search_params.max_depth, params.max_depth);
+        assert_eq!(search_params.max_nodes, u64::MAX);
+        assert_eq!(search_params.max_elapsed_cycles, u64::MAX);
+
+        let search_params = go("go nodes 5000 infinite").unwrap().search_params(&params, false, 72);
+        assert_eq!(search_params.max_nodes, 5000);
+        assert_eq!(search_params.max_elapsed_cycles, u64::MAX);
+    }
+}
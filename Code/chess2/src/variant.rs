@@ -0,0 +1,53 @@
+// Chess variants that change how a game is won
+// The pieces move the same way in every variant, so only the end of the game (new_turn) and the evaluation depend on the variant
+// The variant is kept on the board (Board::variant), so the search and anything else that only has a board plays by the same rules
+
+use crate::board::board_representation;
+use crate::board::move_generator::TurnError;
+
+// Bitboard of the 4 centre squares (d5, e5, d4, e4)
+pub const CENTRE: u64 = 1 << 27 | 1 << 28 | 1 << 35 | 1 << 36;
+
+// Number of checks a team has to give to win Three-Check
+pub const CHECKS_TO_WIN: u8 = 3;
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Variant {
+    Standard,
+    KingOfTheHill, // A team also wins by moving its king to one of the 4 centre squares
+    ThreeCheck, // A team also wins by checking the enemy king 3 times
+}
+
+// Every variant, in the order the firmware offers them
+pub const VARIANTS: [Variant; 3] = [Variant::Standard, Variant::KingOfTheHill, Variant::ThreeCheck];
+
+impl Variant {
+    // Name of the variant, short enough for one row of the lcd
+    pub fn name(&self) -> &'static str {
+        match self {
+            Variant::Standard => "Standard",
+            Variant::KingOfTheHill => "King of the Hill",
+            Variant::ThreeCheck => "Three-Check",
+        }
+    }
+}
+
+// Returns the way the team that just moved has won by the rules of the board's variant, None if it hasn't
+// Called by new_turn after the move has been made, so the check counts of the board already include the move (see Board::checks)
+pub fn variant_win(board: &board_representation::Board, moved_white: bool) -> Option<TurnError> {
+    let king_index;
+    let team;
+    if moved_white {
+        king_index = 5;
+        team = 0;
+    } else {
+        king_index = 11;
+        team = 1;
+    }
+
+    match board.variant {
+        Variant::KingOfTheHill if board.board[king_index] & CENTRE != 0 => Some(TurnError::KingOfTheHill),
+        Variant::ThreeCheck if board.checks[team] >= CHECKS_TO_WIN => Some(TurnError::ThreeCheck),
+        _ => None,
+    }
+}
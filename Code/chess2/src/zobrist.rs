@@ -0,0 +1,290 @@
+use crate::board::board_representation;
+
+// Random keys used to build zobrist hashes
+// https://www.chessprogramming.org/Zobrist_Hashing
+pub struct Keys {
+    pub pieces: [[u64; 64]; 12], // Key for every piece (board index) on every bit
+    pub black_to_move: u64, // Key used when it is the black teams move
+    pub castling: [u64; 4], // Keys for castling rights (see castling_rights)
+    pub en_passant_file: [u64; 8], // Key for the file of the en passant target
+    pub checks: [[u64; 3]; 2], // Keys for the number of checks (1 - 3) given by the white and black teams in Three-Check (see checks_key)
+}
+
+// Keys are generated at compile time so there is no runtime initialisation
+pub const KEYS: Keys = gen_keys();
+
+// Xorshift pseudo random number generator
+const fn next_random(state: u64) -> u64 {
+    let mut x = state;
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    x
+}
+
+const fn gen_keys() -> Keys {
+    let mut state: u64 = 0x9E3779B97F4A7C15; // Seed
+
+    let mut pieces = [[0; 64]; 12];
+    let mut i = 0;
+    while i < 12 {
+        let mut j = 0;
+        while j < 64 {
+            state = next_random(state);
+            pieces[i][j] = state;
+            j += 1;
+        }
+        i += 1;
+    }
+
+    state = next_random(state);
+    let black_to_move = state;
+
+    let mut castling = [0; 4];
+    let mut i = 0;
+    while i < 4 {
+        state = next_random(state);
+        castling[i] = state;
+        i += 1;
+    }
+
+    let mut en_passant_file = [0; 8];
+    let mut i = 0;
+    while i < 8 {
+        state = next_random(state);
+        en_passant_file[i] = state;
+        i += 1;
+    }
+
+    // Generated last so the other keys stay the same
+    let mut checks = [[0; 3]; 2];
+    let mut i = 0;
+    while i < 6 {
+        state = next_random(state);
+        checks[i / 3][i % 3] = state;
+        i += 1;
+    }
+
+    Keys {
+        pieces: pieces,
+        black_to_move: black_to_move,
+        castling: castling,
+        en_passant_file: en_passant_file,
+        checks: checks,
+    }
+}
+
+// Returns the castling rights of the board
+// A right is kept while the king and rook have moved 0 times (their bits are off in board[12])
+// [White king side, white queen side, black king side, black queen side]
+pub fn castling_rights(board: &board_representation::Board) -> [bool; 4] {
+    bitboard_castling_rights(&board.board)
+}
+
+// Returns the castling rights of a board's bitboards, see castling_rights
+fn bitboard_castling_rights(bitboards: &[u64; 13]) -> [bool; 4] {
+    use crate::bit_on;
+
+    // King bit, rook bit, rook board index, king board index
+    let castles: [(usize, usize, usize, usize); 4] = [(60, 63, 1, 5), (60, 56, 1, 5), (4, 7, 7, 11), (4, 0, 7, 11)];
+
+    let mut rights = [false; 4];
+    for i in 0..castles.len() {
+        let (king_bit, rook_bit, rook_index, king_index) = castles[i];
+        rights[i] = bit_on(bitboards[king_index], king_bit) && !bit_on(bitboards[12], king_bit)
+            && bit_on(bitboards[rook_index], rook_bit) && !bit_on(bitboards[12], rook_bit);
+    }
+    rights
+}
+
+// Returns the key for the number of checks a team (0 for white, 1 for black) has given, 0 if it hasn't given any
+// Checks are only counted in Three-Check, so the hashes of other variants don't change
+pub fn checks_key(team: usize, checks: u8) -> u64 {
+    if checks == 0 {
+        return 0;
+    }
+    KEYS.checks[team][checks as usize - 1]
+}
+
+// Generates the zobrist hash of a board from scratch
+// Boards keep their hash in board.hash, so this is only needed when a board is created or changed outside of new_turn
+pub fn hash(board: &board_representation::Board) -> u64 {
+    let mut hash = 0;
+
+    for i in 0..12 {
+        let mut bitboard = board.board[i];
+        while bitboard != 0 {
+            let bit = bitboard.trailing_zeros() as usize;
+            hash ^= KEYS.pieces[i][bit];
+            bitboard &= bitboard - 1; // Turn off the lowest bit
+        }
+    }
+
+    if !board.whites_move {
+        hash ^= KEYS.black_to_move;
+    }
+
+    let rights = castling_rights(board);
+    for i in 0..rights.len() {
+        if rights[i] {
+            hash ^= KEYS.castling[i];
+        }
+    }
+
+    match board.en_passant_target {
+        Some(bit) => hash ^= KEYS.en_passant_file[bit % 8],
+        None => (),
+    }
+
+    for team in 0..2 {
+        hash ^= checks_key(team, board.checks[team]);
+    }
+
+    hash
+}
+
+// Updates the hash of a board after a move, given the bitboards and en passant target from before the move
+// Only the pieces that changed are hashed, so captures, castling, en passant and promotions are all handled
+// A move always changes the team to move
+pub fn update_hash(initial_bitboards: &[u64; 13], initial_en_passant_target: Option<usize>, board: &mut board_representation::Board) {
+    let mut hash = board.hash;
+
+    for i in 0..12 {
+        let mut changed = initial_bitboards[i] ^ board.board[i];
+        while changed != 0 {
+            let bit = changed.trailing_zeros() as usize;
+            hash ^= KEYS.pieces[i][bit];
+            changed &= changed - 1; // Turn off the lowest bit
+        }
+    }
+
+    hash ^= KEYS.black_to_move;
+
+    let initial_rights = bitboard_castling_rights(initial_bitboards);
+    let rights = bitboard_castling_rights(&board.board);
+    for i in 0..rights.len() {
+        if initial_rights[i] != rights[i] {
+            hash ^= KEYS.castling[i];
+        }
+    }
+
+    match initial_en_passant_target {
+        Some(bit) => hash ^= KEYS.en_passant_file[bit % 8],
+        None => (),
+    }
+    match board.en_passant_target {
+        Some(bit) => hash ^= KEYS.en_passant_file[bit % 8],
+        None => (),
+    }
+
+    board.hash = hash;
+}
+
+// Generates a zobrist hash of only the pawns on the board
+// Used to cache evaluations that only depend on the pawn structure
+pub fn pawn_hash(board: &board_representation::Board) -> u64 {
+    let mut hash = 0;
+
+    for i in [0, 6] {
+        let mut bitboard = board.board[i];
+        while bitboard != 0 {
+            let bit = bitboard.trailing_zeros() as usize;
+            hash ^= KEYS.pieces[i][bit];
+            bitboard &= bitboard - 1; // Turn off the lowest bit
+        }
+    }
+
+    hash
+}
+
+include!("polyglot_randoms.rs");
+
+// Generates the hash of a board the same way as a Polyglot opening book
+// The keys come from the Random64 array of the Polyglot book format (POLYGLOT_RANDOMS), so the hash matches the keys of existing .bin books
+// http://hgm.nubati.net/book_format.html
+pub fn polyglot_hash(board: &board_representation::Board) -> u64 {
+    use crate::bit_on;
+
+    // Polyglot piece types for the board indexes of one team (pawn, rook, knight, bishop, queen, king)
+    let piece_types = [0, 3, 1, 2, 4, 5];
+
+    let mut hash = 0;
+
+    for i in 0..12 {
+        // Polyglot pieces alternate black, white (black pawn = 0, white pawn = 1, black knight = 2...)
+        let mut kind = piece_types[i % 6] * 2;
+        if crate::board_index_white(i) {
+            kind += 1;
+        }
+
+        let mut bitboard = board.board[i];
+        while bitboard != 0 {
+            let bit = bitboard.trailing_zeros() as usize;
+
+            // Polyglot squares start at a1, the board starts at a8
+            let square = (7 - bit / 8) * 8 + bit % 8;
+            hash ^= POLYGLOT_RANDOMS[64 * kind + square];
+            bitboard &= bitboard - 1; // Turn off the lowest bit
+        }
+    }
+
+    let rights = castling_rights(board);
+    for i in 0..rights.len() {
+        if rights[i] {
+            hash ^= POLYGLOT_RANDOMS[768 + i];
+        }
+    }
+
+    // The en passant file is only hashed if a pawn of the team to move can capture en passant
+    match board.en_passant_target {
+        Some(target_bit) => {
+            let pawn_bit;
+            let capturing_pawns;
+            if board.whites_move {
+                pawn_bit = target_bit + 8;
+                capturing_pawns = board.board[0];
+            } else {
+                pawn_bit = target_bit - 8;
+                capturing_pawns = board.board[6];
+            }
+
+            let file = pawn_bit % 8;
+            if (file > 0 && bit_on(capturing_pawns, pawn_bit - 1)) || (file < 7 && bit_on(capturing_pawns, pawn_bit + 1)) {
+                hash ^= POLYGLOT_RANDOMS[772 + file];
+            }
+        },
+        None => (),
+    }
+
+    if board.whites_move {
+        hash ^= POLYGLOT_RANDOMS[780];
+    }
+
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Keys of the example positions from the Polyglot book format, including en passant targets that can and can't be captured and lost castling rights
+    #[test]
+    fn polyglot_keys() {
+        let positions = [
+            ("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1", 0x463b96181691fc9c),
+            ("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1", 0x823c9b50fd114196),
+            ("rnbqkbnr/ppp1pppp/8/3p4/4P3/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2", 0x0756b94461c50fb0),
+            ("rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR b KQkq - 0 2", 0x662fafb965db29d4),
+            ("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPP1PPP/RNBQKBNR w KQkq f6 0 3", 0x22a48b5a8e47ff78),
+            ("rnbqkbnr/ppp1p1pp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR b kq - 0 3", 0x652a607ca3f242c1),
+            ("rnbq1bnr/ppp1pkpp/8/3pPp2/8/8/PPPPKPPP/RNBQ1BNR w - - 0 4", 0x00fdd303c946bdd9),
+            ("rnbqkbnr/p1pppppp/8/8/PpP4P/8/1P1PPPP1/RNBQKBNR b KQkq c3 0 3", 0x3c8123ea7b067637),
+            ("rnbqkbnr/p1pppppp/8/8/P6P/R1p5/1P1PPPP1/1NBQKBNR b Kkq - 0 4", 0x5c3f9b829b279560),
+        ];
+
+        for (fen, key) in positions {
+            let board = board_representation::Board::from_fen(fen).unwrap();
+            assert_eq!(polyglot_hash(&board), key, "{}", fen);
+        }
+    }
+}